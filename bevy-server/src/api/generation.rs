@@ -8,6 +8,7 @@
 
 use axum::{extract::State, routing::post, Json, Router};
 use serde::{Deserialize, Serialize};
+use std::time::Instant;
 
 use super::ApiState;
 use crate::destruction::FloorDestructionManager;
@@ -147,9 +148,10 @@ pub struct SemanticMatch {
 // ============================================================================
 
 async fn generate_floor(
-    State(_state): State<ApiState>,
+    State(state): State<ApiState>,
     Json(req): Json<FloorRequest>,
 ) -> Json<FloorResponse> {
+    let started = Instant::now();
     let seed = req.tower_seed.wrapping_add(req.floor_id as u64);
     let size = 50u32;
 
@@ -174,6 +176,7 @@ async fn generate_floor(
 
     let biome_id = determine_biome(req.floor_id);
     let tags = generate_floor_tags(req.floor_id, biome_id, seed);
+    state.metrics.generation_latency.observe(started.elapsed());
 
     Json(FloorResponse {
         floor_id: req.floor_id,
@@ -23,6 +23,7 @@
 //! ```
 
 use crate::lmdb_cache::LmdbFloorCache;
+use crate::metrics::LatencyHistogram;
 use crate::proto::tower::game::{
     ChunkData, FloorTileData, SemanticTags as ProtoSemanticTags, TagPair, Vec3,
 };
@@ -34,6 +35,7 @@ use sha3::{Digest, Sha3_256};
 use std::num::NonZeroUsize;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
+use std::time::Instant;
 use tokio::sync::mpsc;
 use tracing::{debug, info, warn};
 
@@ -90,6 +92,8 @@ pub struct FloorGenerator {
     metrics_tier2_hits: Arc<AtomicU64>,
     /// Performance metrics: Tier 3 generations (cache misses)
     metrics_tier3_gens: Arc<AtomicU64>,
+    /// Performance metrics: Tier 3 generation latency
+    generation_latency: Arc<LatencyHistogram>,
 }
 
 /// Internal request for floor generation
@@ -102,6 +106,15 @@ struct GenerationRequest {
 impl FloorGenerator {
     /// Create a new floor generator with the given configuration
     pub fn new(config: GenerationConfig) -> Self {
+        Self::with_latency_histogram(config, LatencyHistogram::new())
+    }
+
+    /// Create a floor generator that records Tier 3 generation latency into
+    /// `generation_latency` (e.g. `ServerMetrics::generation_latency`)
+    pub fn with_latency_histogram(
+        config: GenerationConfig,
+        generation_latency: Arc<LatencyHistogram>,
+    ) -> Self {
         let cache = Arc::new(Mutex::new(LruCache::new(
             NonZeroUsize::new(config.cache_capacity).unwrap(),
         )));
@@ -138,8 +151,16 @@ impl FloorGenerator {
         let worker_cache = cache.clone();
         let worker_lmdb = lmdb_cache.clone();
         let worker_config = config.clone();
+        let worker_latency = generation_latency.clone();
         tokio::spawn(async move {
-            Self::worker_loop(request_rx, worker_cache, worker_lmdb, worker_config).await;
+            Self::worker_loop(
+                request_rx,
+                worker_cache,
+                worker_lmdb,
+                worker_config,
+                worker_latency,
+            )
+            .await;
         });
 
         info!(
@@ -161,6 +182,7 @@ impl FloorGenerator {
             metrics_tier1_hits: Arc::new(AtomicU64::new(0)),
             metrics_tier2_hits: Arc::new(AtomicU64::new(0)),
             metrics_tier3_gens: Arc::new(AtomicU64::new(0)),
+            generation_latency,
         }
     }
 
@@ -170,6 +192,7 @@ impl FloorGenerator {
         cache: Arc<Mutex<LruCache<u32, ChunkData>>>,
         lmdb_cache: Option<Arc<LmdbFloorCache>>,
         config: GenerationConfig,
+        generation_latency: Arc<LatencyHistogram>,
     ) {
         while let Some(req) = request_rx.recv().await {
             // Tier 1: Check LRU cache (might have been generated by another request)
@@ -195,7 +218,9 @@ impl FloorGenerator {
                 "Worker Tier 3 MISS for floor {}, generating...",
                 req.floor_id
             );
+            let started = Instant::now();
             let chunk = Self::generate_floor_sync(req.floor_id, req.seed, &config);
+            generation_latency.observe(started.elapsed());
 
             // Store in Tier 1 cache
             cache.lock().put(req.floor_id, chunk.clone());
@@ -468,6 +493,11 @@ impl FloorGenerator {
         }
     }
 
+    /// Tier 3 generation latency histogram
+    pub fn generation_latency(&self) -> &LatencyHistogram {
+        &self.generation_latency
+    }
+
     /// Reset performance metrics (useful for testing)
     pub fn reset_metrics(&self) {
         self.metrics_tier1_hits.store(0, Ordering::Relaxed);
@@ -138,6 +138,8 @@ pub struct AttackData {
 #[derive(Resource, Debug)]
 pub struct WeaponMovesets {
     pub movesets: HashMap<WeaponType, Vec<AttackData>>,
+    /// Stamina cost of each action, shared by all weapons
+    pub action_costs: ActionCosts,
 }
 
 impl Default for WeaponMovesets {
@@ -273,7 +275,37 @@ impl Default for WeaponMovesets {
             ],
         );
 
-        Self { movesets }
+        Self {
+            movesets,
+            action_costs: ActionCosts::default(),
+        }
+    }
+}
+
+/// Stamina cost per combat action
+#[derive(Debug, Clone)]
+pub struct ActionCosts {
+    pub costs: HashMap<ActionType, f32>,
+}
+
+impl Default for ActionCosts {
+    fn default() -> Self {
+        let costs = HashMap::from([
+            (ActionType::Attack, 10.0),
+            (ActionType::HeavyAttack, 30.0),
+            (ActionType::Dodge, 20.0),
+            (ActionType::Parry, 5.0),
+            (ActionType::Block, 0.0),
+            (ActionType::BlockRelease, 0.0),
+        ]);
+        Self { costs }
+    }
+}
+
+impl ActionCosts {
+    /// Stamina needed for `action` (free if not listed)
+    pub fn cost(&self, action: ActionType) -> f32 {
+        self.costs.get(&action).copied().unwrap_or(0.0)
     }
 }
 
@@ -319,7 +351,7 @@ pub struct CombatAction {
     pub timestamp: f64,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum ActionType {
     /// Light attack (combo chain)
     Attack,
@@ -457,6 +489,27 @@ pub struct CombatEnergy {
     /// Semantic energy: generated by semantic tag interactions
     pub semantic: f32,
     pub semantic_max: f32,
+    /// Stamina: spent by every combat action, regenerates over time
+    #[serde(default = "default_stamina")]
+    pub stamina: f32,
+    #[serde(default = "default_stamina")]
+    pub stamina_max: f32,
+    /// Stamina regenerated per second
+    #[serde(default = "default_stamina_regen")]
+    pub stamina_regen: f32,
+}
+
+/// Starting and maximum stamina
+pub const DEFAULT_STAMINA: f32 = 100.0;
+/// Stamina regenerated per second
+pub const DEFAULT_STAMINA_REGEN: f32 = 25.0;
+
+fn default_stamina() -> f32 {
+    DEFAULT_STAMINA
+}
+
+fn default_stamina_regen() -> f32 {
+    DEFAULT_STAMINA_REGEN
 }
 
 impl Default for CombatEnergy {
@@ -468,6 +521,9 @@ impl Default for CombatEnergy {
             thermal_max: 100.0,
             semantic: 0.0,
             semantic_max: 100.0,
+            stamina: DEFAULT_STAMINA,
+            stamina_max: DEFAULT_STAMINA,
+            stamina_regen: DEFAULT_STAMINA_REGEN,
         }
     }
 }
@@ -499,6 +555,11 @@ impl CombatEnergy {
             false
         }
     }
+
+    /// Regenerate stamina for `dt` seconds, up to the cap
+    pub fn regen_stamina(&mut self, dt: f32) {
+        self.stamina = (self.stamina + self.stamina_regen * dt).min(self.stamina_max);
+    }
 }
 
 // ============================================================================
@@ -610,22 +671,49 @@ pub fn update_combat_timers(time: Res<Time>, mut combatants: Query<&mut CombatSt
     }
 }
 
+/// System: regenerate stamina up to its cap
+pub fn update_combat_energy(time: Res<Time>, mut query: Query<&mut CombatEnergy>) {
+    let dt = time.delta_secs();
+    for mut energy in &mut query {
+        energy.regen_stamina(dt);
+    }
+}
+
 /// Validate and apply a combat action to an entity's CombatState.
+/// The action's stamina cost is taken from `energy` only if it succeeds;
+/// actions the entity cannot afford are rejected.
 /// Returns a description of what happened.
 pub fn try_combat_action(
     state: &mut CombatState,
     action: ActionType,
     weapon: &EquippedWeapon,
     movesets: &WeaponMovesets,
+    energy: &mut CombatEnergy,
 ) -> CombatActionResult {
-    match action {
+    let cost = movesets.action_costs.cost(action);
+    if energy.stamina < cost {
+        return CombatActionResult {
+            success: false,
+            action,
+            new_phase: state.phase,
+            combo_step: state.combo_step,
+            attack_data: None,
+            message: format!("Not enough stamina ({:.0}/{:.0})", energy.stamina, cost),
+        };
+    }
+
+    let result = match action {
         ActionType::Attack => try_attack(state, weapon, movesets),
         ActionType::Block => try_block(state),
         ActionType::BlockRelease => try_block_release(state),
         ActionType::Parry => try_parry(state),
         ActionType::Dodge => try_dodge(state),
         ActionType::HeavyAttack => try_heavy_attack(state, weapon, movesets),
+    };
+    if result.success {
+        energy.stamina -= cost;
     }
+    result
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -958,8 +1046,15 @@ mod tests {
         let mut state = CombatState::default();
         let weapon = test_sword();
         let movesets = WeaponMovesets::default();
+        let mut energy = CombatEnergy::default();
 
-        let result = try_combat_action(&mut state, ActionType::Attack, &weapon, &movesets);
+        let result = try_combat_action(
+            &mut state,
+            ActionType::Attack,
+            &weapon,
+            &movesets,
+            &mut energy,
+        );
         assert!(result.success);
         assert_eq!(result.new_phase, CombatPhase::Attacking);
         assert_eq!(result.combo_step, 0);
@@ -971,13 +1066,26 @@ mod tests {
         let mut state = CombatState::default();
         let weapon = test_sword();
         let movesets = WeaponMovesets::default();
+        let mut energy = CombatEnergy::default();
 
         // First attack succeeds
-        let _ = try_combat_action(&mut state, ActionType::Attack, &weapon, &movesets);
+        let _ = try_combat_action(
+            &mut state,
+            ActionType::Attack,
+            &weapon,
+            &movesets,
+            &mut energy,
+        );
         assert_eq!(state.phase, CombatPhase::Attacking);
 
         // Second attack fails (still in attacking phase)
-        let result = try_combat_action(&mut state, ActionType::Attack, &weapon, &movesets);
+        let result = try_combat_action(
+            &mut state,
+            ActionType::Attack,
+            &weapon,
+            &movesets,
+            &mut energy,
+        );
         assert!(!result.success);
     }
 
@@ -986,9 +1094,16 @@ mod tests {
         let mut state = CombatState::default();
         let weapon = test_sword();
         let movesets = WeaponMovesets::default();
+        let mut energy = CombatEnergy::default();
 
         // Attack step 0
-        let r1 = try_combat_action(&mut state, ActionType::Attack, &weapon, &movesets);
+        let r1 = try_combat_action(
+            &mut state,
+            ActionType::Attack,
+            &weapon,
+            &movesets,
+            &mut energy,
+        );
         assert_eq!(r1.combo_step, 0);
 
         // Simulate attack finishing → idle with combo window
@@ -996,14 +1111,26 @@ mod tests {
         state.combo_window = COMBO_WINDOW_SECS;
 
         // Attack step 1
-        let r2 = try_combat_action(&mut state, ActionType::Attack, &weapon, &movesets);
+        let r2 = try_combat_action(
+            &mut state,
+            ActionType::Attack,
+            &weapon,
+            &movesets,
+            &mut energy,
+        );
         assert!(r2.success);
         assert_eq!(r2.combo_step, 1);
 
         // Step 2
         state.phase = CombatPhase::Idle;
         state.combo_window = COMBO_WINDOW_SECS;
-        let r3 = try_combat_action(&mut state, ActionType::Attack, &weapon, &movesets);
+        let r3 = try_combat_action(
+            &mut state,
+            ActionType::Attack,
+            &weapon,
+            &movesets,
+            &mut energy,
+        );
         assert_eq!(r3.combo_step, 2);
     }
 
@@ -1012,9 +1139,16 @@ mod tests {
         let mut state = CombatState::default();
         let weapon = test_sword();
         let movesets = WeaponMovesets::default();
+        let mut energy = CombatEnergy::default();
 
         // Attack step 0
-        let _ = try_combat_action(&mut state, ActionType::Attack, &weapon, &movesets);
+        let _ = try_combat_action(
+            &mut state,
+            ActionType::Attack,
+            &weapon,
+            &movesets,
+            &mut energy,
+        );
 
         // Simulate attack ending but combo window expired
         state.phase = CombatPhase::Idle;
@@ -1022,7 +1156,13 @@ mod tests {
         state.combo_step = 2; // Was at step 2
 
         // Should reset to step 0
-        let result = try_combat_action(&mut state, ActionType::Attack, &weapon, &movesets);
+        let result = try_combat_action(
+            &mut state,
+            ActionType::Attack,
+            &weapon,
+            &movesets,
+            &mut energy,
+        );
         assert_eq!(result.combo_step, 0);
     }
 
@@ -1031,8 +1171,15 @@ mod tests {
         let mut state = CombatState::default();
         let weapon = test_sword();
         let movesets = WeaponMovesets::default();
+        let mut energy = CombatEnergy::default();
 
-        let result = try_combat_action(&mut state, ActionType::Parry, &weapon, &movesets);
+        let result = try_combat_action(
+            &mut state,
+            ActionType::Parry,
+            &weapon,
+            &movesets,
+            &mut energy,
+        );
         assert!(result.success);
         assert_eq!(state.phase, CombatPhase::Parrying);
         assert!((state.parry_window - PARRY_WINDOW_SECS).abs() < 0.001);
@@ -1044,8 +1191,15 @@ mod tests {
         let mut state = CombatState::default();
         let weapon = test_sword();
         let movesets = WeaponMovesets::default();
+        let mut energy = CombatEnergy::default();
 
-        let result = try_combat_action(&mut state, ActionType::Dodge, &weapon, &movesets);
+        let result = try_combat_action(
+            &mut state,
+            ActionType::Dodge,
+            &weapon,
+            &movesets,
+            &mut energy,
+        );
         assert!(result.success);
         assert_eq!(state.phase, CombatPhase::Dodging);
         assert!((state.i_frames - DODGE_IFRAMES_SECS).abs() < 0.001);
@@ -1057,13 +1211,26 @@ mod tests {
         let mut state = CombatState::default();
         let weapon = test_sword();
         let movesets = WeaponMovesets::default();
+        let mut energy = CombatEnergy::default();
 
-        let result = try_combat_action(&mut state, ActionType::Block, &weapon, &movesets);
+        let result = try_combat_action(
+            &mut state,
+            ActionType::Block,
+            &weapon,
+            &movesets,
+            &mut energy,
+        );
         assert!(result.success);
         assert_eq!(state.phase, CombatPhase::Blocking);
 
         // Release block
-        let result = try_combat_action(&mut state, ActionType::BlockRelease, &weapon, &movesets);
+        let result = try_combat_action(
+            &mut state,
+            ActionType::BlockRelease,
+            &weapon,
+            &movesets,
+            &mut energy,
+        );
         assert!(result.success);
         assert_eq!(state.phase, CombatPhase::Idle);
     }
@@ -1228,6 +1395,84 @@ mod tests {
         assert_eq!(energy.kinetic, 50.0); // Unchanged
     }
 
+    #[test]
+    fn test_drained_player_cannot_heavy_attack() {
+        let mut state = CombatState::default();
+        let weapon = test_sword();
+        let movesets = WeaponMovesets::default();
+        let mut energy = CombatEnergy::default();
+        energy.stamina = 20.0;
+
+        let result = try_combat_action(
+            &mut state,
+            ActionType::HeavyAttack,
+            &weapon,
+            &movesets,
+            &mut energy,
+        );
+        assert!(!result.success);
+        assert_eq!(state.phase, CombatPhase::Idle);
+        assert_eq!(energy.stamina, 20.0); // Nothing spent on a rejected action
+
+        // A light attack is still affordable
+        let result = try_combat_action(
+            &mut state,
+            ActionType::Attack,
+            &weapon,
+            &movesets,
+            &mut energy,
+        );
+        assert!(result.success);
+        assert_eq!(
+            energy.stamina,
+            20.0 - movesets.action_costs.cost(ActionType::Attack)
+        );
+    }
+
+    #[test]
+    fn test_failed_action_costs_no_stamina() {
+        let mut state = CombatState::default();
+        let weapon = test_sword();
+        let movesets = WeaponMovesets::default();
+        let mut energy = CombatEnergy::default();
+
+        let _ = try_combat_action(
+            &mut state,
+            ActionType::Attack,
+            &weapon,
+            &movesets,
+            &mut energy,
+        );
+        let after_first = energy.stamina;
+        // Still attacking: rejected by the state machine, not charged
+        let result = try_combat_action(
+            &mut state,
+            ActionType::Dodge,
+            &weapon,
+            &movesets,
+            &mut energy,
+        );
+        assert!(!result.success);
+        assert_eq!(energy.stamina, after_first);
+    }
+
+    #[test]
+    fn test_stamina_regenerates_to_cap() {
+        let mut energy = CombatEnergy {
+            stamina: 0.0,
+            ..Default::default()
+        };
+
+        energy.regen_stamina(1.0);
+        assert_eq!(energy.stamina, DEFAULT_STAMINA_REGEN);
+
+        // Simulate 10 seconds at 60 ticks per second
+        for _ in 0..600 {
+            energy.regen_stamina(1.0 / 60.0);
+        }
+        assert_eq!(energy.stamina, energy.stamina_max);
+    }
+
     #[test]
     fn test_mastery_xp_rewards() {
         let (domain, xp) = mastery_xp_for_action(ActionType::Parry, DamageOutcome::Parried);
@@ -1244,8 +1489,15 @@ mod tests {
         let mut state = CombatState::default();
         let weapon = test_sword();
         let movesets = WeaponMovesets::default();
+        let mut energy = CombatEnergy::default();
 
-        let result = try_combat_action(&mut state, ActionType::HeavyAttack, &weapon, &movesets);
+        let result = try_combat_action(
+            &mut state,
+            ActionType::HeavyAttack,
+            &weapon,
+            &movesets,
+            &mut energy,
+        );
         assert!(result.success);
         assert_eq!(result.new_phase, CombatPhase::Attacking);
         assert_eq!(result.combo_step, 0); // Heavy resets combo
@@ -1283,13 +1535,26 @@ mod tests {
         let mut state = CombatState::default();
         let weapon = test_sword();
         let movesets = WeaponMovesets::default();
+        let mut energy = CombatEnergy::default();
 
         // Enter blocking
-        let _ = try_combat_action(&mut state, ActionType::Block, &weapon, &movesets);
+        let _ = try_combat_action(
+            &mut state,
+            ActionType::Block,
+            &weapon,
+            &movesets,
+            &mut energy,
+        );
         assert_eq!(state.phase, CombatPhase::Blocking);
 
         // Parry from blocking should work
-        let result = try_combat_action(&mut state, ActionType::Parry, &weapon, &movesets);
+        let result = try_combat_action(
+            &mut state,
+            ActionType::Parry,
+            &weapon,
+            &movesets,
+            &mut energy,
+        );
         assert!(result.success);
         assert_eq!(state.phase, CombatPhase::Parrying);
     }
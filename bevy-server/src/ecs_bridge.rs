@@ -273,7 +273,10 @@ pub fn process_game_commands(
     mut players: Query<(Entity, &mut Player)>,
     mut monsters: Query<(Entity, &mut Monster)>,
     mut destruction: ResMut<crate::destruction::FloorDestructionManager>,
-    mut combat_states: Query<&mut crate::combat::CombatState>,
+    mut combat_states: Query<(
+        &mut crate::combat::CombatState,
+        &mut crate::combat::CombatEnergy,
+    )>,
     weapons: Query<&crate::combat::EquippedWeapon>,
     movesets: Res<crate::combat::WeaponMovesets>,
 ) {
@@ -445,10 +448,15 @@ pub fn process_game_commands(
                     let weapon = weapons.get(entity);
 
                     match (combat_state, weapon) {
-                        (Ok(mut cs), Ok(w)) => {
+                        (Ok((mut cs, mut energy)), Ok(w)) => {
                             cs.facing = facing;
-                            let action_result =
-                                crate::combat::try_combat_action(&mut cs, action, w, &movesets);
+                            let action_result = crate::combat::try_combat_action(
+                                &mut cs,
+                                action,
+                                w,
+                                &movesets,
+                                &mut energy,
+                            );
                             CombatActionCommandResult {
                                 success: action_result.success,
                                 message: action_result.message.clone(),
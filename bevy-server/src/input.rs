@@ -8,6 +8,7 @@
 
 use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 // ============================================================================
 // Input Types
@@ -64,6 +65,70 @@ pub const MAX_MOVE_SPEED: f32 = 10.0;
 pub const SPEED_TOLERANCE: f32 = 1.5;
 /// Maximum facing change per tick (anti-spinbot, radians)
 pub const MAX_FACING_DELTA: f32 = std::f32::consts::TAU;
+/// A tick's move this many times past the allowed distance is a teleport
+/// (rejected outright instead of clamped)
+pub const TELEPORT_FACTOR: f32 = 3.0;
+
+// ============================================================================
+// Anti-cheat Flags
+// ============================================================================
+
+/// How a proposed per-tick displacement compares to the speed limit
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DisplacementCheck {
+    /// Within `max_speed * SPEED_TOLERANCE * dt`
+    Legit,
+    /// Too far for one tick; clamped to the allowed distance
+    OverSpeed,
+    /// Far past the limit (or non-finite); the player stays put
+    Teleport,
+}
+
+/// Raised by `process_player_input` for every move that failed
+/// `check_displacement`, for the anti-cheat module to consume
+#[derive(Event, Debug, Clone)]
+pub struct SuspiciousMovement {
+    pub player_id: u64,
+    pub check: DisplacementCheck,
+    /// Distance the client tried to cover this tick
+    pub attempted: f32,
+    /// Distance allowed this tick
+    pub allowed: f32,
+}
+
+/// Per-player tally of `SuspiciousMovement` flags
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MovementViolationCounts {
+    pub over_speed: u32,
+    pub teleports: u32,
+}
+
+/// Movement violations seen so far, keyed by player id
+#[derive(Resource, Debug, Default)]
+pub struct MovementViolations {
+    pub per_player: HashMap<u64, MovementViolationCounts>,
+}
+
+impl MovementViolations {
+    pub fn get(&self, player_id: u64) -> MovementViolationCounts {
+        self.per_player.get(&player_id).copied().unwrap_or_default()
+    }
+}
+
+/// Anti-cheat consumer of `SuspiciousMovement`: tallies flags per player
+pub fn record_suspicious_movement(
+    mut events: EventReader<SuspiciousMovement>,
+    mut violations: ResMut<MovementViolations>,
+) {
+    for event in events.read() {
+        let counts = violations.per_player.entry(event.player_id).or_default();
+        match event.check {
+            DisplacementCheck::Legit => {}
+            DisplacementCheck::OverSpeed => counts.over_speed += 1,
+            DisplacementCheck::Teleport => counts.teleports += 1,
+        }
+    }
+}
 
 // ============================================================================
 // Validation Functions
@@ -94,6 +159,58 @@ pub fn validate_movement(movement: [f32; 3]) -> Option<Vec3> {
     }
 }
 
+/// Distance a player may cover in one tick of `dt` seconds at `max_speed`
+pub fn max_tick_distance(dt: f32, max_speed: f32) -> f32 {
+    max_speed.max(0.0) * SPEED_TOLERANCE * dt.max(0.0)
+}
+
+/// Classify the move from `prev` to `proposed` over one tick
+pub fn check_displacement(
+    prev: Vec3,
+    proposed: Vec3,
+    dt: f32,
+    max_speed: f32,
+) -> DisplacementCheck {
+    if !proposed.is_finite() {
+        return DisplacementCheck::Teleport;
+    }
+    let distance = prev.distance(proposed);
+    let allowed = max_tick_distance(dt, max_speed);
+    if distance <= allowed {
+        DisplacementCheck::Legit
+    } else if distance > allowed * TELEPORT_FACTOR {
+        DisplacementCheck::Teleport
+    } else {
+        DisplacementCheck::OverSpeed
+    }
+}
+
+/// Bound a per-tick move: legit moves pass through, over-speed moves are
+/// clamped to the allowed distance along their direction, teleports are
+/// rejected (the player stays at `prev`). Use `check_displacement` to find
+/// out which case applied.
+pub fn validate_displacement(prev: Vec3, proposed: Vec3, dt: f32, max_speed: f32) -> Vec3 {
+    match check_displacement(prev, proposed, dt, max_speed) {
+        DisplacementCheck::Legit => proposed,
+        DisplacementCheck::OverSpeed => {
+            prev + (proposed - prev).normalize() * max_tick_distance(dt, max_speed)
+        }
+        DisplacementCheck::Teleport => prev,
+    }
+}
+
+/// Apply one tick of raw client movement from the authoritative `prev`.
+/// The unclamped delta goes through `validate_displacement`, so legit moves
+/// pass, over-speed moves are clamped and teleports leave the player at
+/// `prev`; the returned check says which case applied.
+pub fn resolve_movement(prev: Vec3, movement: [f32; 3], dt: f32) -> (Vec3, DisplacementCheck) {
+    let proposed = prev + Vec3::from(movement) * dt;
+    (
+        validate_displacement(prev, proposed, dt, MAX_MOVE_SPEED),
+        check_displacement(prev, proposed, dt, MAX_MOVE_SPEED),
+    )
+}
+
 /// Validate facing direction (must be finite, within valid range)
 pub fn validate_facing(facing: f32) -> f32 {
     if !facing.is_finite() {
@@ -155,6 +272,100 @@ mod tests {
         assert!((result.z - 8.0).abs() < 0.01);
     }
 
+    #[test]
+    fn test_validate_displacement_legit_move() {
+        let prev = Vec3::new(1.0, 0.0, 1.0);
+        let proposed = prev + Vec3::new(0.3, 0.0, 0.4); // 0.5 in a 50ms tick = 10 u/s
+        assert_eq!(
+            check_displacement(prev, proposed, 0.05, MAX_MOVE_SPEED),
+            DisplacementCheck::Legit
+        );
+        assert_eq!(
+            validate_displacement(prev, proposed, 0.05, MAX_MOVE_SPEED),
+            proposed
+        );
+    }
+
+    #[test]
+    fn test_validate_displacement_clamps_over_speed() {
+        let prev = Vec3::ZERO;
+        let proposed = Vec3::new(2.0, 0.0, 0.0); // 40 u/s, allowed 15 u/s
+        assert_eq!(
+            check_displacement(prev, proposed, 0.05, MAX_MOVE_SPEED),
+            DisplacementCheck::OverSpeed
+        );
+        let result = validate_displacement(prev, proposed, 0.05, MAX_MOVE_SPEED);
+        assert!((result.length() - max_tick_distance(0.05, MAX_MOVE_SPEED)).abs() < 0.001);
+        assert!(result.x > 0.0 && result.z == 0.0);
+    }
+
+    #[test]
+    fn test_validate_displacement_rejects_teleport() {
+        let prev = Vec3::new(5.0, 0.0, 5.0);
+        for proposed in [Vec3::new(500.0, 0.0, 5.0), Vec3::new(f32::NAN, 0.0, 0.0)] {
+            assert_eq!(
+                check_displacement(prev, proposed, 0.05, MAX_MOVE_SPEED),
+                DisplacementCheck::Teleport
+            );
+            assert_eq!(
+                validate_displacement(prev, proposed, 0.05, MAX_MOVE_SPEED),
+                prev
+            );
+        }
+        // No time elapsed: standing still is fine, any move is not
+        assert_eq!(validate_displacement(prev, prev, 0.0, MAX_MOVE_SPEED), prev);
+        assert_eq!(
+            check_displacement(prev, prev + Vec3::X, 0.0, MAX_MOVE_SPEED),
+            DisplacementCheck::Teleport
+        );
+    }
+
+    #[test]
+    fn test_resolve_movement_uses_raw_delta() {
+        let prev = Vec3::new(1.0, 0.0, 1.0);
+        let (next, check) = resolve_movement(prev, [12.0, 0.0, 0.0], 0.05);
+        assert_eq!(check, DisplacementCheck::Legit);
+        assert!((next.x - (1.0 + 12.0 * 0.05)).abs() < 0.001);
+
+        // Over-speed moves get the same clamp as validate_displacement
+        let (next, check) = resolve_movement(prev, [30.0, 0.0, 0.0], 0.05);
+        assert_eq!(check, DisplacementCheck::OverSpeed);
+        let clamped =
+            validate_displacement(prev, prev + Vec3::X * 30.0 * 0.05, 0.05, MAX_MOVE_SPEED);
+        assert_eq!(next, clamped);
+        assert!((next.x - (1.0 + max_tick_distance(0.05, MAX_MOVE_SPEED))).abs() < 0.001);
+
+        for movement in [[1000.0, 0.0, 0.0], [f32::NAN, 0.0, 0.0]] {
+            let (next, check) = resolve_movement(prev, movement, 0.05);
+            assert_eq!(check, DisplacementCheck::Teleport);
+            assert_eq!(next, prev);
+        }
+    }
+
+    #[test]
+    fn test_record_suspicious_movement() {
+        let mut app = App::new();
+        app.add_event::<SuspiciousMovement>()
+            .init_resource::<MovementViolations>()
+            .add_systems(Update, record_suspicious_movement);
+        for check in [DisplacementCheck::OverSpeed, DisplacementCheck::Teleport] {
+            app.world_mut().send_event(SuspiciousMovement {
+                player_id: 3,
+                check,
+                attempted: 50.0,
+                allowed: 0.75,
+            });
+        }
+        app.update();
+        let counts = app.world().resource::<MovementViolations>().get(3);
+        assert_eq!(counts.over_speed, 1);
+        assert_eq!(counts.teleports, 1);
+        assert_eq!(
+            app.world().resource::<MovementViolations>().get(4),
+            MovementViolationCounts::default()
+        );
+    }
+
     #[test]
     fn test_validate_facing_normal() {
         assert!((validate_facing(1.5) - 1.5).abs() < 0.01);
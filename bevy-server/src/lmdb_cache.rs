@@ -23,21 +23,58 @@
 //! cache.set(1, &chunk_data)?;
 //! let chunk = cache.get(1)?;
 //! ```
+//!
+//! ## Eviction
+//! Each floor is written with an expiry time (`set` uses the cache TTL,
+//! `set_with_ttl` an explicit one) in a second `expiry` database.
+//! `evict_expired` drops floors past their expiry; `evict_to_size` trims the
+//! least recently accessed floors once the count exceeds a cap. Access order
+//! is tracked in memory so reads stay read-only transactions; floors not
+//! touched since the process started count as oldest.
 
 use crate::proto::tower::game::ChunkData;
+use heed::byteorder::NativeEndian;
 use heed::{Database, Env, EnvOpenOptions};
+use parking_lot::Mutex;
 use prost::Message;
+use std::collections::HashMap;
 use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
 use tracing::{debug, error, info, warn};
 
+/// Default time-to-live for cached floors (7 days)
+pub const DEFAULT_FLOOR_TTL_SECS: u64 = 7 * 24 * 60 * 60;
+
+/// In-memory access order for `evict_to_size`
+#[derive(Default)]
+struct AccessLog {
+    clock: u64,
+    last_access: HashMap<u32, u64>,
+}
+
+impl AccessLog {
+    fn touch(&mut self, floor_id: u32) {
+        self.clock += 1;
+        self.last_access.insert(floor_id, self.clock);
+    }
+}
+
 /// LMDB cache for persistent floor storage (embedded database)
 #[derive(Clone)]
 pub struct LmdbFloorCache {
     /// LMDB environment (holds the database)
     env: Arc<Env>,
     /// Database handle for floor storage (key: u32 floor_id, value: Vec<u8> protobuf)
-    db: Database<heed::types::U32<heed::byteorder::NativeEndian>, heed::types::Bytes>,
+    db: Database<heed::types::U32<NativeEndian>, heed::types::Bytes>,
+    /// Expiry per floor (key: u32 floor_id, value: u64 unix seconds)
+    expiry: Database<heed::types::U32<NativeEndian>, heed::types::U64<NativeEndian>>,
+    /// TTL applied by `set`
+    ttl_secs: u64,
+    access: Arc<Mutex<AccessLog>>,
+    /// Floors removed by `evict_expired` / `evict_to_size`
+    evictions: Arc<AtomicU64>,
 }
 
 impl LmdbFloorCache {
@@ -65,17 +102,14 @@ impl LmdbFloorCache {
         let env = unsafe {
             EnvOpenOptions::new()
                 .map_size(max_size_bytes)
-                .max_dbs(1)
+                .max_dbs(2)
                 .open(path)?
         };
 
         // Create/open database (requires write transaction)
         let mut wtxn = env.write_txn()?;
-        let db = env
-            .create_database::<heed::types::U32<heed::byteorder::NativeEndian>, heed::types::Bytes>(
-                &mut wtxn,
-                Some("floors"),
-            )?;
+        let db = env.create_database(&mut wtxn, Some("floors"))?;
+        let expiry = env.create_database(&mut wtxn, Some("expiry"))?;
         wtxn.commit()?;
 
         info!("LMDB cache opened successfully");
@@ -83,9 +117,19 @@ impl LmdbFloorCache {
         Ok(Self {
             env: Arc::new(env),
             db,
+            expiry,
+            ttl_secs: DEFAULT_FLOOR_TTL_SECS,
+            access: Arc::new(Mutex::new(AccessLog::default())),
+            evictions: Arc::new(AtomicU64::new(0)),
         })
     }
 
+    /// Use `ttl_secs` instead of `DEFAULT_FLOOR_TTL_SECS` for floors stored by `set`
+    pub fn with_ttl(mut self, ttl_secs: u64) -> Self {
+        self.ttl_secs = ttl_secs;
+        self
+    }
+
     /// Get floor from LMDB cache
     ///
     /// Returns `None` if floor not in cache or deserialization fails.
@@ -105,6 +149,7 @@ impl LmdbFloorCache {
             Ok(Some(bytes)) => match ChunkData::decode(bytes) {
                 Ok(chunk) => {
                     debug!("LMDB HIT for floor {}", floor_id);
+                    self.access.lock().touch(floor_id);
                     Some(chunk)
                 }
                 Err(e) => {
@@ -123,11 +168,22 @@ impl LmdbFloorCache {
         }
     }
 
-    /// Store floor in LMDB cache
+    /// Store floor in LMDB cache, expiring after the cache TTL
     ///
     /// # Performance
     /// ~20-100µs (memory-mapped write + fsync)
     pub fn set(&self, floor_id: u32, chunk: &ChunkData) -> Result<(), LmdbError> {
+        self.set_with_ttl(floor_id, chunk, self.ttl_secs, unix_now())
+    }
+
+    /// Store floor in LMDB cache, expiring `ttl_secs` after `now` (unix seconds)
+    pub fn set_with_ttl(
+        &self,
+        floor_id: u32,
+        chunk: &ChunkData,
+        ttl_secs: u64,
+        now: u64,
+    ) -> Result<(), LmdbError> {
         // Serialize to Protobuf binary
         let mut bytes = Vec::new();
         if let Err(e) = chunk.encode(&mut bytes) {
@@ -138,7 +194,10 @@ impl LmdbFloorCache {
         // Write transaction
         let mut wtxn = self.env.write_txn()?;
         self.db.put(&mut wtxn, &floor_id, &bytes)?;
+        self.expiry
+            .put(&mut wtxn, &floor_id, &now.saturating_add(ttl_secs))?;
         wtxn.commit()?;
+        self.access.lock().touch(floor_id);
 
         debug!("LMDB SET floor {}", floor_id);
         Ok(())
@@ -161,7 +220,9 @@ impl LmdbFloorCache {
     pub fn delete(&self, floor_id: u32) -> Result<(), LmdbError> {
         let mut wtxn = self.env.write_txn()?;
         self.db.delete(&mut wtxn, &floor_id)?;
+        self.expiry.delete(&mut wtxn, &floor_id)?;
         wtxn.commit()?;
+        self.access.lock().last_access.remove(&floor_id);
         debug!("LMDB DELETE floor {}", floor_id);
         Ok(())
     }
@@ -170,11 +231,83 @@ impl LmdbFloorCache {
     pub fn clear_all(&self) -> Result<(), LmdbError> {
         let mut wtxn = self.env.write_txn()?;
         self.db.clear(&mut wtxn)?;
+        self.expiry.clear(&mut wtxn)?;
         wtxn.commit()?;
+        self.access.lock().last_access.clear();
         info!("LMDB cache cleared");
         Ok(())
     }
 
+    /// Remove every floor whose expiry is at or before `now` (unix seconds).
+    /// Floors stored without an expiry never expire. Returns how many were removed.
+    pub fn evict_expired(&self, now: u64) -> Result<usize, LmdbError> {
+        let expired = {
+            let rtxn = self.env.read_txn()?;
+            let mut expired = Vec::new();
+            for entry in self.expiry.iter(&rtxn)? {
+                let (floor_id, expires_at) = entry?;
+                if expires_at <= now {
+                    expired.push(floor_id);
+                }
+            }
+            expired
+        };
+        self.evict(&expired)?;
+        if !expired.is_empty() {
+            info!("LMDB evicted {} expired floors", expired.len());
+        }
+        Ok(expired.len())
+    }
+
+    /// Remove the least recently accessed floors until at most `max_entries`
+    /// remain. Returns how many were removed.
+    pub fn evict_to_size(&self, max_entries: usize) -> Result<usize, LmdbError> {
+        let mut floor_ids = {
+            let rtxn = self.env.read_txn()?;
+            let mut floor_ids = Vec::new();
+            for entry in self.db.iter(&rtxn)? {
+                floor_ids.push(entry?.0);
+            }
+            floor_ids
+        };
+        if floor_ids.len() <= max_entries {
+            return Ok(0);
+        }
+
+        {
+            let access = self.access.lock();
+            floor_ids.sort_by_key(|id| (access.last_access.get(id).copied().unwrap_or(0), *id));
+        }
+        let oldest = &floor_ids[..floor_ids.len() - max_entries];
+        self.evict(oldest)?;
+        info!(
+            "LMDB evicted {} floors over the {} cap",
+            oldest.len(),
+            max_entries
+        );
+        Ok(oldest.len())
+    }
+
+    fn evict(&self, floor_ids: &[u32]) -> Result<(), LmdbError> {
+        if floor_ids.is_empty() {
+            return Ok(());
+        }
+        let mut wtxn = self.env.write_txn()?;
+        for floor_id in floor_ids {
+            self.db.delete(&mut wtxn, floor_id)?;
+            self.expiry.delete(&mut wtxn, floor_id)?;
+        }
+        wtxn.commit()?;
+
+        let mut access = self.access.lock();
+        for floor_id in floor_ids {
+            access.last_access.remove(floor_id);
+        }
+        self.evictions
+            .fetch_add(floor_ids.len() as u64, Ordering::Relaxed);
+        Ok(())
+    }
+
     /// Get cache statistics
     pub fn stats(&self) -> Result<LmdbCacheStats, LmdbError> {
         let rtxn = self.env.read_txn()?;
@@ -188,6 +321,7 @@ impl LmdbFloorCache {
             leaf_pages: 0,
             overflow_pages: 0,
             entries: floor_count as u64,
+            evictions: self.evictions.load(Ordering::Relaxed),
         })
     }
 
@@ -209,6 +343,15 @@ pub struct LmdbCacheStats {
     pub leaf_pages: u64,
     pub overflow_pages: u64,
     pub entries: u64,
+    /// Floors removed by TTL or size eviction since the cache was opened
+    pub evictions: u64,
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
 }
 
 /// LMDB error types
@@ -356,4 +499,55 @@ mod tests {
         // Cleanup
         let _ = std::fs::remove_dir_all(&temp_dir);
     }
+
+    fn fresh_cache(name: &str) -> (LmdbFloorCache, std::path::PathBuf) {
+        let temp_dir =
+            std::env::temp_dir().join(format!("lmdb_{}_test_{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&temp_dir);
+        let cache = LmdbFloorCache::new(&temp_dir, 10 * 1024 * 1024).unwrap();
+        (cache, temp_dir)
+    }
+
+    #[test]
+    fn test_lmdb_evict_expired() {
+        let (cache, temp_dir) = fresh_cache("ttl");
+        cache
+            .set_with_ttl(1, &create_test_chunk(1), 10, 1_000)
+            .unwrap();
+        cache
+            .set_with_ttl(2, &create_test_chunk(2), 5_000, 1_000)
+            .unwrap();
+
+        assert_eq!(cache.evict_expired(1_005).unwrap(), 0);
+        assert_eq!(cache.evict_expired(1_010).unwrap(), 1);
+        assert!(!cache.exists(1));
+        assert!(cache.get(1).is_none());
+        assert!(cache.exists(2));
+
+        let stats = cache.stats().unwrap();
+        assert_eq!(stats.floor_count, 1);
+        assert_eq!(stats.evictions, 1);
+
+        let _ = std::fs::remove_dir_all(&temp_dir);
+    }
+
+    #[test]
+    fn test_lmdb_evict_to_size_drops_least_recent() {
+        let (cache, temp_dir) = fresh_cache("lru");
+        for i in 1..=4 {
+            cache.set(i, &create_test_chunk(i)).unwrap();
+        }
+        // Floor 1 becomes the most recently used
+        assert!(cache.get(1).is_some());
+
+        assert_eq!(cache.evict_to_size(4).unwrap(), 0);
+        assert_eq!(cache.evict_to_size(2).unwrap(), 2);
+        assert!(cache.exists(1));
+        assert!(!cache.exists(2));
+        assert!(!cache.exists(3));
+        assert!(cache.exists(4));
+        assert_eq!(cache.stats().unwrap().evictions, 2);
+
+        let _ = std::fs::remove_dir_all(&temp_dir);
+    }
 }
@@ -8,7 +8,7 @@ use bevy_replicon_renet::{
 use std::net::{SocketAddr, UdpSocket};
 use std::sync::Arc;
 use std::time::Duration;
-use tracing::{error, info};
+use tracing::{error, info, warn};
 
 // Shared modules from the library crate (ensures type compatibility with API layer)
 use bevy_rapier3d::prelude::{NoUserData, RapierPhysicsPlugin};
@@ -153,6 +153,8 @@ fn main() {
         .replicate::<FloorTile>()
         // Register client-to-server input event (bevy_replicon networking)
         .add_client_event::<input::PlayerInput>(ChannelKind::Ordered)
+        .add_event::<input::SuspiciousMovement>()
+        .init_resource::<input::MovementViolations>()
         // Server configuration (20 Hz for responsive combat)
         .insert_resource(ServerConfig {
             max_players_per_floor: 100, // Dynamic scaling (see below)
@@ -182,11 +184,15 @@ fn main() {
             (
                 handle_player_connections,
                 process_player_input,
+                input::record_suspicious_movement.after(process_player_input),
                 update_game_state,
             ),
         )
         // Combat systems
-        .add_systems(Update, combat::update_combat_timers)
+        .add_systems(
+            Update,
+            (combat::update_combat_timers, combat::update_combat_energy),
+        )
         // Monster AI systems
         .add_systems(Update, monster_gen::update_monster_ai)
         // Destruction systems
@@ -317,10 +323,11 @@ fn handle_player_connections(
 fn process_player_input(
     mut input_events: EventReader<FromClient<input::PlayerInput>>,
     mut players: Query<(Entity, &mut Player, &mut Transform)>,
-    mut combat_states: Query<&mut combat::CombatState>,
+    mut combat_states: Query<(&mut combat::CombatState, &mut combat::CombatEnergy)>,
     weapons: Query<&combat::EquippedWeapon>,
     movesets: Res<combat::WeaponMovesets>,
     time: Res<Time>,
+    mut suspicious: EventWriter<input::SuspiciousMovement>,
 ) {
     let dt = time.delta_secs();
 
@@ -335,20 +342,34 @@ fn process_player_input(
             continue;
         };
 
-        // Validate and apply movement
-        if let Some(movement) = input::validate_movement(player_input.movement) {
-            transform.translation += movement * dt;
-            player.position = transform.translation;
+        // Validate the raw (unclamped) move against the authoritative position
+        let prev = transform.translation;
+        let (next, check) = input::resolve_movement(prev, player_input.movement, dt);
+        if check != input::DisplacementCheck::Legit {
+            warn!("Suspicious movement from player {}: {:?}", player.id, check);
+            suspicious.send(input::SuspiciousMovement {
+                player_id: player.id,
+                check,
+                attempted: Vec3::from(player_input.movement).length() * dt,
+                allowed: input::max_tick_distance(dt, input::MAX_MOVE_SPEED),
+            });
         }
+        transform.translation = next;
+        player.position = next;
 
         // Process combat action
         if let Some(action) = player_input.action {
             if let Some(combat_action) = action.to_combat_action() {
-                if let Ok(mut cs) = combat_states.get_mut(entity) {
+                if let Ok((mut cs, mut energy)) = combat_states.get_mut(entity) {
                     cs.facing = input::validate_facing(player_input.facing);
                     if let Ok(weapon) = weapons.get(entity) {
-                        let _ =
-                            combat::try_combat_action(&mut cs, combat_action, weapon, &movesets);
+                        let _ = combat::try_combat_action(
+                            &mut cs,
+                            combat_action,
+                            weapon,
+                            &movesets,
+                            &mut energy,
+                        );
                     }
                 }
             }
@@ -362,3 +383,86 @@ fn update_game_state(mut players: Query<(&mut Player, &Transform)>) {
         player.position = transform.translation;
     }
 }
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn input_app(start: Vec3) -> App {
+        let mut app = App::new();
+        app.add_event::<FromClient<input::PlayerInput>>()
+            .add_event::<input::SuspiciousMovement>()
+            .init_resource::<input::MovementViolations>()
+            .init_resource::<Time>()
+            .insert_resource(combat::WeaponMovesets::default())
+            .add_systems(
+                Update,
+                (process_player_input, input::record_suspicious_movement).chain(),
+            );
+        app.world_mut().spawn((
+            Player {
+                id: 7,
+                position: start,
+                health: 100.0,
+                current_floor: 1,
+            },
+            Transform::from_translation(start),
+        ));
+        app.world_mut()
+            .resource_mut::<Time>()
+            .advance_by(Duration::from_millis(50));
+        app
+    }
+
+    fn send_move(app: &mut App, movement: [f32; 3]) {
+        app.world_mut().send_event(FromClient {
+            client_id: ClientId::new(7),
+            event: input::PlayerInput {
+                movement,
+                facing: 0.0,
+                action: None,
+                sequence: 1,
+            },
+        });
+        app.update();
+    }
+
+    fn player_position(app: &mut App) -> Vec3 {
+        let mut query = app.world_mut().query::<(&Player, &Transform)>();
+        let (player, transform) = query.single(app.world());
+        assert_eq!(player.position, transform.translation);
+        transform.translation
+    }
+
+    #[test]
+    fn test_teleport_input_rejected() {
+        let start = Vec3::new(5.0, 0.0, 5.0);
+        let mut app = input_app(start);
+
+        send_move(&mut app, [1000.0, 0.0, 0.0]);
+
+        assert_eq!(player_position(&mut app), start);
+        let counts = app.world().resource::<input::MovementViolations>().get(7);
+        assert_eq!(counts.teleports, 1);
+        assert_eq!(counts.over_speed, 0);
+    }
+
+    #[test]
+    fn test_legit_input_applied() {
+        let start = Vec3::new(5.0, 0.0, 5.0);
+        let mut app = input_app(start);
+
+        send_move(&mut app, [4.0, 0.0, 0.0]);
+
+        let pos = player_position(&mut app);
+        assert!((pos.x - 5.2).abs() < 0.001);
+        assert_eq!(
+            app.world().resource::<input::MovementViolations>().get(7),
+            input::MovementViolationCounts::default()
+        );
+    }
+}
@@ -1,6 +1,8 @@
 //! Server Metrics — Lightweight request/game metrics with Prometheus + JSON export
 //!
 //! Uses lock-free atomics for all counters. No external metrics crate needed.
+//! Floor generation latency is a fixed-bucket histogram (`LatencyHistogram`)
+//! recorded by the `GenerateFloor` endpoint.
 //!
 //! ## Endpoints
 //! - `GET /metrics` — Prometheus text format (for Grafana/Prometheus scraping)
@@ -17,10 +19,131 @@ use axum::{
 use serde::Serialize;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 use crate::api::ApiState;
 
+/// Upper bounds (seconds) of the floor generation latency buckets, 1ms..2s
+pub const GENERATION_LATENCY_BUCKETS: [f64; 11] = [
+    0.001, 0.0025, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.0,
+];
+
+/// Cumulative latency histogram over `GENERATION_LATENCY_BUCKETS` (plus an
+/// implicit `+Inf` bucket), lock-free like the rest of the metrics
+#[derive(Debug, Default)]
+pub struct LatencyHistogram {
+    /// Observations per bucket (not cumulative); last slot is `+Inf`
+    buckets: [AtomicU64; GENERATION_LATENCY_BUCKETS.len() + 1],
+    count: AtomicU64,
+    sum_us: AtomicU64,
+}
+
+/// Histogram snapshot for the JSON export, with percentile estimates for
+/// dashboards that can't read buckets
+#[derive(Debug, Clone, Serialize)]
+pub struct LatencySummary {
+    /// `(upper bound seconds, cumulative count)`; `+Inf` is `count`
+    pub buckets: Vec<(f64, u64)>,
+    pub count: u64,
+    pub sum_secs: f64,
+    pub p50: f64,
+    pub p95: f64,
+    pub p99: f64,
+}
+
+impl LatencyHistogram {
+    pub fn new() -> Arc<Self> {
+        Arc::new(Self::default())
+    }
+
+    pub fn observe(&self, latency: Duration) {
+        let secs = latency.as_secs_f64();
+        let index = GENERATION_LATENCY_BUCKETS
+            .iter()
+            .position(|&le| secs <= le)
+            .unwrap_or(GENERATION_LATENCY_BUCKETS.len());
+        self.buckets[index].fetch_add(1, Ordering::Relaxed);
+        self.count.fetch_add(1, Ordering::Relaxed);
+        self.sum_us
+            .fetch_add(latency.as_micros() as u64, Ordering::Relaxed);
+    }
+
+    pub fn count(&self) -> u64 {
+        self.count.load(Ordering::Relaxed)
+    }
+
+    pub fn sum_secs(&self) -> f64 {
+        self.sum_us.load(Ordering::Relaxed) as f64 / 1_000_000.0
+    }
+
+    /// Cumulative counts for each finite bucket, in bucket order
+    pub fn cumulative_counts(&self) -> Vec<u64> {
+        let mut total = 0;
+        GENERATION_LATENCY_BUCKETS
+            .iter()
+            .enumerate()
+            .map(|(i, _)| {
+                total += self.buckets[i].load(Ordering::Relaxed);
+                total
+            })
+            .collect()
+    }
+
+    /// Estimate the `q` quantile (0..=1) by interpolating inside the bucket
+    /// that holds it, as Prometheus' `histogram_quantile` does. Observations
+    /// past the last bound report the last bound. 0.0 when empty.
+    pub fn percentile(&self, q: f64) -> f64 {
+        let count = self.count();
+        if count == 0 {
+            return 0.0;
+        }
+        let rank = q.clamp(0.0, 1.0) * count as f64;
+        let mut lower = 0.0;
+        let mut below = 0u64;
+        for (i, &upper) in GENERATION_LATENCY_BUCKETS.iter().enumerate() {
+            let in_bucket = self.buckets[i].load(Ordering::Relaxed);
+            if in_bucket > 0 && (below + in_bucket) as f64 >= rank {
+                let fraction = (rank - below as f64) / in_bucket as f64;
+                return lower + (upper - lower) * fraction.clamp(0.0, 1.0);
+            }
+            below += in_bucket;
+            lower = upper;
+        }
+        lower
+    }
+
+    pub fn summary(&self) -> LatencySummary {
+        LatencySummary {
+            buckets: GENERATION_LATENCY_BUCKETS
+                .iter()
+                .copied()
+                .zip(self.cumulative_counts())
+                .collect(),
+            count: self.count(),
+            sum_secs: self.sum_secs(),
+            p50: self.percentile(0.50),
+            p95: self.percentile(0.95),
+            p99: self.percentile(0.99),
+        }
+    }
+
+    /// Prometheus text exposition of this histogram under `name`
+    pub fn render_prometheus(&self, name: &str, help: &str) -> String {
+        let mut out = format!("# HELP {name} {help}\n# TYPE {name} histogram\n");
+        for (le, cumulative) in GENERATION_LATENCY_BUCKETS
+            .iter()
+            .zip(self.cumulative_counts())
+        {
+            out.push_str(&format!("{name}_bucket{{le=\"{le}\"}} {cumulative}\n"));
+        }
+        let count = self.count();
+        out.push_str(&format!("{name}_bucket{{le=\"+Inf\"}} {count}\n"));
+        out.push_str(&format!("{name}_sum {:.6}\n", self.sum_secs()));
+        out.push_str(&format!("{name}_count {count}\n"));
+        out
+    }
+}
+
 /// Shared metrics state (all lock-free atomics)
 #[derive(Debug)]
 pub struct ServerMetrics {
@@ -30,6 +153,9 @@ pub struct ServerMetrics {
     pub total_errors: AtomicU64,
     /// Cumulative request duration in microseconds (for computing average)
     pub total_duration_us: AtomicU64,
+    /// Floor generation latency, recorded by the `GenerateFloor` handler
+    /// (a `FloorGenerator` can share it via `with_latency_histogram`)
+    pub generation_latency: Arc<LatencyHistogram>,
     /// Server start time (for uptime calculation)
     pub start_time: Instant,
 }
@@ -40,6 +166,7 @@ impl Default for ServerMetrics {
             total_requests: AtomicU64::new(0),
             total_errors: AtomicU64::new(0),
             total_duration_us: AtomicU64::new(0),
+            generation_latency: LatencyHistogram::new(),
             start_time: Instant::now(),
         }
     }
@@ -139,7 +266,7 @@ pub async fn prometheus_handler(State(state): State<ApiState>) -> impl IntoRespo
         0.0
     };
 
-    let body = format!(
+    let mut body = format!(
         "# HELP tower_requests_total Total HTTP requests served\n\
          # TYPE tower_requests_total counter\n\
          tower_requests_total {total_requests}\n\
@@ -177,6 +304,11 @@ pub async fn prometheus_handler(State(state): State<ApiState>) -> impl IntoRespo
          tower_uptime_seconds {uptime:.2}\n",
         avg_tick_s = avg_tick_ms / 1000.0,
     );
+    body.push('\n');
+    body.push_str(&m.generation_latency.render_prometheus(
+        "tower_generation_latency_seconds",
+        "Floor generation latency",
+    ));
 
     (
         [(
@@ -202,6 +334,7 @@ pub struct JsonMetrics {
     pub total_errors: u64,
     pub rps: f64,
     pub avg_request_duration_ms: f64,
+    pub generation_latency_seconds: LatencySummary,
 }
 
 pub async fn json_metrics_handler(State(state): State<ApiState>) -> Json<JsonMetrics> {
@@ -230,6 +363,7 @@ pub async fn json_metrics_handler(State(state): State<ApiState>) -> Json<JsonMet
         total_errors: m.total_errors.load(Ordering::Relaxed),
         rps: m.requests_per_second(),
         avg_request_duration_ms: m.avg_duration_ms(),
+        generation_latency_seconds: m.generation_latency.summary(),
     })
 }
 
@@ -276,4 +410,43 @@ mod tests {
         let rps = m.requests_per_second();
         assert!(rps.is_finite());
     }
+
+    #[test]
+    fn test_generation_latency_buckets() {
+        let h = LatencyHistogram::default();
+        for ms in [0.5, 1.0, 3.0, 3.0, 40.0, 900.0, 5000.0] {
+            h.observe(Duration::from_secs_f64(ms / 1000.0));
+        }
+        assert_eq!(h.count(), 7);
+        // le: 1ms, 2.5ms, 5ms, 10ms, 25ms, 50ms, 100ms, 250ms, 500ms, 1s, 2s
+        assert_eq!(h.cumulative_counts(), vec![2, 2, 4, 4, 4, 5, 5, 5, 5, 6, 6]);
+        assert!((h.sum_secs() - 5.9475).abs() < 0.001);
+
+        let text = h.render_prometheus(
+            "tower_generation_latency_seconds",
+            "Floor generation latency",
+        );
+        assert!(text.contains("# TYPE tower_generation_latency_seconds histogram"));
+        assert!(text.contains("tower_generation_latency_seconds_bucket{le=\"0.005\"} 4\n"));
+        assert!(text.contains("tower_generation_latency_seconds_bucket{le=\"+Inf\"} 7\n"));
+        assert!(text.contains("tower_generation_latency_seconds_count 7\n"));
+    }
+
+    #[test]
+    fn test_generation_latency_percentiles_monotonic() {
+        let h = LatencyHistogram::default();
+        assert_eq!(h.summary().p99, 0.0);
+        for i in 0..1000u64 {
+            // Mostly fast floors with a slow tail
+            let ms = if i % 20 == 0 { 300 + i } else { 1 + i % 8 };
+            h.observe(Duration::from_millis(ms));
+        }
+        let summary = h.summary();
+        assert!(summary.p50 > 0.0);
+        assert!(summary.p50 <= summary.p95);
+        assert!(summary.p95 <= summary.p99);
+        assert!(summary.p50 <= 0.01);
+        assert!(summary.p99 >= 0.25 && summary.p99 <= 2.0);
+        assert_eq!(summary.buckets.last().unwrap().1, 1000);
+    }
 }
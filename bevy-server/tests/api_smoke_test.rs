@@ -133,6 +133,48 @@ async fn test_generate_floor_endpoint() {
     assert!(tile["is_walkable"].is_boolean());
 }
 
+#[tokio::test]
+async fn test_generate_floor_records_latency() {
+    let (router, _tmp) = create_test_router().await;
+
+    for floor_id in 1..=3 {
+        let req = Request::builder()
+            .method("POST")
+            .uri("/tower.GenerationService/GenerateFloor")
+            .header("content-type", "application/json")
+            .body(Body::from(format!(
+                r#"{{"tower_seed": 12345, "floor_id": {floor_id}}}"#
+            )))
+            .unwrap();
+        let resp = router.clone().oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), 200);
+    }
+
+    let req = Request::builder()
+        .method("GET")
+        .uri("/metrics/json")
+        .body(Body::empty())
+        .unwrap();
+    let resp = router.clone().oneshot(req).await.unwrap();
+    let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let json: Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["generation_latency_seconds"]["count"], 3);
+
+    let req = Request::builder()
+        .method("GET")
+        .uri("/metrics")
+        .body(Body::empty())
+        .unwrap();
+    let resp = router.oneshot(req).await.unwrap();
+    let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let text = String::from_utf8(body.to_vec()).unwrap();
+    assert!(text.contains("tower_generation_latency_seconds_count 3"));
+}
+
 #[tokio::test]
 async fn test_generate_floor_deterministic() {
     // Same seed + floor_id should produce identical results
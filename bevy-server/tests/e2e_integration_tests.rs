@@ -53,6 +53,7 @@ fn test_full_floor_pipeline() {
             range: 2.0,
         };
         let movesets = combat::WeaponMovesets::default();
+        let mut energy = combat::CombatEnergy::default();
 
         // Attack combo
         let r1 = combat::try_combat_action(
@@ -60,6 +61,7 @@ fn test_full_floor_pipeline() {
             combat::ActionType::Attack,
             &weapon,
             &movesets,
+            &mut energy,
         );
         assert!(r1.success);
         assert_eq!(r1.combo_step, 0);
@@ -116,6 +118,8 @@ fn test_combat_parry_counter_sequence() {
         range: 2.0,
     };
     let movesets = combat::WeaponMovesets::default();
+    let mut attacker_energy = combat::CombatEnergy::default();
+    let mut defender_energy = combat::CombatEnergy::default();
 
     // Attacker attacks
     let atk = combat::try_combat_action(
@@ -123,12 +127,18 @@ fn test_combat_parry_counter_sequence() {
         combat::ActionType::Attack,
         &weapon,
         &movesets,
+        &mut attacker_energy,
     );
     assert!(atk.success);
 
     // Defender parries
-    let parry =
-        combat::try_combat_action(&mut defender, combat::ActionType::Parry, &weapon, &movesets);
+    let parry = combat::try_combat_action(
+        &mut defender,
+        combat::ActionType::Parry,
+        &weapon,
+        &movesets,
+        &mut defender_energy,
+    );
     assert!(parry.success);
     assert!(defender.parry_window > 0.0);
 
@@ -172,6 +182,7 @@ fn test_combat_parry_counter_sequence() {
         combat::ActionType::Attack,
         &weapon,
         &movesets,
+        &mut defender_energy,
     );
     assert!(counter.success);
 
@@ -591,6 +602,7 @@ fn test_kill_monster_full_pipeline() {
         combat::ActionType::HeavyAttack,
         &weapon,
         &movesets,
+        &mut player_energy,
     );
     assert!(result.success);
 
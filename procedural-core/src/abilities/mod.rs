@@ -14,6 +14,9 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+use crate::mastery::{MasteryDomain, MasteryProfile, MasteryTier, SkillTree};
+use crate::specialization::{all_specialization_branches, CooldownPolicy, UltimateAbility};
+
 /// Targeting type for abilities
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum AbilityTarget {
@@ -108,6 +111,90 @@ pub struct Ability {
     pub source: AbilitySource,
 }
 
+impl Ability {
+    /// Mastery domain whose tier gates this ability's upgrades: the domain
+    /// of the skill tree node or specialization branch it comes from.
+    /// Innate and equipment abilities fall under SemanticAttunement.
+    pub fn mastery_domain(&self) -> MasteryDomain {
+        match &self.source {
+            AbilitySource::MasteryNode(id) => SkillTree::new().get_node(id).map(|n| n.domain),
+            AbilitySource::Specialization(id) => all_specialization_branches()
+                .into_iter()
+                .find(|b| b.id == *id)
+                .map(|b| b.domain),
+            AbilitySource::Equipment(_) | AbilitySource::Innate => None,
+        }
+        .unwrap_or(MasteryDomain::SemanticAttunement)
+    }
+
+    /// Copy with effect magnitudes and radius scaled for `rank`
+    pub fn at_rank(&self, rank: AbilityRank) -> Ability {
+        let power = rank.power_multiplier();
+        let mut ability = self.clone();
+        ability.radius *= rank.radius_multiplier();
+        for effect in &mut ability.effects {
+            match effect {
+                AbilityEffect::Damage { base, .. } | AbilityEffect::Heal { base, .. } => {
+                    *base *= power
+                }
+                AbilityEffect::Shield { amount, .. } => *amount *= power,
+                _ => {}
+            }
+        }
+        ability
+    }
+}
+
+/// Highest rank an ability can reach
+pub const MAX_ABILITY_RANK: u32 = 5;
+/// Effect magnitude gained per rank above 1
+const RANK_POWER_STEP: f32 = 0.15;
+/// Radius gained per rank above 1
+const RANK_RADIUS_STEP: f32 = 0.10;
+
+/// Upgrade rank of a learned ability (1..=MAX_ABILITY_RANK)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct AbilityRank(pub u32);
+
+impl Default for AbilityRank {
+    fn default() -> Self {
+        Self(1)
+    }
+}
+
+impl AbilityRank {
+    /// Mastery tier needed to reach this rank
+    pub fn required_tier(&self) -> MasteryTier {
+        match self.0 {
+            0 | 1 => MasteryTier::Novice,
+            2 => MasteryTier::Apprentice,
+            3 => MasteryTier::Journeyman,
+            4 => MasteryTier::Expert,
+            _ => MasteryTier::Master,
+        }
+    }
+
+    pub fn power_multiplier(&self) -> f32 {
+        1.0 + self.0.saturating_sub(1) as f32 * RANK_POWER_STEP
+    }
+
+    pub fn radius_multiplier(&self) -> f32 {
+        1.0 + self.0.saturating_sub(1) as f32 * RANK_RADIUS_STEP
+    }
+}
+
+/// Why an ability upgrade was refused
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum UpgradeError {
+    UnknownAbility(String),
+    MaxRank,
+    TierTooLow {
+        domain: MasteryDomain,
+        required: MasteryTier,
+        current: MasteryTier,
+    },
+}
+
 /// Where the ability was unlocked from
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum AbilitySource {
@@ -175,6 +262,9 @@ pub enum AbilityEffect {
 pub struct AbilityCooldownTracker {
     /// ability_id → remaining cooldown seconds
     pub cooldowns: HashMap<String, f32>,
+    /// Remaining shared lockout across all ultimates (SharedGlobal policy)
+    #[serde(default)]
+    pub ultimate_lockout: f32,
 }
 
 impl AbilityCooldownTracker {
@@ -197,6 +287,30 @@ impl AbilityCooldownTracker {
         for cd in self.cooldowns.values_mut() {
             *cd = (*cd - delta).max(0.0);
         }
+        self.ultimate_lockout = (self.ultimate_lockout - delta).max(0.0);
+    }
+
+    /// Check if an ultimate is usable under the profile's cooldown policy
+    pub fn is_ultimate_ready(&self, ultimate_id: &str, policy: CooldownPolicy) -> bool {
+        let shared_ready = match policy {
+            CooldownPolicy::Independent => true,
+            CooldownPolicy::SharedGlobal { .. } => self.ultimate_lockout <= 0.0,
+        };
+        shared_ready && self.is_ready(ultimate_id)
+    }
+
+    /// Start an ultimate's own cooldown, plus the shared lockout if the policy demands it
+    pub fn start_ultimate_cooldown(&mut self, ultimate: &UltimateAbility, policy: CooldownPolicy) {
+        self.cooldowns
+            .insert(ultimate.id.clone(), ultimate.cooldown_seconds);
+        self.lock_ultimates(policy);
+    }
+
+    /// Start the shared ultimate lockout if the policy demands it
+    fn lock_ultimates(&mut self, policy: CooldownPolicy) {
+        if let CooldownPolicy::SharedGlobal { lockout } = policy {
+            self.ultimate_lockout = lockout;
+        }
     }
 
     /// Get remaining cooldown for an ability
@@ -212,6 +326,49 @@ impl AbilityCooldownTracker {
     }
 }
 
+/// Combat energy pools spent by ability costs
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AbilityResources {
+    pub kinetic: f32,
+    pub thermal: f32,
+    pub semantic: f32,
+}
+
+impl AbilityResources {
+    /// First pool that can't cover `cost`, if any (HP costs are not gated here)
+    fn shortfall(&self, cost: &AbilityCost) -> Option<(&'static str, f32, f32)> {
+        [
+            ("kinetic", cost.kinetic, self.kinetic),
+            ("thermal", cost.thermal, self.thermal),
+            ("semantic", cost.semantic, self.semantic),
+        ]
+        .into_iter()
+        .find(|(_, needed, available)| needed > available)
+    }
+
+    fn pay(&mut self, cost: &AbilityCost) {
+        self.kinetic -= cost.kinetic;
+        self.thermal -= cost.thermal;
+        self.semantic -= cost.semantic;
+    }
+}
+
+/// Why a cast was refused
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum CastError {
+    /// Slot is out of range or holds no ability
+    EmptySlot(usize),
+    OnCooldown {
+        ability_id: String,
+        remaining: f32,
+    },
+    InsufficientResources {
+        resource: String,
+        needed: f32,
+        available: f32,
+    },
+}
+
 /// Player's ability loadout (hotbar)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AbilityLoadout {
@@ -220,6 +377,9 @@ pub struct AbilityLoadout {
     /// All learned abilities (id → Ability)
     pub known_abilities: HashMap<String, Ability>,
     pub max_slots: usize,
+    /// ability_id → upgrade rank (missing = rank 1)
+    #[serde(default)]
+    pub ranks: HashMap<String, AbilityRank>,
 }
 
 impl Default for AbilityLoadout {
@@ -234,6 +394,7 @@ impl AbilityLoadout {
             slots: vec![None; 6],
             known_abilities: HashMap::new(),
             max_slots: 6,
+            ranks: HashMap::new(),
         }
     }
 
@@ -281,6 +442,100 @@ impl AbilityLoadout {
             .and_then(|id| self.known_abilities.get(id))
     }
 
+    /// Cast the ability in `slot`, with cooldowns kept in `cooldowns`.
+    ///
+    /// Refuses if the slot is empty, the ability is still cooling down (for
+    /// specialization abilities, also while `policy`'s shared ultimate
+    /// lockout runs), or any energy pool can't cover its cost. On success the
+    /// cost is deducted from `energy` and the cooldown starts, shortened by
+    /// `cdr` (0.2 = 20% shorter); on failure nothing changes.
+    pub fn try_cast(
+        &self,
+        slot: usize,
+        cooldowns: &mut AbilityCooldownTracker,
+        energy: &mut AbilityResources,
+        cdr: f32,
+        policy: CooldownPolicy,
+    ) -> Result<(), CastError> {
+        let ability = self.get_slot(slot).ok_or(CastError::EmptySlot(slot))?;
+
+        let ultimate = matches!(ability.source, AbilitySource::Specialization(_));
+        let ready = if ultimate {
+            cooldowns.is_ultimate_ready(&ability.id, policy)
+        } else {
+            cooldowns.is_ready(&ability.id)
+        };
+        if !ready {
+            let lockout = if ultimate {
+                cooldowns.ultimate_lockout
+            } else {
+                0.0
+            };
+            return Err(CastError::OnCooldown {
+                ability_id: ability.id.clone(),
+                remaining: cooldowns.remaining(&ability.id).max(lockout),
+            });
+        }
+        if let Some((resource, needed, available)) = energy.shortfall(&ability.cost) {
+            return Err(CastError::InsufficientResources {
+                resource: resource.to_string(),
+                needed,
+                available,
+            });
+        }
+
+        energy.pay(&ability.cost);
+        cooldowns.start_cooldown(ability);
+        cooldowns.apply_cdr(&ability.id, cdr);
+        if ultimate {
+            cooldowns.lock_ultimates(policy);
+        }
+        Ok(())
+    }
+
+    /// Current rank of a known ability
+    pub fn rank(&self, ability_id: &str) -> AbilityRank {
+        self.ranks.get(ability_id).copied().unwrap_or_default()
+    }
+
+    /// Raise a known ability one rank, if its mastery domain's tier allows it
+    pub fn upgrade(
+        &mut self,
+        ability_id: &str,
+        mastery: &MasteryProfile,
+    ) -> Result<(), UpgradeError> {
+        let ability = self
+            .known_abilities
+            .get(ability_id)
+            .ok_or_else(|| UpgradeError::UnknownAbility(ability_id.to_string()))?;
+
+        let current = self.rank(ability_id);
+        if current.0 >= MAX_ABILITY_RANK {
+            return Err(UpgradeError::MaxRank);
+        }
+        let next = AbilityRank(current.0 + 1);
+
+        let domain = ability.mastery_domain();
+        let tier = mastery.tier(domain);
+        if tier < next.required_tier() {
+            return Err(UpgradeError::TierTooLow {
+                domain,
+                required: next.required_tier(),
+                current: tier,
+            });
+        }
+
+        self.ranks.insert(ability_id.to_string(), next);
+        Ok(())
+    }
+
+    /// A known ability with its effects scaled by its current rank
+    pub fn effective_stats(&self, ability_id: &str) -> Option<Ability> {
+        self.known_abilities
+            .get(ability_id)
+            .map(|a| a.at_rank(self.rank(ability_id)))
+    }
+
     /// Count equipped abilities
     pub fn equipped_count(&self) -> usize {
         self.slots.iter().filter(|s| s.is_some()).count()
@@ -537,6 +792,224 @@ mod tests {
         assert!(!loadout.equip(0, "nonexistent"));
     }
 
+    fn rising_slash_loadout() -> AbilityLoadout {
+        let mut loadout = AbilityLoadout::new();
+        let abilities = default_abilities();
+        loadout.learn(abilities[0].clone()); // Rising Slash: 12s, 25 kinetic
+        loadout.equip(0, &abilities[0].id);
+        loadout
+    }
+
+    fn energy(kinetic: f32) -> AbilityResources {
+        AbilityResources {
+            kinetic,
+            thermal: 0.0,
+            semantic: 0.0,
+        }
+    }
+
+    fn cast(
+        loadout: &AbilityLoadout,
+        slot: usize,
+        cooldowns: &mut AbilityCooldownTracker,
+        pools: &mut AbilityResources,
+    ) -> Result<(), CastError> {
+        loadout.try_cast(slot, cooldowns, pools, 0.0, CooldownPolicy::Independent)
+    }
+
+    #[test]
+    fn test_try_cast_success() {
+        let loadout = rising_slash_loadout();
+        let mut cooldowns = AbilityCooldownTracker::new();
+        let mut pools = energy(100.0);
+        assert_eq!(cast(&loadout, 0, &mut cooldowns, &mut pools), Ok(()));
+        assert_eq!(pools.kinetic, 75.0);
+        assert_eq!(cooldowns.remaining("rising_slash"), 12.0);
+    }
+
+    #[test]
+    fn test_try_cast_rejects_on_cooldown() {
+        let loadout = rising_slash_loadout();
+        let mut cooldowns = AbilityCooldownTracker::new();
+        let mut pools = energy(100.0);
+        cast(&loadout, 0, &mut cooldowns, &mut pools).unwrap();
+
+        cooldowns.tick(4.0);
+        let err = cast(&loadout, 0, &mut cooldowns, &mut pools).unwrap_err();
+        assert!(matches!(err, CastError::OnCooldown { remaining, .. } if remaining == 8.0));
+        assert_eq!(pools.kinetic, 75.0, "refused casts cost nothing");
+
+        cooldowns.tick(8.0);
+        assert!(cast(&loadout, 0, &mut cooldowns, &mut pools).is_ok());
+    }
+
+    #[test]
+    fn test_try_cast_applies_cdr() {
+        let loadout = rising_slash_loadout();
+        let mut cooldowns = AbilityCooldownTracker::new();
+        let mut pools = energy(100.0);
+        loadout
+            .try_cast(
+                0,
+                &mut cooldowns,
+                &mut pools,
+                0.25,
+                CooldownPolicy::Independent,
+            )
+            .unwrap();
+        assert_eq!(cooldowns.remaining("rising_slash"), 9.0);
+        cooldowns.tick(9.0);
+        assert!(cast(&loadout, 0, &mut cooldowns, &mut pools).is_ok());
+    }
+
+    #[test]
+    fn test_try_cast_ultimates_share_lockout() {
+        let mut loadout = AbilityLoadout::new();
+        for id in ["ult_a", "ult_b"] {
+            let mut ability = default_abilities()[0].clone();
+            ability.id = id.into();
+            ability.source = AbilitySource::Specialization("test".into());
+            loadout.learn(ability);
+        }
+        loadout.equip(0, "ult_a");
+        loadout.equip(1, "ult_b");
+        let policy = CooldownPolicy::SharedGlobal { lockout: 30.0 };
+        let mut cooldowns = AbilityCooldownTracker::new();
+        let mut pools = energy(100.0);
+
+        loadout
+            .try_cast(0, &mut cooldowns, &mut pools, 0.0, policy)
+            .unwrap();
+        let err = loadout
+            .try_cast(1, &mut cooldowns, &mut pools, 0.0, policy)
+            .unwrap_err();
+        assert!(matches!(err, CastError::OnCooldown { remaining, .. } if remaining == 30.0));
+        // Independent ultimates only wait on their own cooldown
+        assert!(cast(&loadout, 1, &mut cooldowns, &mut pools).is_ok());
+    }
+
+    #[test]
+    fn test_try_cast_rejects_insufficient_resources() {
+        let loadout = rising_slash_loadout();
+        let mut cooldowns = AbilityCooldownTracker::new();
+        let mut pools = energy(10.0);
+        let err = cast(&loadout, 0, &mut cooldowns, &mut pools).unwrap_err();
+        assert_eq!(
+            err,
+            CastError::InsufficientResources {
+                resource: "kinetic".into(),
+                needed: 25.0,
+                available: 10.0,
+            }
+        );
+        assert_eq!(pools.kinetic, 10.0);
+        assert!(
+            cooldowns.is_ready("rising_slash"),
+            "no cooldown on a refused cast"
+        );
+    }
+
+    #[test]
+    fn test_try_cast_empty_slot() {
+        let loadout = rising_slash_loadout();
+        let mut cooldowns = AbilityCooldownTracker::new();
+        assert_eq!(
+            cast(&loadout, 2, &mut cooldowns, &mut energy(100.0)),
+            Err(CastError::EmptySlot(2))
+        );
+    }
+
+    fn sword_xp(xp: u64) -> MasteryProfile {
+        let mut mastery = MasteryProfile::new();
+        mastery.gain_xp(MasteryDomain::SwordMastery, xp);
+        mastery
+    }
+
+    #[test]
+    fn test_upgrade_blocked_below_required_tier() {
+        let mut loadout = rising_slash_loadout();
+        let err = loadout.upgrade("rising_slash", &sword_xp(0)).unwrap_err();
+        assert_eq!(
+            err,
+            UpgradeError::TierTooLow {
+                domain: MasteryDomain::SwordMastery,
+                required: MasteryTier::Apprentice,
+                current: MasteryTier::Novice,
+            }
+        );
+        assert_eq!(loadout.rank("rising_slash"), AbilityRank(1));
+    }
+
+    #[test]
+    fn test_upgrade_at_and_above_required_tier() {
+        let mut loadout = rising_slash_loadout();
+        let apprentice = sword_xp(MasteryTier::Apprentice.xp_threshold());
+        assert!(loadout.upgrade("rising_slash", &apprentice).is_ok());
+        assert_eq!(loadout.rank("rising_slash"), AbilityRank(2));
+        // Rank 3 needs Journeyman
+        assert!(loadout.upgrade("rising_slash", &apprentice).is_err());
+
+        let master = sword_xp(MasteryTier::Master.xp_threshold());
+        for _ in 0..3 {
+            loadout.upgrade("rising_slash", &master).unwrap();
+        }
+        assert_eq!(loadout.rank("rising_slash"), AbilityRank(MAX_ABILITY_RANK));
+        assert_eq!(
+            loadout.upgrade("rising_slash", &master),
+            Err(UpgradeError::MaxRank)
+        );
+    }
+
+    #[test]
+    fn test_rank_scales_effective_stats() {
+        let mut loadout = rising_slash_loadout();
+        let base = loadout.effective_stats("rising_slash").unwrap();
+        loadout
+            .upgrade(
+                "rising_slash",
+                &sword_xp(MasteryTier::Apprentice.xp_threshold()),
+            )
+            .unwrap();
+        let ranked = loadout.effective_stats("rising_slash").unwrap();
+
+        let damage = |a: &Ability| match a.effects[0] {
+            AbilityEffect::Damage { base, .. } => base,
+            _ => panic!("rising slash deals damage"),
+        };
+        assert!(damage(&ranked) > damage(&base));
+        assert!(ranked.radius >= base.radius);
+        assert!(loadout.effective_stats("unknown").is_none());
+    }
+
+    #[test]
+    fn test_mastery_domain_follows_source() {
+        let domain = |id: &str| {
+            default_abilities()
+                .into_iter()
+                .find(|a| a.id == id)
+                .unwrap()
+                .mastery_domain()
+        };
+        assert_eq!(domain("rising_slash"), MasteryDomain::SwordMastery);
+        assert_eq!(domain("ground_slam"), MasteryDomain::GauntletMastery);
+        assert_eq!(domain("shadow_strike"), MasteryDomain::DodgeMastery);
+        assert_eq!(domain("war_cry"), MasteryDomain::SemanticAttunement);
+
+        // The icon is cosmetic; reskinning doesn't move the ability to another domain
+        let mut slash = default_abilities().remove(0);
+        slash.icon_tag = "staff_glow".into();
+        assert_eq!(slash.mastery_domain(), MasteryDomain::SwordMastery);
+    }
+
+    #[test]
+    fn test_upgrade_unknown_ability() {
+        let mut loadout = AbilityLoadout::new();
+        assert!(matches!(
+            loadout.upgrade("nope", &MasteryProfile::new()),
+            Err(UpgradeError::UnknownAbility(_))
+        ));
+    }
+
     #[test]
     fn test_unequip() {
         let mut loadout = AbilityLoadout::new();
@@ -582,6 +1055,44 @@ mod tests {
         assert!((tracker.remaining(&ability.id) - 9.0).abs() < 0.01);
     }
 
+    fn test_ultimate(id: &str) -> UltimateAbility {
+        UltimateAbility {
+            id: id.into(),
+            name: id.into(),
+            description: String::new(),
+            cooldown_seconds: 90.0,
+            effect: crate::specialization::UltimateEffect::Invulnerable { duration: 1.0 },
+        }
+    }
+
+    #[test]
+    fn test_independent_ultimates_usable_separately() {
+        let mut tracker = AbilityCooldownTracker::new();
+        let (a, b) = (test_ultimate("ult_a"), test_ultimate("ult_b"));
+        let policy = CooldownPolicy::Independent;
+
+        tracker.start_ultimate_cooldown(&a, policy);
+        assert!(!tracker.is_ultimate_ready(&a.id, policy));
+        assert!(tracker.is_ultimate_ready(&b.id, policy));
+    }
+
+    #[test]
+    fn test_shared_ultimates_enforce_global_cooldown() {
+        let mut tracker = AbilityCooldownTracker::new();
+        let (a, b) = (test_ultimate("ult_a"), test_ultimate("ult_b"));
+        let policy = CooldownPolicy::SharedGlobal { lockout: 30.0 };
+
+        tracker.start_ultimate_cooldown(&a, policy);
+        assert!(!tracker.is_ultimate_ready(&b.id, policy));
+
+        tracker.tick(30.0);
+        assert!(tracker.is_ultimate_ready(&b.id, policy));
+        assert!(
+            !tracker.is_ultimate_ready(&a.id, policy),
+            "own cooldown still running"
+        );
+    }
+
     #[test]
     fn test_ability_cost_types() {
         let k = AbilityCost::kinetic(20.0);
@@ -1,6 +1,8 @@
 use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
 
+use crate::movement::SprintState;
+
 pub struct AerialPlugin;
 
 impl Plugin for AerialPlugin {
@@ -110,37 +112,98 @@ impl Default for DiveAttack {
     }
 }
 
-fn update_flight_state(time: Res<Time>, mut query: Query<(&mut FlightState, &Transform)>) {
+/// Height (units) per +1.0 dive damage multiplier
+const DIVE_HEIGHT_SCALE: f32 = 20.0;
+/// Cap on the height bonus so long falls can't one-shot
+const MAX_DIVE_HEIGHT_BONUS: f32 = 2.0;
+/// Extra multiplier per unit of downward velocity, and its cap
+const DIVE_VELOCITY_SCALE: f32 = 0.02;
+const MAX_DIVE_VELOCITY_BONUS: f32 = 0.5;
+/// Knockback at zero height/velocity, and per unit of velocity
+const DIVE_BASE_KNOCKBACK: f32 = 2.0;
+const DIVE_KNOCKBACK_PER_VELOCITY: f32 = 0.25;
+/// Stamina a dive attack costs before AerialEfficiency
+const DIVE_STAMINA_COST: f32 = 25.0;
+/// Most stamina AerialEfficiency can save
+const MAX_AERIAL_EFFICIENCY: f32 = 0.8;
+
+/// Outcome of a dive attack landing
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct DiveResult {
+    /// Total damage dealt (base + bonus)
+    pub damage: f32,
+    /// Damage added by height and velocity
+    pub bonus_damage: f32,
+    /// Knockback magnitude along the dive direction
+    pub knockback: f32,
+    pub stamina_cost: f32,
+}
+
+/// Damage multiplier bonus from fall height, capped at `MAX_DIVE_HEIGHT_BONUS`
+pub(crate) fn dive_height_bonus(height: f32) -> f32 {
+    (height.max(0.0) / DIVE_HEIGHT_SCALE).min(MAX_DIVE_HEIGHT_BONUS)
+}
+
+/// Resolve a dive attack from `height` with downward `velocity`.
+/// `efficiency` is the player's total AerialEfficiency (0.2 = 20% cheaper).
+pub fn dive_attack(height: f32, velocity: f32, base_damage: f32, efficiency: f32) -> DiveResult {
+    let velocity = velocity.abs();
+    let multiplier_bonus =
+        dive_height_bonus(height) + (velocity * DIVE_VELOCITY_SCALE).min(MAX_DIVE_VELOCITY_BONUS);
+    let bonus_damage = base_damage.max(0.0) * multiplier_bonus;
+    let efficiency = efficiency.clamp(0.0, MAX_AERIAL_EFFICIENCY);
+
+    DiveResult {
+        damage: base_damage.max(0.0) + bonus_damage,
+        bonus_damage,
+        knockback: DIVE_BASE_KNOCKBACK * (1.0 + multiplier_bonus)
+            + velocity * DIVE_KNOCKBACK_PER_VELOCITY,
+        stamina_cost: DIVE_STAMINA_COST * (1.0 - efficiency),
+    }
+}
+
+fn update_flight_state(
+    time: Res<Time>,
+    mut query: Query<(&mut FlightState, &Transform, Option<&SprintState>)>,
+) {
     let dt = time.delta_secs();
 
-    for (mut flight, transform) in &mut query {
+    for (mut flight, transform, sprint) in &mut query {
         flight.altitude = transform.translation.y;
+        let sprinting = sprint.is_some_and(|s| s.sprinting);
+        update_stamina(&mut flight, sprinting, dt);
+    }
+}
 
-        match flight.mode {
-            FlightMode::Grounded => {
+/// Spend or recover stamina for the current flight mode. Grounded entities
+/// regenerate unless they are sprinting, which spends the same pool.
+fn update_stamina(flight: &mut FlightState, sprinting: bool, dt: f32) {
+    match flight.mode {
+        FlightMode::Grounded => {
+            if !sprinting {
                 flight.stamina =
                     (flight.stamina + flight.stamina_regen_rate * dt).min(flight.max_stamina);
             }
-            FlightMode::Ascending => {
-                flight.stamina -= flight.stamina_drain_rate * 2.0 * dt;
-            }
-            FlightMode::Hovering => {
-                flight.stamina -= flight.stamina_drain_rate * dt;
-            }
-            FlightMode::Gliding => {
-                flight.stamina -= flight.stamina_drain_rate * 0.3 * dt;
-            }
-            FlightMode::Diving => {
-                // Diving doesn't cost stamina, generates kinetic energy
-            }
         }
+        FlightMode::Ascending => {
+            flight.stamina -= flight.stamina_drain_rate * 2.0 * dt;
+        }
+        FlightMode::Hovering => {
+            flight.stamina -= flight.stamina_drain_rate * dt;
+        }
+        FlightMode::Gliding => {
+            flight.stamina -= flight.stamina_drain_rate * 0.3 * dt;
+        }
+        FlightMode::Diving => {
+            // Diving doesn't cost stamina, generates kinetic energy
+        }
+    }
 
-        // Force land if out of stamina
-        if flight.stamina <= 0.0 {
-            flight.stamina = 0.0;
-            if flight.mode != FlightMode::Grounded && flight.mode != FlightMode::Diving {
-                flight.mode = FlightMode::Gliding; // gentle forced descent
-            }
+    // Force land if out of stamina
+    if flight.stamina <= 0.0 {
+        flight.stamina = 0.0;
+        if flight.mode != FlightMode::Grounded && flight.mode != FlightMode::Diving {
+            flight.mode = FlightMode::Gliding; // gentle forced descent
         }
     }
 }
@@ -150,7 +213,7 @@ fn process_aerial_combat(mut query: Query<(&mut DiveAttack, &FlightState, &Trans
         if flight.mode == FlightMode::Diving && dive.active {
             // Speed increases damage
             dive.speed = transform.translation.y.abs(); // simplified
-            dive.impact_damage_multiplier = 1.0 + (dive.speed / 20.0).min(2.0);
+            dive.impact_damage_multiplier = 1.0 + dive_height_bonus(dive.speed);
             dive.kinetic_energy_generated = dive.speed * 0.5;
         }
     }
@@ -208,4 +271,58 @@ mod tests {
         assert_eq!(flight.mode, FlightMode::Grounded);
         assert!((flight.stamina - 100.0).abs() < f32::EPSILON);
     }
+
+    #[test]
+    fn test_stamina_regenerates_when_not_sprinting() {
+        let mut flight = FlightState {
+            stamina: 0.0,
+            ..Default::default()
+        };
+        update_stamina(&mut flight, true, 2.0);
+        assert_eq!(flight.stamina, 0.0);
+        update_stamina(&mut flight, false, 2.0);
+        assert!((flight.stamina - 40.0).abs() < f32::EPSILON);
+        for _ in 0..100 {
+            update_stamina(&mut flight, false, 1.0);
+        }
+        assert_eq!(flight.stamina, flight.max_stamina);
+
+        let mut sprint = SprintState::default();
+        assert!(sprint.try_sprint(&mut flight.stamina, 0.1));
+    }
+
+    #[test]
+    fn test_higher_dive_deals_more_damage_up_to_cap() {
+        let low = dive_attack(5.0, 0.0, 100.0, 0.0);
+        let high = dive_attack(30.0, 0.0, 100.0, 0.0);
+        assert!(high.damage > low.damage);
+        assert!(high.knockback > low.knockback);
+
+        // Both past the height cap: no extra damage
+        let capped = dive_attack(40.0, 0.0, 100.0, 0.0);
+        let way_higher = dive_attack(4000.0, 0.0, 100.0, 0.0);
+        assert_eq!(capped.damage, way_higher.damage);
+        assert!((capped.bonus_damage - 200.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_velocity_adds_capped_bonus() {
+        let still = dive_attack(10.0, 0.0, 100.0, 0.0);
+        let fast = dive_attack(10.0, 20.0, 100.0, 0.0);
+        assert!(fast.damage > still.damage);
+        assert_eq!(
+            dive_attack(10.0, 1000.0, 100.0, 0.0).damage,
+            dive_attack(10.0, 5000.0, 100.0, 0.0).damage
+        );
+    }
+
+    #[test]
+    fn test_efficiency_lowers_stamina_cost() {
+        let base = dive_attack(10.0, 5.0, 100.0, 0.0);
+        let efficient = dive_attack(10.0, 5.0, 100.0, 0.2);
+        assert!((efficient.stamina_cost - base.stamina_cost * 0.8).abs() < 0.001);
+        assert_eq!(efficient.damage, base.damage);
+        // Efficiency can't make dives free
+        assert!(dive_attack(10.0, 5.0, 100.0, 5.0).stamina_cost > 0.0);
+    }
 }
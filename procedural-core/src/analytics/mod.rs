@@ -17,6 +17,8 @@ use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+use crate::semantic::SemanticInteraction;
+
 pub struct AnalyticsPlugin;
 
 impl Plugin for AnalyticsPlugin {
@@ -35,6 +37,7 @@ pub struct AnalyticsCollector {
     pub equipment_stats: EquipmentStats,
     pub economy_stats: EconomyStats,
     pub behavior_stats: BehaviorStats,
+    pub discovery_stats: DiscoveryStats,
     pub session_start_time: f64,
 }
 
@@ -49,6 +52,32 @@ pub struct CombatStats {
     pub failed_parries: u32,
     pub dodges: u32,
     pub ability_uses: HashMap<String, u32>,
+    /// Hits, damage and kills per weapon name
+    #[serde(default)]
+    pub per_weapon: HashMap<String, WeaponUsage>,
+}
+
+/// How much one weapon has been used and how well it performed
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct WeaponUsage {
+    pub hits: u32,
+    pub total_damage: u64,
+    pub kills: u32,
+}
+
+impl CombatStats {
+    /// Weapon with the most hits; ties go to more damage, then to the name
+    pub fn most_used_weapon(&self) -> Option<&str> {
+        self.per_weapon
+            .iter()
+            .max_by(|(a_name, a), (b_name, b)| {
+                a.hits
+                    .cmp(&b.hits)
+                    .then(a.total_damage.cmp(&b.total_damage))
+                    .then(b_name.cmp(a_name))
+            })
+            .map(|(name, _)| name.as_str())
+    }
 }
 
 /// Progression statistics
@@ -93,6 +122,54 @@ pub struct BehaviorStats {
     pub rest_time_percent: f32,
 }
 
+/// A semantic tag pairing the player has triggered at least once
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SemanticDiscovery {
+    pub tag_a: String,
+    pub tag_b: String,
+    pub interaction: SemanticInteraction,
+}
+
+/// Semantic interaction discovery log, in first-discovered order
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DiscoveryStats {
+    pub discoveries: Vec<SemanticDiscovery>,
+}
+
+impl DiscoveryStats {
+    /// Record a tag pair interaction. Pairs are unordered, so fire+water and
+    /// water+fire are the same discovery. Returns true if it was new.
+    pub fn record(&mut self, tag_a: &str, tag_b: &str, interaction: SemanticInteraction) -> bool {
+        let (a, b) = if tag_a <= tag_b {
+            (tag_a, tag_b)
+        } else {
+            (tag_b, tag_a)
+        };
+        if self
+            .discoveries
+            .iter()
+            .any(|d| d.tag_a == a && d.tag_b == b)
+        {
+            return false;
+        }
+        self.discoveries.push(SemanticDiscovery {
+            tag_a: a.to_string(),
+            tag_b: b.to_string(),
+            interaction,
+        });
+        true
+    }
+
+    pub fn count(&self) -> usize {
+        self.discoveries.len()
+    }
+
+    /// Most recent discoveries, newest first
+    pub fn recent(&self, limit: usize) -> Vec<&SemanticDiscovery> {
+        self.discoveries.iter().rev().take(limit).collect()
+    }
+}
+
 /// Analytics event types
 #[derive(Event, Debug, Clone)]
 pub enum AnalyticsEvent {
@@ -150,6 +227,13 @@ impl AnalyticsCollector {
                     .kills_by_weapon
                     .entry(weapon.clone())
                     .or_insert(0) += 0;
+                let usage = self
+                    .combat_stats
+                    .per_weapon
+                    .entry(weapon.clone())
+                    .or_default();
+                usage.hits += 1;
+                usage.total_damage += *amount as u64;
             }
             AnalyticsEvent::CombatDamageTaken { amount } => {
                 self.combat_stats.total_damage_taken += *amount as u64;
@@ -160,6 +244,11 @@ impl AnalyticsCollector {
                     .kills_by_weapon
                     .entry(weapon.clone())
                     .or_insert(0) += 1;
+                self.combat_stats
+                    .per_weapon
+                    .entry(weapon.clone())
+                    .or_default()
+                    .kills += 1;
                 *self
                     .combat_stats
                     .deaths_by_floor_tier
@@ -247,6 +336,16 @@ impl AnalyticsCollector {
         }
     }
 
+    /// Log a semantic synergy/conflict the player triggered; duplicates are ignored
+    pub fn record_semantic_discovery(
+        &mut self,
+        tag_a: &str,
+        tag_b: &str,
+        interaction: SemanticInteraction,
+    ) -> bool {
+        self.discovery_stats.record(tag_a, tag_b, interaction)
+    }
+
     pub fn compute_derived_stats(&mut self, current_time: f64) {
         // Compute playtime
         self.progression_stats.total_playtime_secs = current_time - self.session_start_time;
@@ -301,6 +400,10 @@ pub struct AnalyticsSnapshot {
     pub equipment: EquipmentStats,
     pub economy: EconomyStats,
     pub behavior: BehaviorStats,
+    #[serde(default)]
+    pub discoveries: DiscoveryStats,
+    #[serde(default)]
+    pub most_used_weapon: Option<String>,
 }
 
 impl AnalyticsSnapshot {
@@ -311,6 +414,11 @@ impl AnalyticsSnapshot {
             equipment: collector.equipment_stats.clone(),
             economy: collector.economy_stats.clone(),
             behavior: collector.behavior_stats.clone(),
+            discoveries: collector.discovery_stats.clone(),
+            most_used_weapon: collector
+                .combat_stats
+                .most_used_weapon()
+                .map(str::to_string),
         }
     }
 
@@ -363,6 +471,45 @@ mod tests {
         assert_eq!(collector.combat_stats.kills_by_weapon.get("Bow"), Some(&1));
     }
 
+    #[test]
+    fn test_per_weapon_breakdown() {
+        let mut collector = AnalyticsCollector::default();
+        for (weapon, amount) in [("Sword", 40), ("Bow", 15), ("Bow", 25), ("Bow", 10)] {
+            collector.record_event(&AnalyticsEvent::CombatDamageDealt {
+                weapon: weapon.to_string(),
+                amount,
+            });
+        }
+        collector.record_event(&AnalyticsEvent::CombatKill {
+            weapon: "Sword".to_string(),
+            floor_tier: 1,
+        });
+
+        let stats = &collector.combat_stats;
+        assert_eq!(
+            stats.per_weapon["Sword"],
+            WeaponUsage {
+                hits: 1,
+                total_damage: 40,
+                kills: 1
+            }
+        );
+        assert_eq!(
+            stats.per_weapon["Bow"],
+            WeaponUsage {
+                hits: 3,
+                total_damage: 50,
+                kills: 0
+            }
+        );
+        assert_eq!(stats.most_used_weapon(), Some("Bow"));
+        assert_eq!(
+            AnalyticsSnapshot::capture(&collector).most_used_weapon,
+            Some("Bow".to_string())
+        );
+        assert_eq!(CombatStats::default().most_used_weapon(), None);
+    }
+
     #[test]
     fn test_parry_tracking() {
         let mut collector = AnalyticsCollector::default();
@@ -559,4 +706,41 @@ mod tests {
         assert_eq!(collector.progression_stats.floors_by_tier.get(&2), Some(&1));
         assert_eq!(collector.progression_stats.floors_by_tier.get(&3), Some(&1));
     }
+
+    #[test]
+    fn test_semantic_discovery_deduplicates() {
+        let mut collector = AnalyticsCollector::default();
+        let steam = SemanticInteraction::Conflict(0.1);
+        assert!(collector.record_semantic_discovery("fire", "water", steam.clone()));
+        assert!(!collector.record_semantic_discovery("water", "fire", steam));
+        assert_eq!(collector.discovery_stats.count(), 1);
+    }
+
+    #[test]
+    fn test_semantic_discovery_distinct_pairs() {
+        let mut collector = AnalyticsCollector::default();
+        collector.record_semantic_discovery("fire", "water", SemanticInteraction::Conflict(0.1));
+        collector.record_semantic_discovery("fire", "ice", SemanticInteraction::Conflict(0.2));
+        collector.record_semantic_discovery("fire", "fire", SemanticInteraction::Synergy(1.0));
+        assert_eq!(collector.discovery_stats.count(), 3);
+    }
+
+    #[test]
+    fn test_semantic_discovery_recent_order() {
+        let mut stats = DiscoveryStats::default();
+        stats.record("fire", "water", SemanticInteraction::Conflict(0.1));
+        stats.record("holy", "void", SemanticInteraction::Conflict(0.0));
+        stats.record("earth", "nature", SemanticInteraction::Synergy(0.8));
+
+        let recent = stats.recent(2);
+        assert_eq!(recent.len(), 2);
+        assert_eq!(
+            (recent[0].tag_a.as_str(), recent[0].tag_b.as_str()),
+            ("earth", "nature")
+        );
+        assert_eq!(
+            (recent[1].tag_a.as_str(), recent[1].tag_b.as_str()),
+            ("holy", "void")
+        );
+    }
 }
@@ -5,12 +5,16 @@
 //! - Damage hacks (exceeding theoretical maximum)
 //! - Bot patterns (inhuman input regularity)
 //! - Exploit abuse (repeated impossible actions)
+//! - Attack cadence faster than the equipped weapon allows
 //!
 //! From opensourcestack.txt Category 11:
 //! "petgraph for behavior analysis through windowed functions"
 
 use serde::{Deserialize, Serialize};
-use std::collections::VecDeque;
+use std::collections::{HashMap, VecDeque};
+
+use crate::combat::weapons::Weapon;
+use crate::generation::wfc::FloorLayout;
 
 /// Types of suspicious behavior
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -322,9 +326,188 @@ impl PlayerAnalyzer {
     }
 }
 
+// =====================
+// Floor Clear Plausibility
+// =====================
+
+/// Fastest possible movement, in tiles per second (sprint with chained dashes)
+pub const MAX_TILES_PER_SEC: f32 = 12.0;
+/// Fastest possible kill, in seconds per monster
+pub const MIN_SECS_PER_MONSTER: f32 = 1.5;
+/// Clears under this share of the minimum are impossible rather than suspicious
+const IMPOSSIBLE_CLEAR_RATIO: f32 = 0.5;
+
+/// Outcome of `check_clear_time`
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum ClearVerdict {
+    Plausible,
+    /// Faster than the computed minimum (could be a routing trick or desync)
+    Suspicious {
+        minimum_secs: f32,
+    },
+    /// Under half the computed minimum
+    Impossible {
+        minimum_secs: f32,
+    },
+}
+
+/// Lower bound on how long clearing `floor` and killing `monsters` can take:
+/// shortest spawn-to-exit walk at max speed plus the fastest kill per monster.
+/// Falls back to Manhattan distance if the exit isn't reachable.
+pub fn minimum_clear_secs(floor: &FloorLayout, monsters: u32) -> f32 {
+    let spawn = floor.spawn_points.first().copied().unwrap_or((0, 0));
+    let exit = floor.exit_point;
+    let steps = floor
+        .path_length(spawn, exit)
+        .unwrap_or_else(|| spawn.0.abs_diff(exit.0) + spawn.1.abs_diff(exit.1));
+    steps as f32 / MAX_TILES_PER_SEC + monsters as f32 * MIN_SECS_PER_MONSTER
+}
+
+/// Flag floor clears faster than `minimum_clear_secs`
+pub fn check_clear_time(floor: &FloorLayout, monsters: u32, clear_secs: f32) -> ClearVerdict {
+    let minimum_secs = minimum_clear_secs(floor, monsters);
+    if clear_secs >= minimum_secs {
+        ClearVerdict::Plausible
+    } else if clear_secs < minimum_secs * IMPOSSIBLE_CLEAR_RATIO {
+        ClearVerdict::Impossible { minimum_secs }
+    } else {
+        ClearVerdict::Suspicious { minimum_secs }
+    }
+}
+
+// =====================
+// Combat Cadence
+// =====================
+
+/// Attacks this much earlier than the weapon allows still count as on time
+/// (network jitter)
+pub const CADENCE_TOLERANCE_MS: u64 = 80;
+/// Suspicion added per too-fast attack
+pub const CADENCE_SUSPICION_PER_VIOLATION: f32 = 1.0;
+/// Suspicion removed per on-time attack
+pub const CADENCE_SUSPICION_DECAY: f32 = 0.25;
+/// Suspicion at which a player is flagged
+pub const CADENCE_SUSPICION_LIMIT: f32 = 3.0;
+/// Attack timestamps kept per player for `average_interval_ms`
+pub const CADENCE_WINDOW: usize = 16;
+/// Floor on the attack speed multiplier so a bad stat can't zero the interval
+const MIN_ATTACK_SPEED: f32 = 0.1;
+
+/// Outcome of `CombatCadenceTracker::check_action`
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum CheatVerdict {
+    Clean,
+    /// Faster than the weapon allows; counted toward suspicion
+    TooFast {
+        interval_ms: u64,
+        min_interval_ms: u64,
+    },
+    /// Suspicion reached `CADENCE_SUSPICION_LIMIT`
+    Flagged {
+        suspicion: f32,
+    },
+}
+
+/// Shortest time between two attacks with `weapon`: the full duration
+/// (windup + active + recovery) of its fastest ground or aerial attack,
+/// shortened by the attacker's `attack_speed` multiplier (1.0 = base,
+/// 1.15 = 15% faster). 0 for a weapon with no attacks.
+pub fn min_attack_interval_ms(weapon: &Weapon, attack_speed: f32) -> u64 {
+    let speed = attack_speed.max(MIN_ATTACK_SPEED);
+    weapon
+        .combo_chain
+        .iter()
+        .chain(&weapon.aerial_chain)
+        .map(|a| ((a.windup + a.active + a.recovery) * 1000.0 / speed) as u64)
+        .min()
+        .unwrap_or(0)
+}
+
+#[derive(Debug, Clone, Default)]
+struct CadenceState {
+    /// Recent attack timestamps (ms), oldest first
+    attacks: VecDeque<u64>,
+    suspicion: f32,
+}
+
+/// Per-player attack timing, checked against each weapon's fastest attack
+#[derive(Debug, Clone, Default)]
+pub struct CombatCadenceTracker {
+    players: HashMap<String, CadenceState>,
+}
+
+impl CombatCadenceTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record `action` at `now` (ms) and judge its timing against `weapon`
+    /// swung at `attack_speed`. Only attacks are cadence-limited; other
+    /// actions are always `Clean`.
+    pub fn check_action(
+        &mut self,
+        player_id: &str,
+        action: ActionType,
+        now: u64,
+        weapon: &Weapon,
+        attack_speed: f32,
+    ) -> CheatVerdict {
+        if action != ActionType::Attack {
+            return CheatVerdict::Clean;
+        }
+        let state = self.players.entry(player_id.to_string()).or_default();
+        let previous = state.attacks.back().copied();
+        if state.attacks.len() == CADENCE_WINDOW {
+            state.attacks.pop_front();
+        }
+        state.attacks.push_back(now);
+
+        let min_interval_ms = min_attack_interval_ms(weapon, attack_speed);
+        let early = previous
+            .map(|prev| now.saturating_sub(prev))
+            .filter(|&interval| interval + CADENCE_TOLERANCE_MS < min_interval_ms);
+        match early {
+            Some(interval_ms) => {
+                state.suspicion += CADENCE_SUSPICION_PER_VIOLATION;
+                if state.suspicion >= CADENCE_SUSPICION_LIMIT {
+                    CheatVerdict::Flagged {
+                        suspicion: state.suspicion,
+                    }
+                } else {
+                    CheatVerdict::TooFast {
+                        interval_ms,
+                        min_interval_ms,
+                    }
+                }
+            }
+            None => {
+                state.suspicion = (state.suspicion - CADENCE_SUSPICION_DECAY).max(0.0);
+                CheatVerdict::Clean
+            }
+        }
+    }
+
+    pub fn suspicion(&self, player_id: &str) -> f32 {
+        self.players.get(player_id).map_or(0.0, |s| s.suspicion)
+    }
+
+    pub fn is_flagged(&self, player_id: &str) -> bool {
+        self.suspicion(player_id) >= CADENCE_SUSPICION_LIMIT
+    }
+
+    /// Mean time between the player's recent attacks
+    pub fn average_interval_ms(&self, player_id: &str) -> Option<u64> {
+        let attacks = &self.players.get(player_id)?.attacks;
+        let span = attacks.back()? - attacks.front()?;
+        (attacks.len() > 1).then(|| span / (attacks.len() as u64 - 1))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::generation::wfc::{generate_layout, TileType};
+    use crate::generation::{FloorSpec, TowerSeed};
 
     fn move_action(ts: u64, x: f32, z: f32) -> PlayerAction {
         PlayerAction {
@@ -515,4 +698,156 @@ mod tests {
             "Human-like input should not trigger bot detection"
         );
     }
+
+    fn corridor(length: usize) -> FloorLayout {
+        FloorLayout {
+            width: length,
+            height: 1,
+            tiles: vec![vec![TileType::Floor; length]],
+            rooms: vec![],
+            spawn_points: vec![(0, 0)],
+            exit_point: (length - 1, 0),
+            lever_gates: vec![],
+            phase_gated: vec![],
+        }
+    }
+
+    #[test]
+    fn test_reasonable_clear_passes() {
+        let spec = FloorSpec::generate(&TowerSeed { seed: 42 }, 10);
+        let layout = generate_layout(&spec);
+        assert_eq!(
+            check_clear_time(&layout, 12, 240.0),
+            ClearVerdict::Plausible
+        );
+    }
+
+    #[test]
+    fn test_impossibly_fast_clear_flagged() {
+        let spec = FloorSpec::generate(&TowerSeed { seed: 42 }, 10);
+        let layout = generate_layout(&spec);
+        assert!(matches!(
+            check_clear_time(&layout, 12, 2.0),
+            ClearVerdict::Impossible { .. }
+        ));
+
+        let min = minimum_clear_secs(&layout, 12);
+        assert_eq!(
+            check_clear_time(&layout, 12, min * 0.8),
+            ClearVerdict::Suspicious { minimum_secs: min }
+        );
+    }
+
+    #[test]
+    fn test_minimum_scales_with_size_and_monsters() {
+        let short = minimum_clear_secs(&corridor(25), 0);
+        let long = minimum_clear_secs(&corridor(97), 0);
+        assert!((short - 24.0 / MAX_TILES_PER_SEC).abs() < 0.001);
+        assert!((long - 96.0 / MAX_TILES_PER_SEC).abs() < 0.001);
+
+        let with_monsters = minimum_clear_secs(&corridor(25), 10);
+        assert!((with_monsters - short - 10.0 * MIN_SECS_PER_MONSTER).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_minimum_uses_walkable_path() {
+        // A wall forces a detour around it
+        let mut layout = FloorLayout {
+            width: 5,
+            height: 3,
+            tiles: vec![vec![TileType::Floor; 5]; 3],
+            rooms: vec![],
+            spawn_points: vec![(0, 0)],
+            exit_point: (4, 0),
+            lever_gates: vec![],
+            phase_gated: vec![],
+        };
+        layout.tiles[0][2] = TileType::Wall;
+        layout.tiles[1][2] = TileType::Wall;
+        assert_eq!(layout.path_length((0, 0), (4, 0)), Some(8));
+        assert!((minimum_clear_secs(&layout, 0) - 8.0 / MAX_TILES_PER_SEC).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_normal_cadence_passes() {
+        let weapon = crate::combat::weapons::sword();
+        let interval = min_attack_interval_ms(&weapon, 1.0);
+        assert!(interval > 0);
+        let mut tracker = CombatCadenceTracker::new();
+        for i in 0..20 {
+            let verdict =
+                tracker.check_action("p1", ActionType::Attack, i * interval, &weapon, 1.0);
+            assert_eq!(verdict, CheatVerdict::Clean);
+        }
+        assert_eq!(tracker.suspicion("p1"), 0.0);
+        assert_eq!(tracker.average_interval_ms("p1"), Some(interval));
+        // Jitter inside the tolerance is still on time
+        let late = 20 * interval + interval - CADENCE_TOLERANCE_MS;
+        assert_eq!(
+            tracker.check_action("p1", ActionType::Attack, late, &weapon, 1.0),
+            CheatVerdict::Clean
+        );
+    }
+
+    #[test]
+    fn test_single_early_attack_tolerated() {
+        let weapon = crate::combat::weapons::sword();
+        let interval = min_attack_interval_ms(&weapon, 1.0);
+        let mut tracker = CombatCadenceTracker::new();
+        tracker.check_action("p1", ActionType::Attack, 0, &weapon, 1.0);
+        let verdict = tracker.check_action("p1", ActionType::Attack, interval / 3, &weapon, 1.0);
+        assert!(matches!(verdict, CheatVerdict::TooFast { .. }));
+        assert!(!tracker.is_flagged("p1"));
+
+        // Back to a normal rhythm, suspicion drains away
+        let mut now = interval / 3;
+        for _ in 0..8 {
+            now += interval;
+            tracker.check_action("p1", ActionType::Attack, now, &weapon, 1.0);
+        }
+        assert_eq!(tracker.suspicion("p1"), 0.0);
+        // Non-attacks are never cadence-limited
+        assert_eq!(
+            tracker.check_action("p1", ActionType::Interact, now + 1, &weapon, 1.0),
+            CheatVerdict::Clean
+        );
+    }
+
+    #[test]
+    fn test_fast_weapon_cadence_passes() {
+        let weapon = crate::combat::weapons::sword();
+        let base = min_attack_interval_ms(&weapon, 1.0);
+        let fast = min_attack_interval_ms(&weapon, 1.5);
+        assert!(fast < base);
+
+        // A hasted attacker swinging on its own rhythm is clean...
+        let mut tracker = CombatCadenceTracker::new();
+        for i in 0..20 {
+            let verdict = tracker.check_action("p1", ActionType::Attack, i * fast, &weapon, 1.5);
+            assert_eq!(verdict, CheatVerdict::Clean);
+        }
+        assert_eq!(tracker.suspicion("p1"), 0.0);
+
+        // ...but the same rhythm at base speed is too fast
+        let mut tracker = CombatCadenceTracker::new();
+        tracker.check_action("p2", ActionType::Attack, 0, &weapon, 1.0);
+        assert!(matches!(
+            tracker.check_action("p2", ActionType::Attack, fast, &weapon, 1.0),
+            CheatVerdict::TooFast { .. }
+        ));
+    }
+
+    #[test]
+    fn test_sustained_attack_spam_flagged() {
+        let weapon = crate::combat::weapons::sword();
+        let mut tracker = CombatCadenceTracker::new();
+        let verdicts: Vec<CheatVerdict> = (0..10)
+            .map(|i| tracker.check_action("spammer", ActionType::Attack, i * 50, &weapon, 1.0))
+            .collect();
+        assert!(verdicts
+            .iter()
+            .any(|v| matches!(v, CheatVerdict::Flagged { .. })));
+        assert!(tracker.is_flagged("spammer"));
+        assert!(!tracker.is_flagged("someone_else"));
+    }
 }
@@ -4,32 +4,47 @@
 //! Data is serialized as JSON across the boundary.
 //! All *_json functions return heap-allocated strings — caller must free with `free_string`.
 
+use bevy::math::Vec3;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::ffi::{CStr, CString};
 use std::os::raw::c_char;
+use std::sync::{Mutex, OnceLock};
 
-use crate::combat::AttackAngle;
+use crate::combat::weapons::WeaponType;
+use crate::combat::{
+    angle_multiplier_for, apply_status, evaluate_parry, hit_direction, resolve_status_ticks,
+    AttackAngle, DamagePacket, HitDirection, ParryResult, StatusEffect,
+};
 use crate::constants::*;
+use crate::death::{DeathEcho, EchoField};
+use crate::economy::{AuctionHouse, EconomyLedger, ItemRarity, Wallet};
+use crate::equipment::{self, RolledStats};
 use crate::events::{self, EventTriggerType, TriggerContext};
-use crate::generation::wfc::TileType;
-use crate::generation::{FloorSpec, FloorTier, TowerSeed};
+use crate::faction::{self, ReputationProfile};
+use crate::generation::wfc::{RoomType, TileType};
+use crate::generation::{self, FloorSpec, FloorTier, ShrineEffect, TierBoundaries, TowerSeed};
 use crate::loot;
-use crate::monster::MonsterTemplate;
+use crate::monster::{BossMechanic, BossPhase, MonsterTemplate};
+use crate::player::Inventory;
 use crate::replication::{DeltaLog, DeltaType, FloorSnapshot};
-use crate::semantic::SemanticTags;
+use crate::semantic::{SemanticTags, MAX_RESISTANCE};
 
 // New module imports for extended FFI
-use crate::abilities::{default_abilities, AbilityLoadout};
+use crate::abilities::{
+    default_abilities, AbilityCooldownTracker, AbilityLoadout, AbilityResources,
+};
 use crate::achievements::AchievementTracker;
 use crate::cosmetics::{tower_cosmetics, tower_dyes, CosmeticProfile, CosmeticSlot, DyeChannel};
-use crate::mastery::{xp_for_action, MasteryDomain, MasteryProfile, MasteryTier};
+use crate::mastery::{xp_for_action, DecayConfig, MasteryDomain, MasteryProfile, MasteryTier};
 use crate::seasons::{
-    generate_daily_quests, generate_season_rewards, generate_weekly_quests, SeasonPass,
+    find_recurring_quest, generate_daily_quests, generate_season_rewards, generate_weekly_quests,
+    SeasonPass, SeasonProgress,
 };
-use crate::social::{Guild, Party, PartyRole, Trade, TradeItem};
+use crate::social::{Guild, Party, PartyRole, PlayerInventory, Raid, Trade, TradeItem};
 use crate::sockets::{
-    combine_gems, starter_gems, starter_runes, Gem, Rune, SocketColor, SocketContent,
-    SocketedEquipment,
+    combine_gems, combine_gems_risky, starter_gems, starter_runes, Gem, Rune, SocketColor,
+    SocketContent, SocketedEquipment,
 };
 use crate::specialization::{
     all_specialization_branches, find_active_synergies, SpecializationProfile,
@@ -110,6 +125,19 @@ pub struct MonsterInfo {
     pub detection_range: f32,
     pub xp_reward: u32,
     pub semantic_tags: Vec<(String, f32)>,
+    /// `semantic_tags` plus weakness/resistance entries; send these as
+    /// `defender_tags_json` to `calculate_combat`
+    #[serde(default)]
+    pub combat_tags: Vec<(String, f32)>,
+}
+
+/// Boss for UE5 spawning: monster fields plus phases and mechanics
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BossInfo {
+    #[serde(flatten)]
+    pub monster: MonsterInfo,
+    pub phases: Vec<BossPhase>,
+    pub mechanics: Vec<BossMechanic>,
 }
 
 /// Loot item for UE5 display
@@ -120,6 +148,9 @@ pub struct LootInfo {
     pub rarity: String,
     pub quantity: u32,
     pub semantic_tags: Vec<(String, f32)>,
+    /// Rolled affixes, present on equipment
+    #[serde(default)]
+    pub stats: Option<RolledStats>,
 }
 
 /// Combat calculation request
@@ -130,6 +161,13 @@ pub struct CombatCalcRequest {
     pub combo_step: u32,
     pub attacker_tags_json: String,
     pub defender_tags_json: String,
+    /// Defender armor; reduces only the physical part of the hit
+    #[serde(default)]
+    pub defender_armor: f32,
+    /// Breath phase `semantic_intensity`; scales the synergy/conflict bonus
+    /// (absent = unscaled)
+    #[serde(default)]
+    pub semantic_intensity: Option<f32>,
 }
 
 /// Combat calculation result
@@ -139,6 +177,19 @@ pub struct CombatCalcResult {
     pub angle_multiplier: f32,
     pub semantic_bonus: f32,
     pub is_synergy: bool,
+    /// Share of damage removed by the defender's negative (resistance) tags
+    #[serde(default)]
+    pub resistance_mitigation: f32,
+    /// Damage-taken multiplier from the defender's weaknesses/resistances
+    #[serde(default = "default_weakness_multiplier")]
+    pub weakness_multiplier: f32,
+    /// `final_damage` split by type; its total equals `final_damage`
+    #[serde(default)]
+    pub packet: DamagePacket,
+}
+
+fn default_weakness_multiplier() -> f32 {
+    1.0
 }
 
 /// Breath of Tower state
@@ -241,6 +292,114 @@ pub extern "C" fn generate_floor_layout(seed: u64, floor_id: u32) -> *mut c_char
     json_to_cstring(&response)
 }
 
+/// Generate full floor layout as a compact binary buffer (no JSON).
+///
+/// Layout (all integers little-endian):
+/// - `u32 width`, `u32 height`
+/// - `width * height` tile bytes, row-major, same ids as `generate_floor_layout`
+/// - `u32 room_count`, then per room: `u32 x, u32 y, u32 width, u32 height, u8 room_type`
+///   (room_type: 0=Combat, 1=Treasure, 2=Puzzle, 3=Rest, 4=Boss, 5=Entrance, 6=Exit)
+///
+/// Writes the buffer length to `out_len`. Caller must free with `free_bytes`.
+#[no_mangle]
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+pub extern "C" fn generate_floor_layout_binary(
+    seed: u64,
+    floor_id: u32,
+    out_len: *mut usize,
+) -> *mut u8 {
+    if out_len.is_null() {
+        return std::ptr::null_mut();
+    }
+
+    let tower_seed = TowerSeed { seed };
+    let spec = FloorSpec::generate(&tower_seed, floor_id);
+    let layout = crate::generation::wfc::generate_layout(&spec);
+
+    let mut buf: Vec<u8> =
+        Vec::with_capacity(12 + layout.width * layout.height + layout.rooms.len() * 17);
+    buf.extend_from_slice(&(layout.width as u32).to_le_bytes());
+    buf.extend_from_slice(&(layout.height as u32).to_le_bytes());
+    for row in &layout.tiles {
+        buf.extend(row.iter().map(tile_to_u8));
+    }
+
+    buf.extend_from_slice(&(layout.rooms.len() as u32).to_le_bytes());
+    for room in &layout.rooms {
+        buf.extend_from_slice(&(room.x as u32).to_le_bytes());
+        buf.extend_from_slice(&(room.y as u32).to_le_bytes());
+        buf.extend_from_slice(&(room.width as u32).to_le_bytes());
+        buf.extend_from_slice(&(room.height as u32).to_le_bytes());
+        buf.push(room_type_to_u8(&room.room_type));
+    }
+
+    let boxed = buf.into_boxed_slice();
+    unsafe {
+        *out_len = boxed.len();
+    }
+    Box::into_raw(boxed) as *mut u8
+}
+
+/// Free a byte buffer allocated by Rust (e.g. `generate_floor_layout_binary`).
+/// `len` must be the length reported when the buffer was created.
+#[no_mangle]
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+pub extern "C" fn free_bytes(ptr: *mut u8, len: usize) {
+    if !ptr.is_null() {
+        unsafe {
+            drop(Box::from_raw(std::ptr::slice_from_raw_parts_mut(ptr, len)));
+        }
+    }
+}
+
+/// Generate ambient prop placements for a floor, return JSON array
+#[no_mangle]
+pub extern "C" fn generate_floor_props(seed: u64, floor_id: u32) -> *mut c_char {
+    let tower_seed = TowerSeed { seed };
+    let spec = FloorSpec::generate(&tower_seed, floor_id);
+    let layout = crate::generation::wfc::generate_layout(&spec);
+
+    let props = crate::generation::place_props(&layout, &spec.biome_tags, spec.hash);
+    json_to_cstring(&props)
+}
+
+/// Audio mood of a floor (biome, tier and its mutators), return FloorMood JSON
+#[no_mangle]
+pub extern "C" fn floor_mood(seed: u64, floor_id: u32) -> *mut c_char {
+    let spec = FloorSpec::generate(&TowerSeed { seed }, floor_id);
+    let mutators = mutators::generate_floor_mutators(seed, floor_id);
+    json_to_cstring(&generation::floor_mood(&spec, &mutators))
+}
+
+/// Effect of a floor's `shrine_index`-th shrine (row-major tile order):
+/// 0=Heal, 1=Buff, 2=Cleanse, 3=Teleport
+#[no_mangle]
+pub extern "C" fn shrine_effect(floor_hash: u64, shrine_index: u32) -> u32 {
+    match generation::shrine_effect(floor_hash, shrine_index) {
+        ShrineEffect::Heal => 0,
+        ShrineEffect::Buff => 1,
+        ShrineEffect::Cleanse => 2,
+        ShrineEffect::Teleport => 3,
+    }
+}
+
+/// Check a client's floor layout (FloorLayout JSON) against the one the seed
+/// produces. Returns LayoutVerdict JSON: `"Match"` or `{"Mismatch": {...}}`
+/// listing differing tiles, room count, spawn and exit points. Null on bad input.
+#[no_mangle]
+pub extern "C" fn verify_floor_layout(
+    seed: u64,
+    floor_id: u32,
+    layout_json: *const c_char,
+) -> *mut c_char {
+    let claimed: crate::generation::wfc::FloorLayout =
+        match parse_cstr(layout_json).and_then(|s| serde_json::from_str(&s).ok()) {
+            Some(l) => l,
+            None => return std::ptr::null_mut(),
+        };
+    json_to_cstring(&generation::verify_layout(seed, floor_id, &claimed))
+}
+
 /// Get deterministic floor hash
 #[no_mangle]
 pub extern "C" fn get_floor_hash(seed: u64, floor_id: u32) -> u64 {
@@ -250,24 +409,32 @@ pub extern "C" fn get_floor_hash(seed: u64, floor_id: u32) -> u64 {
 /// Get floor tier as integer (0=Echelon1, 1=Echelon2, 2=Echelon3, 3=Echelon4)
 #[no_mangle]
 pub extern "C" fn get_floor_tier(floor_id: u32) -> u32 {
-    match FloorTier::from_floor_id(floor_id) {
-        FloorTier::Echelon1 => 0,
-        FloorTier::Echelon2 => 1,
-        FloorTier::Echelon3 => 2,
-        FloorTier::Echelon4 => 3,
-    }
+    tier_to_u32(FloorTier::from_floor_id(floor_id))
+}
+
+/// Get floor tier with ruleset boundaries override
+/// boundaries_json: `{"echelon1_max":..,"echelon2_max":..,"echelon3_max":..}`
+/// (null or invalid = default boundaries, same as `get_floor_tier`)
+#[no_mangle]
+pub extern "C" fn get_floor_tier_with(floor_id: u32, boundaries_json: *const c_char) -> u32 {
+    let boundaries: TierBoundaries = parse_cstr(boundaries_json)
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default();
+    tier_to_u32(FloorTier::from_floor_id_with(floor_id, &boundaries))
 }
 
 // ========================
 // C-ABI: Monster Generation
 // ========================
 
-/// Generate a monster from hash and floor level, return JSON
+/// Generate a monster from hash and floor level, return JSON.
+/// `semantic_tags` are normalized to unit length.
 #[no_mangle]
 pub extern "C" fn generate_monster(hash: u64, floor_level: u32) -> *mut c_char {
     let template = MonsterTemplate::from_hash(hash, floor_level);
     let stats = template.compute_stats();
     let tags = template.semantic_tags();
+    let combat_tags = template.combat_tags();
 
     let info = MonsterInfo {
         name: template.name,
@@ -283,12 +450,49 @@ pub extern "C" fn generate_monster(hash: u64, floor_level: u32) -> *mut c_char {
         detection_range: stats.detection_range,
         xp_reward: stats.xp_reward,
         semantic_tags: tags.tags,
+        combat_tags: combat_tags.tags,
+    };
+
+    json_to_cstring(&info)
+}
+
+/// Generate a floor boss from hash and floor level, return JSON.
+/// Same fields as `generate_monster` (with boss-scaled stats) plus
+/// `phases` and `mechanics`.
+#[no_mangle]
+pub extern "C" fn generate_boss(hash: u64, floor_level: u32) -> *mut c_char {
+    let boss = MonsterTemplate::boss_from_hash(hash, floor_level);
+    let stats = boss.compute_stats();
+    let tags = boss.template.semantic_tags();
+    let combat_tags = boss.template.combat_tags();
+    let template = boss.template;
+
+    let info = BossInfo {
+        monster: MonsterInfo {
+            name: template.name,
+            size: format!("{:?}", template.size),
+            element: format!("{:?}", template.element),
+            corruption: format!("{:?}", template.corruption),
+            behavior: format!("{:?}", template.behavior),
+            base_level: template.base_level,
+            max_hp: stats.max_hp,
+            damage: stats.damage,
+            speed: stats.speed,
+            armor: stats.armor,
+            detection_range: stats.detection_range,
+            xp_reward: stats.xp_reward,
+            semantic_tags: tags.tags,
+            combat_tags: combat_tags.tags,
+        },
+        phases: boss.phases,
+        mechanics: boss.mechanics,
     };
 
     json_to_cstring(&info)
 }
 
-/// Generate multiple monsters for a floor, return JSON array
+/// Generate multiple monsters for a floor, return JSON array.
+/// `semantic_tags` are normalized to unit length.
 #[no_mangle]
 pub extern "C" fn generate_floor_monsters(seed: u64, floor_id: u32, count: u32) -> *mut c_char {
     let tower_seed = TowerSeed { seed };
@@ -300,6 +504,7 @@ pub extern "C" fn generate_floor_monsters(seed: u64, floor_id: u32, count: u32)
         let template = MonsterTemplate::from_hash(hash, floor_id);
         let stats = template.compute_stats();
         let tags = template.semantic_tags();
+        let combat_tags = template.combat_tags();
 
         monsters.push(MonsterInfo {
             name: template.name,
@@ -315,6 +520,7 @@ pub extern "C" fn generate_floor_monsters(seed: u64, floor_id: u32, count: u32)
             detection_range: stats.detection_range,
             xp_reward: stats.xp_reward,
             semantic_tags: tags.tags,
+            combat_tags: combat_tags.tags,
         });
     }
 
@@ -325,14 +531,22 @@ pub extern "C" fn generate_floor_monsters(seed: u64, floor_id: u32, count: u32)
 // C-ABI: Combat
 // ========================
 
-/// Get attack angle damage multiplier
+/// Get attack angle damage multiplier (Sword baseline)
 #[no_mangle]
 pub extern "C" fn get_angle_multiplier(angle_id: u32) -> f32 {
-    match angle_id {
-        0 => AttackAngle::Front.multiplier(),
-        1 => AttackAngle::Side.multiplier(),
-        2 => AttackAngle::Back.multiplier(),
-        _ => 1.0,
+    AttackAngle::from_id(angle_id).map_or(1.0, |a| a.multiplier())
+}
+
+/// Get attack angle damage multiplier for a weapon type (0 Sword ..
+/// 5 Staff). Unknown weapon ids use the baseline multipliers.
+#[no_mangle]
+pub extern "C" fn get_weapon_angle_multiplier(weapon_id: u32, angle_id: u32) -> f32 {
+    let Some(angle) = AttackAngle::from_id(angle_id) else {
+        return 1.0;
+    };
+    match WeaponType::from_id(weapon_id) {
+        Some(weapon) => angle_multiplier_for(weapon, angle),
+        None => angle.multiplier(),
     }
 }
 
@@ -349,14 +563,6 @@ pub extern "C" fn calculate_combat(request_json: *const c_char) -> *mut c_char {
         Err(_) => return std::ptr::null_mut(),
     };
 
-    let angle_mult = match request.angle_id {
-        0 => AttackAngle::Front.multiplier(),
-        1 => AttackAngle::Side.multiplier(),
-        2 => AttackAngle::Back.multiplier(),
-        _ => 1.0,
-    };
-
-    // Semantic bonus from tag similarity
     let attacker_tags: Vec<(String, f32)> =
         serde_json::from_str(&request.attacker_tags_json).unwrap_or_default();
     let defender_tags: Vec<(String, f32)> =
@@ -368,7 +574,157 @@ pub extern "C" fn calculate_combat(request_json: *const c_char) -> *mut c_char {
     let sem_b = SemanticTags {
         tags: defender_tags,
     };
-    let similarity = sem_a.similarity(&sem_b);
+
+    let result = compute_combat(&request, &sem_a, &sem_b);
+    json_to_cstring(&result)
+}
+
+/// Calculate many combat requests in one call.
+///
+/// Takes a JSON array of `CombatCalcRequest` and returns a JSON array of
+/// `CombatCalcResult` in the same order. Malformed entries yield `null` in
+/// their slot. Identical attacker/defender tag strings are parsed once.
+#[no_mangle]
+pub extern "C" fn calculate_combat_batch(requests_json: *const c_char) -> *mut c_char {
+    let json_str = match parse_cstr(requests_json) {
+        Some(s) => s,
+        None => return std::ptr::null_mut(),
+    };
+
+    let entries: Vec<serde_json::Value> = match serde_json::from_str(&json_str) {
+        Ok(v) => v,
+        Err(_) => return std::ptr::null_mut(),
+    };
+    if entries.is_empty() {
+        return json_to_cstring(&Vec::<CombatCalcResult>::new());
+    }
+
+    let mut tag_cache: HashMap<String, SemanticTags> = HashMap::new();
+    let mut parse_tags = |json: &str| -> SemanticTags {
+        tag_cache
+            .entry(json.to_owned())
+            .or_insert_with(|| SemanticTags {
+                tags: serde_json::from_str(json).unwrap_or_default(),
+            })
+            .clone()
+    };
+
+    let results: Vec<Option<CombatCalcResult>> = entries
+        .into_iter()
+        .map(|entry| {
+            let request: CombatCalcRequest = serde_json::from_value(entry).ok()?;
+            let sem_a = parse_tags(&request.attacker_tags_json);
+            let sem_b = parse_tags(&request.defender_tags_json);
+            Some(compute_combat(&request, &sem_a, &sem_b))
+        })
+        .collect();
+
+    json_to_cstring(&results)
+}
+
+/// Server tick for damage-over-time: apply `incoming_json` (array of new
+/// StatusEffects, may be null) onto `effects_json`, then advance `dt`
+/// seconds. Returns `{effects, damage}` with expired effects removed.
+#[no_mangle]
+pub extern "C" fn combat_resolve_status(
+    effects_json: *const c_char,
+    incoming_json: *const c_char,
+    dt: f32,
+) -> *mut c_char {
+    let effects_str = match parse_cstr(effects_json) {
+        Some(s) => s,
+        None => return std::ptr::null_mut(),
+    };
+    let mut effects: Vec<StatusEffect> = match serde_json::from_str(&effects_str) {
+        Ok(e) => e,
+        Err(_) => return std::ptr::null_mut(),
+    };
+    let incoming: Vec<StatusEffect> = parse_cstr(incoming_json)
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default();
+
+    for effect in incoming {
+        apply_status(&mut effects, effect);
+    }
+    let damage = resolve_status_ticks(&mut effects, dt);
+    json_to_cstring(&serde_json::json!({
+        "effects": effects,
+        "damage": damage,
+    }))
+}
+
+/// Judge a parry against the hit it answered. `bonus_ms` is the player's
+/// unlocked ParryWindowExtension total, added to `window_ms`.
+/// 0=Miss, 1=Parry, 2=PerfectParry
+#[no_mangle]
+pub extern "C" fn combat_evaluate_parry(
+    incoming_tick: u64,
+    parry_tick: u64,
+    window_ms: f32,
+    perfect_ms: f32,
+    bonus_ms: f32,
+) -> u32 {
+    match evaluate_parry(
+        incoming_tick,
+        parry_tick,
+        window_ms + bonus_ms.max(0.0),
+        perfect_ms,
+    ) {
+        ParryResult::Miss => 0,
+        ParryResult::Parry => 1,
+        ParryResult::PerfectParry => 2,
+    }
+}
+
+/// Side of the victim a hit came from, for the UI damage indicator.
+/// `victim_facing` is the victim's yaw in radians.
+/// 0=Front, 1=Left, 2=Right, 3=Back
+#[no_mangle]
+pub extern "C" fn combat_hit_direction(
+    victim_facing: f32,
+    attacker_x: f32,
+    attacker_z: f32,
+    victim_x: f32,
+    victim_z: f32,
+) -> u32 {
+    match hit_direction(
+        victim_facing,
+        Vec3::new(attacker_x, 0.0, attacker_z),
+        Vec3::new(victim_x, 0.0, victim_z),
+    ) {
+        HitDirection::Front => 0,
+        HitDirection::Left => 1,
+        HitDirection::Right => 2,
+        HitDirection::Back => 3,
+    }
+}
+
+/// Resolve a dive attack. `efficiency` is the player's AerialEfficiency
+/// total. Returns DiveResult JSON.
+#[no_mangle]
+pub extern "C" fn aerial_dive_attack(
+    height: f32,
+    velocity: f32,
+    base_damage: f32,
+    efficiency: f32,
+) -> *mut c_char {
+    json_to_cstring(&crate::aerial::dive_attack(
+        height,
+        velocity,
+        base_damage,
+        efficiency,
+    ))
+}
+
+fn compute_combat(
+    request: &CombatCalcRequest,
+    sem_a: &SemanticTags,
+    sem_b: &SemanticTags,
+) -> CombatCalcResult {
+    let angle_mult = AttackAngle::from_id(request.angle_id).map_or(1.0, |a| a.multiplier());
+
+    // Semantic bonus from tag similarity (weakness entries aren't part of the theme)
+    let similarity = sem_a.core_tags().similarity(&sem_b.core_tags());
 
     let semantic_bonus = if similarity > SEMANTIC_HIGH_THRESHOLD {
         SEMANTIC_SYNERGY_BONUS
@@ -376,19 +732,32 @@ pub extern "C" fn calculate_combat(request_json: *const c_char) -> *mut c_char {
         SEMANTIC_CONFLICT_PENALTY
     } else {
         0.0
-    };
+    } * request
+        .semantic_intensity
+        .map_or(1.0, crate::world::semantic_bonus_scale);
 
-    let combo_mult = 1.0 + request.combo_step as f32 * COMBO_STEP_MULT;
-    let final_damage = request.base_damage * angle_mult * combo_mult * (1.0 + semantic_bonus);
+    // Resistance from the defender's negative tags, applied after the synergy bonus
+    let mitigation = sem_b.resistance_against(sem_a);
+    // Elemental weakness/resistance; combined reduction is capped like resistance
+    let weakness_mult = sem_b.vulnerability_against(sem_a);
+    let damage_taken = ((1.0 - mitigation) * weakness_mult).max(1.0 - MAX_RESISTANCE);
 
-    let result = CombatCalcResult {
-        final_damage,
+    let combo_mult = 1.0 + request.combo_step as f32 * COMBO_STEP_MULT;
+    let pre_armor =
+        request.base_damage * angle_mult * combo_mult * (1.0 + semantic_bonus) * damage_taken;
+    // Resistances are already in `damage_taken`; armor applies per part
+    let packet = DamagePacket::split(pre_armor, &sem_a.core_tags())
+        .mitigated(request.defender_armor, &HashMap::new());
+
+    CombatCalcResult {
+        final_damage: packet.total(),
         angle_multiplier: angle_mult,
         semantic_bonus,
         is_synergy: similarity > SEMANTIC_HIGH_THRESHOLD,
-    };
-
-    json_to_cstring(&result)
+        resistance_mitigation: mitigation,
+        weakness_multiplier: weakness_mult,
+        packet,
+    }
 }
 
 // ========================
@@ -436,9 +805,125 @@ pub extern "C" fn generate_loot(
     let tags_vec: Vec<(String, f32)> = serde_json::from_str(&tags_str).unwrap_or_default();
     let source_tags = SemanticTags { tags: tags_vec };
 
-    let items = loot::generate_loot(&source_tags, floor_level, drop_hash);
+    let items = loot::generate_loot(&source_tags, floor_level, drop_hash, None);
+    loot_items_to_cstring(&items)
+}
+
+/// Generate loot with stack sizes scaled by the Breath phase's resource multiplier
+#[no_mangle]
+pub extern "C" fn generate_loot_breath(
+    source_tags_json: *const c_char,
+    floor_level: u32,
+    drop_hash: u64,
+    resource_mult: f32,
+) -> *mut c_char {
+    let tags_str = match parse_cstr(source_tags_json) {
+        Some(s) => s,
+        None => return std::ptr::null_mut(),
+    };
+    let tags_vec: Vec<(String, f32)> = serde_json::from_str(&tags_str).unwrap_or_default();
+    let source_tags = SemanticTags { tags: tags_vec };
+
+    let items =
+        loot::generate_loot_breath(&source_tags, floor_level, drop_hash, None, resource_mult);
+    loot_items_to_cstring(&items)
+}
+
+/// Generate loot whose tags blend the monster's tags with the floor's tags
+#[no_mangle]
+pub extern "C" fn generate_loot_blended(
+    source_tags_json: *const c_char,
+    floor_tags_json: *const c_char,
+    floor_level: u32,
+    drop_hash: u64,
+) -> *mut c_char {
+    let tags_str = match parse_cstr(source_tags_json) {
+        Some(s) => s,
+        None => return std::ptr::null_mut(),
+    };
+    let floor_str = match parse_cstr(floor_tags_json) {
+        Some(s) => s,
+        None => return std::ptr::null_mut(),
+    };
+    let tags_vec: Vec<(String, f32)> = serde_json::from_str(&tags_str).unwrap_or_default();
+    let floor_vec: Vec<(String, f32)> = serde_json::from_str(&floor_str).unwrap_or_default();
+    let source_tags = SemanticTags { tags: tags_vec };
+    let floor_tags = SemanticTags { tags: floor_vec };
+
+    let items = loot::generate_loot(&source_tags, floor_level, drop_hash, Some(&floor_tags));
+    loot_items_to_cstring(&items)
+}
+
+/// Generate loot nudged toward the player's dominant tag.
+/// `player_tags_json` uses the same format as `source_tags_json`;
+/// `bias` is clamped to [0, 0.5].
+#[no_mangle]
+pub extern "C" fn generate_loot_targeted(
+    source_tags_json: *const c_char,
+    player_tags_json: *const c_char,
+    floor_level: u32,
+    drop_hash: u64,
+    bias: f32,
+) -> *mut c_char {
+    let tags_str = match parse_cstr(source_tags_json) {
+        Some(s) => s,
+        None => return std::ptr::null_mut(),
+    };
+    let player_str = match parse_cstr(player_tags_json) {
+        Some(s) => s,
+        None => return std::ptr::null_mut(),
+    };
+    let tags_vec: Vec<(String, f32)> = serde_json::from_str(&tags_str).unwrap_or_default();
+    let player_vec: Vec<(String, f32)> = serde_json::from_str(&player_str).unwrap_or_default();
+    let source_tags = SemanticTags { tags: tags_vec };
+    let player_tags = SemanticTags { tags: player_vec };
+
+    let items = loot::generate_loot_targeted(
+        &source_tags,
+        floor_level,
+        drop_hash,
+        None,
+        &player_tags,
+        bias,
+    );
+    loot_items_to_cstring(&items)
+}
+
+/// Generate loot with bad-luck protection.
+/// `pity_json` is a LootPityState (null/empty = fresh state).
+/// Returns {"items": [...], "pity": LootPityState} to store for the next drop.
+#[no_mangle]
+pub extern "C" fn generate_loot_with_pity(
+    source_tags_json: *const c_char,
+    floor_level: u32,
+    drop_hash: u64,
+    pity_json: *const c_char,
+) -> *mut c_char {
+    let tags_str = match parse_cstr(source_tags_json) {
+        Some(s) => s,
+        None => return std::ptr::null_mut(),
+    };
+    let mut pity: loot::LootPityState = match parse_cstr(pity_json) {
+        Some(s) if !s.is_empty() => match serde_json::from_str(&s) {
+            Ok(p) => p,
+            Err(_) => return std::ptr::null_mut(),
+        },
+        _ => loot::LootPityState::default(),
+    };
+    let tags_vec: Vec<(String, f32)> = serde_json::from_str(&tags_str).unwrap_or_default();
+    let source_tags = SemanticTags { tags: tags_vec };
+
+    let items =
+        loot::generate_loot_with_pity(&source_tags, floor_level, drop_hash, None, &mut pity);
+    let result = serde_json::json!({
+        "items": loot_infos(&items),
+        "pity": pity,
+    });
+    json_to_cstring(&result)
+}
 
-    let loot_infos: Vec<LootInfo> = items
+fn loot_infos(items: &[loot::LootItem]) -> Vec<LootInfo> {
+    items
         .iter()
         .map(|item| LootInfo {
             name: item.name.clone(),
@@ -446,10 +931,86 @@ pub extern "C" fn generate_loot(
             rarity: format!("{:?}", item.rarity),
             quantity: item.quantity,
             semantic_tags: item.semantic_tags.clone(),
+            stats: item.stats.clone(),
         })
-        .collect();
+        .collect()
+}
+
+fn loot_items_to_cstring(items: &[loot::LootItem]) -> *mut c_char {
+    json_to_cstring(&loot_infos(items))
+}
+
+/// Create an auto-pickup filter, return JSON
+/// min_rarity_id: 0=Common..5=Mythic
+/// ignore_categories_json: e.g. `["Material", "Consumable"]` (null = ignore nothing)
+#[no_mangle]
+pub extern "C" fn loot_create_pickup_filter(
+    min_rarity_id: u32,
+    ignore_categories_json: *const c_char,
+) -> *mut c_char {
+    let min_rarity = match rarity_from_id(min_rarity_id) {
+        Some(r) => r,
+        None => return std::ptr::null_mut(),
+    };
+    let ignore_categories: Vec<loot::LootCategory> = parse_cstr(ignore_categories_json)
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default();
+
+    let filter = loot::PickupFilter {
+        min_rarity,
+        ignore_categories,
+    };
+    json_to_cstring(&filter)
+}
+
+/// Check whether a loot item passes a pickup filter (1 = pick up, 0 = skip)
+/// item_json: a single entry from `generate_loot` output
+#[no_mangle]
+pub extern "C" fn loot_should_pickup(filter_json: *const c_char, item_json: *const c_char) -> u32 {
+    let filter: loot::PickupFilter =
+        match parse_cstr(filter_json).and_then(|s| serde_json::from_str(&s).ok()) {
+            Some(f) => f,
+            None => return 0,
+        };
+    let item: loot::LootItem =
+        match parse_cstr(item_json).and_then(|s| serde_json::from_str(&s).ok()) {
+            Some(i) => i,
+            None => return 0,
+        };
+
+    filter.should_pickup(&item) as u32
+}
+
+/// Reroll an equipment item's affixes with the Enchanting mastery
+/// enchant_tier: 0=Novice..5=Grandmaster
+/// Returns the updated item JSON, or null if the item can't be enchanted
+#[no_mangle]
+pub extern "C" fn loot_enchant(
+    item_json: *const c_char,
+    enchant_tier: u32,
+    hash: u64,
+) -> *mut c_char {
+    let mut item: loot::LootItem =
+        match parse_cstr(item_json).and_then(|s| serde_json::from_str(&s).ok()) {
+            Some(i) => i,
+            None => return std::ptr::null_mut(),
+        };
+    let tier = match mastery_tier_from_id(enchant_tier) {
+        Some(t) => t,
+        None => return std::ptr::null_mut(),
+    };
+
+    match loot::enchant_reroll(&mut item, tier, hash) {
+        Ok(()) => json_to_cstring(&item),
+        Err(_) => std::ptr::null_mut(),
+    }
+}
 
-    json_to_cstring(&loot_infos)
+/// Open a shard-bought mystery box (tier 0..3, higher = better odds),
+/// return the LootItem JSON
+#[no_mangle]
+pub extern "C" fn loot_mystery_box(tier: u32, floor_level: u32, hash: u64) -> *mut c_char {
+    json_to_cstring(&loot::open_mystery_box(tier, floor_level, hash))
 }
 
 // ========================
@@ -459,32 +1020,7 @@ pub extern "C" fn generate_loot(
 /// Get current Breath of Tower phase based on elapsed seconds
 #[no_mangle]
 pub extern "C" fn get_breath_state(elapsed_seconds: f32) -> *mut c_char {
-    use crate::world::BreathPhase;
-
-    let cycle_pos = elapsed_seconds % BREATH_CYCLE_TOTAL;
-
-    let hold_start = BREATH_INHALE_SECS;
-    let exhale_start = hold_start + BREATH_HOLD_SECS;
-    let pause_start = exhale_start + BREATH_EXHALE_SECS;
-
-    let (phase, phase_progress) = if cycle_pos < hold_start {
-        (BreathPhase::Inhale, cycle_pos / BREATH_INHALE_SECS)
-    } else if cycle_pos < exhale_start {
-        (
-            BreathPhase::Hold,
-            (cycle_pos - hold_start) / BREATH_HOLD_SECS,
-        )
-    } else if cycle_pos < pause_start {
-        (
-            BreathPhase::Exhale,
-            (cycle_pos - exhale_start) / BREATH_EXHALE_SECS,
-        )
-    } else {
-        (
-            BreathPhase::Pause,
-            (cycle_pos - pause_start) / BREATH_PAUSE_SECS,
-        )
-    };
+    let (phase, phase_progress) = crate::world::breath_phase_at(elapsed_seconds);
 
     let state = BreathState {
         phase: format!("{:?}", phase),
@@ -497,6 +1033,25 @@ pub extern "C" fn get_breath_state(elapsed_seconds: f32) -> *mut c_char {
     json_to_cstring(&state)
 }
 
+/// Next Breath phase and seconds until it starts: {"phase", "seconds_until"}
+#[no_mangle]
+pub extern "C" fn breath_next_transition(elapsed_seconds: f32) -> *mut c_char {
+    let (phase, seconds_until) = crate::world::next_phase_transition(elapsed_seconds);
+    json_to_cstring(&serde_json::json!({
+        "phase": format!("{:?}", phase),
+        "seconds_until": seconds_until,
+    }))
+}
+
+/// Phase entered between two ticks (e.g. `"Hold"`), or null if no boundary was crossed
+#[no_mangle]
+pub extern "C" fn breath_crossed_boundary(prev_elapsed: f32, now_elapsed: f32) -> *mut c_char {
+    match crate::world::crossed_boundary(prev_elapsed, now_elapsed) {
+        Some(phase) => json_to_cstring(&format!("{:?}", phase)),
+        None => std::ptr::null_mut(),
+    }
+}
+
 // ========================
 // C-ABI: Replication
 // ========================
@@ -542,8 +1097,28 @@ pub extern "C" fn create_floor_snapshot(
     floor_id: u32,
     deltas_json: *const c_char,
 ) -> *mut c_char {
-    let json_str = match parse_cstr(deltas_json) {
-        Some(s) => s,
+    floor_snapshot_from_json(seed, floor_id, deltas_json, false)
+}
+
+/// `create_floor_snapshot` with superseded entity-scoped deltas (e.g. repeated
+/// door toggles) collapsed away
+#[no_mangle]
+pub extern "C" fn create_floor_snapshot_compacted(
+    seed: u64,
+    floor_id: u32,
+    deltas_json: *const c_char,
+) -> *mut c_char {
+    floor_snapshot_from_json(seed, floor_id, deltas_json, true)
+}
+
+fn floor_snapshot_from_json(
+    seed: u64,
+    floor_id: u32,
+    deltas_json: *const c_char,
+    collapse: bool,
+) -> *mut c_char {
+    let json_str = match parse_cstr(deltas_json) {
+        Some(s) => s,
         None => return std::ptr::null_mut(),
     };
     let deltas: Vec<crate::replication::Delta> =
@@ -555,10 +1130,82 @@ pub extern "C" fn create_floor_snapshot(
     }
 
     let tower_seed = TowerSeed { seed };
-    let snapshot = FloorSnapshot::capture(&tower_seed, floor_id, &log, 0);
+    let snapshot = FloorSnapshot::capture(&tower_seed, floor_id, &log, 0, collapse);
     json_to_cstring(&snapshot)
 }
 
+/// Replay a floor snapshot's deltas, return FloorState JSON
+/// (opened chests, killed monsters, unlocked doors, disarmed traps, activated shrines)
+#[no_mangle]
+pub extern "C" fn floor_snapshot_apply(snapshot_json: *const c_char) -> *mut c_char {
+    let json_str = match parse_cstr(snapshot_json) {
+        Some(s) => s,
+        None => return std::ptr::null_mut(),
+    };
+    let snapshot = match FloorSnapshot::from_json(&json_str) {
+        Some(s) => s,
+        None => return std::ptr::null_mut(),
+    };
+
+    json_to_cstring(&snapshot.apply())
+}
+
+/// Desync checksum of a floor snapshot (seed, floor, ordered deltas).
+/// Returns 0 on invalid input.
+#[no_mangle]
+pub extern "C" fn snapshot_checksum(snapshot_json: *const c_char) -> u64 {
+    parse_cstr(snapshot_json)
+        .and_then(|s| FloorSnapshot::from_json(&s))
+        .map_or(0, |snapshot| snapshot.checksum())
+}
+
+// ========================
+// C-ABI: Death Echoes
+// ========================
+
+/// Record a player death echo. `field_json` is the current EchoField (null
+/// starts an empty one), `echo_json` a DeathEcho. Returns the updated field.
+#[no_mangle]
+pub extern "C" fn death_record_echo(
+    field_json: *const c_char,
+    echo_json: *const c_char,
+) -> *mut c_char {
+    let echo_str = match parse_cstr(echo_json) {
+        Some(s) => s,
+        None => return std::ptr::null_mut(),
+    };
+    let echo: DeathEcho = match serde_json::from_str(&echo_str) {
+        Ok(e) => e,
+        Err(_) => return std::ptr::null_mut(),
+    };
+    let mut field: EchoField = match parse_cstr(field_json) {
+        Some(s) => match serde_json::from_str(&s) {
+            Ok(f) => f,
+            Err(_) => return std::ptr::null_mut(),
+        },
+        None => EchoField::default(),
+    };
+
+    field.record(echo);
+    json_to_cstring(&field)
+}
+
+/// Number of live echoes in `field_json` on `floor_id` within `radius` of
+/// (x, y, z), for TriggerContext.echo_count. Invalid input counts as 0.
+#[no_mangle]
+pub extern "C" fn death_count_echoes_near(
+    field_json: *const c_char,
+    floor_id: u32,
+    x: f32,
+    y: f32,
+    z: f32,
+    radius: f32,
+) -> u32 {
+    parse_cstr(field_json)
+        .and_then(|s| serde_json::from_str::<EchoField>(&s).ok())
+        .map_or(0, |field| field.count_near(floor_id, [x, y, z], radius))
+}
+
 // ========================
 // C-ABI: Events
 // ========================
@@ -595,6 +1242,51 @@ pub extern "C" fn evaluate_event_trigger(
     }
 }
 
+/// Capture event cooldowns for persistence across floor loads
+/// cooldowns_json: e.g. `[["BreathShift", 42.5]]`
+/// Returns EventCooldownSnapshot JSON
+#[no_mangle]
+pub extern "C" fn event_capture_cooldowns(
+    cooldowns_json: *const c_char,
+    events_triggered: u64,
+) -> *mut c_char {
+    let json_str = match parse_cstr(cooldowns_json) {
+        Some(s) => s,
+        None => return std::ptr::null_mut(),
+    };
+    let cooldowns: Vec<(EventTriggerType, f32)> = match serde_json::from_str(&json_str) {
+        Ok(c) => c,
+        Err(_) => return std::ptr::null_mut(),
+    };
+
+    let mut manager = events::EventManager {
+        events_triggered,
+        ..Default::default()
+    };
+    for (trigger, remaining) in cooldowns {
+        manager.set_cooldown(trigger, remaining);
+    }
+    json_to_cstring(&manager.capture())
+}
+
+/// Restore event cooldowns from a snapshot, dropping expired entries
+/// Returns the restored EventCooldownSnapshot JSON
+#[no_mangle]
+pub extern "C" fn event_restore_cooldowns(snapshot_json: *const c_char) -> *mut c_char {
+    let json_str = match parse_cstr(snapshot_json) {
+        Some(s) => s,
+        None => return std::ptr::null_mut(),
+    };
+    let snapshot: events::EventCooldownSnapshot = match serde_json::from_str(&json_str) {
+        Ok(s) => s,
+        Err(_) => return std::ptr::null_mut(),
+    };
+
+    let mut manager = events::EventManager::default();
+    manager.restore(&snapshot);
+    json_to_cstring(&manager.capture())
+}
+
 // ========================
 // C-ABI: Mastery System
 // ========================
@@ -632,6 +1324,60 @@ pub extern "C" fn mastery_gain_xp(
     json_to_cstring(&profile)
 }
 
+/// Apply opt-in XP decay for idle domains, return updated profile JSON
+/// spec_json: SpecializationProfile JSON (null = no spec floors);
+/// specialized domains never decay below their branch's required tier
+#[no_mangle]
+pub extern "C" fn mastery_apply_decay(
+    profile_json: *const c_char,
+    spec_json: *const c_char,
+    elapsed_secs: f32,
+    fraction_per_day: f32,
+) -> *mut c_char {
+    let json_str = match parse_cstr(profile_json) {
+        Some(s) => s,
+        None => return std::ptr::null_mut(),
+    };
+    let mut profile: MasteryProfile = match serde_json::from_str(&json_str) {
+        Ok(p) => p,
+        Err(_) => return std::ptr::null_mut(),
+    };
+    let spec: SpecializationProfile = parse_cstr(spec_json)
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default();
+
+    let config = DecayConfig {
+        fraction_per_day,
+        tier_floors: spec.decay_floors(),
+    };
+    profile.apply_decay(elapsed_secs, &config);
+    json_to_cstring(&profile)
+}
+
+/// Prestige a Grandmaster domain, return updated profile JSON
+/// Returns null if the domain is invalid or not yet Grandmaster
+#[no_mangle]
+pub extern "C" fn mastery_prestige(profile_json: *const c_char, domain_id: u32) -> *mut c_char {
+    let json_str = match parse_cstr(profile_json) {
+        Some(s) => s,
+        None => return std::ptr::null_mut(),
+    };
+    let mut profile: MasteryProfile = match serde_json::from_str(&json_str) {
+        Ok(p) => p,
+        Err(_) => return std::ptr::null_mut(),
+    };
+
+    let domain = match domain_from_id(domain_id) {
+        Some(d) => d,
+        None => return std::ptr::null_mut(),
+    };
+
+    match profile.prestige(domain) {
+        Ok(_) => json_to_cstring(&profile),
+        Err(_) => std::ptr::null_mut(),
+    }
+}
+
 /// Get mastery tier for a domain (0=Novice..5=Grandmaster), -1 if invalid
 #[no_mangle]
 pub extern "C" fn mastery_get_tier(profile_json: *const c_char, domain_id: u32) -> i32 {
@@ -769,6 +1515,37 @@ pub extern "C" fn spec_choose_branch(
     }
 }
 
+/// Reset a domain's branch for shards (cost doubles per prior respec).
+/// domain_id: same ids as mastery_gain_xp.
+/// Returns {"profile", "shards", "cost"}, or {"error": RespecError} if refused.
+#[no_mangle]
+pub extern "C" fn spec_reset_branch_paid(
+    profile_json: *const c_char,
+    domain_id: u32,
+    shards: u64,
+) -> *mut c_char {
+    let mut profile: SpecializationProfile =
+        match parse_cstr(profile_json).and_then(|s| serde_json::from_str(&s).ok()) {
+            Some(p) => p,
+            None => return std::ptr::null_mut(),
+        };
+    let domain = match domain_from_id(domain_id) {
+        Some(d) => d,
+        None => return std::ptr::null_mut(),
+    };
+
+    let cost = profile.respec_cost();
+    let mut shards = shards;
+    match profile.reset_branch_paid(domain, &mut shards) {
+        Ok(()) => json_to_cstring(&serde_json::json!({
+            "profile": profile,
+            "shards": shards,
+            "cost": cost,
+        })),
+        Err(e) => json_to_cstring(&serde_json::json!({ "error": e })),
+    }
+}
+
 /// Find active synergies for chosen branches, return JSON
 #[no_mangle]
 pub extern "C" fn spec_find_synergies(branch_ids_json: *const c_char) -> *mut c_char {
@@ -887,6 +1664,116 @@ pub extern "C" fn ability_equip(
     json_to_cstring(&loadout)
 }
 
+/// Cast the ability in a hotbar slot.
+/// cooldowns_json: AbilityCooldownTracker JSON (null = nothing cooling down);
+/// spec_json: SpecializationProfile JSON for the ultimate cooldown policy
+/// (null = independent ultimates); cdr: cooldown reduction (0.2 = 20%).
+/// Returns {"loadout", "cooldowns", "resources"} on success, {"error": CastError}
+/// if refused, or null if any non-null input is malformed.
+#[no_mangle]
+pub extern "C" fn ability_try_cast(
+    loadout_json: *const c_char,
+    slot: u32,
+    cooldowns_json: *const c_char,
+    resources_json: *const c_char,
+    spec_json: *const c_char,
+    cdr: f32,
+) -> *mut c_char {
+    let loadout: AbilityLoadout =
+        match parse_cstr(loadout_json).and_then(|s| serde_json::from_str(&s).ok()) {
+            Some(l) => l,
+            None => return std::ptr::null_mut(),
+        };
+    let mut resources: AbilityResources =
+        match parse_cstr(resources_json).and_then(|s| serde_json::from_str(&s).ok()) {
+            Some(r) => r,
+            None => return std::ptr::null_mut(),
+        };
+    let mut cooldowns: AbilityCooldownTracker = match parse_cstr(cooldowns_json) {
+        Some(s) => match serde_json::from_str(&s) {
+            Ok(c) => c,
+            Err(_) => return std::ptr::null_mut(),
+        },
+        None => AbilityCooldownTracker::default(),
+    };
+    let spec: SpecializationProfile = match parse_cstr(spec_json) {
+        Some(s) => match serde_json::from_str(&s) {
+            Ok(p) => p,
+            Err(_) => return std::ptr::null_mut(),
+        },
+        None => SpecializationProfile::default(),
+    };
+
+    match loadout.try_cast(
+        slot as usize,
+        &mut cooldowns,
+        &mut resources,
+        cdr,
+        spec.ultimate_cooldown_policy(),
+    ) {
+        Ok(()) => json_to_cstring(&serde_json::json!({
+            "loadout": loadout,
+            "cooldowns": cooldowns,
+            "resources": resources,
+        })),
+        Err(e) => json_to_cstring(&serde_json::json!({ "error": e })),
+    }
+}
+
+/// Raise a learned ability one rank, gated by the player's mastery tier.
+/// Returns updated loadout JSON, or {"error": UpgradeError} if refused.
+#[no_mangle]
+pub extern "C" fn ability_upgrade(
+    loadout_json: *const c_char,
+    ability_id: *const c_char,
+    mastery_json: *const c_char,
+) -> *mut c_char {
+    let mut loadout: AbilityLoadout =
+        match parse_cstr(loadout_json).and_then(|s| serde_json::from_str(&s).ok()) {
+            Some(l) => l,
+            None => return std::ptr::null_mut(),
+        };
+    let aid = match parse_cstr(ability_id) {
+        Some(s) => s,
+        None => return std::ptr::null_mut(),
+    };
+    let mastery: MasteryProfile =
+        match parse_cstr(mastery_json).and_then(|s| serde_json::from_str(&s).ok()) {
+            Some(m) => m,
+            None => return std::ptr::null_mut(),
+        };
+
+    match loadout.upgrade(&aid, &mastery) {
+        Ok(()) => json_to_cstring(&loadout),
+        Err(e) => json_to_cstring(&serde_json::json!({ "error": e })),
+    }
+}
+
+/// A learned ability scaled by its rank: {"rank", "ability"} JSON, or null if unknown
+#[no_mangle]
+pub extern "C" fn ability_effective_stats(
+    loadout_json: *const c_char,
+    ability_id: *const c_char,
+) -> *mut c_char {
+    let loadout: AbilityLoadout =
+        match parse_cstr(loadout_json).and_then(|s| serde_json::from_str(&s).ok()) {
+            Some(l) => l,
+            None => return std::ptr::null_mut(),
+        };
+    let aid = match parse_cstr(ability_id) {
+        Some(s) => s,
+        None => return std::ptr::null_mut(),
+    };
+
+    match loadout.effective_stats(&aid) {
+        Some(ability) => json_to_cstring(&serde_json::json!({
+            "rank": loadout.rank(&aid),
+            "ability": ability,
+        })),
+        None => std::ptr::null_mut(),
+    }
+}
+
 // ========================
 // C-ABI: Socket System
 // ========================
@@ -995,6 +1882,17 @@ pub extern "C" fn socket_insert_rune(
     }
 }
 
+/// Sum socketed gem/rune effects of equipment, return SocketEffects JSON or null
+#[no_mangle]
+pub extern "C" fn socket_aggregate_effects(equipment_json: *const c_char) -> *mut c_char {
+    let equip: SocketedEquipment =
+        match parse_cstr(equipment_json).and_then(|s| serde_json::from_str(&s).ok()) {
+            Some(e) => e,
+            None => return std::ptr::null_mut(),
+        };
+    json_to_cstring(&equip.aggregate_effects())
+}
+
 /// Combine 3 gems of same tier into next tier, return new gem JSON or null
 #[no_mangle]
 pub extern "C" fn socket_combine_gems(gems_json: *const c_char) -> *mut c_char {
@@ -1021,6 +1919,60 @@ pub extern "C" fn socket_combine_gems(gems_json: *const c_char) -> *mut c_char {
     }
 }
 
+/// Roll stat affixes for an equipment piece, return RolledStats JSON
+/// tags_json: `[["fire", 0.8], ...]`, rarity_id: 0=Common..5=Mythic
+#[no_mangle]
+pub extern "C" fn equipment_roll_stats(
+    tags_json: *const c_char,
+    rarity_id: u32,
+    roll_hash: u64,
+) -> *mut c_char {
+    let tags: Vec<(String, f32)> =
+        match parse_cstr(tags_json).and_then(|s| serde_json::from_str(&s).ok()) {
+            Some(t) => t,
+            None => return std::ptr::null_mut(),
+        };
+    let rarity = match rarity_from_id(rarity_id) {
+        Some(r) => r,
+        None => return std::ptr::null_mut(),
+    };
+
+    let stats = equipment::roll_stats(&SemanticTags { tags }, rarity, roll_hash);
+    json_to_cstring(&stats)
+}
+
+/// Tag-keyed set bonuses active for equipped pieces, return SetBonus array JSON
+/// equipped_json: one tag list per piece, e.g. `[[["fire", 0.8]], [["fire", 0.7]]]`
+#[no_mangle]
+pub extern "C" fn equipment_active_set_bonuses(equipped_json: *const c_char) -> *mut c_char {
+    let pieces: Vec<Vec<(String, f32)>> =
+        match parse_cstr(equipped_json).and_then(|s| serde_json::from_str(&s).ok()) {
+            Some(p) => p,
+            None => return std::ptr::null_mut(),
+        };
+    let equipped: Vec<SemanticTags> = pieces
+        .into_iter()
+        .map(|tags| SemanticTags { tags })
+        .collect();
+
+    json_to_cstring(&equipment::active_set_bonuses(&equipped))
+}
+
+/// Combine 3 gems with failure risk, return CombineOutcome JSON or null
+#[no_mangle]
+pub extern "C" fn socket_combine_gems_risky(gems_json: *const c_char, roll: u64) -> *mut c_char {
+    let gems: Vec<Gem> = match parse_cstr(gems_json).and_then(|s| serde_json::from_str(&s).ok()) {
+        Some(g) => g,
+        None => return std::ptr::null_mut(),
+    };
+    let arr: [Gem; 3] = match gems.try_into() {
+        Ok(a) => a,
+        Err(_) => return std::ptr::null_mut(),
+    };
+
+    json_to_cstring(&combine_gems_risky(&arr, roll))
+}
+
 // ========================
 // C-ABI: Cosmetics
 // ========================
@@ -1326,6 +2278,140 @@ pub extern "C" fn season_get_rewards(season_number: u32) -> *mut c_char {
     json_to_cstring(&rewards)
 }
 
+/// Mark a recurring quest complete, return updated SeasonProgress JSON
+/// progress_json: null/empty = fresh progress
+#[no_mangle]
+pub extern "C" fn season_complete_quest(
+    progress_json: *const c_char,
+    quest_id: *const c_char,
+) -> *mut c_char {
+    let mut progress: SeasonProgress = match parse_cstr(progress_json) {
+        Some(s) if !s.is_empty() => match serde_json::from_str(&s) {
+            Ok(p) => p,
+            Err(_) => return std::ptr::null_mut(),
+        },
+        _ => SeasonProgress::new(),
+    };
+    let quest_id = match parse_cstr(quest_id) {
+        Some(s) => s,
+        None => return std::ptr::null_mut(),
+    };
+
+    progress.complete_quest(&quest_id);
+    json_to_cstring(&progress)
+}
+
+/// Claim a completed quest's reward, feeding its season XP into the pass.
+/// The quest is looked up by `quest_id`, so the reward always comes from the
+/// server definition. Returns {"progress", "pass", "reward"}, or null if the
+/// quest is unknown, incomplete or already claimed.
+#[no_mangle]
+pub extern "C" fn season_claim_reward(
+    progress_json: *const c_char,
+    quest_id: *const c_char,
+    pass_json: *const c_char,
+) -> *mut c_char {
+    let mut progress: SeasonProgress =
+        match parse_cstr(progress_json).and_then(|s| serde_json::from_str(&s).ok()) {
+            Some(p) => p,
+            None => return std::ptr::null_mut(),
+        };
+    let quest = match parse_cstr(quest_id).and_then(|id| find_recurring_quest(&id)) {
+        Some(q) => q,
+        None => return std::ptr::null_mut(),
+    };
+    let mut pass: SeasonPass =
+        match parse_cstr(pass_json).and_then(|s| serde_json::from_str(&s).ok()) {
+            Some(p) => p,
+            None => return std::ptr::null_mut(),
+        };
+
+    match progress.claim(&quest, &mut pass) {
+        Some(reward) => json_to_cstring(&serde_json::json!({
+            "progress": progress,
+            "pass": pass,
+            "reward": reward,
+        })),
+        None => std::ptr::null_mut(),
+    }
+}
+
+// ========================
+// C-ABI: Faction Reputation
+// ========================
+
+fn parse_reputation_profile(profile_json: *const c_char) -> Option<ReputationProfile> {
+    match parse_cstr(profile_json) {
+        Some(s) if !s.is_empty() => serde_json::from_str(&s).ok(),
+        _ => Some(ReputationProfile::with_tower_rivalries()),
+    }
+}
+
+/// Change reputation with a faction (rivals move the opposite way).
+/// profile_json: null/empty = fresh profile with the tower rivalries.
+/// Returns {"profile", "unlocked_perks"}.
+#[no_mangle]
+pub extern "C" fn faction_gain_rep(
+    profile_json: *const c_char,
+    faction: *const c_char,
+    amount: f32,
+) -> *mut c_char {
+    let mut profile = match parse_reputation_profile(profile_json) {
+        Some(p) => p,
+        None => return std::ptr::null_mut(),
+    };
+    let faction = match parse_cstr(faction) {
+        Some(s) => s,
+        None => return std::ptr::null_mut(),
+    };
+
+    let unlocked = profile.gain(&faction, amount);
+    json_to_cstring(&serde_json::json!({
+        "profile": profile,
+        "unlocked_perks": unlocked,
+    }))
+}
+
+/// Reputation tier with a faction, e.g. `"Friendly"`
+#[no_mangle]
+pub extern "C" fn faction_get_tier(
+    profile_json: *const c_char,
+    faction: *const c_char,
+) -> *mut c_char {
+    let profile = match parse_reputation_profile(profile_json) {
+        Some(p) => p,
+        None => return std::ptr::null_mut(),
+    };
+    let faction = match parse_cstr(faction) {
+        Some(s) => s,
+        None => return std::ptr::null_mut(),
+    };
+    json_to_cstring(&profile.tier(&faction))
+}
+
+/// Influence zones for the factions on a floor, plus the contested pairs.
+/// factions_json: `["AscendingOrder", "DeepDwellers"]`
+/// Returns {"zones", "contested"}; `contested` fits TriggerContext.contested_factions.
+#[no_mangle]
+pub extern "C" fn faction_influence_zones(
+    seed: u64,
+    floor_id: u32,
+    factions_json: *const c_char,
+) -> *mut c_char {
+    let factions: Vec<String> =
+        match parse_cstr(factions_json).and_then(|s| serde_json::from_str(&s).ok()) {
+            Some(f) => f,
+            None => return std::ptr::null_mut(),
+        };
+
+    let zones = faction::influence_zones(seed, floor_id, &factions);
+    let contested = faction::overlapping_pairs(&zones);
+    json_to_cstring(&serde_json::json!({
+        "zones": zones,
+        "contested": contested,
+    }))
+}
+
 // ========================
 // C-ABI: Social — Guild
 // ========================
@@ -1398,21 +2484,97 @@ pub extern "C" fn social_guild_add_member(
     }
 }
 
-// ========================
-// C-ABI: Social — Party
-// ========================
-
-/// Create a new party, return JSON
+/// Deposit an item into the guild bank.
+/// `item_json` is a TradeItem; `now` is a unix timestamp.
+/// Returns updated guild JSON, or null if the member can't deposit.
 #[no_mangle]
-pub extern "C" fn social_create_party(
-    leader_id: *const c_char,
-    leader_name: *const c_char,
+pub extern "C" fn guild_bank_deposit(
+    guild_json: *const c_char,
+    user_id: *const c_char,
+    tab: u32,
+    item_json: *const c_char,
+    now: u64,
 ) -> *mut c_char {
-    let lid_str = match parse_cstr(leader_id) {
+    let guild_str = match parse_cstr(guild_json) {
         Some(s) => s,
         None => return std::ptr::null_mut(),
     };
-    let lname_str = match parse_cstr(leader_name) {
+    let uid_str = match parse_cstr(user_id) {
+        Some(s) => s,
+        None => return std::ptr::null_mut(),
+    };
+    let item_str = match parse_cstr(item_json) {
+        Some(s) => s,
+        None => return std::ptr::null_mut(),
+    };
+
+    let mut guild: Guild = match serde_json::from_str(&guild_str) {
+        Ok(g) => g,
+        Err(_) => return std::ptr::null_mut(),
+    };
+    let item: TradeItem = match serde_json::from_str(&item_str) {
+        Ok(i) => i,
+        Err(_) => return std::ptr::null_mut(),
+    };
+
+    if guild.bank_deposit(&uid_str, tab as usize, item, now) {
+        json_to_cstring(&guild)
+    } else {
+        std::ptr::null_mut()
+    }
+}
+
+/// Withdraw items from the guild bank within the member's daily rank limit.
+/// Returns updated guild JSON, or null on permission/limit/stock failure.
+#[no_mangle]
+pub extern "C" fn guild_bank_withdraw(
+    guild_json: *const c_char,
+    user_id: *const c_char,
+    tab: u32,
+    item_name: *const c_char,
+    quantity: u32,
+    now: u64,
+) -> *mut c_char {
+    let guild_str = match parse_cstr(guild_json) {
+        Some(s) => s,
+        None => return std::ptr::null_mut(),
+    };
+    let uid_str = match parse_cstr(user_id) {
+        Some(s) => s,
+        None => return std::ptr::null_mut(),
+    };
+    let name_str = match parse_cstr(item_name) {
+        Some(s) => s,
+        None => return std::ptr::null_mut(),
+    };
+
+    let mut guild: Guild = match serde_json::from_str(&guild_str) {
+        Ok(g) => g,
+        Err(_) => return std::ptr::null_mut(),
+    };
+
+    if guild.bank_withdraw(&uid_str, tab as usize, &name_str, quantity, now) {
+        json_to_cstring(&guild)
+    } else {
+        std::ptr::null_mut()
+    }
+}
+
+// ========================
+// C-ABI: Social — Party
+// ========================
+
+/// Create a new party, return JSON
+#[no_mangle]
+pub extern "C" fn social_create_party(
+    leader_id: *const c_char,
+    leader_name: *const c_char,
+) -> *mut c_char {
+    let lid_str = match parse_cstr(leader_id) {
+        Some(s) => s,
+        None => return std::ptr::null_mut(),
+    };
+    let lname_str = match parse_cstr(leader_name) {
         Some(s) => s,
         None => return std::ptr::null_mut(),
     };
@@ -1463,6 +2625,93 @@ pub extern "C" fn social_party_add_member(
     }
 }
 
+/// Assign a dropped item to a party member under the party's loot mode.
+/// `item_json` is a TradeItem; `rolls_json` is `[["user_id", roll], ...]`
+/// (add NEED_ROLL_BONUS to declare need, 0 = pass).
+/// Returns {"winner": user_id, "party": updated Party} (round-robin cursor advanced).
+#[no_mangle]
+pub extern "C" fn party_assign_loot(
+    party_json: *const c_char,
+    item_json: *const c_char,
+    rolls_json: *const c_char,
+) -> *mut c_char {
+    let party_str = match parse_cstr(party_json) {
+        Some(s) => s,
+        None => return std::ptr::null_mut(),
+    };
+    let item_str = match parse_cstr(item_json) {
+        Some(s) => s,
+        None => return std::ptr::null_mut(),
+    };
+    let rolls_str = match parse_cstr(rolls_json) {
+        Some(s) => s,
+        None => return std::ptr::null_mut(),
+    };
+
+    let mut party: Party = match serde_json::from_str(&party_str) {
+        Ok(p) => p,
+        Err(_) => return std::ptr::null_mut(),
+    };
+    let item: TradeItem = match serde_json::from_str(&item_str) {
+        Ok(i) => i,
+        Err(_) => return std::ptr::null_mut(),
+    };
+    let rolls: Vec<(String, u32)> = serde_json::from_str(&rolls_str).unwrap_or_default();
+
+    let winner = party.assign_loot(&item, &rolls);
+    json_to_cstring(&serde_json::json!({
+        "winner": winner,
+        "party": party,
+    }))
+}
+
+/// Create an empty raid. `max_parties` 0 uses DEFAULT_RAID_PARTIES.
+#[no_mangle]
+pub extern "C" fn social_create_raid(max_parties: u32) -> *mut c_char {
+    let max_parties = match max_parties {
+        0 => crate::social::DEFAULT_RAID_PARTIES,
+        n => n as usize,
+    };
+    json_to_cstring(&Raid::new(max_parties))
+}
+
+/// Add a party to a raid, return updated raid JSON or null (raid full,
+/// member already in the raid, or invalid input)
+#[no_mangle]
+pub extern "C" fn social_raid_add_party(
+    raid_json: *const c_char,
+    party_json: *const c_char,
+) -> *mut c_char {
+    let mut raid: Raid = match parse_cstr(raid_json).and_then(|s| serde_json::from_str(&s).ok()) {
+        Some(r) => r,
+        None => return std::ptr::null_mut(),
+    };
+    let party: Party = match parse_cstr(party_json).and_then(|s| serde_json::from_str(&s).ok()) {
+        Some(p) => p,
+        None => return std::ptr::null_mut(),
+    };
+
+    if raid.add_party(party) {
+        json_to_cstring(&raid)
+    } else {
+        std::ptr::null_mut()
+    }
+}
+
+/// Raid-wide role bonuses: {"total_members": N, "composition": CompositionBonus}
+#[no_mangle]
+pub extern "C" fn social_raid_composition(raid_json: *const c_char) -> *mut c_char {
+    let raid: Raid = match parse_cstr(raid_json).and_then(|s| serde_json::from_str(&s).ok()) {
+        Some(r) => r,
+        None => return std::ptr::null_mut(),
+    };
+
+    json_to_cstring(&serde_json::json!({
+        "total_members": raid.total_members(),
+        "composition": raid.raid_composition(),
+    }))
+}
+
 // ========================
 // C-ABI: Social — Trade
 // ========================
@@ -1574,7 +2823,9 @@ pub extern "C" fn social_trade_confirm(
     json_to_cstring(&trade)
 }
 
-/// Execute a confirmed trade, return result JSON (completed or error)
+/// Legacy entry point kept for ABI compatibility. Trades can no longer be
+/// executed without both inventories, so this always returns `{error}`
+/// pointing callers to `social_trade_execute_validated`.
 #[no_mangle]
 pub extern "C" fn social_trade_execute(trade_json: *const c_char) -> *mut c_char {
     let trade_str = match parse_cstr(trade_json) {
@@ -1582,13 +2833,203 @@ pub extern "C" fn social_trade_execute(trade_json: *const c_char) -> *mut c_char
         None => return std::ptr::null_mut(),
     };
 
+    if serde_json::from_str::<Trade>(&trade_str).is_err() {
+        return std::ptr::null_mut();
+    }
+
+    json_to_cstring(&serde_json::json!({
+        "error": "social_trade_execute is deprecated; use social_trade_execute_validated \
+                  with both players' inventories",
+    }))
+}
+
+/// Execute a confirmed trade against both players' inventories.
+/// Returns `{trade, inventory_a, inventory_b}` on success or `{error}` with
+/// a `TradeError` if validation fails.
+#[no_mangle]
+pub extern "C" fn social_trade_execute_validated(
+    trade_json: *const c_char,
+    inventory_a_json: *const c_char,
+    inventory_b_json: *const c_char,
+) -> *mut c_char {
+    let trade_str = match parse_cstr(trade_json) {
+        Some(s) => s,
+        None => return std::ptr::null_mut(),
+    };
+    let inv_a_str = match parse_cstr(inventory_a_json) {
+        Some(s) => s,
+        None => return std::ptr::null_mut(),
+    };
+    let inv_b_str = match parse_cstr(inventory_b_json) {
+        Some(s) => s,
+        None => return std::ptr::null_mut(),
+    };
+
     let mut trade: Trade = match serde_json::from_str(&trade_str) {
         Ok(t) => t,
         Err(_) => return std::ptr::null_mut(),
     };
+    let mut inv_a: PlayerInventory = match serde_json::from_str(&inv_a_str) {
+        Ok(i) => i,
+        Err(_) => return std::ptr::null_mut(),
+    };
+    let mut inv_b: PlayerInventory = match serde_json::from_str(&inv_b_str) {
+        Ok(i) => i,
+        Err(_) => return std::ptr::null_mut(),
+    };
 
-    trade.execute();
-    json_to_cstring(&trade)
+    match trade.execute(&mut inv_a, &mut inv_b) {
+        Ok(()) => json_to_cstring(&serde_json::json!({
+            "trade": trade,
+            "inventory_a": inv_a,
+            "inventory_b": inv_b,
+        })),
+        Err(e) => json_to_cstring(&serde_json::json!({ "error": e })),
+    }
+}
+
+// ========================
+// C-ABI: Player — Inventory
+// ========================
+
+/// Add a LootItem to an inventory, stacking where possible. Pass null/empty
+/// `inventory_json` for a fresh default inventory. Returns
+/// `{inventory, used_slots}` or `{error}` if it doesn't fit.
+#[no_mangle]
+pub extern "C" fn inventory_add_item(
+    inventory_json: *const c_char,
+    item_json: *const c_char,
+) -> *mut c_char {
+    let mut inventory: Inventory = match parse_cstr(inventory_json) {
+        Some(s) if !s.is_empty() => match serde_json::from_str(&s) {
+            Ok(i) => i,
+            Err(_) => return std::ptr::null_mut(),
+        },
+        _ => Inventory::default(),
+    };
+    let item: loot::LootItem =
+        match parse_cstr(item_json).and_then(|s| serde_json::from_str(&s).ok()) {
+            Some(i) => i,
+            None => return std::ptr::null_mut(),
+        };
+
+    match inventory.add(item) {
+        Ok(()) => json_to_cstring(&serde_json::json!({
+            "used_slots": inventory.used_slots(),
+            "inventory": inventory,
+        })),
+        Err(e) => json_to_cstring(&serde_json::json!({ "error": e })),
+    }
+}
+
+// ========================
+// C-ABI: Economy — Auction House
+// ========================
+
+/// List an item (economy `TradeItem` JSON) on the auction house.
+/// Returns `{house, listing_id}` or `{error}`.
+#[no_mangle]
+pub extern "C" fn auction_list(
+    house_json: *const c_char,
+    seller_id: *const c_char,
+    item_json: *const c_char,
+    buyout: u64,
+    duration_ticks: u64,
+) -> *mut c_char {
+    let house_str = match parse_cstr(house_json) {
+        Some(s) => s,
+        None => return std::ptr::null_mut(),
+    };
+    let seller = match parse_cstr(seller_id) {
+        Some(s) => s,
+        None => return std::ptr::null_mut(),
+    };
+    let item_str = match parse_cstr(item_json) {
+        Some(s) => s,
+        None => return std::ptr::null_mut(),
+    };
+
+    let mut house: AuctionHouse = match serde_json::from_str(&house_str) {
+        Ok(h) => h,
+        Err(_) => return std::ptr::null_mut(),
+    };
+    let item: crate::economy::TradeItem = match serde_json::from_str(&item_str) {
+        Ok(i) => i,
+        Err(_) => return std::ptr::null_mut(),
+    };
+
+    match house.list(&seller, item, buyout, duration_ticks) {
+        Ok(id) => json_to_cstring(&serde_json::json!({
+            "house": house,
+            "listing_id": id,
+        })),
+        Err(e) => json_to_cstring(&serde_json::json!({ "error": e })),
+    }
+}
+
+/// Buy a listing outright with the buyer's wallet.
+/// Returns `{house, wallet, item}` or `{error}`.
+#[no_mangle]
+pub extern "C" fn auction_buyout(
+    house_json: *const c_char,
+    listing_id: *const c_char,
+    buyer_id: *const c_char,
+    wallet_json: *const c_char,
+) -> *mut c_char {
+    let house_str = match parse_cstr(house_json) {
+        Some(s) => s,
+        None => return std::ptr::null_mut(),
+    };
+    let id = match parse_cstr(listing_id) {
+        Some(s) => s,
+        None => return std::ptr::null_mut(),
+    };
+    let buyer = match parse_cstr(buyer_id) {
+        Some(s) => s,
+        None => return std::ptr::null_mut(),
+    };
+    let wallet_str = match parse_cstr(wallet_json) {
+        Some(s) => s,
+        None => return std::ptr::null_mut(),
+    };
+
+    let mut house: AuctionHouse = match serde_json::from_str(&house_str) {
+        Ok(h) => h,
+        Err(_) => return std::ptr::null_mut(),
+    };
+    let mut wallet: Wallet = match serde_json::from_str(&wallet_str) {
+        Ok(w) => w,
+        Err(_) => return std::ptr::null_mut(),
+    };
+
+    match house.buyout(&id, &buyer, &mut wallet) {
+        Ok(item) => json_to_cstring(&serde_json::json!({
+            "house": house,
+            "wallet": wallet,
+            "item": item,
+        })),
+        Err(e) => json_to_cstring(&serde_json::json!({ "error": e })),
+    }
+}
+
+/// Close listings that have run out by `current_tick`.
+/// Returns `{house, expired}`.
+#[no_mangle]
+pub extern "C" fn auction_expire(house_json: *const c_char, current_tick: u64) -> *mut c_char {
+    let house_str = match parse_cstr(house_json) {
+        Some(s) => s,
+        None => return std::ptr::null_mut(),
+    };
+    let mut house: AuctionHouse = match serde_json::from_str(&house_str) {
+        Ok(h) => h,
+        Err(_) => return std::ptr::null_mut(),
+    };
+
+    let expired = house.expire_listings(current_tick);
+    json_to_cstring(&serde_json::json!({
+        "house": house,
+        "expired": expired,
+    }))
 }
 
 // ========================
@@ -1622,6 +3063,39 @@ fn domain_from_id(id: u32) -> Option<MasteryDomain> {
     }
 }
 
+fn tier_to_u32(tier: FloorTier) -> u32 {
+    match tier {
+        FloorTier::Echelon1 => 0,
+        FloorTier::Echelon2 => 1,
+        FloorTier::Echelon3 => 2,
+        FloorTier::Echelon4 => 3,
+    }
+}
+
+fn rarity_from_id(id: u32) -> Option<ItemRarity> {
+    match id {
+        0 => Some(ItemRarity::Common),
+        1 => Some(ItemRarity::Uncommon),
+        2 => Some(ItemRarity::Rare),
+        3 => Some(ItemRarity::Epic),
+        4 => Some(ItemRarity::Legendary),
+        5 => Some(ItemRarity::Mythic),
+        _ => None,
+    }
+}
+
+fn mastery_tier_from_id(id: u32) -> Option<MasteryTier> {
+    match id {
+        0 => Some(MasteryTier::Novice),
+        1 => Some(MasteryTier::Apprentice),
+        2 => Some(MasteryTier::Journeyman),
+        3 => Some(MasteryTier::Expert),
+        4 => Some(MasteryTier::Master),
+        5 => Some(MasteryTier::Grandmaster),
+        _ => None,
+    }
+}
+
 fn socket_color_from_id(id: u32) -> SocketColor {
     match id {
         0 => SocketColor::Red,
@@ -1650,6 +3124,18 @@ fn cosmetic_slot_from_id(id: u32) -> Option<CosmeticSlot> {
     }
 }
 
+fn room_type_to_u8(room_type: &RoomType) -> u8 {
+    match room_type {
+        RoomType::Combat => 0,
+        RoomType::Treasure => 1,
+        RoomType::Puzzle => 2,
+        RoomType::Rest => 3,
+        RoomType::Boss => 4,
+        RoomType::Entrance => 5,
+        RoomType::Exit => 6,
+    }
+}
+
 fn tile_to_u8(tile: &TileType) -> u8 {
     match tile {
         TileType::Empty => 0,
@@ -1664,6 +3150,8 @@ fn tile_to_u8(tile: &TileType) -> u8 {
         TileType::Shrine => 9,
         TileType::WindColumn => 10,
         TileType::VoidPit => 11,
+        TileType::Lever => 12,
+        TileType::Gate => 13,
     }
 }
 
@@ -1685,6 +3173,42 @@ pub extern "C" fn get_all_mutator_types() -> *mut c_char {
     json_to_cstring(&all)
 }
 
+/// Generate the shared "Mutation of the Week" set for a week seed and tier
+/// (0=Echelon1..3=Echelon4), return FloorMutatorSet JSON. Null on invalid tier.
+#[no_mangle]
+pub extern "C" fn generate_weekly_mutators(week_seed: u64, tier: u32) -> *mut c_char {
+    let tier = match tier {
+        0 => FloorTier::Echelon1,
+        1 => FloorTier::Echelon2,
+        2 => FloorTier::Echelon3,
+        3 => FloorTier::Echelon4,
+        _ => return std::ptr::null_mut(),
+    };
+    json_to_cstring(&mutators::weekly_mutator_set(week_seed, tier))
+}
+
+/// Reroll one slot of a FloorMutatorSet JSON, return the updated set JSON.
+/// Returns null on bad input, an out-of-range slot, or no eligible replacement.
+#[no_mangle]
+pub extern "C" fn mutator_reroll(
+    set_json: *const c_char,
+    slot: u32,
+    reroll_seed: u64,
+) -> *mut c_char {
+    let json_str = match parse_cstr(set_json) {
+        Some(s) => s,
+        None => return std::ptr::null_mut(),
+    };
+    let mut set: mutators::FloorMutatorSet = match serde_json::from_str(&json_str) {
+        Ok(s) => s,
+        Err(_) => return std::ptr::null_mut(),
+    };
+    if !mutators::reroll_mutator(&mut set, slot as usize, reroll_seed) {
+        return std::ptr::null_mut();
+    }
+    json_to_cstring(&set)
+}
+
 /// Compute aggregate effects from a JSON array of mutators
 #[no_mangle]
 pub extern "C" fn compute_mutator_effects(mutators_json: *const c_char) -> *mut c_char {
@@ -1733,25 +3257,48 @@ pub extern "C" fn migrate_save(save_json: *const c_char) -> *mut c_char {
     json_to_cstring(&result)
 }
 
-/// Get the save version from a JSON string, returns 0 if invalid
+/// Migrate a save all-or-nothing, return JSON MigrationResult whose `data`
+/// is the original save if any step failed
 #[no_mangle]
-pub extern "C" fn get_save_version(save_json: *const c_char) -> u32 {
+pub extern "C" fn migrate_save_checked(save_json: *const c_char) -> *mut c_char {
     let json_str = match parse_cstr(save_json) {
         Some(s) => s,
-        None => return 0,
+        None => return std::ptr::null_mut(),
     };
-    savemigration::get_save_version(&json_str).unwrap_or(0)
+    let result = savemigration::migrate_save_checked(&json_str);
+    json_to_cstring(&result)
 }
 
-/// Create a new empty save at current version, return JSON
+/// Salvage a damaged save, return JSON RecoveryResult (never null for valid input)
 #[no_mangle]
-pub extern "C" fn create_new_save(player_name: *const c_char) -> *mut c_char {
-    let name = match parse_cstr(player_name) {
+pub extern "C" fn recover_save(save_json: *const c_char) -> *mut c_char {
+    let json_str = match parse_cstr(save_json) {
         Some(s) => s,
         None => return std::ptr::null_mut(),
     };
-    let save = savemigration::create_new_save(&name);
-    json_to_cstring(&save)
+    let result = savemigration::recover_save(&json_str);
+    json_to_cstring(&result)
+}
+
+/// Get the save version from a JSON string, returns 0 if invalid
+#[no_mangle]
+pub extern "C" fn get_save_version(save_json: *const c_char) -> u32 {
+    let json_str = match parse_cstr(save_json) {
+        Some(s) => s,
+        None => return 0,
+    };
+    savemigration::get_save_version(&json_str).unwrap_or(0)
+}
+
+/// Create a new empty save at current version, return JSON
+#[no_mangle]
+pub extern "C" fn create_new_save(player_name: *const c_char) -> *mut c_char {
+    let name = match parse_cstr(player_name) {
+        Some(s) => s,
+        None => return std::ptr::null_mut(),
+    };
+    let save = savemigration::create_new_save(&name);
+    json_to_cstring(&save)
 }
 
 /// Get current save format version
@@ -1914,6 +3461,43 @@ pub extern "C" fn replay_create_playback(recording_json: *const c_char) -> *mut
     json_to_cstring(&playback)
 }
 
+/// Verify that a recording reproduces its floor and inputs.
+/// Returns VerifyReport JSON: seed_ok, hash_ok, layout_ok, frames_ok, frame_count, ...
+#[no_mangle]
+pub extern "C" fn replay_verify(recording_json: *const c_char) -> *mut c_char {
+    let json_str = match parse_cstr(recording_json) {
+        Some(s) => s,
+        None => return std::ptr::null_mut(),
+    };
+
+    let recording = match replay::ReplayRecording::from_json(&json_str) {
+        Some(r) => r,
+        None => return std::ptr::null_mut(),
+    };
+
+    json_to_cstring(&replay::verify(&recording))
+}
+
+/// Diff two recordings of the same seed + floor.
+/// Returns ReplayDiff JSON (first_divergent_tick, differing_frames), or
+/// {"error": ...} when the seeds or floors differ.
+#[no_mangle]
+pub extern "C" fn replay_diff(a_json: *const c_char, b_json: *const c_char) -> *mut c_char {
+    let a = match parse_cstr(a_json).and_then(|s| replay::ReplayRecording::from_json(&s)) {
+        Some(r) => r,
+        None => return std::ptr::null_mut(),
+    };
+    let b = match parse_cstr(b_json).and_then(|s| replay::ReplayRecording::from_json(&s)) {
+        Some(r) => r,
+        None => return std::ptr::null_mut(),
+    };
+
+    match replay::diff(&a, &b) {
+        Ok(d) => json_to_cstring(&d),
+        Err(e) => json_to_cstring(&serde_json::json!({ "error": e })),
+    }
+}
+
 /// Get replay snapshot for FFI
 #[no_mangle]
 pub extern "C" fn replay_get_snapshot() -> *mut c_char {
@@ -2101,83 +3685,254 @@ pub extern "C" fn towermap_kill_monster(map_json: *const c_char, floor_id: u32)
     json_to_cstring(&map)
 }
 
+/// Get depth milestones reached so far as JSON array of floor numbers
+#[no_mangle]
+pub extern "C" fn towermap_depth_milestones(map_json: *const c_char) -> *mut c_char {
+    let json_str = match parse_cstr(map_json) {
+        Some(s) => s,
+        None => return std::ptr::null_mut(),
+    };
+
+    let map = match towermap::TowerMap::from_json(&json_str) {
+        Some(m) => m,
+        None => return std::ptr::null_mut(),
+    };
+
+    json_to_cstring(&map.depth_milestones_reached())
+}
+
+/// Claim a depth milestone reward.
+/// Returns JSON: {"map": {...}, "reward": {...}} — reward is null if not claimable.
+#[no_mangle]
+pub extern "C" fn towermap_claim_milestone(map_json: *const c_char, floor: u32) -> *mut c_char {
+    let json_str = match parse_cstr(map_json) {
+        Some(s) => s,
+        None => return std::ptr::null_mut(),
+    };
+
+    let mut map = match towermap::TowerMap::from_json(&json_str) {
+        Some(m) => m,
+        None => return std::ptr::null_mut(),
+    };
+
+    let reward = map.claim_depth_milestone(floor);
+    json_to_cstring(&serde_json::json!({
+        "map": map,
+        "reward": reward,
+    }))
+}
+
+/// Fast-travel check from `from` to `to`.
+/// Returns JSON: {"allowed": bool, "cost": u64} — only cleared floors are allowed.
+#[no_mangle]
+pub extern "C" fn towermap_fast_travel_cost(
+    map_json: *const c_char,
+    from: u32,
+    to: u32,
+) -> *mut c_char {
+    let json_str = match parse_cstr(map_json) {
+        Some(s) => s,
+        None => return std::ptr::null_mut(),
+    };
+
+    let map = match towermap::TowerMap::from_json(&json_str) {
+        Some(m) => m,
+        None => return std::ptr::null_mut(),
+    };
+
+    json_to_cstring(&serde_json::json!({
+        "allowed": map.can_fast_travel(to),
+        "cost": towermap::fast_travel_cost(from, to),
+    }))
+}
+
+/// Per-floor deaths vs clears, highest death rate first.
+/// Returns JSON array of FloorDifficulty.
+#[no_mangle]
+pub extern "C" fn towermap_difficulty_report(map_json: *const c_char) -> *mut c_char {
+    let json_str = match parse_cstr(map_json) {
+        Some(s) => s,
+        None => return std::ptr::null_mut(),
+    };
+
+    let map = match towermap::TowerMap::from_json(&json_str) {
+        Some(m) => m,
+        None => return std::ptr::null_mut(),
+    };
+
+    json_to_cstring(&map.difficulty_report())
+}
+
+/// Plan a route from the highest cleared floor up to `target_floor`.
+/// Returns JSON: {"reachable": bool, "route": [u32], "estimated_clear_secs": f32}
+#[no_mangle]
+pub extern "C" fn towermap_route_to(map_json: *const c_char, target_floor: u32) -> *mut c_char {
+    let json_str = match parse_cstr(map_json) {
+        Some(s) => s,
+        None => return std::ptr::null_mut(),
+    };
+
+    let map = match towermap::TowerMap::from_json(&json_str) {
+        Some(m) => m,
+        None => return std::ptr::null_mut(),
+    };
+
+    let route = map.route_to(target_floor);
+    json_to_cstring(&serde_json::json!({
+        "reachable": route.is_some(),
+        "estimated_clear_secs": route.as_deref().map_or(0.0, |r| map.route_clear_time(r)),
+        "route": route.unwrap_or_default(),
+    }))
+}
+
 // ========================
 // C-ABI: Hot-Reload (Session 22)
 // ========================
 
-/// Get hot-reload status
+/// Get hot-reload status as last published by the running app
 #[no_mangle]
 pub extern "C" fn hotreload_get_status() -> *mut c_char {
-    // Create a default status since we can't access Bevy resources from FFI
-    let status = hotreload::HotReloadStatus {
-        enabled: true,
-        watched_file: Some("config/engine.json".to_string()),
-        reload_count: 0,
-        last_reload_success: false,
-        last_reload_time: 0.0,
-        last_error: None,
-    };
-    json_to_cstring(&status)
+    json_to_cstring(&hotreload::shared_status())
 }
 
-/// Trigger manual config reload
+/// Queue a config reload for the running app. Returns 1 if queued.
 #[no_mangle]
 pub extern "C" fn hotreload_trigger_reload() -> u32 {
-    // In a real implementation, this would post an event to Bevy
-    // For now, just return success
-    1
+    hotreload::post_ffi_command(hotreload::HotReloadCommand::Reload) as u32
 }
 
 // ========================
 // C-ABI: Analytics (Session 22)
 // ========================
 
-/// Get analytics snapshot
+/// Analytics aggregate fed by the `analytics_record_*` calls, shared across FFI calls
+static ANALYTICS: OnceLock<Mutex<analytics::AnalyticsCollector>> = OnceLock::new();
+
+fn analytics_collector() -> std::sync::MutexGuard<'static, analytics::AnalyticsCollector> {
+    ANALYTICS
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+}
+
+/// Get the live analytics aggregate
 #[no_mangle]
 pub extern "C" fn analytics_get_snapshot() -> *mut c_char {
-    // Create an empty snapshot
-    let snapshot = analytics::AnalyticsSnapshot {
-        combat: analytics::CombatStats::default(),
-        progression: analytics::ProgressionStats::default(),
-        equipment: analytics::EquipmentStats::default(),
-        economy: analytics::EconomyStats::default(),
-        behavior: analytics::BehaviorStats::default(),
-    };
-    json_to_cstring(&snapshot)
+    json_to_cstring(&analytics::AnalyticsSnapshot::capture(
+        &analytics_collector(),
+    ))
 }
 
-/// Reset analytics
+/// Reset the analytics aggregate and the shard ledger `analytics_record_gold` feeds
 #[no_mangle]
 pub extern "C" fn analytics_reset() {
-    // In a real implementation, this would post an event to Bevy
-    // For now, this is a no-op
+    analytics_collector().reset();
+    *economy_ledger() = EconomyLedger::new();
 }
 
-/// Record combat event
+/// Record damage dealt with a weapon
 #[no_mangle]
-pub extern "C" fn analytics_record_damage(weapon: *const c_char, _amount: u32) {
-    let _weapon_name = match parse_cstr(weapon) {
+pub extern "C" fn analytics_record_damage(weapon: *const c_char, amount: u32) {
+    let weapon = match parse_cstr(weapon) {
         Some(w) => w,
         None => return,
     };
-    // In a real implementation, this would send an AnalyticsEvent
-    // For now, this is a no-op
+    analytics_collector()
+        .record_event(&analytics::AnalyticsEvent::CombatDamageDealt { weapon, amount });
+}
+
+/// Record a kill landed with a weapon
+#[no_mangle]
+pub extern "C" fn analytics_record_kill(weapon: *const c_char) {
+    let weapon = match parse_cstr(weapon) {
+        Some(w) => w,
+        None => return,
+    };
+    // Floor tier isn't known on this path; 0 leaves the per-tier counts alone
+    analytics_collector().record_event(&analytics::AnalyticsEvent::CombatKill {
+        weapon,
+        floor_tier: 0,
+    });
 }
 
 /// Record floor cleared
 #[no_mangle]
 pub extern "C" fn analytics_record_floor_cleared(floor_id: u32, tier: u8, time_secs: f32) {
-    // In a real implementation, this would send an AnalyticsEvent
-    // For now, this is a no-op
-    let _ = (floor_id, tier, time_secs);
+    analytics_collector().record_event(&analytics::AnalyticsEvent::FloorCleared {
+        floor_id,
+        tier,
+        time_secs: time_secs as f64,
+    });
+}
+
+/// Shard ledger fed by `analytics_record_gold`, shared across FFI calls
+static ECONOMY_LEDGER: Mutex<EconomyLedger> = Mutex::new(EconomyLedger::new());
+
+fn economy_ledger() -> std::sync::MutexGuard<'static, EconomyLedger> {
+    ECONOMY_LEDGER.lock().unwrap_or_else(|e| e.into_inner())
 }
 
 /// Record gold transaction
 #[no_mangle]
 pub extern "C" fn analytics_record_gold(amount: u64, earned: u32) {
-    // earned: 1 = earned, 0 = spent
-    // In a real implementation, this would send an AnalyticsEvent
-    let _ = (amount, earned);
+    // earned: 1 = earned (faucet), 0 = spent (sink)
+    let mut ledger = economy_ledger();
+    if earned != 0 {
+        ledger.record_faucet(amount);
+        analytics_collector().record_event(&analytics::AnalyticsEvent::GoldEarned { amount });
+    } else {
+        ledger.record_sink(amount);
+        analytics_collector().record_event(&analytics::AnalyticsEvent::GoldSpent { amount });
+    }
+}
+
+/// Get the shard ledger: `{ledger, net_flow, inflation_ratio}`
+#[no_mangle]
+pub extern "C" fn economy_get_ledger() -> *mut c_char {
+    let ledger = economy_ledger();
+    json_to_cstring(&serde_json::json!({
+        "net_flow": ledger.net_flow(),
+        "inflation_ratio": ledger.inflation_ratio(),
+        "ledger": *ledger,
+    }))
+}
+
+/// Record a semantic interaction discovery into a DiscoveryStats JSON.
+/// Pass null/empty `stats_json` to start a fresh log. Returns
+/// {"stats": DiscoveryStats, "new": bool, "count": N, "recent": [...]}
+/// with the 10 most recent discoveries, newest first.
+#[no_mangle]
+pub extern "C" fn analytics_record_semantic_discovery(
+    stats_json: *const c_char,
+    tag_a: *const c_char,
+    tag_b: *const c_char,
+    interaction_json: *const c_char,
+) -> *mut c_char {
+    let mut stats: analytics::DiscoveryStats = match parse_cstr(stats_json) {
+        Some(s) if !s.is_empty() => match serde_json::from_str(&s) {
+            Ok(st) => st,
+            Err(_) => return std::ptr::null_mut(),
+        },
+        _ => analytics::DiscoveryStats::default(),
+    };
+    let (a, b) = match (parse_cstr(tag_a), parse_cstr(tag_b)) {
+        (Some(a), Some(b)) => (a, b),
+        _ => return std::ptr::null_mut(),
+    };
+    let interaction: crate::semantic::SemanticInteraction =
+        match parse_cstr(interaction_json).and_then(|s| serde_json::from_str(&s).ok()) {
+            Some(i) => i,
+            None => return std::ptr::null_mut(),
+        };
+
+    let is_new = stats.record(&a, &b, interaction);
+    let result = serde_json::json!({
+        "new": is_new,
+        "count": stats.count(),
+        "recent": stats.recent(10),
+        "stats": stats,
+    });
+    json_to_cstring(&result)
 }
 
 /// Get analytics event types
@@ -2236,6 +3991,94 @@ mod tests {
         free_string(result_ptr);
     }
 
+    #[test]
+    fn test_generate_floor_layout_binary_matches_json() {
+        for floor_id in [1, 150, 600] {
+            let json_ptr = generate_floor_layout(42, floor_id);
+            let json_str = unsafe { CStr::from_ptr(json_ptr).to_str().unwrap() };
+            let expected: FloorLayoutResponse = serde_json::from_str(json_str).unwrap();
+
+            let mut len = 0usize;
+            let ptr = generate_floor_layout_binary(42, floor_id, &mut len);
+            assert!(!ptr.is_null());
+            let buf = unsafe { std::slice::from_raw_parts(ptr, len) };
+
+            let read_u32 =
+                |at: usize| u32::from_le_bytes(buf[at..at + 4].try_into().unwrap()) as usize;
+            let width = read_u32(0);
+            let height = read_u32(4);
+            assert_eq!(width, expected.width);
+            assert_eq!(height, expected.height);
+
+            let tiles = &buf[8..8 + width * height];
+            for (y, row) in expected.tiles.iter().enumerate() {
+                assert_eq!(&tiles[y * width..(y + 1) * width], row.as_slice());
+            }
+
+            let mut at = 8 + width * height;
+            let room_count = read_u32(at);
+            at += 4;
+            assert_eq!(room_count, expected.rooms.len());
+            for room in &expected.rooms {
+                assert_eq!(read_u32(at), room.x);
+                assert_eq!(read_u32(at + 4), room.y);
+                assert_eq!(read_u32(at + 8), room.width);
+                assert_eq!(read_u32(at + 12), room.height);
+                at += 17;
+            }
+            assert_eq!(at, len, "buffer fully consumed");
+
+            free_bytes(ptr, len);
+            free_string(json_ptr);
+        }
+    }
+
+    #[test]
+    fn test_generate_floor_layout_binary_null_len() {
+        assert!(generate_floor_layout_binary(42, 1, std::ptr::null_mut()).is_null());
+        free_bytes(std::ptr::null_mut(), 0);
+    }
+
+    #[test]
+    fn test_generate_floor_props_ffi() {
+        let a = generate_floor_props(42, 5);
+        let b = generate_floor_props(42, 5);
+        assert!(!a.is_null());
+        let json_a = unsafe { CStr::from_ptr(a).to_str().unwrap() };
+        let json_b = unsafe { CStr::from_ptr(b).to_str().unwrap() };
+        assert_eq!(json_a, json_b);
+        let props: Vec<crate::generation::PropPlacement> = serde_json::from_str(json_a).unwrap();
+        assert!(!props.is_empty());
+        free_string(a);
+        free_string(b);
+    }
+
+    #[test]
+    fn test_verify_floor_layout_ffi() {
+        let spec = FloorSpec::generate(&TowerSeed { seed: 42 }, 9);
+        let mut layout = crate::generation::wfc::generate_layout(&spec);
+        let genuine = CString::new(serde_json::to_string(&layout).unwrap()).unwrap();
+        let ptr = verify_floor_layout(42, 9, genuine.as_ptr());
+        assert!(!ptr.is_null());
+        let json = unsafe { CStr::from_ptr(ptr).to_str().unwrap() };
+        assert_eq!(json, "\"Match\"");
+        free_string(ptr);
+
+        layout.exit_point = (0, 0);
+        let forged = CString::new(serde_json::to_string(&layout).unwrap()).unwrap();
+        let ptr = verify_floor_layout(42, 9, forged.as_ptr());
+        let json = unsafe { CStr::from_ptr(ptr).to_str().unwrap() };
+        let v: serde_json::Value = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            v["Mismatch"]["exit_point"]["claimed"],
+            serde_json::json!([0, 0])
+        );
+        free_string(ptr);
+
+        let garbage = CString::new("{}").unwrap();
+        assert!(verify_floor_layout(42, 9, garbage.as_ptr()).is_null());
+    }
+
     #[test]
     fn test_generate_monster_ffi() {
         let result_ptr = generate_monster(12345, 10);
@@ -2247,6 +4090,21 @@ mod tests {
         free_string(result_ptr);
     }
 
+    #[test]
+    fn test_generate_boss_ffi() {
+        let result_ptr = generate_boss(12345, 350);
+        assert!(!result_ptr.is_null());
+        let json_str = unsafe { CStr::from_ptr(result_ptr).to_str().unwrap() };
+        let info: BossInfo = serde_json::from_str(json_str).unwrap();
+        assert!(!info.monster.name.is_empty());
+        assert!(info.phases.len() >= 2);
+        assert!(!info.mechanics.is_empty());
+
+        let regular: MonsterInfo = serde_json::from_str(json_str).unwrap();
+        assert_eq!(regular.max_hp, info.monster.max_hp);
+        free_string(result_ptr);
+    }
+
     #[test]
     fn test_generate_floor_monsters_ffi() {
         let result_ptr = generate_floor_monsters(42, 5, 3);
@@ -2266,6 +4124,15 @@ mod tests {
         assert_ne!(hash1, hash3);
     }
 
+    #[test]
+    fn test_shrine_effect_ffi() {
+        let hash = get_floor_hash(42, 7);
+        assert_eq!(shrine_effect(hash, 3), shrine_effect(hash, 3));
+        let ids: std::collections::HashSet<u32> =
+            (0..100).map(|i| shrine_effect(hash, i)).collect();
+        assert_eq!(ids, (0..4).collect());
+    }
+
     #[test]
     fn test_floor_tier_ffi() {
         assert_eq!(get_floor_tier(50), 0); // Echelon1
@@ -2274,6 +4141,18 @@ mod tests {
         assert_eq!(get_floor_tier(600), 3); // Echelon4
     }
 
+    #[test]
+    fn test_floor_tier_with_override_ffi() {
+        let custom =
+            CString::new(r#"{"echelon1_max":10,"echelon2_max":20,"echelon3_max":30}"#).unwrap();
+        assert_eq!(get_floor_tier_with(15, custom.as_ptr()), 1);
+        assert_eq!(get_floor_tier_with(50, custom.as_ptr()), 3);
+        assert_eq!(
+            get_floor_tier_with(50, std::ptr::null()),
+            get_floor_tier(50)
+        );
+    }
+
     #[test]
     fn test_angle_multiplier_ffi() {
         assert!((get_angle_multiplier(0) - 1.0).abs() < f32::EPSILON);
@@ -2282,6 +4161,130 @@ mod tests {
         assert!((get_angle_multiplier(99) - 1.0).abs() < f32::EPSILON);
     }
 
+    #[test]
+    fn test_combat_resolve_status_ffi() {
+        let burn = StatusEffect::new(crate::combat::StatusType::Burning, 2.0, 1.0);
+        let effects = CString::new(serde_json::to_string(&vec![burn.clone()]).unwrap()).unwrap();
+        let incoming = CString::new(serde_json::to_string(&vec![burn]).unwrap()).unwrap();
+
+        let ptr = combat_resolve_status(effects.as_ptr(), incoming.as_ptr(), 1.0);
+        assert!(!ptr.is_null());
+        let v: serde_json::Value =
+            serde_json::from_str(unsafe { CStr::from_ptr(ptr) }.to_str().unwrap()).unwrap();
+        free_string(ptr);
+        // Re-applied burn stacks to 2: 16 damage over one second
+        assert!((v["damage"].as_f64().unwrap() - 16.0).abs() < 0.001);
+        assert_eq!(v["effects"][0]["stacks"], 2);
+
+        let ptr = combat_resolve_status(effects.as_ptr(), std::ptr::null(), 5.0);
+        let v: serde_json::Value =
+            serde_json::from_str(unsafe { CStr::from_ptr(ptr) }.to_str().unwrap()).unwrap();
+        free_string(ptr);
+        assert!(v["effects"].as_array().unwrap().is_empty());
+
+        assert!(combat_resolve_status(std::ptr::null(), std::ptr::null(), 1.0).is_null());
+    }
+
+    #[test]
+    fn test_combat_evaluate_parry_ffi() {
+        assert_eq!(combat_evaluate_parry(600, 600, 120.0, 30.0, 0.0), 2);
+        assert_eq!(combat_evaluate_parry(600, 602, 120.0, 30.0, 0.0), 1);
+        assert_eq!(combat_evaluate_parry(600, 603, 120.0, 30.0, 0.0), 0);
+        // ParryWindowExtension(30ms) widens the window
+        assert_eq!(combat_evaluate_parry(600, 603, 120.0, 30.0, 30.0), 1);
+    }
+
+    #[test]
+    fn test_combat_hit_direction_ffi() {
+        // Victim at origin facing -Z
+        assert_eq!(combat_hit_direction(0.0, 0.0, -2.0, 0.0, 0.0), 0);
+        assert_eq!(combat_hit_direction(0.0, -2.0, 0.0, 0.0, 0.0), 1);
+        assert_eq!(combat_hit_direction(0.0, 2.0, 0.0, 0.0, 0.0), 2);
+        assert_eq!(combat_hit_direction(0.0, 0.0, 2.0, 0.0, 0.0), 3);
+        assert_eq!(
+            combat_hit_direction(std::f32::consts::PI, 0.0, 2.0, 0.0, 0.0),
+            0
+        );
+    }
+
+    #[test]
+    fn test_aerial_dive_attack_ffi() {
+        let ptr = aerial_dive_attack(40.0, 10.0, 100.0, 0.2);
+        assert!(!ptr.is_null());
+        let v: serde_json::Value =
+            serde_json::from_str(unsafe { CStr::from_ptr(ptr) }.to_str().unwrap()).unwrap();
+        free_string(ptr);
+        assert!(v["damage"].as_f64().unwrap() > 100.0);
+        assert!(v["knockback"].as_f64().unwrap() > 0.0);
+        assert!((v["stamina_cost"].as_f64().unwrap() - 20.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_death_echo_ffi() {
+        let echo = CString::new(
+            r#"{"position":[0.0,0.0,0.0],"floor_id":3,"player_tags":[["void",0.5]],"remaining_ticks":600}"#,
+        )
+        .unwrap();
+        let mut field_ptr = death_record_echo(std::ptr::null(), echo.as_ptr());
+        assert!(!field_ptr.is_null());
+        for _ in 0..4 {
+            let field =
+                CString::new(unsafe { CStr::from_ptr(field_ptr) }.to_str().unwrap()).unwrap();
+            free_string(field_ptr);
+            field_ptr = death_record_echo(field.as_ptr(), echo.as_ptr());
+        }
+        let field = CString::new(unsafe { CStr::from_ptr(field_ptr) }.to_str().unwrap()).unwrap();
+        free_string(field_ptr);
+
+        assert_eq!(
+            death_count_echoes_near(field.as_ptr(), 3, 1.0, 0.0, 0.0, 2.0),
+            5
+        );
+        assert_eq!(
+            death_count_echoes_near(field.as_ptr(), 3, 50.0, 0.0, 0.0, 2.0),
+            0
+        );
+        // Echoes on another floor don't converge here
+        assert_eq!(
+            death_count_echoes_near(field.as_ptr(), 4, 1.0, 0.0, 0.0, 2.0),
+            0
+        );
+        assert_eq!(
+            death_count_echoes_near(std::ptr::null(), 3, 0.0, 0.0, 0.0, 2.0),
+            0
+        );
+        assert!(death_record_echo(std::ptr::null(), std::ptr::null()).is_null());
+    }
+
+    #[test]
+    fn test_floor_mood_ffi() {
+        let ptr = floor_mood(42, 450);
+        assert!(!ptr.is_null());
+        let json = unsafe { CStr::from_ptr(ptr) }.to_str().unwrap().to_string();
+        free_string(ptr);
+        let v: serde_json::Value = serde_json::from_str(&json).unwrap();
+        for key in ["tension", "mystery", "danger"] {
+            let x = v[key].as_f64().unwrap();
+            assert!((0.0..=1.0).contains(&x), "{key} = {x}");
+        }
+        assert!(v["music_tag"].is_string());
+
+        let again = floor_mood(42, 450);
+        assert_eq!(unsafe { CStr::from_ptr(again) }.to_str().unwrap(), json);
+        free_string(again);
+    }
+
+    #[test]
+    fn test_weapon_angle_multiplier_ffi() {
+        // Sword (0) matches the baseline; daggers (2) hit harder from behind
+        assert_eq!(get_weapon_angle_multiplier(0, 2), get_angle_multiplier(2));
+        assert!(get_weapon_angle_multiplier(2, 2) > get_weapon_angle_multiplier(0, 2));
+        assert!((get_weapon_angle_multiplier(2, 3) - 1.3).abs() < f32::EPSILON);
+        // Unknown weapon falls back to the baseline
+        assert_eq!(get_weapon_angle_multiplier(99, 2), get_angle_multiplier(2));
+        assert_eq!(get_weapon_angle_multiplier(2, 99), 1.0);
+    }
+
     #[test]
     fn test_version_ffi() {
         let ptr = get_version();
@@ -2301,6 +4304,145 @@ mod tests {
         free_string(result_ptr);
     }
 
+    #[test]
+    fn test_generate_loot_breath_ffi() {
+        let tags_json = CString::new(r#"[["fire", 0.8]]"#).unwrap();
+        let quantity = |ptr: *mut c_char| -> u32 {
+            let json_str = unsafe { CStr::from_ptr(ptr).to_str().unwrap() };
+            let items: Vec<LootInfo> = serde_json::from_str(json_str).unwrap();
+            free_string(ptr);
+            items.iter().map(|i| i.quantity).sum()
+        };
+        let base = quantity(generate_loot(tags_json.as_ptr(), 10, 42));
+        let doubled = quantity(generate_loot_breath(tags_json.as_ptr(), 10, 42, 2.0));
+        assert!(doubled > base);
+    }
+
+    #[test]
+    fn test_generate_loot_blended_ffi() {
+        let tags_json = CString::new(r#"[["fire", 0.9]]"#).unwrap();
+        let floor_json = CString::new(r#"[["water", 0.9]]"#).unwrap();
+        let result_ptr = generate_loot_blended(tags_json.as_ptr(), floor_json.as_ptr(), 10, 42);
+        assert!(!result_ptr.is_null());
+        let json_str = unsafe { CStr::from_ptr(result_ptr).to_str().unwrap() };
+        let items: Vec<LootInfo> = serde_json::from_str(json_str).unwrap();
+        assert!(!items.is_empty());
+        assert!(items
+            .iter()
+            .all(|i| i.semantic_tags.iter().any(|(k, _)| k == "water")));
+        free_string(result_ptr);
+
+        assert!(generate_loot_blended(std::ptr::null(), floor_json.as_ptr(), 10, 42).is_null());
+    }
+
+    #[test]
+    fn test_generate_loot_targeted_ffi() {
+        let tags_json = CString::new(r#"[["fire", 0.5], ["water", 0.6]]"#).unwrap();
+        let player_json = CString::new(r#"[["fire", 0.9]]"#).unwrap();
+        let embers: usize = (0..100u64)
+            .map(|h| {
+                let ptr =
+                    generate_loot_targeted(tags_json.as_ptr(), player_json.as_ptr(), 10, h, 0.5);
+                assert!(!ptr.is_null());
+                let json_str = unsafe { CStr::from_ptr(ptr).to_str().unwrap() };
+                let items: Vec<LootInfo> = serde_json::from_str(json_str).unwrap();
+                free_string(ptr);
+                items.iter().filter(|i| i.name.starts_with("Ember")).count()
+            })
+            .sum();
+        assert!(embers > 0);
+
+        assert!(
+            generate_loot_targeted(tags_json.as_ptr(), std::ptr::null(), 10, 42, 0.5).is_null()
+        );
+    }
+
+    #[test]
+    fn test_loot_pickup_filter_ffi() {
+        let ignore = CString::new(r#"["Consumable"]"#).unwrap();
+        let filter_ptr = loot_create_pickup_filter(2, ignore.as_ptr());
+        assert!(!filter_ptr.is_null());
+
+        let common = CString::new(
+            r#"{"name":"Tower Shards","category":"Currency","rarity":"Common","quantity":5,"semantic_tags":[]}"#,
+        )
+        .unwrap();
+        let rare_potion = CString::new(
+            r#"{"name":"Potion","category":"Consumable","rarity":"Rare","quantity":1,"semantic_tags":[]}"#,
+        )
+        .unwrap();
+        let quest = CString::new(
+            r#"{"name":"Key","category":"QuestItem","rarity":"Common","quantity":1,"semantic_tags":[]}"#,
+        )
+        .unwrap();
+
+        assert_eq!(loot_should_pickup(filter_ptr, common.as_ptr()), 0);
+        assert_eq!(loot_should_pickup(filter_ptr, rare_potion.as_ptr()), 0);
+        assert_eq!(loot_should_pickup(filter_ptr, quest.as_ptr()), 1);
+        assert!(loot_create_pickup_filter(99, ignore.as_ptr()).is_null());
+        free_string(filter_ptr);
+    }
+
+    #[test]
+    fn test_generate_loot_with_pity_ffi() {
+        let tags = CString::new(r#"[["neutral", 0.5]]"#).unwrap();
+        let pity = CString::new(
+            r#"{"kills_since_rare": 9, "kills_since_legendary": 9, "rare_threshold": 10}"#,
+        )
+        .unwrap();
+        let ptr = generate_loot_with_pity(tags.as_ptr(), 1, 777, pity.as_ptr());
+        assert!(!ptr.is_null());
+        let json = unsafe { CStr::from_ptr(ptr).to_str().unwrap() };
+        let v: serde_json::Value = serde_json::from_str(json).unwrap();
+        let rarities: Vec<&str> = v["items"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|i| i["rarity"].as_str().unwrap())
+            .collect();
+        assert!(rarities.iter().any(|r| *r != "Common" && *r != "Uncommon"));
+        assert_eq!(v["pity"]["kills_since_rare"], 0);
+
+        // Fresh state when no pity JSON is given
+        let fresh = generate_loot_with_pity(tags.as_ptr(), 1, 777, std::ptr::null());
+        assert!(!fresh.is_null());
+
+        free_string(ptr);
+        free_string(fresh);
+    }
+
+    #[test]
+    fn test_loot_mystery_box_ffi() {
+        let ptr = loot_mystery_box(3, 10, 42);
+        assert!(!ptr.is_null());
+        let json = unsafe { CStr::from_ptr(ptr).to_str().unwrap() };
+        let item: loot::LootItem = serde_json::from_str(json).unwrap();
+        assert!(item.rarity >= crate::economy::ItemRarity::Uncommon);
+        assert_eq!(item.quantity, 1);
+        free_string(ptr);
+    }
+
+    #[test]
+    fn test_loot_enchant_ffi() {
+        let blade = CString::new(
+            r#"{"name":"Ember Blade","category":"Equipment","rarity":"Epic","quantity":1,"semantic_tags":[["fire",0.1]]}"#,
+        )
+        .unwrap();
+        let ptr = loot_enchant(blade.as_ptr(), 3, 42);
+        assert!(!ptr.is_null());
+        let json = unsafe { CStr::from_ptr(ptr).to_str().unwrap() };
+        let item: loot::LootItem = serde_json::from_str(json).unwrap();
+        assert!(item.semantic_tags[0].1 >= 0.2);
+        free_string(ptr);
+
+        let potion = CString::new(
+            r#"{"name":"Potion","category":"Consumable","rarity":"Rare","quantity":1,"semantic_tags":[["water",0.4]]}"#,
+        )
+        .unwrap();
+        assert!(loot_enchant(potion.as_ptr(), 3, 42).is_null());
+        assert!(loot_enchant(blade.as_ptr(), 9, 42).is_null());
+    }
+
     #[test]
     fn test_breath_state_ffi() {
         let ptr = get_breath_state(100.0); // early in Inhale phase
@@ -2312,6 +4454,26 @@ mod tests {
         free_string(ptr);
     }
 
+    #[test]
+    fn test_breath_transition_ffi() {
+        let ptr = breath_next_transition(100.0);
+        assert!(!ptr.is_null());
+        let json = unsafe { CStr::from_ptr(ptr).to_str().unwrap() };
+        let v: serde_json::Value = serde_json::from_str(json).unwrap();
+        assert_eq!(v["phase"], "Hold");
+        assert!(v["seconds_until"].as_f64().unwrap() > 0.0);
+        free_string(ptr);
+
+        let hold_start = crate::world::next_phase_transition(0.0).1;
+        let ptr = breath_crossed_boundary(hold_start - 1.0, hold_start + 1.0);
+        assert!(!ptr.is_null());
+        let json = unsafe { CStr::from_ptr(ptr).to_str().unwrap() };
+        assert_eq!(json, "\"Hold\"");
+        free_string(ptr);
+
+        assert!(breath_crossed_boundary(10.0, 20.0).is_null());
+    }
+
     #[test]
     fn test_record_delta_ffi() {
         let player = CString::new("player1").unwrap();
@@ -2332,6 +4494,64 @@ mod tests {
         free_string(result);
     }
 
+    #[test]
+    fn test_create_floor_snapshot_compaction_is_opt_in() {
+        let mut log = DeltaLog::default();
+        for i in 0..4 {
+            log.record(i, DeltaType::DoorUnlock, 1, 77, "p1", "open");
+        }
+        let deltas_json = CString::new(serde_json::to_string(&log.deltas).unwrap()).unwrap();
+        let delta_count = |ptr: *mut c_char| {
+            let snapshot: FloorSnapshot =
+                serde_json::from_str(unsafe { CStr::from_ptr(ptr) }.to_str().unwrap()).unwrap();
+            free_string(ptr);
+            snapshot.deltas.len()
+        };
+
+        assert_eq!(
+            delta_count(create_floor_snapshot(42, 1, deltas_json.as_ptr())),
+            4
+        );
+        assert_eq!(
+            delta_count(create_floor_snapshot_compacted(42, 1, deltas_json.as_ptr())),
+            1
+        );
+    }
+
+    #[test]
+    fn test_floor_snapshot_apply_ffi() {
+        let mut log = DeltaLog::default();
+        log.record(1, DeltaType::MonsterKill, 1, 11, "p1", "");
+        log.record(2, DeltaType::ChestOpen, 1, 22, "p1", "");
+        let snapshot = FloorSnapshot::capture(&TowerSeed { seed: 42 }, 1, &log, 3, false);
+        let snapshot_json = CString::new(snapshot.to_json()).unwrap();
+
+        let result = floor_snapshot_apply(snapshot_json.as_ptr());
+        assert!(!result.is_null());
+        let json_str = unsafe { CStr::from_ptr(result).to_str().unwrap() };
+        let state: crate::replication::FloorState = serde_json::from_str(json_str).unwrap();
+        assert!(state.killed_monsters.contains(&11));
+        assert!(state.opened_chests.contains(&22));
+        free_string(result);
+
+        assert!(floor_snapshot_apply(std::ptr::null()).is_null());
+    }
+
+    #[test]
+    fn test_snapshot_checksum_ffi() {
+        let mut log = DeltaLog::default();
+        log.record(1, DeltaType::MonsterKill, 1, 11, "p1", "");
+        let snapshot = FloorSnapshot::capture(&TowerSeed { seed: 42 }, 1, &log, 3, false);
+        let json = CString::new(snapshot.to_json()).unwrap();
+        assert_eq!(snapshot_checksum(json.as_ptr()), snapshot.checksum());
+
+        log.record(2, DeltaType::ChestOpen, 1, 22, "p1", "");
+        let diverged = FloorSnapshot::capture(&TowerSeed { seed: 42 }, 1, &log, 3, false);
+        let json = CString::new(diverged.to_json()).unwrap();
+        assert_ne!(snapshot_checksum(json.as_ptr()), snapshot.checksum());
+        assert_eq!(snapshot_checksum(std::ptr::null()), 0);
+    }
+
     #[test]
     fn test_evaluate_event_breath_shift() {
         let ctx = crate::events::TriggerContext {
@@ -2360,6 +4580,26 @@ mod tests {
         assert!(result.is_null(), "Low corruption should not trigger event");
     }
 
+    #[test]
+    fn test_event_cooldown_snapshot_ffi() {
+        let cooldowns = CString::new(r#"[["BreathShift", 20.0], ["FloorAnomaly", -1.0]]"#).unwrap();
+        let captured = event_capture_cooldowns(cooldowns.as_ptr(), 3);
+        assert!(!captured.is_null());
+
+        let restored = event_restore_cooldowns(captured);
+        let json = unsafe { CStr::from_ptr(restored).to_str().unwrap() };
+        let snapshot: crate::events::EventCooldownSnapshot = serde_json::from_str(json).unwrap();
+        assert_eq!(snapshot.events_triggered, 3);
+        assert_eq!(
+            snapshot.cooldowns,
+            vec![(EventTriggerType::BreathShift, 20.0)]
+        );
+
+        assert!(event_restore_cooldowns(std::ptr::null()).is_null());
+        free_string(captured);
+        free_string(restored);
+    }
+
     #[test]
     fn test_combat_calc_ffi() {
         let request = CombatCalcRequest {
@@ -2368,6 +4608,8 @@ mod tests {
             combo_step: 1,
             attacker_tags_json: r#"[["fire", 0.8]]"#.into(),
             defender_tags_json: r#"[["water", 0.9]]"#.into(),
+            defender_armor: 0.0,
+            semantic_intensity: None,
         };
         let request_json = CString::new(serde_json::to_string(&request).unwrap()).unwrap();
         let result_ptr = calculate_combat(request_json.as_ptr());
@@ -2379,9 +4621,228 @@ mod tests {
             "Back attack + combo should increase damage"
         );
         assert!((result.angle_multiplier - 1.5).abs() < f32::EPSILON);
+        assert_eq!(result.resistance_mitigation, 0.0);
         free_string(result_ptr);
     }
 
+    #[test]
+    fn test_combat_calc_batch_preserves_order() {
+        let requests: Vec<CombatCalcRequest> = (0..3)
+            .map(|i| CombatCalcRequest {
+                base_damage: 100.0 * (i + 1) as f32,
+                angle_id: i,
+                combo_step: 0,
+                attacker_tags_json: r#"[["fire", 0.8]]"#.into(),
+                defender_tags_json: r#"[["water", 0.9]]"#.into(),
+                defender_armor: 0.0,
+                semantic_intensity: None,
+            })
+            .collect();
+        let json = CString::new(serde_json::to_string(&requests).unwrap()).unwrap();
+        let ptr = calculate_combat_batch(json.as_ptr());
+        assert!(!ptr.is_null());
+
+        let json_str = unsafe { CStr::from_ptr(ptr).to_str().unwrap() };
+        let results: Vec<Option<CombatCalcResult>> = serde_json::from_str(json_str).unwrap();
+        assert_eq!(results.len(), 3);
+
+        for (request, result) in requests.iter().zip(&results) {
+            let single_json = CString::new(serde_json::to_string(request).unwrap()).unwrap();
+            let single_ptr = calculate_combat(single_json.as_ptr());
+            let single_str = unsafe { CStr::from_ptr(single_ptr).to_str().unwrap() };
+            let single: CombatCalcResult = serde_json::from_str(single_str).unwrap();
+
+            let result = result.as_ref().unwrap();
+            assert!((result.final_damage - single.final_damage).abs() < f32::EPSILON);
+            free_string(single_ptr);
+        }
+        free_string(ptr);
+    }
+
+    #[test]
+    fn test_combat_calc_batch_mixed_entries() {
+        let json = CString::new(
+            r#"[
+                {"base_damage": 50.0, "angle_id": 0, "combo_step": 0,
+                 "attacker_tags_json": "[]", "defender_tags_json": "[]"},
+                {"base_damage": "oops"},
+                42,
+                {"base_damage": 80.0, "angle_id": 2, "combo_step": 1,
+                 "attacker_tags_json": "not json", "defender_tags_json": "[]"}
+            ]"#,
+        )
+        .unwrap();
+        let ptr = calculate_combat_batch(json.as_ptr());
+        let json_str = unsafe { CStr::from_ptr(ptr).to_str().unwrap() };
+        let results: Vec<Option<CombatCalcResult>> = serde_json::from_str(json_str).unwrap();
+
+        assert_eq!(results.len(), 4);
+        assert!(results[0].is_some());
+        assert!(results[1].is_none());
+        assert!(results[2].is_none());
+        assert!(
+            results[3].is_some(),
+            "bad tag JSON falls back to empty tags"
+        );
+        free_string(ptr);
+    }
+
+    #[test]
+    fn test_combat_calc_batch_empty_and_invalid() {
+        let empty = CString::new("[]").unwrap();
+        let ptr = calculate_combat_batch(empty.as_ptr());
+        assert_eq!(unsafe { CStr::from_ptr(ptr).to_str().unwrap() }, "[]");
+        free_string(ptr);
+
+        let bad = CString::new("{not an array}").unwrap();
+        assert!(calculate_combat_batch(bad.as_ptr()).is_null());
+        assert!(calculate_combat_batch(std::ptr::null()).is_null());
+    }
+
+    #[test]
+    fn test_combat_calc_resistance_ffi() {
+        let request = CombatCalcRequest {
+            base_damage: 100.0,
+            angle_id: 0,
+            combo_step: 0,
+            attacker_tags_json: r#"[["fire", 0.9]]"#.into(),
+            defender_tags_json: r#"[["fire", -0.7]]"#.into(),
+            defender_armor: 0.0,
+            semantic_intensity: None,
+        };
+        let request_json = CString::new(serde_json::to_string(&request).unwrap()).unwrap();
+        let result_ptr = calculate_combat(request_json.as_ptr());
+        let json_str = unsafe { CStr::from_ptr(result_ptr).to_str().unwrap() };
+        let result: CombatCalcResult = serde_json::from_str(json_str).unwrap();
+
+        assert!((result.resistance_mitigation - 0.7).abs() < 0.001);
+        let expected = 100.0 * (1.0 + result.semantic_bonus) * 0.3;
+        assert!((result.final_damage - expected).abs() < 0.01);
+        free_string(result_ptr);
+    }
+
+    fn combat_ffi(attacker: &str, defender: &str) -> CombatCalcResult {
+        let request = CombatCalcRequest {
+            base_damage: 100.0,
+            angle_id: 0,
+            combo_step: 0,
+            attacker_tags_json: attacker.into(),
+            defender_tags_json: defender.into(),
+            defender_armor: 0.0,
+            semantic_intensity: None,
+        };
+        let request_json = CString::new(serde_json::to_string(&request).unwrap()).unwrap();
+        let result_ptr = calculate_combat(request_json.as_ptr());
+        let json_str = unsafe { CStr::from_ptr(result_ptr).to_str().unwrap() };
+        let result: CombatCalcResult = serde_json::from_str(json_str).unwrap();
+        free_string(result_ptr);
+        result
+    }
+
+    #[test]
+    fn test_combat_calc_breath_intensity_amplifies_synergy() {
+        use crate::world::BreathPhase;
+
+        let request = |intensity: Option<f32>| CombatCalcRequest {
+            base_damage: 100.0,
+            angle_id: 0,
+            combo_step: 0,
+            attacker_tags_json: r#"[["fire", 0.9]]"#.into(),
+            defender_tags_json: r#"[["fire", 0.8]]"#.into(),
+            defender_armor: 0.0,
+            semantic_intensity: intensity,
+        };
+        let run = |req: CombatCalcRequest| {
+            let json = CString::new(serde_json::to_string(&req).unwrap()).unwrap();
+            let ptr = calculate_combat(json.as_ptr());
+            let s = unsafe { CStr::from_ptr(ptr).to_str().unwrap() };
+            let result: CombatCalcResult = serde_json::from_str(s).unwrap();
+            free_string(ptr);
+            result
+        };
+
+        let baseline = run(request(None));
+        let hold = run(request(Some(BreathPhase::Hold.semantic_intensity())));
+        assert!(baseline.is_synergy);
+        assert!(hold.semantic_bonus > baseline.semantic_bonus);
+        assert!(hold.final_damage > baseline.final_damage);
+    }
+
+    #[test]
+    fn test_combat_calc_monster_weakness_ffi() {
+        let monster = crate::monster::MonsterTemplate {
+            name: "Ember Guardian".into(),
+            size: crate::monster::MonsterSize::Medium,
+            element: crate::monster::MonsterElement::Fire,
+            corruption: crate::monster::CorruptionLevel::Pure,
+            behavior: crate::monster::MonsterBehavior::Aggressive,
+            base_level: 1,
+        };
+        let defender = serde_json::to_string(&monster.combat_tags().tags).unwrap();
+
+        let water = combat_ffi(r#"[["water", 0.9]]"#, &defender);
+        let neutral = combat_ffi(r#"[["physical", 0.9]]"#, &defender);
+        let fire = combat_ffi(r#"[["fire", 0.9]]"#, &defender);
+
+        assert!((water.weakness_multiplier - 1.5).abs() < 0.001);
+        assert_eq!(neutral.weakness_multiplier, 1.0);
+        assert!(water.final_damage > neutral.final_damage);
+        assert!((fire.weakness_multiplier - 0.5).abs() < 0.001);
+        // Weakness composes with the synergy bonus rather than replacing it
+        let expected = 100.0 * (1.0 + water.semantic_bonus) * 1.5;
+        assert!((water.final_damage - expected).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_combat_calc_armor_hits_physical_only_ffi() {
+        let request = |armor: f32| {
+            let req = CombatCalcRequest {
+                base_damage: 100.0,
+                angle_id: 0,
+                combo_step: 0,
+                attacker_tags_json: r#"[["fire", 0.5], ["slash", 0.5]]"#.into(),
+                defender_tags_json: r#"[["stone", 0.5]]"#.into(),
+                defender_armor: armor,
+                semantic_intensity: None,
+            };
+            let json = CString::new(serde_json::to_string(&req).unwrap()).unwrap();
+            let ptr = calculate_combat(json.as_ptr());
+            let json_str = unsafe { CStr::from_ptr(ptr).to_str().unwrap() };
+            let result: CombatCalcResult = serde_json::from_str(json_str).unwrap();
+            free_string(ptr);
+            result
+        };
+
+        let bare = request(0.0);
+        let armored = request(100.0);
+        assert_eq!(
+            bare.packet.elemental["fire"],
+            armored.packet.elemental["fire"]
+        );
+        assert!((armored.packet.physical - bare.packet.physical * 0.5).abs() < 0.01);
+        assert!((armored.final_damage - armored.packet.total()).abs() < f32::EPSILON);
+        assert!(armored.final_damage < bare.final_damage);
+    }
+
+    #[test]
+    fn test_combat_calc_reduction_capped_ffi() {
+        let result = combat_ffi(
+            r#"[["fire", 0.9]]"#,
+            r#"[["fire", -1.0], ["vuln:fire", -1.0]]"#,
+        );
+        let floor = 100.0 * (1.0 + result.semantic_bonus) * (1.0 - MAX_RESISTANCE);
+        assert!((result.final_damage - floor).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_generate_monster_has_combat_tags_ffi() {
+        let ptr = generate_monster(12345, 10);
+        let json_str = unsafe { CStr::from_ptr(ptr).to_str().unwrap() };
+        let info: MonsterInfo = serde_json::from_str(json_str).unwrap();
+        assert!(info.combat_tags.len() >= info.semantic_tags.len());
+        free_string(ptr);
+    }
+
     // ========================
     // Mastery FFI Tests
     // ========================
@@ -2410,6 +4871,21 @@ mod tests {
         free_string(updated);
     }
 
+    #[test]
+    fn test_mastery_apply_decay_ffi() {
+        let profile_ptr = mastery_create_profile();
+        let trained = mastery_gain_xp(profile_ptr, 0, 5000); // SwordMastery → Master
+
+        let decayed = mastery_apply_decay(trained, std::ptr::null(), 86_400.0 * 10.0, 0.5);
+        assert!(!decayed.is_null());
+        assert_eq!(mastery_get_tier(decayed, 0), 2); // floored at Journeyman
+
+        assert!(mastery_apply_decay(std::ptr::null(), std::ptr::null(), 1.0, 0.1).is_null());
+        free_string(profile_ptr);
+        free_string(trained);
+        free_string(decayed);
+    }
+
     #[test]
     fn test_mastery_xp_for_action() {
         let action = CString::new("sword_attack").unwrap();
@@ -2417,15 +4893,67 @@ mod tests {
         assert!(xp > 0, "sword_attack should give XP");
     }
 
-    #[test]
-    fn test_mastery_get_all_domains() {
-        let ptr = mastery_get_all_domains();
-        assert!(!ptr.is_null());
-        let json = unsafe { CStr::from_ptr(ptr).to_str().unwrap() };
-        let domains: Vec<String> = serde_json::from_str(json).unwrap();
-        assert_eq!(domains.len(), 21);
-        assert!(domains.contains(&"SwordMastery".to_string()));
-        free_string(ptr);
+    #[test]
+    fn test_mastery_get_all_domains() {
+        let ptr = mastery_get_all_domains();
+        assert!(!ptr.is_null());
+        let json = unsafe { CStr::from_ptr(ptr).to_str().unwrap() };
+        let domains: Vec<String> = serde_json::from_str(json).unwrap();
+        assert_eq!(domains.len(), 21);
+        assert!(domains.contains(&"SwordMastery".to_string()));
+        free_string(ptr);
+    }
+
+    #[test]
+    fn test_mastery_prestige_ffi() {
+        let profile_ptr = mastery_create_profile();
+        assert!(mastery_prestige(profile_ptr, 0).is_null()); // Novice can't prestige
+
+        let maxed = mastery_gain_xp(profile_ptr, 0, 8000);
+        let prestiged = mastery_prestige(maxed, 0);
+        assert!(!prestiged.is_null());
+        let json = unsafe { CStr::from_ptr(prestiged).to_str().unwrap() };
+        let profile: MasteryProfile = serde_json::from_str(json).unwrap();
+        let sword = profile.get(MasteryDomain::SwordMastery).unwrap();
+        assert_eq!(sword.prestige_level, 1);
+        assert_eq!(sword.xp, 0);
+
+        free_string(profile_ptr);
+        free_string(maxed);
+        free_string(prestiged);
+    }
+
+    #[test]
+    fn test_analytics_semantic_discovery_ffi() {
+        let fire = CString::new("fire").unwrap();
+        let water = CString::new("water").unwrap();
+        let conflict = CString::new(r#"{"Conflict":0.1}"#).unwrap();
+
+        let first = analytics_record_semantic_discovery(
+            std::ptr::null(),
+            fire.as_ptr(),
+            water.as_ptr(),
+            conflict.as_ptr(),
+        );
+        assert!(!first.is_null());
+        let json = unsafe { CStr::from_ptr(first).to_str().unwrap() };
+        let v: serde_json::Value = serde_json::from_str(json).unwrap();
+        assert_eq!(v["new"], true);
+        let stats = CString::new(v["stats"].to_string()).unwrap();
+
+        let second = analytics_record_semantic_discovery(
+            stats.as_ptr(),
+            water.as_ptr(),
+            fire.as_ptr(),
+            conflict.as_ptr(),
+        );
+        let json = unsafe { CStr::from_ptr(second).to_str().unwrap() };
+        let v: serde_json::Value = serde_json::from_str(json).unwrap();
+        assert_eq!(v["new"], false);
+        assert_eq!(v["count"], 1);
+
+        free_string(first);
+        free_string(second);
     }
 
     #[test]
@@ -2461,6 +4989,33 @@ mod tests {
         free_string(ptr);
     }
 
+    #[test]
+    fn test_spec_reset_branch_paid() {
+        let mut mastery = MasteryProfile::new();
+        mastery.gain_xp(MasteryDomain::SwordMastery, 2000);
+        let mut spec = SpecializationProfile::new();
+        spec.choose_branch(&all_specialization_branches()[0], &mastery)
+            .unwrap();
+        let spec_json = CString::new(serde_json::to_string(&spec).unwrap()).unwrap();
+
+        let ptr = spec_reset_branch_paid(spec_json.as_ptr(), 0, 40);
+        let json = unsafe { CStr::from_ptr(ptr).to_str().unwrap() };
+        let v: serde_json::Value = serde_json::from_str(json).unwrap();
+        assert_eq!(v["error"]["InsufficientShards"]["cost"], 100);
+        free_string(ptr);
+
+        let ptr = spec_reset_branch_paid(spec_json.as_ptr(), 0, 500);
+        let json = unsafe { CStr::from_ptr(ptr).to_str().unwrap() };
+        let v: serde_json::Value = serde_json::from_str(json).unwrap();
+        assert_eq!(v["shards"], 400);
+        assert_eq!(v["profile"]["respec_count"], 1);
+        assert!(v["profile"]["chosen_branches"]
+            .as_object()
+            .unwrap()
+            .is_empty());
+        free_string(ptr);
+    }
+
     #[test]
     fn test_spec_find_synergies() {
         let ids = CString::new(r#"["sword_berserker","parry_counter"]"#).unwrap();
@@ -2507,6 +5062,100 @@ mod tests {
         free_string(loadout_ptr);
     }
 
+    #[test]
+    fn test_ability_upgrade_and_effective_stats() {
+        let mut loadout = AbilityLoadout::new();
+        loadout.learn(default_abilities().remove(0));
+        let loadout_json = CString::new(serde_json::to_string(&loadout).unwrap()).unwrap();
+        let aid = CString::new("rising_slash").unwrap();
+
+        let novice = CString::new(serde_json::to_string(&MasteryProfile::new()).unwrap()).unwrap();
+        let ptr = ability_upgrade(loadout_json.as_ptr(), aid.as_ptr(), novice.as_ptr());
+        let json = unsafe { CStr::from_ptr(ptr).to_str().unwrap() };
+        assert!(json.contains("TierTooLow"));
+        free_string(ptr);
+
+        let mut mastery = MasteryProfile::new();
+        mastery.gain_xp(MasteryDomain::SwordMastery, 200);
+        let trained = CString::new(serde_json::to_string(&mastery).unwrap()).unwrap();
+        let upgraded = ability_upgrade(loadout_json.as_ptr(), aid.as_ptr(), trained.as_ptr());
+        assert!(!upgraded.is_null());
+
+        let ptr = ability_effective_stats(upgraded, aid.as_ptr());
+        assert!(!ptr.is_null());
+        let json = unsafe { CStr::from_ptr(ptr).to_str().unwrap() };
+        let v: serde_json::Value = serde_json::from_str(json).unwrap();
+        assert_eq!(v["rank"], 2);
+        assert!(
+            v["ability"]["effects"][0]["Damage"]["base"]
+                .as_f64()
+                .unwrap()
+                > 80.0
+        );
+        free_string(ptr);
+        free_string(upgraded);
+    }
+
+    #[test]
+    fn test_ability_try_cast() {
+        let mut loadout = AbilityLoadout::new();
+        let slash = default_abilities().remove(0);
+        loadout.learn(slash.clone());
+        loadout.equip(0, &slash.id);
+        let loadout_json = CString::new(serde_json::to_string(&loadout).unwrap()).unwrap();
+        let pools = CString::new(r#"{"kinetic": 30.0, "thermal": 0.0, "semantic": 0.0}"#).unwrap();
+
+        let ptr = ability_try_cast(
+            loadout_json.as_ptr(),
+            0,
+            std::ptr::null(),
+            pools.as_ptr(),
+            std::ptr::null(),
+            0.0,
+        );
+        assert!(!ptr.is_null());
+        let json = unsafe { CStr::from_ptr(ptr).to_str().unwrap() };
+        let v: serde_json::Value = serde_json::from_str(json).unwrap();
+        assert_eq!(v["loadout"]["slots"][0], slash.id.as_str());
+        assert_eq!(v["resources"]["kinetic"], 5.0);
+        assert_eq!(
+            v["cooldowns"]["cooldowns"][&slash.id],
+            slash.cooldown as f64
+        );
+        let cooldowns = CString::new(v["cooldowns"].to_string()).unwrap();
+        free_string(ptr);
+
+        let ptr = ability_try_cast(
+            loadout_json.as_ptr(),
+            0,
+            cooldowns.as_ptr(),
+            pools.as_ptr(),
+            std::ptr::null(),
+            0.0,
+        );
+        let json = unsafe { CStr::from_ptr(ptr).to_str().unwrap() };
+        let v: serde_json::Value = serde_json::from_str(json).unwrap();
+        assert!(v["error"]["OnCooldown"].is_object());
+        free_string(ptr);
+
+        // A corrupt tracker must not read as "nothing cooling down"
+        let garbage = CString::new("not json").unwrap();
+        for (cooldowns, spec) in [
+            (garbage.as_ptr(), std::ptr::null()),
+            (cooldowns.as_ptr(), garbage.as_ptr()),
+        ] {
+            let ptr = ability_try_cast(
+                loadout_json.as_ptr(),
+                0,
+                cooldowns,
+                pools.as_ptr(),
+                spec,
+                0.0,
+            );
+            assert!(ptr.is_null());
+        }
+    }
+
     // ========================
     // Socket FFI Tests
     // ========================
@@ -2539,6 +5188,32 @@ mod tests {
         free_string(ptr);
     }
 
+    #[test]
+    fn test_equipment_roll_stats() {
+        let tags = CString::new(r#"[["fire", 0.8]]"#).unwrap();
+        let ptr = equipment_roll_stats(tags.as_ptr(), 4, 99);
+        assert!(!ptr.is_null());
+        let json = unsafe { CStr::from_ptr(ptr).to_str().unwrap() };
+        let stats: RolledStats = serde_json::from_str(json).unwrap();
+        assert_eq!(stats.secondary.len(), 4);
+        free_string(ptr);
+
+        assert!(equipment_roll_stats(tags.as_ptr(), 6, 99).is_null());
+    }
+
+    #[test]
+    fn test_equipment_active_set_bonuses() {
+        let equipped =
+            CString::new(r#"[[["fire", 0.8]], [["fire", 0.7]], [["water", 0.9]]]"#).unwrap();
+        let ptr = equipment_active_set_bonuses(equipped.as_ptr());
+        assert!(!ptr.is_null());
+        let json = unsafe { CStr::from_ptr(ptr).to_str().unwrap() };
+        let bonuses: Vec<equipment::SetBonus> = serde_json::from_str(json).unwrap();
+        assert_eq!(bonuses.len(), 1);
+        assert_eq!(bonuses[0].pieces_required, 2);
+        free_string(ptr);
+    }
+
     #[test]
     fn test_socket_insert_gem() {
         let name = CString::new("Gemmed Blade").unwrap();
@@ -2560,6 +5235,40 @@ mod tests {
         free_string(equip_ptr);
     }
 
+    #[test]
+    fn test_socket_aggregate_effects() {
+        let mut equip =
+            SocketedEquipment::new("blade".into(), vec![SocketColor::Red, SocketColor::Red]);
+        let ruby = starter_gems().into_iter().next().unwrap();
+        equip
+            .insert_at(0, SocketContent::Gem(ruby.clone()))
+            .unwrap();
+        equip.insert_at(1, SocketContent::Gem(ruby)).unwrap();
+        let equip_json = CString::new(serde_json::to_string(&equip).unwrap()).unwrap();
+
+        let ptr = socket_aggregate_effects(equip_json.as_ptr());
+        assert!(!ptr.is_null());
+        let json = unsafe { CStr::from_ptr(ptr).to_str().unwrap() };
+        let effects: serde_json::Value = serde_json::from_str(json).unwrap();
+        assert_eq!(effects["color_matched"], true);
+        assert!(effects["attack_power"].as_f64().unwrap() > 4.0);
+        free_string(ptr);
+    }
+
+    #[test]
+    fn test_socket_combine_gems_risky() {
+        let gem = starter_gems().into_iter().next().unwrap();
+        let gems = CString::new(serde_json::to_string(&vec![gem; 3]).unwrap()).unwrap();
+        let ptr = socket_combine_gems_risky(gems.as_ptr(), 42);
+        assert!(!ptr.is_null());
+        let json = unsafe { CStr::from_ptr(ptr).to_str().unwrap() };
+        assert!(json.contains("Success") || json.contains("Partial"));
+        free_string(ptr);
+
+        let two = CString::new("[]").unwrap();
+        assert!(socket_combine_gems_risky(two.as_ptr(), 42).is_null());
+    }
+
     // ========================
     // Cosmetics FFI Tests
     // ========================
@@ -2724,6 +5433,87 @@ mod tests {
         free_string(ptr);
     }
 
+    #[test]
+    fn test_season_complete_and_claim() {
+        let quest = generate_daily_quests(11).remove(0);
+        let qid = CString::new(quest.id.as_str()).unwrap();
+        let pass = SeasonPass::new(1, "Test".into());
+        let pass_json = CString::new(serde_json::to_string(&pass).unwrap()).unwrap();
+
+        let fresh = CString::new(serde_json::to_string(&SeasonProgress::new()).unwrap()).unwrap();
+        assert!(
+            season_claim_reward(fresh.as_ptr(), qid.as_ptr(), pass_json.as_ptr()).is_null(),
+            "incomplete quest can't be claimed"
+        );
+
+        let progress = season_complete_quest(std::ptr::null(), qid.as_ptr());
+        assert!(!progress.is_null());
+
+        let ptr = season_claim_reward(progress, qid.as_ptr(), pass_json.as_ptr());
+        assert!(!ptr.is_null());
+        let json = unsafe { CStr::from_ptr(ptr).to_str().unwrap() };
+        let v: serde_json::Value = serde_json::from_str(json).unwrap();
+        assert_eq!(v["pass"]["xp"], quest.season_xp_reward);
+        assert_eq!(v["reward"]["shards"], quest.shard_reward);
+        let claimed = CString::new(v["progress"].to_string()).unwrap();
+        free_string(ptr);
+        free_string(progress);
+
+        assert!(
+            season_claim_reward(claimed.as_ptr(), qid.as_ptr(), pass_json.as_ptr()).is_null(),
+            "double claim rejected"
+        );
+
+        // A client can't name its own reward: only known quest ids resolve
+        let forged_id = CString::new("daily_combat_forged").unwrap();
+        let progress = season_complete_quest(std::ptr::null(), forged_id.as_ptr());
+        assert!(season_claim_reward(progress, forged_id.as_ptr(), pass_json.as_ptr()).is_null());
+        free_string(progress);
+    }
+
+    #[test]
+    fn test_faction_gain_rep_and_tier() {
+        let faction = CString::new("AscendingOrder").unwrap();
+        let ptr = faction_gain_rep(std::ptr::null(), faction.as_ptr(), 60.0);
+        assert!(!ptr.is_null());
+        let json = unsafe { CStr::from_ptr(ptr).to_str().unwrap() };
+        let v: serde_json::Value = serde_json::from_str(json).unwrap();
+        assert_eq!(v["unlocked_perks"].as_array().unwrap().len(), 2);
+        let profile = CString::new(v["profile"].to_string()).unwrap();
+        free_string(ptr);
+
+        let ptr = faction_get_tier(profile.as_ptr(), faction.as_ptr());
+        let json = unsafe { CStr::from_ptr(ptr).to_str().unwrap() };
+        assert_eq!(json, "\"Honored\"");
+        free_string(ptr);
+
+        let rival = CString::new("DeepDwellers").unwrap();
+        let ptr = faction_get_tier(profile.as_ptr(), rival.as_ptr());
+        let json = unsafe { CStr::from_ptr(ptr).to_str().unwrap() };
+        assert_eq!(json, "\"Unfriendly\"");
+        free_string(ptr);
+    }
+
+    #[test]
+    fn test_faction_influence_zones() {
+        let factions =
+            CString::new(r#"["AscendingOrder", "DeepDwellers", "EchoKeepers"]"#).unwrap();
+        let a = faction_influence_zones(42, 10, factions.as_ptr());
+        let b = faction_influence_zones(42, 10, factions.as_ptr());
+        assert!(!a.is_null());
+        let json_a = unsafe { CStr::from_ptr(a).to_str().unwrap() };
+        let json_b = unsafe { CStr::from_ptr(b).to_str().unwrap() };
+        assert_eq!(json_a, json_b);
+        let v: serde_json::Value = serde_json::from_str(json_a).unwrap();
+        assert_eq!(v["zones"].as_array().unwrap().len(), 3);
+        assert!(v["contested"].is_array());
+        free_string(a);
+        free_string(b);
+
+        let bad = CString::new("not json").unwrap();
+        assert!(faction_influence_zones(42, 10, bad.as_ptr()).is_null());
+    }
+
     // ========================
     // Social FFI Tests
     // ========================
@@ -2775,6 +5565,47 @@ mod tests {
         free_string(updated);
     }
 
+    #[test]
+    fn test_guild_bank_ffi() {
+        let guild = Guild::new(
+            "g1".into(),
+            "Bankers".into(),
+            "BK".into(),
+            "leader1".into(),
+            "Leader".into(),
+        );
+        let guild_json = CString::new(serde_json::to_string(&guild).unwrap()).unwrap();
+        let lid = CString::new("leader1").unwrap();
+        let item =
+            CString::new(r#"{"item_name":"Potion","quantity":3,"rarity":"Common"}"#).unwrap();
+
+        let deposited = guild_bank_deposit(guild_json.as_ptr(), lid.as_ptr(), 0, item.as_ptr(), 0);
+        assert!(!deposited.is_null());
+
+        let recruit = CString::new("member1").unwrap();
+        let rname = CString::new("Recruit").unwrap();
+        let with_recruit = social_guild_add_member(deposited, recruit.as_ptr(), rname.as_ptr());
+        assert!(!with_recruit.is_null());
+
+        let potion = CString::new("Potion").unwrap();
+        // Recruits can't withdraw
+        assert!(
+            guild_bank_withdraw(with_recruit, recruit.as_ptr(), 0, potion.as_ptr(), 1, 0).is_null()
+        );
+
+        let withdrawn = guild_bank_withdraw(with_recruit, lid.as_ptr(), 0, potion.as_ptr(), 2, 0);
+        assert!(!withdrawn.is_null());
+        let json = unsafe { CStr::from_ptr(withdrawn).to_str().unwrap() };
+        let updated: Guild = serde_json::from_str(json).unwrap();
+        let bank = updated.bank.unwrap();
+        assert_eq!(bank.tabs[0].items[0].quantity, 1);
+        assert_eq!(bank.log.len(), 2);
+
+        free_string(deposited);
+        free_string(with_recruit);
+        free_string(withdrawn);
+    }
+
     #[test]
     fn test_social_create_party() {
         let lid = CString::new("player1").unwrap();
@@ -2799,6 +5630,56 @@ mod tests {
         free_string(updated);
     }
 
+    #[test]
+    fn test_social_raid_ffi() {
+        let raid_ptr = social_create_raid(1);
+        let mut party = Party::new("p1".into(), "One".into());
+        party.add_member("p2".into(), "Two".into(), PartyRole::Support);
+        let party_json = CString::new(serde_json::to_string(&party).unwrap()).unwrap();
+
+        let with_party = social_raid_add_party(raid_ptr, party_json.as_ptr());
+        assert!(!with_party.is_null());
+        let other = Party::new("p3".into(), "Three".into());
+        let other_json = CString::new(serde_json::to_string(&other).unwrap()).unwrap();
+        assert!(social_raid_add_party(with_party, other_json.as_ptr()).is_null());
+
+        let comp_ptr = social_raid_composition(with_party);
+        let v: serde_json::Value =
+            serde_json::from_str(unsafe { CStr::from_ptr(comp_ptr) }.to_str().unwrap()).unwrap();
+        assert_eq!(v["total_members"], 2);
+        assert_eq!(v["composition"]["role_counts"]["Support"], 1);
+        assert!(social_raid_composition(std::ptr::null()).is_null());
+
+        free_string(raid_ptr);
+        free_string(with_party);
+        free_string(comp_ptr);
+    }
+
+    #[test]
+    fn test_party_assign_loot_ffi() {
+        let mut party = Party::new("p1".into(), "One".into());
+        party.add_member("p2".into(), "Two".into(), PartyRole::Support);
+        party.loot_rule = crate::social::LootRule::RoundRobin;
+        let item = CString::new(r#"{"item_name":"Relic","quantity":1,"rarity":"Rare"}"#).unwrap();
+        let rolls = CString::new("[]").unwrap();
+
+        let mut party_json = serde_json::to_string(&party).unwrap();
+        let mut winners = Vec::new();
+        for _ in 0..3 {
+            let input = CString::new(party_json).unwrap();
+            let ptr = party_assign_loot(input.as_ptr(), item.as_ptr(), rolls.as_ptr());
+            assert!(!ptr.is_null());
+            let json = unsafe { CStr::from_ptr(ptr).to_str().unwrap() };
+            let v: serde_json::Value = serde_json::from_str(json).unwrap();
+            winners.push(v["winner"].as_str().unwrap().to_string());
+            party_json = v["party"].to_string();
+            free_string(ptr);
+        }
+        assert_eq!(winners, vec!["p1", "p2", "p1"]);
+
+        assert!(party_assign_loot(std::ptr::null(), item.as_ptr(), rolls.as_ptr()).is_null());
+    }
+
     #[test]
     fn test_social_trade_workflow() {
         let pa = CString::new("player_a").unwrap();
@@ -2822,6 +5703,222 @@ mod tests {
         free_string(locked);
     }
 
+    #[test]
+    fn test_social_trade_execute_legacy_returns_error() {
+        let mut trade = Trade::new("a".into(), "b".into());
+        trade.lock("a");
+        trade.lock("b");
+        trade.confirm("a");
+        trade.confirm("b");
+        let tc = CString::new(serde_json::to_string(&trade).unwrap()).unwrap();
+
+        let ptr = social_trade_execute(tc.as_ptr());
+        assert!(!ptr.is_null());
+        let v: serde_json::Value =
+            serde_json::from_str(unsafe { CStr::from_ptr(ptr) }.to_str().unwrap()).unwrap();
+        assert!(v["error"]
+            .as_str()
+            .unwrap()
+            .contains("social_trade_execute_validated"));
+        assert!(v.get("state").is_none());
+        free_string(ptr);
+    }
+
+    #[test]
+    fn test_social_trade_execute_validated() {
+        let mut trade = Trade::new("a".into(), "b".into());
+        trade.set_shards("a", 100);
+        trade.lock("a");
+        trade.lock("b");
+        trade.confirm("a");
+        trade.confirm("b");
+        let tc = CString::new(serde_json::to_string(&trade).unwrap()).unwrap();
+        let inv_a = CString::new(r#"{"player_id":"a","shards":150,"items":[]}"#).unwrap();
+        let inv_b = CString::new(r#"{"player_id":"b","shards":0,"items":[]}"#).unwrap();
+        let poor_a = CString::new(r#"{"player_id":"a","shards":50,"items":[]}"#).unwrap();
+
+        let ptr = social_trade_execute_validated(tc.as_ptr(), inv_a.as_ptr(), inv_b.as_ptr());
+        assert!(!ptr.is_null());
+        let v: serde_json::Value =
+            serde_json::from_str(unsafe { CStr::from_ptr(ptr) }.to_str().unwrap()).unwrap();
+        assert_eq!(v["trade"]["state"], "Completed");
+        assert_eq!(v["inventory_a"]["shards"], 50);
+        assert_eq!(v["inventory_b"]["shards"], 100);
+        free_string(ptr);
+
+        let ptr = social_trade_execute_validated(tc.as_ptr(), poor_a.as_ptr(), inv_b.as_ptr());
+        let v: serde_json::Value =
+            serde_json::from_str(unsafe { CStr::from_ptr(ptr) }.to_str().unwrap()).unwrap();
+        assert_eq!(v["error"]["InsufficientItems"]["item_name"], "shards");
+        free_string(ptr);
+
+        assert!(
+            social_trade_execute_validated(std::ptr::null(), inv_a.as_ptr(), inv_b.as_ptr())
+                .is_null()
+        );
+    }
+
+    #[test]
+    fn test_auction_ffi_roundtrip() {
+        let house = CString::new(serde_json::to_string(&AuctionHouse::default()).unwrap()).unwrap();
+        let seller = CString::new("seller").unwrap();
+        let item = CString::new(
+            r#"{"name":"Fire Blade","rarity":"Epic","base_price":100,"stack_size":1,"max_stack":1,"soulbound":false}"#,
+        )
+        .unwrap();
+
+        let ptr = auction_list(house.as_ptr(), seller.as_ptr(), item.as_ptr(), 300, 20);
+        assert!(!ptr.is_null());
+        let v: serde_json::Value =
+            serde_json::from_str(unsafe { CStr::from_ptr(ptr) }.to_str().unwrap()).unwrap();
+        free_string(ptr);
+        let id = CString::new(v["listing_id"].as_str().unwrap()).unwrap();
+        let listed = CString::new(v["house"].to_string()).unwrap();
+
+        let buyer = CString::new("buyer").unwrap();
+        let wallet = CString::new(
+            serde_json::to_string(&Wallet {
+                tower_shards: 500,
+                ..Default::default()
+            })
+            .unwrap(),
+        )
+        .unwrap();
+        let ptr = auction_buyout(
+            listed.as_ptr(),
+            id.as_ptr(),
+            buyer.as_ptr(),
+            wallet.as_ptr(),
+        );
+        let v: serde_json::Value =
+            serde_json::from_str(unsafe { CStr::from_ptr(ptr) }.to_str().unwrap()).unwrap();
+        free_string(ptr);
+        assert_eq!(v["wallet"]["tower_shards"], 200);
+        assert_eq!(v["house"]["payouts"]["seller"], 300);
+        assert_eq!(v["item"]["name"], "Fire Blade");
+
+        let ptr = auction_expire(listed.as_ptr(), 20);
+        let v: serde_json::Value =
+            serde_json::from_str(unsafe { CStr::from_ptr(ptr) }.to_str().unwrap()).unwrap();
+        free_string(ptr);
+        assert_eq!(v["expired"].as_array().unwrap().len(), 1);
+        assert_eq!(v["house"]["deliveries"]["seller"][0]["name"], "Fire Blade");
+
+        assert!(auction_expire(std::ptr::null(), 0).is_null());
+    }
+
+    #[test]
+    fn test_inventory_add_item_ffi() {
+        let item = CString::new(
+            r#"{"name":"Ember Herb","category":"Material","rarity":"Common","quantity":60,"semantic_tags":[]}"#,
+        )
+        .unwrap();
+        let ptr = inventory_add_item(std::ptr::null(), item.as_ptr());
+        assert!(!ptr.is_null());
+        let v: serde_json::Value =
+            serde_json::from_str(unsafe { CStr::from_ptr(ptr) }.to_str().unwrap()).unwrap();
+        free_string(ptr);
+        assert_eq!(v["used_slots"], 1);
+
+        let inv = CString::new(v["inventory"].to_string()).unwrap();
+        let ptr = inventory_add_item(inv.as_ptr(), item.as_ptr());
+        let v: serde_json::Value =
+            serde_json::from_str(unsafe { CStr::from_ptr(ptr) }.to_str().unwrap()).unwrap();
+        free_string(ptr);
+        // 120 herbs: one full stack of 99 plus a second slot
+        assert_eq!(v["used_slots"], 2);
+
+        assert!(inventory_add_item(std::ptr::null(), std::ptr::null()).is_null());
+    }
+
+    /// Held by tests that record into or reset the shared analytics state
+    static ANALYTICS_STATE: Mutex<()> = Mutex::new(());
+
+    #[test]
+    fn test_economy_ledger_ffi() {
+        let _guard = ANALYTICS_STATE.lock().unwrap_or_else(|e| e.into_inner());
+        let read = || {
+            let ptr = economy_get_ledger();
+            assert!(!ptr.is_null());
+            let v: serde_json::Value =
+                serde_json::from_str(unsafe { CStr::from_ptr(ptr) }.to_str().unwrap()).unwrap();
+            free_string(ptr);
+            v
+        };
+        let before = read();
+        analytics_record_gold(500, 1);
+        analytics_record_gold(200, 0);
+        let after = read();
+
+        let created = |v: &serde_json::Value| v["ledger"]["total_created"].as_u64().unwrap();
+        let destroyed = |v: &serde_json::Value| v["ledger"]["total_destroyed"].as_u64().unwrap();
+        assert_eq!(created(&after) - created(&before), 500);
+        assert_eq!(destroyed(&after) - destroyed(&before), 200);
+        assert!(after["inflation_ratio"].as_f64().unwrap() > 0.0);
+
+        // Resetting analytics clears the ledger it feeds as well
+        analytics_reset();
+        let cleared = read();
+        assert_eq!(created(&cleared), 0);
+        assert_eq!(destroyed(&cleared), 0);
+    }
+
+    #[test]
+    fn test_hotreload_ffi_queue_round_trip() {
+        assert_eq!(hotreload_trigger_reload(), 1);
+        assert_eq!(hotreload_trigger_reload(), 1);
+        assert_eq!(
+            hotreload::take_ffi_commands(),
+            vec![hotreload::HotReloadCommand::Reload; 2]
+        );
+        assert!(hotreload::take_ffi_commands().is_empty());
+
+        let ptr = hotreload_get_status();
+        let status =
+            hotreload::HotReloadStatus::from_json(unsafe { CStr::from_ptr(ptr) }.to_str().unwrap());
+        free_string(ptr);
+        assert!(status.is_some());
+    }
+
+    #[test]
+    fn test_analytics_aggregate_ffi() {
+        let _guard = ANALYTICS_STATE.lock().unwrap_or_else(|e| e.into_inner());
+        let read = || {
+            let ptr = analytics_get_snapshot();
+            assert!(!ptr.is_null());
+            let v: serde_json::Value =
+                serde_json::from_str(unsafe { CStr::from_ptr(ptr) }.to_str().unwrap()).unwrap();
+            free_string(ptr);
+            v
+        };
+        let sword = CString::new("Sword").unwrap();
+        let bow = CString::new("Bow").unwrap();
+
+        analytics_reset();
+        analytics_record_damage(sword.as_ptr(), 120);
+        analytics_record_damage(sword.as_ptr(), 30);
+        analytics_record_damage(std::ptr::null(), 999);
+        analytics_record_damage(bow.as_ptr(), 5);
+        analytics_record_kill(bow.as_ptr());
+        analytics_record_floor_cleared(12, 1, 90.0);
+        analytics_record_floor_cleared(13, 1, 110.0);
+        let v = read();
+        assert_eq!(v["combat"]["total_damage_dealt"], 155);
+        assert_eq!(v["combat"]["per_weapon"]["Sword"]["hits"], 2);
+        assert_eq!(v["combat"]["per_weapon"]["Sword"]["total_damage"], 150);
+        assert_eq!(v["combat"]["per_weapon"]["Bow"]["kills"], 1);
+        assert_eq!(v["most_used_weapon"], "Sword");
+        assert_eq!(v["progression"]["floors_cleared"], 2);
+        assert_eq!(v["progression"]["highest_floor"], 13);
+        assert_eq!(v["progression"]["average_floor_clear_time"], 100.0);
+
+        analytics_reset();
+        let v = read();
+        assert_eq!(v["combat"]["total_damage_dealt"], 0);
+        assert_eq!(v["progression"]["floors_cleared"], 0);
+        assert!(v["most_used_weapon"].is_null());
+    }
+
     // ========================
     // Version test (updated)
     // ========================
@@ -2906,6 +6003,45 @@ mod tests {
         free_string(ptr);
     }
 
+    #[test]
+    fn test_generate_weekly_mutators_ffi() {
+        let a = generate_weekly_mutators(12, 1);
+        let b = generate_weekly_mutators(12, 1);
+        assert!(!a.is_null());
+        let ja = unsafe { CStr::from_ptr(a).to_str().unwrap() };
+        let jb = unsafe { CStr::from_ptr(b).to_str().unwrap() };
+        assert_eq!(ja, jb);
+        let set: mutators::FloorMutatorSet = serde_json::from_str(ja).unwrap();
+        assert_eq!(set.tier, "Echelon2");
+        assert_eq!(set.mutators.len(), 2);
+
+        assert!(generate_weekly_mutators(12, 4).is_null());
+        free_string(a);
+        free_string(b);
+    }
+
+    #[test]
+    fn test_mutator_reroll_ffi() {
+        let set_ptr = generate_floor_mutators(42, 600);
+        let rerolled = mutator_reroll(set_ptr, 1, 99);
+        assert!(!rerolled.is_null());
+        let before: mutators::FloorMutatorSet =
+            serde_json::from_str(unsafe { CStr::from_ptr(set_ptr).to_str().unwrap() }).unwrap();
+        let after: mutators::FloorMutatorSet =
+            serde_json::from_str(unsafe { CStr::from_ptr(rerolled).to_str().unwrap() }).unwrap();
+        assert_ne!(
+            after.mutators[1].mutator_type,
+            before.mutators[1].mutator_type
+        );
+        assert!(after.effects.total_difficulty >= before.effects.total_difficulty);
+
+        assert!(mutator_reroll(set_ptr, 10, 99).is_null());
+        assert!(mutator_reroll(std::ptr::null(), 0, 99).is_null());
+
+        free_string(set_ptr);
+        free_string(rerolled);
+    }
+
     #[test]
     fn test_get_all_mutator_types_ffi() {
         let ptr = get_all_mutator_types();
@@ -2995,6 +6131,47 @@ mod tests {
         free_string(ptr);
     }
 
+    #[test]
+    fn test_migrate_save_checked_ffi() {
+        let broken = serde_json::json!({
+            "version": 3,
+            "player_name": "Careful",
+            "mastery": 7
+        });
+        let cstr = CString::new(broken.to_string()).unwrap();
+        let ptr = migrate_save_checked(cstr.as_ptr());
+        assert!(!ptr.is_null());
+        let json = unsafe { CStr::from_ptr(ptr).to_str().unwrap() };
+        let result: savemigration::MigrationResult = serde_json::from_str(json).unwrap();
+        assert!(!result.success);
+        assert_eq!(result.data, Some(broken));
+        free_string(ptr);
+
+        assert!(migrate_save_checked(std::ptr::null()).is_null());
+    }
+
+    #[test]
+    fn test_recover_save_ffi() {
+        let save = serde_json::json!({
+            "version": 3,
+            "player_name": "Salvager",
+            "inventory": {"items": [], "shards": "???", "echo_fragments": 9}
+        });
+        let cstr = CString::new(save.to_string()).unwrap();
+        let ptr = recover_save(cstr.as_ptr());
+        assert!(!ptr.is_null());
+        let json = unsafe { CStr::from_ptr(ptr).to_str().unwrap() };
+        let result: savemigration::RecoveryResult = serde_json::from_str(json).unwrap();
+        assert!(!result.full_loss);
+        assert_eq!(result.data["player_name"], "Salvager");
+        assert_eq!(result.data["inventory"]["echo_fragments"], 9);
+        // Migrated to v4 first, so the bad shards value lands in currency
+        assert!(result.lost.contains(&"inventory.currency".to_string()));
+        free_string(ptr);
+
+        assert!(recover_save(std::ptr::null()).is_null());
+    }
+
     #[test]
     fn test_get_save_version_ffi() {
         let save = serde_json::json!({"version": 2}).to_string();
@@ -3075,6 +6252,74 @@ mod tests {
         free_string(ptr);
     }
 
+    #[test]
+    fn test_replay_verify_ffi() {
+        let header = replay::ReplayHeader::new("qa", 42, 3, "P1", "{}");
+        let frames = vec![replay::InputFrame::new(0, replay::InputType::Jump, "{}")];
+        let mut recording = replay::ReplayRecording::new(header, frames, vec![]);
+        let json = CString::new(recording.to_json()).unwrap();
+
+        let ptr = replay_verify(json.as_ptr());
+        let v: serde_json::Value =
+            serde_json::from_str(unsafe { CStr::from_ptr(ptr) }.to_str().unwrap()).unwrap();
+        free_string(ptr);
+        assert_eq!(v["seed_ok"], true);
+        assert_eq!(v["hash_ok"], true);
+        assert_eq!(v["layout_ok"], true);
+        assert_eq!(v["frame_count"], 1);
+
+        recording.header.seed = 7;
+        let json = CString::new(recording.to_json()).unwrap();
+        let ptr = replay_verify(json.as_ptr());
+        let v: serde_json::Value =
+            serde_json::from_str(unsafe { CStr::from_ptr(ptr) }.to_str().unwrap()).unwrap();
+        free_string(ptr);
+        assert_eq!(v["seed_ok"], false);
+        assert!(replay_verify(std::ptr::null()).is_null());
+    }
+
+    #[test]
+    fn test_replay_diff_ffi() {
+        let frames = |payload: &str| {
+            vec![
+                replay::InputFrame::new(0, replay::InputType::Jump, "{}"),
+                replay::InputFrame::new(4, replay::InputType::Attack, payload),
+            ]
+        };
+        let a = replay::ReplayRecording::new(
+            replay::ReplayHeader::new("a", 42, 3, "P1", "{}"),
+            frames(r#"{"combo":1}"#),
+            vec![],
+        );
+        let b = replay::ReplayRecording::new(
+            replay::ReplayHeader::new("b", 42, 3, "P2", "{}"),
+            frames(r#"{"combo":2}"#),
+            vec![],
+        );
+        let a_json = CString::new(a.to_json()).unwrap();
+        let b_json = CString::new(b.to_json()).unwrap();
+
+        let ptr = replay_diff(a_json.as_ptr(), b_json.as_ptr());
+        let v: serde_json::Value =
+            serde_json::from_str(unsafe { CStr::from_ptr(ptr) }.to_str().unwrap()).unwrap();
+        free_string(ptr);
+        assert_eq!(v["first_divergent_tick"], 4);
+        assert_eq!(v["differing_frames"]["Attack"], 1);
+
+        let c = replay::ReplayRecording::new(
+            replay::ReplayHeader::new("c", 7, 3, "P3", "{}"),
+            vec![],
+            vec![],
+        );
+        let c_json = CString::new(c.to_json()).unwrap();
+        let ptr = replay_diff(a_json.as_ptr(), c_json.as_ptr());
+        let v: serde_json::Value =
+            serde_json::from_str(unsafe { CStr::from_ptr(ptr) }.to_str().unwrap()).unwrap();
+        free_string(ptr);
+        assert_eq!(v["error"]["SeedMismatch"]["b"], 7);
+        assert!(replay_diff(a_json.as_ptr(), std::ptr::null()).is_null());
+    }
+
     // ========================
     // Tower Map FFI Tests (Session 21)
     // ========================
@@ -3135,4 +6380,113 @@ mod tests {
         free_string(discovered);
         free_string(overview_ptr);
     }
+
+    #[test]
+    fn test_towermap_fast_travel_cost_ffi() {
+        let map_ptr = towermap_create();
+        let discovered = towermap_discover_floor(map_ptr, 10, 0, 5, 10, 3);
+        let cleared = towermap_clear_floor(discovered, 10, 90.0);
+
+        let ptr = towermap_fast_travel_cost(cleared, 1, 10);
+        let v: serde_json::Value =
+            serde_json::from_str(unsafe { CStr::from_ptr(ptr) }.to_str().unwrap()).unwrap();
+        assert_eq!(v["allowed"], true);
+        assert_eq!(v["cost"], towermap::fast_travel_cost(1, 10));
+
+        let blocked = towermap_fast_travel_cost(discovered, 1, 10);
+        let v: serde_json::Value =
+            serde_json::from_str(unsafe { CStr::from_ptr(blocked) }.to_str().unwrap()).unwrap();
+        assert_eq!(v["allowed"], false);
+        assert!(towermap_fast_travel_cost(std::ptr::null(), 1, 10).is_null());
+
+        free_string(map_ptr);
+        free_string(discovered);
+        free_string(cleared);
+        free_string(ptr);
+        free_string(blocked);
+    }
+
+    #[test]
+    fn test_towermap_difficulty_report_ffi() {
+        let mut map = towermap::TowerMap::default();
+        for floor in 1..=3 {
+            map.discover_floor(floor, FloorTier::Echelon1, 5, 10, 3);
+        }
+        map.clear_floor(1, 60.0);
+        map.record_death(2);
+        map.clear_floor(2, 80.0);
+        let json = CString::new(map.to_json()).unwrap();
+
+        let ptr = towermap_difficulty_report(json.as_ptr());
+        let v: serde_json::Value =
+            serde_json::from_str(unsafe { CStr::from_ptr(ptr) }.to_str().unwrap()).unwrap();
+        free_string(ptr);
+        let entries = v.as_array().unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0]["floor_id"], 2);
+        assert_eq!(entries[0]["death_rate"], 0.5);
+        assert_eq!(entries[1]["death_rate"], 0.0);
+        assert!(towermap_difficulty_report(std::ptr::null()).is_null());
+    }
+
+    #[test]
+    fn test_towermap_route_to_ffi() {
+        let mut map = towermap::TowerMap::default();
+        for floor in 1..=6 {
+            map.discover_floor(floor, FloorTier::Echelon1, 5, 10, 3);
+        }
+        map.clear_floor(1, 60.0);
+        map.clear_floor(4, 90.0);
+        map.unlock_shortcut(1, 4);
+        let json = CString::new(map.to_json()).unwrap();
+
+        let ptr = towermap_route_to(json.as_ptr(), 4);
+        let v: serde_json::Value =
+            serde_json::from_str(unsafe { CStr::from_ptr(ptr) }.to_str().unwrap()).unwrap();
+        free_string(ptr);
+        assert_eq!(v["reachable"], true);
+        assert_eq!(v["route"], serde_json::json!([4]));
+
+        let ptr = towermap_route_to(json.as_ptr(), 6);
+        let v: serde_json::Value =
+            serde_json::from_str(unsafe { CStr::from_ptr(ptr) }.to_str().unwrap()).unwrap();
+        free_string(ptr);
+        assert_eq!(v["route"], serde_json::json!([4, 5, 6]));
+        assert!(v["estimated_clear_secs"].as_f64().unwrap() > 0.0);
+
+        let ptr = towermap_route_to(json.as_ptr(), 40);
+        let v: serde_json::Value =
+            serde_json::from_str(unsafe { CStr::from_ptr(ptr) }.to_str().unwrap()).unwrap();
+        free_string(ptr);
+        assert_eq!(v["reachable"], false);
+        assert!(towermap_route_to(std::ptr::null(), 4).is_null());
+    }
+
+    #[test]
+    fn test_towermap_claim_milestone_ffi() {
+        let map_ptr = towermap_create();
+        let discovered = towermap_discover_floor(map_ptr, 10, 0, 5, 10, 3);
+
+        let milestones_ptr = towermap_depth_milestones(discovered);
+        let json = unsafe { CStr::from_ptr(milestones_ptr).to_str().unwrap() };
+        assert_eq!(json, "[10]");
+
+        let claim_ptr = towermap_claim_milestone(discovered, 10);
+        let json = unsafe { CStr::from_ptr(claim_ptr).to_str().unwrap() };
+        let result: serde_json::Value = serde_json::from_str(json).unwrap();
+        assert_eq!(result["reward"]["depth"], 10);
+
+        // Re-claim against the updated map yields no reward
+        let updated = CString::new(result["map"].to_string()).unwrap();
+        let reclaim_ptr = towermap_claim_milestone(updated.as_ptr(), 10);
+        let json = unsafe { CStr::from_ptr(reclaim_ptr).to_str().unwrap() };
+        let result: serde_json::Value = serde_json::from_str(json).unwrap();
+        assert!(result["reward"].is_null());
+
+        free_string(map_ptr);
+        free_string(discovered);
+        free_string(milestones_ptr);
+        free_string(claim_ptr);
+        free_string(reclaim_ptr);
+    }
 }
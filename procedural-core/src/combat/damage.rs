@@ -0,0 +1,154 @@
+//! Damage split into physical and elemental parts.
+//!
+//! Armor only reduces the physical part; resistances only reduce the
+//! element they name. A packet's total is always the sum of its parts.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::semantic::{SemanticTags, MAX_RESISTANCE};
+
+/// Tags treated as elemental damage; everything else is physical
+pub const ELEMENTAL_TAGS: [&str; 7] = [
+    "fire",
+    "water",
+    "earth",
+    "wind",
+    "void",
+    "corruption",
+    "holy",
+];
+
+/// Armor at which physical damage is halved (`ARMOR_HALF / (ARMOR_HALF + armor)`)
+const ARMOR_HALF: f32 = 100.0;
+
+/// One hit's damage, by type
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DamagePacket {
+    pub physical: f32,
+    pub elemental: HashMap<String, f32>,
+}
+
+impl DamagePacket {
+    /// Split `amount` by the attacker's tags: each positive elemental tag takes
+    /// its value as a share, physical takes whatever is left. If the elemental
+    /// shares exceed 1 they are normalized and nothing is physical.
+    pub fn split(amount: f32, attacker: &SemanticTags) -> Self {
+        let shares: Vec<(&str, f32)> = ELEMENTAL_TAGS
+            .iter()
+            .map(|&tag| (tag, attacker.get(tag).max(0.0)))
+            .filter(|(_, v)| *v > 0.0)
+            .collect();
+        let elemental_total: f32 = shares.iter().map(|(_, v)| v).sum();
+        let scale = if elemental_total > 1.0 {
+            1.0 / elemental_total
+        } else {
+            1.0
+        };
+
+        let elemental = shares
+            .iter()
+            .map(|(tag, v)| (tag.to_string(), amount * v * scale))
+            .collect();
+        Self {
+            physical: amount * (1.0 - elemental_total * scale).max(0.0),
+            elemental,
+        }
+    }
+
+    /// Sum of all parts
+    pub fn total(&self) -> f32 {
+        self.physical + self.elemental.values().sum::<f32>()
+    }
+
+    /// This packet after defenses: armor on the physical part, each
+    /// resistance on its own element. Resistances are reduction fractions,
+    /// capped at `MAX_RESISTANCE`; negative values are weaknesses (down to
+    /// -1.0, double damage).
+    pub fn mitigated(&self, armor: f32, resistances: &HashMap<String, f32>) -> Self {
+        let armor_factor = ARMOR_HALF / (ARMOR_HALF + armor.max(0.0));
+        Self {
+            physical: self.physical * armor_factor,
+            elemental: self
+                .elemental
+                .iter()
+                .map(|(tag, amount)| {
+                    let resist = resistances
+                        .get(tag)
+                        .copied()
+                        .unwrap_or(0.0)
+                        .clamp(-1.0, MAX_RESISTANCE);
+                    (tag.clone(), amount * (1.0 - resist))
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Damage dealt by `packet` after armor and per-element resistances
+pub fn resolve_packet(
+    packet: &DamagePacket,
+    armor: f32,
+    resistances: &HashMap<String, f32>,
+) -> f32 {
+    packet.mitigated(armor, resistances).total()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fire_packet() -> DamagePacket {
+        DamagePacket {
+            physical: 60.0,
+            elemental: HashMap::from([("fire".to_string(), 40.0)]),
+        }
+    }
+
+    #[test]
+    fn test_armor_reduces_only_physical() {
+        let out = fire_packet().mitigated(100.0, &HashMap::new());
+        assert!((out.physical - 30.0).abs() < 0.001);
+        assert_eq!(out.elemental["fire"], 40.0);
+        assert!((resolve_packet(&fire_packet(), 100.0, &HashMap::new()) - 70.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_fire_resistance_reduces_only_fire() {
+        let resist = HashMap::from([("fire".to_string(), 0.5), ("water".to_string(), 0.9)]);
+        let out = fire_packet().mitigated(0.0, &resist);
+        assert_eq!(out.physical, 60.0);
+        assert!((out.elemental["fire"] - 20.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_resistance_capped() {
+        let resist = HashMap::from([("fire".to_string(), 5.0)]);
+        let out = fire_packet().mitigated(0.0, &resist);
+        assert!((out.elemental["fire"] - 40.0 * (1.0 - MAX_RESISTANCE)).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_total_matches_component_sum() {
+        let tags = SemanticTags::new(vec![("fire", 0.5), ("wind", 0.25), ("agility", 0.9)]);
+        let packet = DamagePacket::split(200.0, &tags);
+        assert!((packet.total() - 200.0).abs() < 0.001);
+        assert!((packet.physical - 50.0).abs() < 0.001);
+        assert!((packet.elemental["fire"] - 100.0).abs() < 0.001);
+        assert!(!packet.elemental.contains_key("agility"));
+
+        let resolved = packet.mitigated(25.0, &HashMap::from([("wind".to_string(), 0.3)]));
+        let sum = resolved.physical + resolved.elemental.values().sum::<f32>();
+        assert!((resolved.total() - sum).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn test_split_normalizes_heavy_elemental() {
+        let tags = SemanticTags::new(vec![("fire", 0.9), ("void", 0.9)]);
+        let packet = DamagePacket::split(100.0, &tags);
+        assert_eq!(packet.physical, 0.0);
+        assert!((packet.elemental["fire"] - 50.0).abs() < 0.001);
+        assert!((packet.total() - 100.0).abs() < 0.001);
+    }
+}
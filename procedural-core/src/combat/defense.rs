@@ -7,7 +7,10 @@
 use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
 
+use super::damage::ELEMENTAL_TAGS;
 use super::{AttackPhase, CombatResources, CombatState};
+use crate::constants::SERVER_TICK_MS;
+use crate::mastery::SkillEffect;
 
 /// Defensive state for parry/dodge/block
 #[derive(Component, Debug)]
@@ -108,22 +111,28 @@ pub fn check_defense(
             }
         }
         DefenseAction::Block => {
-            if resources.thermal_energy >= BLOCK_DRAIN_PER_HIT {
+            if breaks_guard(defense, resources) {
+                // No energy to block — guard break
+                DefenseResult::NoDefense
+            } else {
                 let absorbed = _incoming_damage * BLOCK_REDUCTION;
                 let remaining = _incoming_damage - absorbed;
                 DefenseResult::BlockAbsorb {
                     absorbed,
                     remaining,
                 }
-            } else {
-                // No energy to block — guard break
-                DefenseResult::NoDefense
             }
         }
         DefenseAction::None => DefenseResult::NoDefense,
     }
 }
 
+/// Whether a hit breaks the defender's guard: blocking without enough
+/// thermal energy left to absorb it
+pub fn breaks_guard(defense: &DefenseState, resources: &CombatResources) -> bool {
+    defense.action == DefenseAction::Block && resources.thermal_energy < BLOCK_DRAIN_PER_HIT
+}
+
 /// System: process defense timers
 pub fn update_defense_state(
     time: Res<Time>,
@@ -228,6 +237,80 @@ pub fn stop_block(defense: &mut DefenseState) {
     }
 }
 
+/// Reflect share kept without the Riposte Master specialization
+const REFLECT_WITHOUT_RIPOSTE: f32 = 0.5;
+
+/// Damage sent back to the attacker by a perfect parry
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReflectedAttack {
+    /// Element of the reflected hit, None for neutral (physical) damage
+    pub element: Option<String>,
+    pub damage: f32,
+}
+
+/// Reflect an attack off a perfect parry. Elemental attacks come back as the
+/// same element; anything else is reflected as neutral damage. Without the
+/// Riposte specialization only half of `base_reflect` is returned.
+pub fn parry_reflect_element(
+    incoming_element: &str,
+    base_reflect: f32,
+    has_riposte: bool,
+) -> ReflectedAttack {
+    let element = ELEMENTAL_TAGS
+        .iter()
+        .find(|e| e.eq_ignore_ascii_case(incoming_element))
+        .map(|e| e.to_string());
+    let share = if has_riposte {
+        1.0
+    } else {
+        REFLECT_WITHOUT_RIPOSTE
+    };
+
+    ReflectedAttack {
+        element,
+        damage: base_reflect.max(0.0) * share,
+    }
+}
+
+/// How well a parry lined up with the hit it answered
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ParryResult {
+    Miss,
+    Parry,
+    PerfectParry,
+}
+
+/// Judge a parry by its distance in ms (early or late) from the incoming hit:
+/// within `perfect_ms` is a perfect parry, within `window_ms` a normal one.
+/// Mastery extensions are added to `window_ms` by the caller, see
+/// `parry_window_bonus_ms`.
+pub fn evaluate_parry(
+    incoming_tick: u64,
+    parry_tick: u64,
+    window_ms: f32,
+    perfect_ms: f32,
+) -> ParryResult {
+    let delta_ms = incoming_tick.abs_diff(parry_tick) as f32 * SERVER_TICK_MS;
+    if delta_ms <= perfect_ms.min(window_ms) {
+        ParryResult::PerfectParry
+    } else if delta_ms <= window_ms {
+        ParryResult::Parry
+    } else {
+        ParryResult::Miss
+    }
+}
+
+/// Total parry window extension (ms) from unlocked mastery effects
+pub fn parry_window_bonus_ms(effects: &[SkillEffect]) -> f32 {
+    effects
+        .iter()
+        .map(|e| match e {
+            SkillEffect::ParryWindowExtension(ms) => *ms,
+            _ => 0.0,
+        })
+        .sum()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -364,4 +447,76 @@ mod tests {
         };
         assert!(!start_parry(&mut defense, &combat));
     }
+
+    #[test]
+    fn test_parry_reflects_fire() {
+        let reflected = parry_reflect_element("fire", 40.0, true);
+        assert_eq!(reflected.element.as_deref(), Some("fire"));
+        assert!((reflected.damage - 40.0).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn test_parry_reflect_without_riposte_is_weaker() {
+        let with = parry_reflect_element("water", 40.0, true);
+        let without = parry_reflect_element("water", 40.0, false);
+        assert_eq!(without.element.as_deref(), Some("water"));
+        assert!(without.damage < with.damage);
+    }
+
+    #[test]
+    fn test_parry_reflects_every_damage_element() {
+        for element in ELEMENTAL_TAGS {
+            let reflected = parry_reflect_element(element, 10.0, true);
+            assert_eq!(reflected.element.as_deref(), Some(element));
+        }
+    }
+
+    #[test]
+    fn test_parry_reflect_non_elemental_is_neutral() {
+        let reflected = parry_reflect_element("slash", 30.0, true);
+        assert_eq!(reflected.element, None);
+        assert!((reflected.damage - 30.0).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn test_on_time_parry_is_perfect() {
+        assert_eq!(
+            evaluate_parry(600, 600, 120.0, 30.0),
+            ParryResult::PerfectParry
+        );
+        // One tick (50ms) off is still inside a 60ms perfect window
+        assert_eq!(
+            evaluate_parry(600, 601, 120.0, 60.0),
+            ParryResult::PerfectParry
+        );
+    }
+
+    #[test]
+    fn test_late_parry_inside_window() {
+        // 2 ticks late = 100ms
+        assert_eq!(evaluate_parry(600, 602, 120.0, 30.0), ParryResult::Parry);
+        // Early by the same amount counts the same
+        assert_eq!(evaluate_parry(600, 598, 120.0, 30.0), ParryResult::Parry);
+    }
+
+    #[test]
+    fn test_parry_outside_window_misses() {
+        // 3 ticks = 150ms
+        assert_eq!(evaluate_parry(600, 603, 120.0, 30.0), ParryResult::Miss);
+        assert_eq!(evaluate_parry(600, 0, 120.0, 30.0), ParryResult::Miss);
+    }
+
+    #[test]
+    fn test_window_extension_turns_miss_into_parry() {
+        let effects = vec![
+            SkillEffect::ParryWindowExtension(30.0),
+            SkillEffect::DamageBonus(0.1),
+        ];
+        let bonus = parry_window_bonus_ms(&effects);
+        assert_eq!(bonus, 30.0);
+        assert_eq!(
+            evaluate_parry(600, 603, 120.0 + bonus, 30.0),
+            ParryResult::Parry
+        );
+    }
 }
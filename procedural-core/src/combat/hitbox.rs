@@ -6,7 +6,12 @@
 use bevy::prelude::*;
 use bevy_rapier3d::prelude::*;
 
-use super::{AttackAngle, AttackPhase, CombatState};
+use super::defense::{breaks_guard, DefenseState};
+use super::weapons::{Weapon, WeaponType};
+use super::{
+    facing_yaw, hit_angle_multiplier_for, hit_direction, thorns_for_hit, AttackAngle, AttackPhase,
+    CombatResources, CombatState, GuardBreakState, HitDirection, HitRange, Stealthed, Thorns,
+};
 
 /// Hitbox spawned during attack Active phase
 #[derive(Component, Debug)]
@@ -16,6 +21,7 @@ pub struct Hitbox {
     pub knockback: f32,
     pub hit_entities: Vec<Entity>,
     pub lifetime: f32,
+    pub range: HitRange,
 }
 
 /// Hurtbox attached to damageable entities
@@ -63,6 +69,8 @@ pub struct DamageEvent {
     pub attacker: Entity,
     pub amount: f32,
     pub angle: AttackAngle,
+    /// Side of the target the hit came from, for damage indicators
+    pub direction: HitDirection,
     pub position: Vec3,
 }
 
@@ -115,6 +123,7 @@ pub fn spawn_attack_hitboxes(
                 knockback,
                 hit_entities: Vec::new(),
                 lifetime: 0.15,
+                range: HitRange::Melee,
             },
         ));
     }
@@ -135,15 +144,29 @@ pub fn update_hitbox_lifetime(
     }
 }
 
+/// Defender components that change how a landed hit resolves
+type DefenderState = (
+    Option<&'static Thorns>,
+    Option<&'static GuardBreakState>,
+    Option<&'static DefenseState>,
+    Option<&'static CombatResources>,
+);
+
 /// Detect hitbox-hurtbox collisions and apply damage
+#[allow(clippy::too_many_arguments)]
 pub fn process_hitbox_collisions(
+    mut commands: Commands,
     mut collision_events: EventReader<CollisionEvent>,
     mut hitbox_query: Query<&mut Hitbox>,
     hurtbox_query: Query<&Hurtbox>,
     transform_query: Query<&Transform>,
     mut health_query: Query<&mut Health>,
+    defender_query: Query<DefenderState>,
+    attacker_query: Query<(Option<&Weapon>, Has<Stealthed>)>,
+    time: Res<Time>,
     mut damage_events: EventWriter<DamageEvent>,
 ) {
+    let now_ms = time.elapsed().as_millis() as u64;
     for event in collision_events.read() {
         let (e1, e2, started) = match event {
             CollisionEvent::Started(e1, e2, _) => (*e1, *e2, true),
@@ -177,19 +200,65 @@ pub fn process_hitbox_collisions(
             continue;
         }
 
-        // Calculate angle-based damage
-        let angle = if let (Ok(attacker_tf), Ok(target_tf)) = (
+        // Calculate angle-based damage (dagger/stealth backstabs get their own bonus)
+        let (weapon, stealthed) = attacker_query.get(hitbox.owner).unwrap_or((None, false));
+        let (angle, angle_mult, directions) = if let (Ok(attacker_tf), Ok(target_tf)) = (
             transform_query.get(hitbox.owner),
             transform_query.get(hurtbox.owner),
         ) {
-            AttackAngle::from_transforms(attacker_tf, target_tf)
+            (
+                AttackAngle::from_transforms(attacker_tf, target_tf),
+                hit_angle_multiplier_for(
+                    attacker_tf,
+                    target_tf,
+                    weapon.map_or(WeaponType::Sword, |w| w.weapon_type),
+                    stealthed,
+                ),
+                // (hit on the target, thorns on the attacker)
+                (
+                    hit_direction(
+                        facing_yaw(target_tf),
+                        attacker_tf.translation,
+                        target_tf.translation,
+                    ),
+                    hit_direction(
+                        facing_yaw(attacker_tf),
+                        target_tf.translation,
+                        attacker_tf.translation,
+                    ),
+                ),
+            )
         } else {
-            AttackAngle::Front
+            (
+                AttackAngle::Front,
+                AttackAngle::Front.multiplier(),
+                (HitDirection::Front, HitDirection::Front),
+            )
         };
 
-        let final_damage = hitbox.base_damage * angle.multiplier();
+        // Guard-broken targets take bonus damage during the punish window
+        let (thorns, guard_break, defense, resources) = defender_query
+            .get(hurtbox.owner)
+            .unwrap_or((None, None, None, None));
+        let guard_mult = guard_break
+            .map(|g| g.damage_multiplier(now_ms))
+            .unwrap_or(1.0);
+
+        // A block running out of energy breaks, opening the window for follow-ups;
+        // further hits while it stays broken don't refresh it
+        if let (Some(defense), Some(resources)) = (defense, resources) {
+            let previous = guard_break.copied().unwrap_or_default();
+            let mut state = previous;
+            state.update_guard(breaks_guard(defense, resources), now_ms);
+            if state != previous {
+                commands.entity(hurtbox.owner).insert(state);
+            }
+        }
+
+        let final_damage = hitbox.base_damage * angle_mult * guard_mult;
 
         // Apply damage
+        let mut dealt = 0.0;
         if let Ok(mut health) = health_query.get_mut(hurtbox.owner) {
             let actual = health.take_damage(final_damage);
             if actual > 0.0 {
@@ -203,9 +272,36 @@ pub fn process_hitbox_collisions(
                     attacker: hitbox.owner,
                     amount: actual,
                     angle,
+                    direction: directions.0,
                     position,
                 });
             }
+            dealt = actual;
+        }
+
+        // Thorns: reflect part of the landed melee damage back to the attacker
+        if let Some(thorns) = thorns {
+            let reflected = thorns_for_hit(hitbox.range, dealt, thorns.pct);
+            if reflected > 0.0 {
+                if let Ok(mut attacker_health) = health_query.get_mut(hitbox.owner) {
+                    let actual = attacker_health.take_damage(reflected);
+                    if actual > 0.0 {
+                        let position = transform_query
+                            .get(hitbox.owner)
+                            .map(|t| t.translation)
+                            .unwrap_or(Vec3::ZERO);
+
+                        damage_events.send(DamageEvent {
+                            target: hitbox.owner,
+                            attacker: hurtbox.owner,
+                            amount: actual,
+                            angle: AttackAngle::Front,
+                            direction: directions.1,
+                            position,
+                        });
+                    }
+                }
+            }
         }
 
         hitbox.hit_entities.push(hurtbox.owner);
@@ -277,6 +373,175 @@ mod tests {
         assert_eq!(s.direction, Vec3::ZERO);
     }
 
+    #[test]
+    fn test_guard_break_opens_punish_window() {
+        use crate::combat::defense::DefenseAction;
+        use crate::combat::{GUARD_BREAK_BONUS, GUARD_BREAK_WINDOW_MS};
+        use bevy_rapier3d::rapier::geometry::CollisionEventFlags;
+        use std::time::Duration;
+
+        let mut app = App::new();
+        app.add_event::<CollisionEvent>()
+            .add_event::<DamageEvent>()
+            .init_resource::<Time>()
+            .add_systems(Update, process_hitbox_collisions);
+
+        let attacker = app.world_mut().spawn_empty().id();
+        let defender = app
+            .world_mut()
+            .spawn((
+                Health::new(1000.0),
+                DefenseState {
+                    action: DefenseAction::Block,
+                    ..Default::default()
+                },
+                CombatResources {
+                    thermal_energy: 0.0,
+                    ..Default::default()
+                },
+            ))
+            .id();
+        let hurtbox = app.world_mut().spawn(Hurtbox { owner: defender }).id();
+
+        let hit = |app: &mut App| -> f32 {
+            let hitbox = app
+                .world_mut()
+                .spawn(Hitbox {
+                    owner: attacker,
+                    base_damage: 100.0,
+                    knockback: 0.0,
+                    hit_entities: Vec::new(),
+                    lifetime: 1.0,
+                    range: HitRange::Melee,
+                })
+                .id();
+            app.world_mut()
+                .get_mut::<Health>(defender)
+                .unwrap()
+                .invulnerable_timer = 0.0;
+            let before = app.world().get::<Health>(defender).unwrap().current;
+            app.world_mut().send_event(CollisionEvent::Started(
+                hitbox,
+                hurtbox,
+                CollisionEventFlags::empty(),
+            ));
+            app.update();
+            before - app.world().get::<Health>(defender).unwrap().current
+        };
+
+        // The breaking hit itself is normal and arms the window
+        assert!((hit(&mut app) - 100.0).abs() < 0.01);
+        let state = *app.world().get::<GuardBreakState>(defender).unwrap();
+        assert!(state.is_vulnerable(0));
+
+        // Follow-up inside the window is punished
+        app.world_mut()
+            .get_mut::<DefenseState>(defender)
+            .unwrap()
+            .action = DefenseAction::None;
+        assert!((hit(&mut app) - 100.0 * GUARD_BREAK_BONUS).abs() < 0.01);
+
+        // Blocking again at zero energy re-breaks the guard; hits while it
+        // stays broken leave the window where it was
+        app.world_mut()
+            .get_mut::<DefenseState>(defender)
+            .unwrap()
+            .action = DefenseAction::Block;
+        app.world_mut()
+            .resource_mut::<Time>()
+            .advance_by(Duration::from_millis(GUARD_BREAK_WINDOW_MS));
+        hit(&mut app);
+        let rearmed = *app.world().get::<GuardBreakState>(defender).unwrap();
+        assert!(rearmed.guard_down);
+        app.world_mut()
+            .resource_mut::<Time>()
+            .advance_by(Duration::from_millis(500));
+        hit(&mut app);
+        assert_eq!(
+            app.world()
+                .get::<GuardBreakState>(defender)
+                .unwrap()
+                .vulnerable_until,
+            rearmed.vulnerable_until
+        );
+        app.world_mut()
+            .get_mut::<DefenseState>(defender)
+            .unwrap()
+            .action = DefenseAction::None;
+
+        // After the window, damage is back to normal
+        app.world_mut()
+            .resource_mut::<Time>()
+            .advance_by(Duration::from_millis(GUARD_BREAK_WINDOW_MS));
+        assert!((hit(&mut app) - 100.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_stealth_status_turns_sword_hit_into_backstab() {
+        use crate::combat::status::{sync_stealth, StatusEffect, StatusEffects, StatusType};
+        use crate::combat::BACKSTAB_MULTIPLIER;
+        use bevy_rapier3d::rapier::geometry::CollisionEventFlags;
+
+        let mut app = App::new();
+        app.add_event::<CollisionEvent>()
+            .add_event::<DamageEvent>()
+            .init_resource::<Time>()
+            .add_systems(Update, (sync_stealth, process_hitbox_collisions).chain());
+
+        // Attacker directly behind a defender that faces -Z
+        let mut statuses = StatusEffects::default();
+        statuses.apply(StatusEffect::new(StatusType::Stealthed, 2.0, 1.0));
+        let attacker = app
+            .world_mut()
+            .spawn((
+                Transform::from_xyz(0.0, 0.0, 2.0).looking_at(Vec3::ZERO, Vec3::Y),
+                statuses,
+            ))
+            .id();
+        let defender = app
+            .world_mut()
+            .spawn((Transform::default(), Health::new(1000.0)))
+            .id();
+        let hurtbox = app.world_mut().spawn(Hurtbox { owner: defender }).id();
+
+        let hit = |app: &mut App| -> f32 {
+            let hitbox = app
+                .world_mut()
+                .spawn(Hitbox {
+                    owner: attacker,
+                    base_damage: 100.0,
+                    knockback: 0.0,
+                    hit_entities: Vec::new(),
+                    lifetime: 1.0,
+                    range: HitRange::Melee,
+                })
+                .id();
+            app.world_mut()
+                .get_mut::<Health>(defender)
+                .unwrap()
+                .invulnerable_timer = 0.0;
+            let before = app.world().get::<Health>(defender).unwrap().current;
+            app.world_mut().send_event(CollisionEvent::Started(
+                hitbox,
+                hurtbox,
+                CollisionEventFlags::empty(),
+            ));
+            app.update();
+            before - app.world().get::<Health>(defender).unwrap().current
+        };
+
+        assert!((hit(&mut app) - 100.0 * BACKSTAB_MULTIPLIER).abs() < 0.01);
+        assert!(app.world().get::<Stealthed>(attacker).is_some());
+
+        // Once the effect is gone it's an ordinary sword hit from behind
+        app.world_mut()
+            .get_mut::<StatusEffects>(attacker)
+            .unwrap()
+            .cleanse(StatusType::Stealthed);
+        assert!((hit(&mut app) - 100.0 * AttackAngle::Back.multiplier()).abs() < 0.01);
+        assert!(app.world().get::<Stealthed>(attacker).is_none());
+    }
+
     #[test]
     fn test_damage_multipliers_with_angle() {
         let base = 100.0;
@@ -1,11 +1,27 @@
 use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
 
+pub mod damage;
 pub mod defense;
 pub mod hitbox;
+pub mod procs;
 pub mod status;
+pub mod threat;
 pub mod weapons;
 
+pub use damage::{resolve_packet, DamagePacket};
+pub use defense::{
+    evaluate_parry, parry_reflect_element, parry_window_bonus_ms, ParryResult, ReflectedAttack,
+};
+pub use procs::{CombatEvent, Proc, ProcEffect, ProcTable};
+pub use status::{
+    apply_status, resolve_status_ticks, HealOverTime, HotStack, StatusEffect, StatusType,
+};
+pub use threat::{mass_taunt, threat_modifier, AggroTable, CombatAction, Taunt};
+pub use weapons::{finisher_effect, FinisherEffect, FinisherKind};
+
+use weapons::WeaponType;
+
 pub struct CombatPlugin;
 
 impl Plugin for CombatPlugin {
@@ -24,6 +40,8 @@ impl Plugin for CombatPlugin {
                 defense::update_defense_state,
                 defense::apply_dodge_movement,
                 status::tick_status_effects,
+                status::tick_hot_stacks,
+                status::sync_stealth,
             )
                 .chain(),
         );
@@ -56,22 +74,46 @@ impl ExecutionQuality {
 }
 
 /// Angle of attack relative to target facing direction
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum AttackAngle {
     Front, // 1.0x damage
     Side,  // 0.7x damage
+    Flank, // rear quarter; 0.7x unless the weapon says otherwise
     Back,  // 1.5x damage
 }
 
+/// Weapon-specific angle multipliers. Anything not listed uses the
+/// baseline from `AttackAngle::multiplier`.
+const WEAPON_ANGLE_TABLE: &[(WeaponType, AttackAngle, f32)] = &[
+    // Daggers reward working around the target
+    (WeaponType::DualDaggers, AttackAngle::Flank, 1.3),
+    (WeaponType::DualDaggers, AttackAngle::Back, 1.8),
+    // Wide arcs lose less on glancing hits
+    (WeaponType::Greatsword, AttackAngle::Side, 0.85),
+    (WeaponType::Greatsword, AttackAngle::Flank, 0.85),
+];
+
 impl AttackAngle {
+    /// Baseline multiplier (the Sword's, and any weapon without an override)
     pub fn multiplier(&self) -> f32 {
         match self {
             Self::Front => 1.0,
-            Self::Side => 0.7,
+            Self::Side | Self::Flank => 0.7,
             Self::Back => 1.5,
         }
     }
 
+    /// Angle from its FFI id (0 Front, 1 Side, 2 Back, 3 Flank)
+    pub fn from_id(id: u32) -> Option<Self> {
+        match id {
+            0 => Some(Self::Front),
+            1 => Some(Self::Side),
+            2 => Some(Self::Back),
+            3 => Some(Self::Flank),
+            _ => None,
+        }
+    }
+
     /// Determine angle from attacker and target transforms
     pub fn from_transforms(attacker: &Transform, target: &Transform) -> Self {
         let to_attacker = (attacker.translation - target.translation).normalize();
@@ -82,12 +124,324 @@ impl AttackAngle {
             Self::Front
         } else if dot < -0.5 {
             Self::Back
+        } else if dot < -0.2 {
+            Self::Flank
         } else {
             Self::Side
         }
     }
 }
 
+/// Damage multiplier for hitting at `angle` with `weapon`
+pub fn angle_multiplier_for(weapon: WeaponType, angle: AttackAngle) -> f32 {
+    WEAPON_ANGLE_TABLE
+        .iter()
+        .find(|(w, a, _)| *w == weapon && *a == angle)
+        .map_or(angle.multiplier(), |(_, _, mult)| *mult)
+}
+
+/// Damage multiplier for a confirmed backstab by a dagger or stealthed
+/// attacker (replaces the Back angle bonus)
+pub const BACKSTAB_MULTIPLIER: f32 = 2.0;
+/// Default backstab cone used by hit resolution
+pub const BACKSTAB_CONE_DEGREES: f32 = 90.0;
+
+/// Horizontal facing direction for a yaw in radians (yaw 0 = -Z, Bevy forward)
+fn facing_dir(yaw: f32) -> Vec3 {
+    Vec3::new(-yaw.sin(), 0.0, -yaw.cos())
+}
+
+/// Yaw (radians) of a transform's forward direction on the horizontal plane
+pub fn facing_yaw(transform: &Transform) -> f32 {
+    let f = transform.forward();
+    f32::atan2(-f.x, -f.z)
+}
+
+/// Backstab check: the attacker must stand behind the defender (inside the
+/// cone at the defender's back) and be facing the defender (inside the same
+/// cone around the attacker's facing). Facings are yaw angles in radians.
+pub fn is_backstab(
+    attacker_facing: f32,
+    defender_facing: f32,
+    attacker_to_defender: Vec3,
+    cone_degrees: f32,
+) -> bool {
+    let dir = Vec3::new(attacker_to_defender.x, 0.0, attacker_to_defender.z).normalize_or_zero();
+    if dir == Vec3::ZERO {
+        return false;
+    }
+    let min_dot = (cone_degrees.clamp(0.0, 360.0) * 0.5).to_radians().cos();
+
+    // Defender faces away from the attacker, i.e. along the attack direction
+    let behind = facing_dir(defender_facing).dot(dir) >= min_dot;
+    let facing = facing_dir(attacker_facing).dot(dir) >= min_dot;
+    behind && facing
+}
+
+/// Side of the victim a hit came from, for damage indicators
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum HitDirection {
+    Front,
+    Left,
+    Right,
+    Back,
+}
+
+/// Direction of `attacker_pos` relative to a victim at `victim_pos` facing
+/// `victim_facing` (yaw, radians), split into four 90° quadrants on the
+/// horizontal plane. An attacker on top of the victim reads as Front.
+pub fn hit_direction(victim_facing: f32, attacker_pos: Vec3, victim_pos: Vec3) -> HitDirection {
+    let to_attacker = attacker_pos - victim_pos;
+    let dir = Vec3::new(to_attacker.x, 0.0, to_attacker.z).normalize_or_zero();
+    if dir == Vec3::ZERO {
+        return HitDirection::Front;
+    }
+    let forward = facing_dir(victim_facing).dot(dir);
+    // Bevy right (+X at yaw 0), rotated by the same yaw as `facing_dir`
+    let right = Vec3::new(victim_facing.cos(), 0.0, -victim_facing.sin()).dot(dir);
+
+    if forward.abs() >= right.abs() {
+        if forward >= 0.0 {
+            HitDirection::Front
+        } else {
+            HitDirection::Back
+        }
+    } else if right > 0.0 {
+        HitDirection::Right
+    } else {
+        HitDirection::Left
+    }
+}
+
+/// Attacker is hidden; its backstabs get `BACKSTAB_MULTIPLIER` whatever
+/// the weapon. Present while the entity has a `StatusType::Stealthed`
+/// effect (see `status::sync_stealth`)
+#[derive(Component, Debug, Clone, Copy, Default)]
+pub struct Stealthed;
+
+/// Angle multiplier for a (non-stealthed) Sword hit
+pub fn hit_angle_multiplier(attacker: &Transform, target: &Transform) -> f32 {
+    hit_angle_multiplier_for(attacker, target, WeaponType::Sword, false)
+}
+
+/// Angle multiplier from `weapon`'s angle table, upgraded to the backstab
+/// bonus when a dagger or `stealthed` attacker lands a backstab
+pub fn hit_angle_multiplier_for(
+    attacker: &Transform,
+    target: &Transform,
+    weapon: WeaponType,
+    stealthed: bool,
+) -> f32 {
+    let backstab = (stealthed || weapon == WeaponType::DualDaggers)
+        && is_backstab(
+            facing_yaw(attacker),
+            facing_yaw(target),
+            target.translation - attacker.translation,
+            BACKSTAB_CONE_DEGREES,
+        );
+    if backstab {
+        BACKSTAB_MULTIPLIER
+    } else {
+        angle_multiplier_for(weapon, AttackAngle::from_transforms(attacker, target))
+    }
+}
+
+/// Whether a hit landed at melee range or from a distance
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum HitRange {
+    Melee,
+    Ranged,
+}
+
+/// Thorns: reflects a share of incoming melee damage back to the attacker
+#[derive(Component, Debug, Clone, Copy)]
+pub struct Thorns {
+    pub pct: f32,
+}
+
+/// Upper bound on reflected share so thorns can't out-damage the attacker
+const THORNS_MAX_PCT: f32 = 0.5;
+
+/// Damage reflected to the attacker by a thorns defender
+pub fn thorns_reflect(incoming_melee: f32, thorns_pct: f32) -> f32 {
+    incoming_melee.max(0.0) * thorns_pct.clamp(0.0, THORNS_MAX_PCT)
+}
+
+/// Thorns reflection for a resolved hit — ranged hits are never reflected
+pub fn thorns_for_hit(range: HitRange, incoming: f32, thorns_pct: f32) -> f32 {
+    match range {
+        HitRange::Melee => thorns_reflect(incoming, thorns_pct),
+        HitRange::Ranged => 0.0,
+    }
+}
+
+/// Impact damage at the center of a slam from ground level
+pub const GROUND_SLAM_BASE_DAMAGE: f32 = 40.0;
+/// Fall height (units) that adds one `base_radius` to the slam radius
+const GROUND_SLAM_HEIGHT_PER_RADIUS: f32 = 10.0;
+/// Largest slam radius, as a multiple of `base_radius`
+const GROUND_SLAM_MAX_RADIUS_SCALE: f32 = 3.0;
+/// Share of center damage dealt at the very edge of the slam
+const GROUND_SLAM_EDGE_FACTOR: f32 = 0.3;
+
+/// Ground slam from a dive landing at `impact_pos` after falling
+/// `fall_height`. Radius and center damage both grow with height (radius
+/// capped at `GROUND_SLAM_MAX_RADIUS_SCALE`, damage by the same height bonus
+/// as a dive attack); damage falls off linearly with
+/// horizontal distance. Returns `(target id, damage)` for every target inside
+/// the radius, in input order.
+pub fn ground_slam(
+    fall_height: f32,
+    impact_pos: Vec3,
+    targets: &[(u64, Vec3)],
+    base_radius: f32,
+) -> Vec<(u64, f32)> {
+    let height = fall_height.max(0.0);
+    let radius = base_radius.max(0.0)
+        * (1.0 + height / GROUND_SLAM_HEIGHT_PER_RADIUS).min(GROUND_SLAM_MAX_RADIUS_SCALE);
+    if radius <= 0.0 {
+        return Vec::new();
+    }
+    let center_damage = GROUND_SLAM_BASE_DAMAGE * (1.0 + crate::aerial::dive_height_bonus(height));
+
+    targets
+        .iter()
+        .filter_map(|&(id, pos)| {
+            let distance = Vec2::new(pos.x - impact_pos.x, pos.z - impact_pos.z).length();
+            (distance <= radius).then(|| {
+                let falloff = 1.0 - (distance / radius) * (1.0 - GROUND_SLAM_EDGE_FACTOR);
+                (id, center_damage * falloff)
+            })
+        })
+        .collect()
+}
+
+/// Punish window opened on a target whose guard was just broken
+#[derive(Component, Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GuardBreakState {
+    /// Game time (ms) until which the target takes bonus damage
+    pub vulnerable_until: u64,
+    /// Whether the guard is currently down (blocking with no energy left);
+    /// the window only opens when this goes from false to true
+    #[serde(default)]
+    pub guard_down: bool,
+}
+
+/// Length of the punish window after a guard break
+pub const GUARD_BREAK_WINDOW_MS: u64 = 1500;
+/// Damage multiplier for hits landed inside the punish window
+pub const GUARD_BREAK_BONUS: f32 = 1.5;
+
+impl GuardBreakState {
+    /// Open (or refresh) the punish window starting at `now`
+    pub fn break_guard(&mut self, now: u64) {
+        self.vulnerable_until = now + GUARD_BREAK_WINDOW_MS;
+    }
+
+    /// Record whether the guard is down for a hit at `now`, opening the
+    /// punish window only on the hit that breaks it. Returns true if opened.
+    pub fn update_guard(&mut self, broken: bool, now: u64) -> bool {
+        let opened = broken && !self.guard_down;
+        if opened {
+            self.break_guard(now);
+        }
+        self.guard_down = broken;
+        opened
+    }
+
+    pub fn is_vulnerable(&self, now: u64) -> bool {
+        now < self.vulnerable_until
+    }
+
+    /// Damage multiplier for a hit landing at `now`
+    pub fn damage_multiplier(&self, now: u64) -> f32 {
+        if self.is_vulnerable(now) {
+            GUARD_BREAK_BONUS
+        } else {
+            1.0
+        }
+    }
+}
+
+/// Ultimate meter for builds that charge through combat instead of cooldown
+#[derive(Component, Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct UltimateCharge {
+    pub charge: f32,
+}
+
+/// Charge at which the ultimate is ready
+pub const ULTIMATE_CHARGE_MAX: f32 = 100.0;
+/// Charge gained per point of damage dealt
+pub const CHARGE_PER_DAMAGE_DEALT: f32 = 0.05;
+/// Charge gained per point of damage taken (tanks charge by absorbing hits)
+pub const CHARGE_PER_DAMAGE_TAKEN: f32 = 0.1;
+
+impl UltimateCharge {
+    pub fn add_from_damage(&mut self, dealt: f32) {
+        self.add(dealt.max(0.0) * CHARGE_PER_DAMAGE_DEALT);
+    }
+
+    pub fn add_from_taken(&mut self, taken: f32) {
+        self.add(taken.max(0.0) * CHARGE_PER_DAMAGE_TAKEN);
+    }
+
+    fn add(&mut self, amount: f32) {
+        self.charge = (self.charge + amount).min(ULTIMATE_CHARGE_MAX);
+    }
+
+    pub fn is_ready(&self) -> bool {
+        self.charge >= ULTIMATE_CHARGE_MAX
+    }
+
+    /// Spend the full meter; returns false (and keeps the charge) if not ready
+    pub fn consume(&mut self) -> bool {
+        if !self.is_ready() {
+            return false;
+        }
+        self.charge = 0.0;
+        true
+    }
+}
+
+/// Fight-length pressure: once a fight runs past `enrage_at` seconds the
+/// monster's damage ramps up until it hits `ENRAGE_MAX_MULTIPLIER`
+#[derive(Component, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct EnrageTimer {
+    /// Seconds since the fight started
+    pub elapsed: f32,
+    /// Seconds after which the monster starts enraging
+    pub enrage_at: f32,
+}
+
+/// Extra damage multiplier gained per second spent enraged
+pub const ENRAGE_RAMP_PER_SEC: f32 = 0.05;
+/// Highest damage multiplier an enrage can reach
+pub const ENRAGE_MAX_MULTIPLIER: f32 = 3.0;
+
+impl EnrageTimer {
+    pub fn new(enrage_at: f32) -> Self {
+        Self {
+            elapsed: 0.0,
+            enrage_at,
+        }
+    }
+
+    pub fn tick(&mut self, dt: f32) {
+        self.elapsed += dt.max(0.0);
+    }
+
+    pub fn is_enraged(&self) -> bool {
+        self.elapsed > self.enrage_at
+    }
+
+    /// 1.0 until the threshold, then +`ENRAGE_RAMP_PER_SEC` per second,
+    /// capped at `ENRAGE_MAX_MULTIPLIER`
+    pub fn damage_multiplier(&self) -> f32 {
+        let over = (self.elapsed - self.enrage_at).max(0.0);
+        (1.0 + over * ENRAGE_RAMP_PER_SEC).min(ENRAGE_MAX_MULTIPLIER)
+    }
+}
+
 /// Combat state component attached to fighters
 #[derive(Component, Debug)]
 pub struct CombatState {
@@ -176,6 +530,157 @@ mod tests {
         assert!((AttackAngle::Back.multiplier() - 1.5).abs() < f32::EPSILON);
     }
 
+    #[test]
+    fn test_dagger_back_attack_beats_sword() {
+        let sword = angle_multiplier_for(WeaponType::Sword, AttackAngle::Back);
+        let dagger = angle_multiplier_for(WeaponType::DualDaggers, AttackAngle::Back);
+        assert!(dagger > sword);
+        assert_eq!(
+            angle_multiplier_for(WeaponType::DualDaggers, AttackAngle::Flank),
+            1.3
+        );
+    }
+
+    #[test]
+    fn test_weapon_without_overrides_uses_defaults() {
+        for angle in [
+            AttackAngle::Front,
+            AttackAngle::Side,
+            AttackAngle::Flank,
+            AttackAngle::Back,
+        ] {
+            assert_eq!(
+                angle_multiplier_for(WeaponType::Sword, angle),
+                angle.multiplier()
+            );
+            assert_eq!(
+                angle_multiplier_for(WeaponType::Staff, angle),
+                angle.multiplier()
+            );
+        }
+    }
+
+    #[test]
+    fn test_flank_between_side_and_back() {
+        let target = Transform::from_xyz(0.0, 0.0, 0.0); // facing -Z
+        let side = Transform::from_xyz(2.0, 0.0, 0.0);
+        let flank = Transform::from_xyz(2.0, 0.0, 1.0);
+        let back = Transform::from_xyz(0.0, 0.0, 2.0);
+        assert_eq!(
+            AttackAngle::from_transforms(&side, &target),
+            AttackAngle::Side
+        );
+        assert_eq!(
+            AttackAngle::from_transforms(&flank, &target),
+            AttackAngle::Flank
+        );
+        assert_eq!(
+            AttackAngle::from_transforms(&back, &target),
+            AttackAngle::Back
+        );
+    }
+
+    #[test]
+    fn test_hit_direction_back_and_left() {
+        // Victim at origin facing -Z
+        assert_eq!(
+            hit_direction(0.0, Vec3::new(0.0, 0.0, 3.0), Vec3::ZERO),
+            HitDirection::Back
+        );
+        assert_eq!(
+            hit_direction(0.0, Vec3::new(0.0, 0.0, -3.0), Vec3::ZERO),
+            HitDirection::Front
+        );
+        assert_eq!(
+            hit_direction(0.0, Vec3::new(-3.0, 1.0, 0.5), Vec3::ZERO),
+            HitDirection::Left
+        );
+        assert_eq!(
+            hit_direction(0.0, Vec3::new(3.0, 0.0, 0.0), Vec3::ZERO),
+            HitDirection::Right
+        );
+    }
+
+    #[test]
+    fn test_hit_direction_follows_victim_facing() {
+        let victim = Vec3::new(5.0, 0.0, 5.0);
+        let attacker = victim + Vec3::new(0.0, 0.0, 3.0);
+        // Turned around (facing +Z) the same attacker is in front
+        assert_eq!(
+            hit_direction(std::f32::consts::PI, attacker, victim),
+            HitDirection::Front
+        );
+        // Facing -X (yaw +90°): +Z is on the victim's left
+        let yaw = facing_yaw(&Transform::IDENTITY.looking_to(Vec3::NEG_X, Vec3::Y));
+        assert_eq!(hit_direction(yaw, attacker, victim), HitDirection::Left);
+        assert_eq!(
+            hit_direction(yaw, victim - Vec3::new(0.0, 0.0, 3.0), victim),
+            HitDirection::Right
+        );
+    }
+
+    #[test]
+    fn test_backstab_from_directly_behind() {
+        // Defender at origin facing -Z; attacker behind at +Z facing -Z too
+        let to_defender = Vec3::new(0.0, 0.0, -2.0);
+        assert!(is_backstab(0.0, 0.0, to_defender, 60.0));
+    }
+
+    #[test]
+    fn test_backstab_not_from_front() {
+        // Attacker at -Z facing +Z, defender facing -Z (toward the attacker)
+        let to_defender = Vec3::new(0.0, 0.0, 2.0);
+        let attacker_yaw = std::f32::consts::PI;
+        assert!(!is_backstab(attacker_yaw, 0.0, to_defender, 60.0));
+        // Behind the defender but facing away from them
+        assert!(!is_backstab(
+            attacker_yaw,
+            0.0,
+            Vec3::new(0.0, 0.0, -2.0),
+            60.0
+        ));
+    }
+
+    #[test]
+    fn test_backstab_cone_width() {
+        // Approach 40 degrees off the defender's back
+        let off = 40f32.to_radians();
+        let to_defender = facing_dir(0.0) * off.cos() + Vec3::X * off.sin();
+        let attacker_yaw = facing_yaw(&Transform::IDENTITY.looking_to(to_defender, Vec3::Y));
+
+        assert!(!is_backstab(attacker_yaw, 0.0, to_defender, 60.0));
+        assert!(is_backstab(attacker_yaw, 0.0, to_defender, 120.0));
+    }
+
+    #[test]
+    fn test_hit_angle_multiplier_backstab() {
+        let target = Transform::from_xyz(0.0, 0.0, 0.0);
+        let attacker = Transform::from_xyz(0.0, 0.0, 2.0); // behind, facing -Z
+        assert_eq!(
+            hit_angle_multiplier_for(&attacker, &target, WeaponType::DualDaggers, false),
+            BACKSTAB_MULTIPLIER
+        );
+        assert_eq!(
+            hit_angle_multiplier_for(&attacker, &target, WeaponType::Greatsword, true),
+            BACKSTAB_MULTIPLIER
+        );
+        // Other weapons keep their own Back multiplier
+        assert_eq!(
+            hit_angle_multiplier(&attacker, &target),
+            AttackAngle::Back.multiplier()
+        );
+        assert_eq!(
+            hit_angle_multiplier_for(&attacker, &target, WeaponType::Greatsword, false),
+            angle_multiplier_for(WeaponType::Greatsword, AttackAngle::Back)
+        );
+
+        let front = Transform::from_xyz(0.0, 0.0, -2.0).looking_at(Vec3::ZERO, Vec3::Y);
+        assert_eq!(
+            hit_angle_multiplier(&front, &target),
+            AttackAngle::Front.multiplier()
+        );
+    }
+
     #[test]
     fn test_execution_quality() {
         let perfect = ExecutionQuality(1.0);
@@ -186,4 +691,172 @@ mod tests {
         assert!((miss.damage_multiplier() - 1.0).abs() < f32::EPSILON);
         assert_eq!(miss.stun_duration_ms(), 0);
     }
+
+    #[test]
+    fn test_thorns_reflects_melee() {
+        let reflected = thorns_for_hit(HitRange::Melee, 100.0, 0.2);
+        assert!((reflected - 20.0).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn test_thorns_ignores_ranged() {
+        assert_eq!(thorns_for_hit(HitRange::Ranged, 100.0, 0.2), 0.0);
+    }
+
+    #[test]
+    fn test_thorns_zero_pct() {
+        assert_eq!(thorns_reflect(100.0, 0.0), 0.0);
+        assert_eq!(thorns_for_hit(HitRange::Melee, 100.0, 0.0), 0.0);
+    }
+
+    #[test]
+    fn test_guard_break_window_bonus() {
+        let mut state = GuardBreakState::default();
+        state.break_guard(10_000);
+
+        assert!(state.is_vulnerable(10_500));
+        assert_eq!(state.damage_multiplier(10_500), GUARD_BREAK_BONUS);
+        assert_eq!(100.0 * state.damage_multiplier(10_500), 150.0);
+    }
+
+    #[test]
+    fn test_guard_break_hit_after_window_normal() {
+        let mut state = GuardBreakState::default();
+        state.break_guard(10_000);
+        assert_eq!(state.damage_multiplier(12_000), 1.0);
+        assert_eq!(GuardBreakState::default().damage_multiplier(0), 1.0);
+    }
+
+    #[test]
+    fn test_guard_break_opens_only_when_guard_first_breaks() {
+        let mut state = GuardBreakState::default();
+        assert!(state.update_guard(true, 1_000));
+        assert!(!state.update_guard(true, 2_000));
+        assert_eq!(state.vulnerable_until, 1_000 + GUARD_BREAK_WINDOW_MS);
+
+        // Recovering then breaking again opens a fresh window
+        assert!(!state.update_guard(false, 3_000));
+        assert!(state.update_guard(true, 4_000));
+        assert_eq!(state.vulnerable_until, 4_000 + GUARD_BREAK_WINDOW_MS);
+    }
+
+    #[test]
+    fn test_guard_break_window_expires_on_schedule() {
+        let mut state = GuardBreakState::default();
+        state.break_guard(5_000);
+        assert!(state.is_vulnerable(5_000 + GUARD_BREAK_WINDOW_MS - 1));
+        assert!(!state.is_vulnerable(5_000 + GUARD_BREAK_WINDOW_MS));
+    }
+
+    #[test]
+    fn test_thorns_capped() {
+        let reflected = thorns_reflect(100.0, 3.0);
+        assert!((reflected - 100.0 * THORNS_MAX_PCT).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn test_ultimate_charge_builds_from_combat() {
+        let mut meter = UltimateCharge::default();
+        meter.add_from_damage(200.0);
+        assert!((meter.charge - 200.0 * CHARGE_PER_DAMAGE_DEALT).abs() < 0.001);
+        let after_dealt = meter.charge;
+        meter.add_from_taken(50.0);
+        assert!((meter.charge - after_dealt - 50.0 * CHARGE_PER_DAMAGE_TAKEN).abs() < 0.001);
+        assert!(!meter.is_ready());
+    }
+
+    #[test]
+    fn test_ultimate_charge_ready_and_consume() {
+        let mut meter = UltimateCharge::default();
+        assert!(!meter.consume());
+
+        meter.add_from_damage(ULTIMATE_CHARGE_MAX / CHARGE_PER_DAMAGE_DEALT);
+        assert!(meter.is_ready());
+        assert!(meter.consume());
+        assert_eq!(meter.charge, 0.0);
+        assert!(!meter.is_ready());
+    }
+
+    #[test]
+    fn test_ultimate_charge_caps_at_full() {
+        let mut meter = UltimateCharge::default();
+        meter.add_from_taken(1_000_000.0);
+        assert_eq!(meter.charge, ULTIMATE_CHARGE_MAX);
+        meter.add_from_damage(-500.0);
+        assert_eq!(meter.charge, ULTIMATE_CHARGE_MAX);
+    }
+
+    #[test]
+    fn test_enrage_inactive_before_threshold() {
+        let mut timer = EnrageTimer::new(120.0);
+        assert_eq!(timer.damage_multiplier(), 1.0);
+        timer.tick(119.0);
+        assert_eq!(timer.damage_multiplier(), 1.0);
+        assert!(!timer.is_enraged());
+    }
+
+    #[test]
+    fn test_enrage_ramps_with_time() {
+        let mut timer = EnrageTimer::new(120.0);
+        timer.tick(130.0);
+        let early = timer.damage_multiplier();
+        timer.tick(10.0);
+        let later = timer.damage_multiplier();
+        assert!(timer.is_enraged());
+        assert!((early - 1.5).abs() < 0.001);
+        assert!(later > early);
+    }
+
+    #[test]
+    fn test_enrage_caps_at_max() {
+        let mut timer = EnrageTimer::new(60.0);
+        timer.tick(10_000.0);
+        assert_eq!(timer.damage_multiplier(), ENRAGE_MAX_MULTIPLIER);
+    }
+
+    fn slam_targets() -> Vec<(u64, Vec3)> {
+        (1..=10)
+            .map(|i| (i as u64, Vec3::new(i as f32, 0.0, 0.0)))
+            .collect()
+    }
+
+    #[test]
+    fn test_higher_dive_slams_wider() {
+        let low = ground_slam(5.0, Vec3::ZERO, &slam_targets(), 3.0);
+        let high = ground_slam(20.0, Vec3::ZERO, &slam_targets(), 3.0);
+        assert!(high.len() > low.len());
+        // Capped radius: absurd falls don't reach the whole floor
+        let huge = ground_slam(10_000.0, Vec3::ZERO, &slam_targets(), 3.0);
+        assert_eq!(huge.len(), 9);
+    }
+
+    #[test]
+    fn test_slam_damage_is_capped() {
+        let center = |height: f32| ground_slam(height, Vec3::ZERO, &[(1, Vec3::ZERO)], 3.0)[0].1;
+        assert!(center(20.0) > center(0.0));
+        // Same cap as a dive: absurd falls don't one-shot
+        assert_eq!(center(10_000.0), center(1_000_000.0));
+        assert!(center(1_000_000.0) <= GROUND_SLAM_BASE_DAMAGE * 3.0);
+    }
+
+    #[test]
+    fn test_slam_damage_falls_off_from_impact() {
+        let hits = ground_slam(10.0, Vec3::new(0.0, 5.0, 0.0), &slam_targets(), 3.0);
+        let damage: Vec<f32> = hits.iter().map(|(_, d)| *d).collect();
+        assert!(damage.windows(2).all(|w| w[0] > w[1]), "{damage:?}");
+        let edge = *damage.last().unwrap();
+        assert!(edge >= damage[0] * GROUND_SLAM_EDGE_FACTOR);
+    }
+
+    #[test]
+    fn test_zero_height_slam_is_minimal() {
+        let flat = ground_slam(0.0, Vec3::ZERO, &slam_targets(), 2.0);
+        assert_eq!(
+            flat.iter().map(|(id, _)| *id).collect::<Vec<_>>(),
+            vec![1, 2]
+        );
+        assert!(flat[0].1 <= GROUND_SLAM_BASE_DAMAGE);
+        assert!(ground_slam(-3.0, Vec3::ZERO, &slam_targets(), 2.0).len() <= flat.len());
+        assert!(ground_slam(10.0, Vec3::ZERO, &slam_targets(), 0.0).is_empty());
+    }
 }
@@ -0,0 +1,123 @@
+//! Conditional on-hit procs granted by gear.
+//!
+//! Each `Proc` fires on one kind of `CombatEvent` with a fixed chance. Rolls
+//! are derived from a per-hit hash (plus the proc's slot in the table), so
+//! server and client agree on which procs fired without sharing RNG state.
+
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Sha3_256};
+
+/// Combat moments a proc can trigger on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum CombatEvent {
+    OnHit,
+    OnCrit,
+    OnKill,
+    OnHitTaken,
+    OnBlock,
+}
+
+/// What happens when a proc fires
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProcEffect {
+    /// e.g. "Chain Lightning"
+    pub name: String,
+    pub damage: f32,
+    /// Element of the proc damage, None for physical
+    pub element: Option<String>,
+}
+
+/// A chance to trigger an effect on a combat event
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Proc {
+    pub trigger: CombatEvent,
+    /// 0.0-1.0
+    pub chance: f32,
+    pub effect: ProcEffect,
+}
+
+/// All procs an entity's gear grants
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ProcTable {
+    pub procs: Vec<Proc>,
+}
+
+impl ProcTable {
+    /// Effects of every proc that fires for `on_event`, in table order.
+    /// `hash` identifies the hit (e.g. attacker, target and tick mixed).
+    pub fn roll_procs(&self, on_event: CombatEvent, hash: u64) -> Vec<ProcEffect> {
+        self.procs
+            .iter()
+            .enumerate()
+            .filter(|(_, p)| p.trigger == on_event)
+            .filter(|(slot, p)| proc_roll(hash, *slot as u32) < p.chance)
+            .map(|(_, p)| p.effect.clone())
+            .collect()
+    }
+}
+
+/// Uniform roll in [0, 1) for one proc slot on one hit
+fn proc_roll(hash: u64, slot: u32) -> f32 {
+    let mut hasher = Sha3_256::new();
+    hasher.update(b"proc");
+    hasher.update(hash.to_le_bytes());
+    hasher.update(slot.to_le_bytes());
+    let result = hasher.finalize();
+    let value = u64::from_le_bytes(result[0..8].try_into().unwrap());
+    (value >> 40) as f32 / (1u64 << 24) as f32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lightning(chance: f32) -> Proc {
+        Proc {
+            trigger: CombatEvent::OnHit,
+            chance,
+            effect: ProcEffect {
+                name: "Chain Lightning".into(),
+                damage: 40.0,
+                element: Some("wind".into()),
+            },
+        }
+    }
+
+    #[test]
+    fn test_certain_proc_always_fires() {
+        let table = ProcTable {
+            procs: vec![lightning(1.0)],
+        };
+        for hash in 0..500 {
+            let fired = table.roll_procs(CombatEvent::OnHit, hash);
+            assert_eq!(fired.len(), 1);
+            assert_eq!(fired[0].name, "Chain Lightning");
+        }
+        // Wrong event never fires
+        assert!(table.roll_procs(CombatEvent::OnKill, 7).is_empty());
+    }
+
+    #[test]
+    fn test_zero_chance_never_fires() {
+        let table = ProcTable {
+            procs: vec![lightning(0.0)],
+        };
+        assert!((0..500).all(|h| table.roll_procs(CombatEvent::OnHit, h).is_empty()));
+    }
+
+    #[test]
+    fn test_rolls_deterministic_from_hash() {
+        let table = ProcTable {
+            procs: vec![lightning(0.1), lightning(0.5)],
+        };
+        for hash in 0..200 {
+            assert_eq!(
+                table.roll_procs(CombatEvent::OnHit, hash),
+                table.roll_procs(CombatEvent::OnHit, hash)
+            );
+        }
+        // Roughly 10% of hits fire the first proc
+        let fired = (0..2000u64).filter(|&h| proc_roll(h, 0) < 0.1).count();
+        assert!((120..280).contains(&fired), "fired {fired}");
+    }
+}
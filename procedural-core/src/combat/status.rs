@@ -31,14 +31,16 @@ pub enum StatusType {
     Shielded,      // damage absorption
     Regenerating,  // heal over time
     SemanticFocus, // enhanced semantic abilities
+    Stealthed,     // hidden: backstabs with any weapon
 }
 
 /// A single status effect instance
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StatusEffect {
     pub effect_type: StatusType,
     pub remaining: f32, // seconds remaining
     pub strength: f32,  // effect intensity (0.0 - 1.0)
+    #[serde(skip)]
     pub source: Option<Entity>,
     pub stacks: u32, // stackable effects
     pub max_stacks: u32,
@@ -136,21 +138,7 @@ pub struct StatusEffects {
 impl StatusEffects {
     /// Apply a new status effect, stacking if applicable
     pub fn apply(&mut self, effect: StatusEffect) {
-        if let Some(existing) = self
-            .effects
-            .iter_mut()
-            .find(|e| e.effect_type == effect.effect_type)
-        {
-            // Refresh duration
-            existing.remaining = existing.remaining.max(effect.remaining);
-            // Add stacks
-            if existing.stacks < existing.max_stacks {
-                existing.stacks += 1;
-                existing.strength = existing.strength.max(effect.strength);
-            }
-        } else {
-            self.effects.push(effect);
-        }
+        apply_status(&mut self.effects, effect);
     }
 
     /// Remove all effects of a type
@@ -214,6 +202,114 @@ impl StatusEffects {
     }
 }
 
+/// Add `effect` to a list of active effects. An effect of a kind already
+/// present refreshes the longer duration, keeps the higher strength and
+/// adds a stack (up to `max_stacks`) instead of being listed twice.
+pub fn apply_status(effects: &mut Vec<StatusEffect>, effect: StatusEffect) {
+    if let Some(existing) = effects
+        .iter_mut()
+        .find(|e| e.effect_type == effect.effect_type)
+    {
+        existing.remaining = existing.remaining.max(effect.remaining);
+        existing.strength = existing.strength.max(effect.strength);
+        if existing.stacks < existing.max_stacks {
+            existing.stacks += 1;
+        }
+    } else {
+        effects.push(effect);
+    }
+}
+
+/// Advance every effect by `dt` seconds and return the damage-over-time
+/// dealt this tick. Effects expiring mid-tick only deal damage for their
+/// remaining time, and are removed afterwards.
+pub fn resolve_status_ticks(effects: &mut Vec<StatusEffect>, dt: f32) -> f32 {
+    let dt = dt.max(0.0);
+    let mut damage = 0.0;
+    for effect in effects.iter_mut() {
+        damage += effect.dps() * dt.min(effect.remaining.max(0.0));
+        effect.remaining -= dt;
+    }
+    effects.retain(|e| !e.is_expired());
+    damage
+}
+
+/// A single heal-over-time applied by a support ability
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HealOverTime {
+    pub heal_per_sec: f32,
+    pub remaining: f32, // seconds remaining
+}
+
+impl HealOverTime {
+    pub fn new(heal_per_sec: f32, duration: f32) -> Self {
+        Self {
+            heal_per_sec,
+            remaining: duration,
+        }
+    }
+
+    pub fn is_expired(&self) -> bool {
+        self.remaining <= 0.0
+    }
+}
+
+/// Component: active HoTs on an entity. Unlike status effects, HoTs from
+/// different casters stack additively rather than refreshing each other.
+#[derive(Component, Debug, Clone, Serialize, Deserialize)]
+pub struct HotStack {
+    pub hots: Vec<HealOverTime>,
+    /// Floor healing multiplier (`MutatorEffects::healing_mult`; 0 under NoHealing)
+    pub healing_mult: f32,
+}
+
+impl Default for HotStack {
+    fn default() -> Self {
+        Self {
+            hots: Vec::new(),
+            healing_mult: 1.0,
+        }
+    }
+}
+
+impl HotStack {
+    pub fn with_healing_mult(mut self, healing_mult: f32) -> Self {
+        self.healing_mult = healing_mult.max(0.0);
+        self
+    }
+
+    pub fn apply(&mut self, hot: HealOverTime) {
+        self.hots.push(hot);
+    }
+
+    /// Advance all HoTs by `dt` seconds, returning the heal for this tick.
+    /// A HoT expiring mid-tick only heals for its remaining time.
+    pub fn tick(&mut self, dt: f32) -> f32 {
+        let mut heal = 0.0;
+        for hot in &mut self.hots {
+            heal += hot.heal_per_sec * dt.min(hot.remaining);
+            hot.remaining -= dt;
+        }
+        self.hots.retain(|h| !h.is_expired());
+        heal * self.healing_mult
+    }
+}
+
+/// System: tick HoT stacks and heal their owners
+pub fn tick_hot_stacks(
+    time: Res<Time>,
+    mut query: Query<(&mut HotStack, &mut super::hitbox::Health)>,
+) {
+    let dt = time.delta_secs();
+
+    for (mut stack, mut health) in &mut query {
+        let heal = stack.tick(dt);
+        if heal > 0.0 {
+            health.current = (health.current + heal).min(health.max);
+        }
+    }
+}
+
 /// System: tick status effect timers and apply DoT/HoT
 pub fn tick_status_effects(
     time: Res<Time>,
@@ -222,25 +318,35 @@ pub fn tick_status_effects(
     let dt = time.delta_secs();
 
     for (mut statuses, mut health) in &mut query {
-        // Apply damage over time
-        let dot = statuses.total_dot();
-        if dot > 0.0 {
-            let dot_damage = dot * dt;
+        // Healing over time, read before timers advance
+        let hot = statuses.total_hot();
+
+        // Apply damage over time, tick timers and remove expired
+        let dot_damage = resolve_status_ticks(&mut statuses.effects, dt);
+        if dot_damage > 0.0 {
             health.current = (health.current - dot_damage).max(0.0);
         }
 
-        // Apply healing over time
-        let hot = statuses.total_hot();
         if hot > 0.0 {
             let heal = hot * dt;
             health.current = (health.current + heal).min(health.max);
         }
+    }
+}
 
-        // Tick timers and remove expired
-        for effect in &mut statuses.effects {
-            effect.remaining -= dt;
+/// System: keep the `Stealthed` marker in step with the stealth status, so
+/// hits land as backstabs only while the effect lasts
+pub fn sync_stealth(
+    mut commands: Commands,
+    query: Query<(Entity, &StatusEffects, Has<super::Stealthed>)>,
+) {
+    for (entity, statuses, marked) in &query {
+        let hidden = statuses.has(StatusType::Stealthed);
+        if hidden && !marked {
+            commands.entity(entity).insert(super::Stealthed);
+        } else if !hidden && marked {
+            commands.entity(entity).remove::<super::Stealthed>();
         }
-        statuses.effects.retain(|e| !e.is_expired());
     }
 }
 
@@ -280,6 +386,57 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_same_kind_refreshes_and_keeps_max_strength() {
+        let mut effects = Vec::new();
+        apply_status(
+            &mut effects,
+            StatusEffect::new(StatusType::Poisoned, 2.0, 0.9),
+        );
+        resolve_status_ticks(&mut effects, 1.5);
+        apply_status(
+            &mut effects,
+            StatusEffect::new(StatusType::Poisoned, 4.0, 0.3),
+        );
+
+        assert_eq!(effects.len(), 1);
+        assert!((effects[0].remaining - 4.0).abs() < 0.001);
+        assert!((effects[0].strength - 0.9).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_status_ticks_expire_effects() {
+        let mut effects = vec![
+            StatusEffect::new(StatusType::Bleeding, 1.0, 1.0),
+            StatusEffect::new(StatusType::Burning, 3.0, 1.0),
+        ];
+        resolve_status_ticks(&mut effects, 1.0);
+        assert_eq!(effects.len(), 1);
+        assert_eq!(effects[0].effect_type, StatusType::Burning);
+
+        resolve_status_ticks(&mut effects, 5.0);
+        assert!(effects.is_empty());
+        assert_eq!(resolve_status_ticks(&mut effects, 1.0), 0.0);
+    }
+
+    #[test]
+    fn test_status_ticks_accumulate_damage() {
+        let mut effects = vec![
+            StatusEffect::new(StatusType::Burning, 10.0, 1.0),
+            StatusEffect::new(StatusType::Poisoned, 10.0, 1.0),
+            StatusEffect::new(StatusType::Slowed, 10.0, 1.0),
+        ];
+        // 8 + 5 per second, Slowed deals nothing
+        let total: f32 = (0..4)
+            .map(|_| resolve_status_ticks(&mut effects, 0.5))
+            .sum();
+        assert!((total - 26.0).abs() < 0.001);
+
+        // An effect running out mid-tick only deals its remaining time
+        let mut short = vec![StatusEffect::new(StatusType::Burning, 0.25, 1.0)];
+        assert!((resolve_status_ticks(&mut short, 1.0) - 2.0).abs() < 0.001);
+    }
+
     #[test]
     fn test_can_act() {
         let mut statuses = StatusEffects::default();
@@ -346,4 +503,51 @@ mod tests {
         let effect = status_from_element("earth", 0.5);
         assert!(effect.is_none());
     }
+
+    #[test]
+    fn test_hot_heals_per_tick() {
+        let mut stack = HotStack::default();
+        stack.apply(HealOverTime::new(10.0, 3.0));
+
+        assert!((stack.tick(1.0) - 10.0).abs() < 0.001);
+        assert!((stack.tick(1.0) - 10.0).abs() < 0.001);
+        assert!((stack.tick(1.0) - 10.0).abs() < 0.001);
+        assert!(stack.hots.is_empty());
+    }
+
+    #[test]
+    fn test_hot_stacks_sum() {
+        let mut stack = HotStack::default();
+        stack.apply(HealOverTime::new(10.0, 5.0));
+        stack.apply(HealOverTime::new(4.0, 5.0));
+
+        assert!((stack.tick(0.5) - 7.0).abs() < 0.001);
+        assert_eq!(stack.hots.len(), 2);
+    }
+
+    #[test]
+    fn test_hot_no_healing_mutator() {
+        // NoHealing sets the floor's healing_mult to 0
+        let fx = crate::mutators::MutatorEffects {
+            healing_mult: 0.0,
+            ..Default::default()
+        };
+        let mut stack = HotStack::default().with_healing_mult(fx.healing_mult);
+        stack.apply(HealOverTime::new(10.0, 5.0));
+        stack.apply(HealOverTime::new(20.0, 5.0));
+
+        assert_eq!(stack.tick(1.0), 0.0);
+    }
+
+    #[test]
+    fn test_hot_expires() {
+        let mut stack = HotStack::default();
+        stack.apply(HealOverTime::new(10.0, 1.5));
+
+        assert!((stack.tick(1.0) - 10.0).abs() < 0.001);
+        // Only 0.5s left — partial heal, then gone
+        assert!((stack.tick(1.0) - 5.0).abs() < 0.001);
+        assert!(stack.hots.is_empty());
+        assert_eq!(stack.tick(1.0), 0.0);
+    }
 }
@@ -0,0 +1,277 @@
+//! Threat (aggro) tracking for monsters.
+//!
+//! Every damaging or supportive action adds threat to the acting player in the
+//! monster's `AggroTable`; the monster targets whoever holds the most threat.
+//! Role and action modify how much threat is generated:
+//! - Vanguards generate extra threat so they can hold aggro
+//! - Strikers generate less, and can feint or drop threat to shed it
+//!
+//! A taunt overrides the table for a while: the monster targets the taunter
+//! until the taunt expires, then goes back to following threat.
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::specialization::CombatRole;
+
+/// Actions that generate (or shed) threat
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CombatAction {
+    LightAttack,
+    HeavyAttack,
+    Ability,
+    Heal,
+    /// Attack disguised as a feint — same damage, much less threat
+    Feint,
+    /// Dedicated threat-shedding action (vanish, fade, smoke bomb)
+    ThreatDrop,
+}
+
+/// Share of accumulated threat removed by `CombatAction::ThreatDrop`
+const STRIKER_THREAT_DROP: f32 = 0.5;
+const DEFAULT_THREAT_DROP: f32 = 0.25;
+
+/// Threat multiplier for an action performed by a given role
+pub fn threat_modifier(action: CombatAction, role: CombatRole) -> f32 {
+    let role_mult = match role {
+        CombatRole::Vanguard => 2.0,
+        CombatRole::Striker => 0.8,
+        CombatRole::Support => 1.0,
+        CombatRole::Sentinel => 1.1,
+        CombatRole::Specialist => 1.0,
+    };
+
+    let action_mult = match action {
+        CombatAction::LightAttack => 1.0,
+        CombatAction::HeavyAttack => 1.2,
+        CombatAction::Ability => 1.0,
+        CombatAction::Heal => 0.5,
+        CombatAction::Feint => match role {
+            CombatRole::Striker => 0.25,
+            _ => 0.5,
+        },
+        CombatAction::ThreatDrop => 0.0,
+    };
+
+    role_mult * action_mult
+}
+
+/// Share of existing threat shed by a `ThreatDrop` for this role
+pub fn threat_drop_fraction(role: CombatRole) -> f32 {
+    match role {
+        CombatRole::Striker => STRIKER_THREAT_DROP,
+        _ => DEFAULT_THREAT_DROP,
+    }
+}
+
+/// Forced targeting from a taunt
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Taunt {
+    pub source: Entity,
+    /// Time (same clock as `now`) at which the taunt ends
+    pub until: u64,
+}
+
+/// Per-monster threat table (attacker → accumulated threat)
+#[derive(Component, Debug, Clone, Default)]
+pub struct AggroTable {
+    pub threat: HashMap<Entity, f32>,
+    pub taunt: Option<Taunt>,
+}
+
+impl AggroTable {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record an action against this monster.
+    /// `amount` is the raw value (damage dealt, HP healed); ignored for `ThreatDrop`.
+    pub fn record(&mut self, source: Entity, action: CombatAction, role: CombatRole, amount: f32) {
+        if action == CombatAction::ThreatDrop {
+            self.drop_threat(source, threat_drop_fraction(role));
+            return;
+        }
+        let gained = amount.max(0.0) * threat_modifier(action, role);
+        *self.threat.entry(source).or_insert(0.0) += gained;
+    }
+
+    /// Remove a fraction (0..1) of a source's accumulated threat
+    pub fn drop_threat(&mut self, source: Entity, fraction: f32) {
+        if let Some(t) = self.threat.get_mut(&source) {
+            *t *= 1.0 - fraction.clamp(0.0, 1.0);
+        }
+    }
+
+    pub fn threat_of(&self, source: Entity) -> f32 {
+        self.threat.get(&source).copied().unwrap_or(0.0)
+    }
+
+    /// Entity holding the most threat (ties broken by lowest entity id)
+    pub fn top_target(&self) -> Option<Entity> {
+        self.threat
+            .iter()
+            .filter(|(_, t)| **t > 0.0)
+            .max_by(|a, b| {
+                a.1.partial_cmp(b.1)
+                    .unwrap_or(std::cmp::Ordering::Equal)
+                    .then_with(|| b.0.cmp(a.0))
+            })
+            .map(|(e, _)| *e)
+    }
+
+    /// Force this monster onto `source` until `until`
+    pub fn taunt(&mut self, source: Entity, until: u64) {
+        self.taunt = Some(Taunt { source, until });
+    }
+
+    /// Target at time `now`: the taunter while a taunt lasts, otherwise
+    /// whoever holds the most threat
+    pub fn current_target(&self, now: u64) -> Option<Entity> {
+        match self.taunt {
+            Some(t) if now < t.until => Some(t.source),
+            _ => self.top_target(),
+        }
+    }
+
+    /// Clear everyone's threat except `keep`'s
+    pub fn wipe_threat_except(&mut self, keep: Entity) {
+        self.threat.retain(|e, _| *e == keep);
+    }
+}
+
+/// Taunt every enemy within `radius` of the taunter for `duration`.
+///
+/// `enemy_positions` pairs an index into `aggro_tables` with that enemy's
+/// position; unknown indices are skipped. `taunter_id` is the taunter's
+/// `Entity::to_bits()`. Returns how many enemies were taunted.
+pub fn mass_taunt(
+    aggro_tables: &mut [AggroTable],
+    taunter_id: u64,
+    radius: f32,
+    taunter_pos: Vec3,
+    enemy_positions: &[(usize, Vec3)],
+    duration: u64,
+    now: u64,
+) -> usize {
+    let Some(taunter) = Entity::try_from_bits(taunter_id).ok() else {
+        return 0;
+    };
+    let mut taunted = 0;
+    for &(idx, pos) in enemy_positions {
+        if pos.distance(taunter_pos) > radius {
+            continue;
+        }
+        if let Some(table) = aggro_tables.get_mut(idx) {
+            table.taunt(taunter, now + duration);
+            taunted += 1;
+        }
+    }
+    taunted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vanguard_out_threats_striker() {
+        let tank = Entity::from_raw(1);
+        let dps = Entity::from_raw(2);
+        let mut table = AggroTable::new();
+
+        table.record(tank, CombatAction::HeavyAttack, CombatRole::Vanguard, 100.0);
+        table.record(dps, CombatAction::HeavyAttack, CombatRole::Striker, 100.0);
+
+        assert!(table.threat_of(tank) > table.threat_of(dps));
+        assert_eq!(table.top_target(), Some(tank));
+    }
+
+    #[test]
+    fn test_feint_generates_less_threat() {
+        let plain = threat_modifier(CombatAction::HeavyAttack, CombatRole::Striker);
+        let feint = threat_modifier(CombatAction::Feint, CombatRole::Striker);
+        assert!(feint < plain);
+    }
+
+    #[test]
+    fn test_threat_drop_reduces_accumulated_threat() {
+        let dps = Entity::from_raw(2);
+        let mut table = AggroTable::new();
+        table.record(dps, CombatAction::LightAttack, CombatRole::Striker, 100.0);
+        let before = table.threat_of(dps);
+
+        table.record(dps, CombatAction::ThreatDrop, CombatRole::Striker, 0.0);
+        let after = table.threat_of(dps);
+        assert!((after - before * (1.0 - STRIKER_THREAT_DROP)).abs() < 0.001);
+    }
+
+    fn tables_with_striker_on_top(n: usize) -> Vec<AggroTable> {
+        let dps = Entity::from_raw(2);
+        (0..n)
+            .map(|_| {
+                let mut table = AggroTable::new();
+                table.record(dps, CombatAction::HeavyAttack, CombatRole::Striker, 500.0);
+                table
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_mass_taunt_switches_enemies_in_range() {
+        let tank = Entity::from_raw(1);
+        let mut tables = tables_with_striker_on_top(3);
+        let enemies = [
+            (0, Vec3::new(3.0, 0.0, 0.0)),
+            (1, Vec3::new(0.0, 0.0, -4.0)),
+            (2, Vec3::new(20.0, 0.0, 0.0)),
+            (7, Vec3::ZERO),
+        ];
+
+        let taunted = mass_taunt(
+            &mut tables,
+            tank.to_bits(),
+            5.0,
+            Vec3::ZERO,
+            &enemies,
+            4,
+            10,
+        );
+        assert_eq!(taunted, 2);
+        assert_eq!(tables[0].current_target(10), Some(tank));
+        assert_eq!(tables[1].current_target(13), Some(tank));
+        // Out of range: still on the striker
+        assert_eq!(tables[2].current_target(10), Some(Entity::from_raw(2)));
+        assert_eq!(tables[2].taunt, None);
+    }
+
+    #[test]
+    fn test_taunt_expires_to_threat_targeting() {
+        let tank = Entity::from_raw(1);
+        let dps = Entity::from_raw(2);
+        let mut tables = tables_with_striker_on_top(1);
+        tables[0].record(tank, CombatAction::LightAttack, CombatRole::Vanguard, 10.0);
+
+        mass_taunt(
+            &mut tables,
+            tank.to_bits(),
+            5.0,
+            Vec3::ZERO,
+            &[(0, Vec3::ONE)],
+            4,
+            10,
+        );
+        assert_eq!(tables[0].current_target(13), Some(tank));
+        assert_eq!(tables[0].current_target(14), Some(dps));
+
+        // Wiping the others' threat leaves the tank on top after expiry
+        tables[0].wipe_threat_except(tank);
+        assert_eq!(tables[0].current_target(14), Some(tank));
+        assert_eq!(tables[0].threat_of(dps), 0.0);
+    }
+
+    #[test]
+    fn test_empty_table_has_no_target() {
+        assert_eq!(AggroTable::new().top_target(), None);
+    }
+}
@@ -19,6 +19,21 @@ pub enum WeaponType {
     Staff,       // semantic-charged attacks, ranged
 }
 
+impl WeaponType {
+    /// Weapon type from its FFI id (declaration order, Sword = 0)
+    pub fn from_id(id: u32) -> Option<Self> {
+        match id {
+            0 => Some(Self::Sword),
+            1 => Some(Self::Greatsword),
+            2 => Some(Self::DualDaggers),
+            3 => Some(Self::Spear),
+            4 => Some(Self::Gauntlets),
+            5 => Some(Self::Staff),
+            _ => None,
+        }
+    }
+}
+
 /// A single attack in a combo chain
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ComboAttack {
@@ -597,6 +612,60 @@ pub fn gauntlets() -> Weapon {
     }
 }
 
+/// Template weapon for a weapon type
+pub fn weapon_template(weapon: WeaponType) -> Weapon {
+    match weapon {
+        WeaponType::Sword => sword(),
+        WeaponType::Greatsword => greatsword(),
+        WeaponType::DualDaggers => dual_daggers(),
+        WeaponType::Spear => spear(),
+        WeaponType::Gauntlets => gauntlets(),
+        WeaponType::Staff => staff(),
+    }
+}
+
+/// What a combo finisher does on top of its damage
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FinisherKind {
+    ArmorBreak,    // Sword: target takes more damage for a while
+    Knockdown,     // Greatsword: target is floored
+    Bleed,         // DualDaggers: damage over time
+    Impale,        // Spear: pins the target in place
+    Launch,        // Gauntlets: knocks the target airborne
+    SemanticBurst, // Staff: area burst of the staff's tags
+}
+
+/// Bonus applied when a combo reaches its last attack
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct FinisherEffect {
+    pub kind: FinisherKind,
+    /// Extra damage multiplier on the finishing hit
+    pub damage_bonus: f32,
+    /// How long the effect lasts on the target (0 = instant)
+    pub duration_secs: f32,
+}
+
+/// Finisher for `weapon` if `combo_step` (0-based) is the last attack of its
+/// ground combo chain, None for any other step
+pub fn finisher_effect(weapon: WeaponType, combo_step: u32) -> Option<FinisherEffect> {
+    if weapon_template(weapon).max_combo().checked_sub(1) != Some(combo_step) {
+        return None;
+    }
+    let (kind, damage_bonus, duration_secs) = match weapon {
+        WeaponType::Sword => (FinisherKind::ArmorBreak, 0.2, 5.0),
+        WeaponType::Greatsword => (FinisherKind::Knockdown, 0.3, 1.5),
+        WeaponType::DualDaggers => (FinisherKind::Bleed, 0.1, 4.0),
+        WeaponType::Spear => (FinisherKind::Impale, 0.25, 1.0),
+        WeaponType::Gauntlets => (FinisherKind::Launch, 0.15, 0.0),
+        WeaponType::Staff => (FinisherKind::SemanticBurst, 0.4, 0.0),
+    };
+    Some(FinisherEffect {
+        kind,
+        damage_bonus,
+        duration_secs,
+    })
+}
+
 /// System: advance combo based on weapon timing
 pub fn weapon_combo_system(
     mut query: Query<(&mut CombatState, &EquippedWeapon, &mut CombatResources)>,
@@ -657,6 +726,43 @@ mod tests {
         assert!(w.current_attack(3, false).is_none());
     }
 
+    #[test]
+    fn test_greatsword_finisher_knocks_down() {
+        let last = greatsword().max_combo() - 1;
+        let finisher = finisher_effect(WeaponType::Greatsword, last).unwrap();
+        assert_eq!(finisher.kind, FinisherKind::Knockdown);
+        assert!(finisher.damage_bonus > 0.0);
+        assert_eq!(finisher_effect(WeaponType::Greatsword, last + 1), None);
+    }
+
+    #[test]
+    fn test_mid_combo_has_no_finisher() {
+        assert_eq!(finisher_effect(WeaponType::Sword, 0), None);
+        assert_eq!(finisher_effect(WeaponType::DualDaggers, 2), None);
+    }
+
+    #[test]
+    fn test_finishers_differ_per_weapon() {
+        let kinds: Vec<FinisherKind> = [
+            WeaponType::Sword,
+            WeaponType::Greatsword,
+            WeaponType::DualDaggers,
+            WeaponType::Spear,
+            WeaponType::Gauntlets,
+            WeaponType::Staff,
+        ]
+        .into_iter()
+        .map(|w| {
+            finisher_effect(w, weapon_template(w).max_combo() - 1)
+                .unwrap()
+                .kind
+        })
+        .collect();
+        for (i, kind) in kinds.iter().enumerate() {
+            assert!(!kinds[i + 1..].contains(kind), "{kind:?} reused");
+        }
+    }
+
     #[test]
     fn test_greatsword_high_damage() {
         let gs = greatsword();
@@ -67,3 +67,14 @@ pub const BASE_MONSTER_COUNT: u64 = 3;
 
 /// Floor ID modulus for additional monster count
 pub const MONSTER_COUNT_MOD: u32 = 5;
+
+// =====================================================
+// Server Tick
+// =====================================================
+
+/// Authoritative server simulation rate (ticks per second), matching
+/// `ServerConfig::tick_rate` in bevy-server
+pub const SERVER_TICK_RATE: u32 = 20;
+
+/// Length of one server tick in milliseconds
+pub const SERVER_TICK_MS: f32 = 1000.0 / SERVER_TICK_RATE as f32;
@@ -1,6 +1,9 @@
 use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
 
+use crate::generation::floor_manager::ActiveFloor;
+use crate::generation::FloorTier;
+use crate::player::Player;
 use crate::semantic::SemanticTags;
 
 pub struct DeathPlugin;
@@ -11,7 +14,13 @@ impl Plugin for DeathPlugin {
             .add_event::<EchoSpawnEvent>()
             .add_systems(
                 Update,
-                (process_death_events, update_echoes, decay_echoes).chain(),
+                (
+                    process_death_events,
+                    spawn_echoes,
+                    update_echoes,
+                    decay_echoes,
+                )
+                    .chain(),
             );
     }
 }
@@ -42,6 +51,9 @@ pub struct EchoSpawnEvent {
     pub semantic_tags: SemanticTags,
     pub echo_type: EchoType,
     pub power: f32,
+    pub floor_id: u32,
+    /// Set when a player died, so convergence only counts player echoes
+    pub original_player_id: Option<u64>,
 }
 
 /// Types of echoes left behind
@@ -72,6 +84,70 @@ pub struct Echo {
     pub max_lifetime: f32,
     pub power: f32, // decreases over time
     pub original_player_id: Option<u64>,
+    pub floor_id: u32,
+}
+
+/// Live player echoes on `floor_id` within `radius` of `pos`; feeds
+/// `TriggerContext::echo_count` for the convergence trigger
+pub fn count_echoes_near<'a>(
+    echoes: impl IntoIterator<Item = (&'a Echo, &'a Transform)>,
+    floor_id: u32,
+    pos: Vec3,
+    radius: f32,
+) -> u32 {
+    echoes
+        .into_iter()
+        .filter(|(echo, transform)| {
+            echo.original_player_id.is_some()
+                && echo.floor_id == floor_id
+                && transform.translation.distance_squared(pos) <= radius * radius
+        })
+        .count() as u32
+}
+
+/// Where a player died, in the serialized echo state exchanged over FFI
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeathEcho {
+    pub position: [f32; 3],
+    pub floor_id: u32,
+    pub player_tags: Vec<(String, f32)>,
+    pub remaining_ticks: u32,
+}
+
+/// Live player death echoes across the tower, for callers outside the ECS
+/// (the app itself tracks echoes as `Echo` entities)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EchoField {
+    pub echoes: Vec<DeathEcho>,
+}
+
+impl EchoField {
+    pub fn record(&mut self, echo: DeathEcho) {
+        if echo.remaining_ticks > 0 {
+            self.echoes.push(echo);
+        }
+    }
+
+    /// Age every echo by one server tick, dropping the ones that ran out
+    pub fn tick(&mut self) {
+        for echo in &mut self.echoes {
+            echo.remaining_ticks = echo.remaining_ticks.saturating_sub(1);
+        }
+        self.echoes.retain(|e| e.remaining_ticks > 0);
+    }
+
+    /// Live echoes on `floor_id` within `radius` of `pos`; feeds
+    /// `TriggerContext::echo_count` for the convergence trigger
+    pub fn count_near(&self, floor_id: u32, pos: [f32; 3], radius: f32) -> u32 {
+        let radius_sq = radius * radius;
+        self.echoes
+            .iter()
+            .filter(|e| {
+                let d = Vec3::from(e.position) - Vec3::from(pos);
+                e.floor_id == floor_id && d.length_squared() <= radius_sq
+            })
+            .count() as u32
+    }
 }
 
 /// Marker for entities that can die and leave echoes
@@ -143,10 +219,11 @@ fn determine_echo_type(cause: &DeathCause, tags: &SemanticTags) -> EchoType {
 fn process_death_events(
     mut death_events: EventReader<DeathEvent>,
     mut echo_spawn: EventWriter<EchoSpawnEvent>,
-    query: Query<(&SemanticTags, &Mortal)>,
+    active_floor: Option<Res<ActiveFloor>>,
+    query: Query<(&SemanticTags, &Mortal, Option<&Player>)>,
 ) {
     for event in death_events.read() {
-        if let Ok((tags, mortal)) = query.get(event.entity) {
+        if let Ok((tags, mortal, player)) = query.get(event.entity) {
             let echo_type = determine_echo_type(&event.cause, tags);
             let power = mortal.echo_power_factor
                 * match &event.cause {
@@ -156,17 +233,42 @@ fn process_death_events(
                     DeathCause::Void => 1.5,
                 };
 
+            let floor_id = player
+                .map(|p| p.current_floor)
+                .or(active_floor.as_ref().map(|f| f.floor_number))
+                .unwrap_or(1);
+
             echo_spawn.send(EchoSpawnEvent {
                 position: event.death_location,
                 original_entity: event.entity,
                 semantic_tags: tags.clone(),
                 echo_type,
                 power,
+                floor_id,
+                original_player_id: player.map(|_| event.entity.to_bits()),
             });
         }
     }
 }
 
+fn spawn_echoes(mut commands: Commands, mut spawns: EventReader<EchoSpawnEvent>) {
+    for event in spawns.read() {
+        let lifetime = echo_lifetime(floor_echelon(event.floor_id), event.power);
+        commands.spawn((
+            Echo {
+                echo_type: event.echo_type.clone(),
+                lifetime,
+                max_lifetime: lifetime,
+                power: event.power,
+                original_player_id: event.original_player_id,
+                floor_id: event.floor_id,
+            },
+            Transform::from_translation(event.position),
+            event.semantic_tags.clone(),
+        ));
+    }
+}
+
 fn update_echoes(time: Res<Time>, mut query: Query<&mut Echo>) {
     let dt = time.delta_secs();
     for mut echo in &mut query {
@@ -185,6 +287,16 @@ fn decay_echoes(mut commands: Commands, query: Query<(Entity, &Echo)>) {
     }
 }
 
+/// Echelon number (1-4) of a floor, as used by `echo_lifetime`
+fn floor_echelon(floor_id: u32) -> u32 {
+    match FloorTier::from_floor_id(floor_id) {
+        FloorTier::Echelon1 => 1,
+        FloorTier::Echelon2 => 2,
+        FloorTier::Echelon3 => 3,
+        FloorTier::Echelon4 => 4,
+    }
+}
+
 /// Calculate echo lifetime based on floor tier and echo power
 pub fn echo_lifetime(floor_echelon: u32, power: f32) -> f32 {
     let base = match floor_echelon {
@@ -240,4 +352,144 @@ mod tests {
         assert!((mortal.hp - 100.0).abs() < f32::EPSILON);
         assert!((mortal.echo_power_factor - 1.0).abs() < f32::EPSILON);
     }
+
+    fn echo_at(position: [f32; 3], remaining_ticks: u32) -> DeathEcho {
+        DeathEcho {
+            position,
+            floor_id: 12,
+            player_tags: vec![("void".into(), 0.6)],
+            remaining_ticks,
+        }
+    }
+
+    #[test]
+    fn test_echoes_expire() {
+        let mut field = EchoField::default();
+        field.record(echo_at([0.0, 0.0, 0.0], 2));
+        field.record(echo_at([1.0, 0.0, 0.0], 5));
+        field.record(echo_at([2.0, 0.0, 0.0], 0)); // already dead, not kept
+        assert_eq!(field.echoes.len(), 2);
+
+        field.tick();
+        field.tick();
+        assert_eq!(field.echoes.len(), 1);
+        for _ in 0..3 {
+            field.tick();
+        }
+        assert!(field.echoes.is_empty());
+    }
+
+    #[test]
+    fn test_count_near_respects_radius() {
+        let mut field = EchoField::default();
+        field.record(echo_at([0.0, 0.0, 0.0], 100));
+        field.record(echo_at([3.0, 0.0, 4.0], 100)); // distance 5
+        field.record(echo_at([10.0, 0.0, 0.0], 100));
+
+        assert_eq!(field.count_near(12, [0.0, 0.0, 0.0], 4.9), 1);
+        assert_eq!(field.count_near(12, [0.0, 0.0, 0.0], 5.0), 2);
+        assert_eq!(field.count_near(12, [0.0, 0.0, 0.0], 20.0), 3);
+        assert_eq!(field.count_near(12, [100.0, 0.0, 0.0], 20.0), 0);
+    }
+
+    #[test]
+    fn test_count_near_ignores_other_floors() {
+        let mut field = EchoField::default();
+        field.record(echo_at([0.0, 0.0, 0.0], 100));
+        field.record(DeathEcho {
+            floor_id: 13,
+            ..echo_at([0.0, 0.0, 0.0], 100)
+        });
+
+        assert_eq!(field.count_near(12, [0.0, 0.0, 0.0], 5.0), 1);
+        assert_eq!(field.count_near(13, [0.0, 0.0, 0.0], 5.0), 1);
+        assert_eq!(field.count_near(14, [0.0, 0.0, 0.0], 5.0), 0);
+    }
+
+    #[test]
+    fn test_five_echoes_trigger_convergence() {
+        use crate::events::{evaluate_trigger, EventSeverity, EventTriggerType, TriggerContext};
+
+        let mut field = EchoField::default();
+        for i in 0..5 {
+            field.record(echo_at([i as f32, 0.0, 0.0], 60));
+        }
+        let ctx = TriggerContext {
+            echo_count: field.count_near(12, [2.0, 0.0, 0.0], 10.0),
+            ..Default::default()
+        };
+        assert_eq!(ctx.echo_count, 5);
+        let event = evaluate_trigger(EventTriggerType::EchoConvergence, &ctx).unwrap();
+        assert_eq!(event.severity, EventSeverity::Critical);
+    }
+
+    fn death_app() -> App {
+        let mut app = App::new();
+        app.init_resource::<Time>().add_plugins(DeathPlugin);
+        app
+    }
+
+    fn kill(app: &mut App, entity: Entity, at: Vec3) {
+        app.world_mut().send_event(DeathEvent {
+            entity,
+            death_location: at,
+            killer: None,
+            cause: DeathCause::Void,
+        });
+        app.update();
+    }
+
+    #[test]
+    fn test_player_death_spawns_counted_echo() {
+        let mut app = death_app();
+        let player = app
+            .world_mut()
+            .spawn((
+                Player {
+                    current_floor: 12,
+                    ..default()
+                },
+                Mortal::default(),
+                SemanticTags::new(vec![("void", 0.6)]),
+            ))
+            .id();
+        let monster = app
+            .world_mut()
+            .spawn((Mortal::default(), SemanticTags::new(vec![("fire", 0.6)])))
+            .id();
+        kill(&mut app, player, Vec3::new(1.0, 0.0, 0.0));
+        kill(&mut app, monster, Vec3::ZERO);
+
+        let mut query = app.world_mut().query::<(&Echo, &Transform)>();
+        let echoes: Vec<_> = query.iter(app.world()).collect();
+        assert_eq!(echoes.len(), 2);
+        let near = |floor_id, radius| {
+            count_echoes_near(echoes.iter().copied(), floor_id, Vec3::ZERO, radius)
+        };
+        // Only the player's echo counts toward convergence
+        assert_eq!(near(12, 5.0), 1);
+        assert_eq!(near(12, 0.5), 0);
+        assert_eq!(near(13, 5.0), 0);
+    }
+
+    #[test]
+    fn test_expired_echo_entities_despawn() {
+        let mut app = death_app();
+        let echo = app
+            .world_mut()
+            .spawn((
+                Echo {
+                    echo_type: EchoType::ActionReplay { duration_secs: 1.0 },
+                    lifetime: 0.0,
+                    max_lifetime: 60.0,
+                    power: 1.0,
+                    original_player_id: Some(1),
+                    floor_id: 12,
+                },
+                Transform::default(),
+            ))
+            .id();
+        app.update();
+        assert!(app.world().get_entity(echo).is_err());
+    }
 }
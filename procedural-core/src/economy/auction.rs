@@ -0,0 +1,371 @@
+//! Auction house: asynchronous item sales between players.
+//!
+//! Sellers list an item with a buyout price and a duration; other players
+//! bid or buy it out while the seller is offline. Shards and items owed to a
+//! player wait in the house (`payouts` / `deliveries`) until collected.
+//! Time is measured in server ticks, advanced by `expire_listings`.
+
+use std::collections::BTreeMap;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Sha3_256};
+
+use super::{TradeItem, Wallet};
+
+/// An item up for sale
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Listing {
+    pub id: String,
+    pub seller: String,
+    pub item: TradeItem,
+    pub buyout: u64,
+    /// Highest bid so far (0 = no bids); held by the house until resolved
+    pub current_bid: u64,
+    pub highest_bidder: Option<String>,
+    pub listed_at: u64,
+    pub expires_at: u64,
+}
+
+/// Why an auction action was rejected
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum AuctionError {
+    NotFound,
+    Soulbound,
+    InvalidPrice,
+    OwnListing,
+    BidTooLow { minimum: u64 },
+    InsufficientFunds { needed: u64, available: u64 },
+}
+
+/// All open listings plus what the house owes each player
+#[derive(Resource, Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AuctionHouse {
+    /// Open listings, keyed (and so ordered) by listing id
+    pub listings: BTreeMap<String, Listing>,
+    /// Shards owed to players: sale proceeds and outbid refunds
+    pub payouts: BTreeMap<String, u64>,
+    /// Items owed to players: auction wins and unsold returns
+    pub deliveries: BTreeMap<String, Vec<TradeItem>>,
+    /// Last tick seen by `expire_listings`
+    pub current_tick: u64,
+}
+
+impl AuctionHouse {
+    /// List an item for `duration_ticks` from the current tick.
+    /// Returns the new listing's id.
+    pub fn list(
+        &mut self,
+        seller: &str,
+        item: TradeItem,
+        buyout: u64,
+        duration_ticks: u64,
+    ) -> Result<String, AuctionError> {
+        if item.soulbound {
+            return Err(AuctionError::Soulbound);
+        }
+        if buyout == 0 || duration_ticks == 0 {
+            return Err(AuctionError::InvalidPrice);
+        }
+
+        let mut nonce = 0u32;
+        let id = loop {
+            let id = listing_id(seller, &item.name, self.current_tick, nonce);
+            if !self.listings.contains_key(&id) {
+                break id;
+            }
+            nonce += 1;
+        };
+
+        self.listings.insert(
+            id.clone(),
+            Listing {
+                id: id.clone(),
+                seller: seller.to_string(),
+                item,
+                buyout,
+                current_bid: 0,
+                highest_bidder: None,
+                listed_at: self.current_tick,
+                expires_at: self.current_tick + duration_ticks,
+            },
+        );
+        Ok(id)
+    }
+
+    /// Bid on a listing. The bid is taken from `wallet` now; the previous
+    /// high bidder is refunded through `payouts`. A bid at or above the
+    /// buyout price is a buyout: only the buyout is charged and the item
+    /// waits in the bidder's `deliveries`.
+    pub fn bid(
+        &mut self,
+        listing_id: &str,
+        bidder: &str,
+        amount: u64,
+        wallet: &mut Wallet,
+    ) -> Result<(), AuctionError> {
+        let listing = self
+            .listings
+            .get_mut(listing_id)
+            .ok_or(AuctionError::NotFound)?;
+        if listing.seller == bidder {
+            return Err(AuctionError::OwnListing);
+        }
+        if amount >= listing.buyout {
+            let item = self.buyout(listing_id, bidder, wallet)?;
+            self.deliveries
+                .entry(bidder.to_string())
+                .or_default()
+                .push(item);
+            return Ok(());
+        }
+        if amount <= listing.current_bid {
+            return Err(AuctionError::BidTooLow {
+                minimum: listing.current_bid + 1,
+            });
+        }
+        if wallet.tower_shards < amount {
+            return Err(AuctionError::InsufficientFunds {
+                needed: amount,
+                available: wallet.tower_shards,
+            });
+        }
+
+        wallet.tower_shards -= amount;
+        if let Some(prev) = listing.highest_bidder.replace(bidder.to_string()) {
+            *self.payouts.entry(prev).or_default() += listing.current_bid;
+        }
+        listing.current_bid = amount;
+        Ok(())
+    }
+
+    /// Buy a listing outright. Shards go to the seller's payout, any
+    /// standing bid is refunded, and the item is returned to the buyer.
+    pub fn buyout(
+        &mut self,
+        listing_id: &str,
+        buyer: &str,
+        wallet: &mut Wallet,
+    ) -> Result<TradeItem, AuctionError> {
+        let listing = self
+            .listings
+            .get(listing_id)
+            .ok_or(AuctionError::NotFound)?;
+        if listing.seller == buyer {
+            return Err(AuctionError::OwnListing);
+        }
+        if wallet.tower_shards < listing.buyout {
+            return Err(AuctionError::InsufficientFunds {
+                needed: listing.buyout,
+                available: wallet.tower_shards,
+            });
+        }
+
+        let listing = self.listings.remove(listing_id).unwrap();
+        wallet.tower_shards -= listing.buyout;
+        *self.payouts.entry(listing.seller).or_default() += listing.buyout;
+        if let Some(bidder) = listing.highest_bidder {
+            *self.payouts.entry(bidder).or_default() += listing.current_bid;
+        }
+        Ok(listing.item)
+    }
+
+    /// Advance to `current_tick` and close every listing that has run out.
+    /// A listing with a bid goes to its high bidder; one without is returned
+    /// to its seller. Returns the closed listings in id order.
+    pub fn expire_listings(&mut self, current_tick: u64) -> Vec<Listing> {
+        self.current_tick = self.current_tick.max(current_tick);
+
+        let expired_ids: Vec<String> = self
+            .listings
+            .values()
+            .filter(|l| l.expires_at <= self.current_tick)
+            .map(|l| l.id.clone())
+            .collect();
+
+        let mut expired = Vec::with_capacity(expired_ids.len());
+        for id in expired_ids {
+            let listing = self.listings.remove(&id).unwrap();
+            match &listing.highest_bidder {
+                Some(bidder) => {
+                    *self.payouts.entry(listing.seller.clone()).or_default() += listing.current_bid;
+                    self.deliveries
+                        .entry(bidder.clone())
+                        .or_default()
+                        .push(listing.item.clone());
+                }
+                None => self
+                    .deliveries
+                    .entry(listing.seller.clone())
+                    .or_default()
+                    .push(listing.item.clone()),
+            }
+            expired.push(listing);
+        }
+        expired
+    }
+}
+
+/// Listing id from seller, item and listing tick (`nonce` breaks ties when
+/// the same seller lists the same item twice in one tick)
+fn listing_id(seller: &str, item_name: &str, tick: u64, nonce: u32) -> String {
+    let mut hasher = Sha3_256::new();
+    hasher.update(b"auction");
+    hasher.update(seller.as_bytes());
+    hasher.update([0u8]);
+    hasher.update(item_name.as_bytes());
+    hasher.update(tick.to_le_bytes());
+    hasher.update(nonce.to_le_bytes());
+    let result = hasher.finalize();
+    format!(
+        "auc_{:016x}",
+        u64::from_le_bytes(result[0..8].try_into().unwrap())
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::economy::ItemRarity;
+
+    fn blade() -> TradeItem {
+        TradeItem {
+            name: "Fire Blade".into(),
+            rarity: ItemRarity::Epic,
+            base_price: 100,
+            stack_size: 1,
+            max_stack: 1,
+            soulbound: false,
+        }
+    }
+
+    fn wallet(shards: u64) -> Wallet {
+        Wallet {
+            tower_shards: shards,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_buyout_transfers_currency() {
+        let mut house = AuctionHouse::default();
+        let id = house.list("seller", blade(), 500, 100).unwrap();
+        let mut bidder = wallet(300);
+        house.bid(&id, "bidder", 200, &mut bidder).unwrap();
+
+        let mut buyer = wallet(600);
+        let item = house.buyout(&id, "buyer", &mut buyer).unwrap();
+        assert_eq!(item.name, "Fire Blade");
+        assert_eq!(buyer.tower_shards, 100);
+        assert_eq!(house.payouts["seller"], 500);
+        assert_eq!(house.payouts["bidder"], 200);
+        assert!(house.listings.is_empty());
+
+        assert_eq!(
+            house.buyout(&id, "buyer", &mut buyer).unwrap_err(),
+            AuctionError::NotFound
+        );
+    }
+
+    #[test]
+    fn test_buyout_needs_funds() {
+        let mut house = AuctionHouse::default();
+        let id = house.list("seller", blade(), 500, 100).unwrap();
+        let mut poor = wallet(10);
+        assert_eq!(
+            house.buyout(&id, "buyer", &mut poor).unwrap_err(),
+            AuctionError::InsufficientFunds {
+                needed: 500,
+                available: 10
+            }
+        );
+        assert_eq!(poor.tower_shards, 10);
+        assert!(house.listings.contains_key(&id));
+    }
+
+    #[test]
+    fn test_bid_below_high_bid_rejected() {
+        let mut house = AuctionHouse::default();
+        let id = house.list("seller", blade(), 500, 100).unwrap();
+        let mut a = wallet(1000);
+        let mut b = wallet(1000);
+        house.bid(&id, "a", 150, &mut a).unwrap();
+
+        assert_eq!(
+            house.bid(&id, "b", 150, &mut b),
+            Err(AuctionError::BidTooLow { minimum: 151 })
+        );
+        assert_eq!(b.tower_shards, 1000);
+
+        house.bid(&id, "b", 160, &mut b).unwrap();
+        assert_eq!(house.payouts["a"], 150);
+        assert_eq!(
+            house.bid(&id, "seller", 900, &mut a),
+            Err(AuctionError::OwnListing)
+        );
+    }
+
+    #[test]
+    fn test_bid_at_or_above_buyout_buys_out() {
+        let mut house = AuctionHouse::default();
+        let id = house.list("seller", blade(), 500, 100).unwrap();
+        let mut a = wallet(1000);
+        house.bid(&id, "a", 200, &mut a).unwrap();
+
+        let mut b = wallet(1000);
+        house.bid(&id, "b", 900, &mut b).unwrap();
+        assert_eq!(b.tower_shards, 500, "only the buyout is charged");
+        assert!(house.listings.is_empty());
+        assert_eq!(house.deliveries["b"][0].name, "Fire Blade");
+        assert_eq!(house.payouts["seller"], 500);
+        assert_eq!(house.payouts["a"], 200);
+    }
+
+    #[test]
+    fn test_expiry_returns_unsold_items() {
+        let mut house = AuctionHouse::default();
+        let unsold = house.list("seller", blade(), 500, 10).unwrap();
+        let mut other = blade();
+        other.name = "Ice Staff".into();
+        let bid_on = house.list("seller", other, 500, 10).unwrap();
+        let later = house.list("seller", blade(), 500, 50).unwrap();
+        let mut bidder = wallet(100);
+        house.bid(&bid_on, "bidder", 80, &mut bidder).unwrap();
+
+        assert!(house.expire_listings(9).is_empty());
+        let expired = house.expire_listings(10);
+        // Closed listings come back in id order
+        let ids: Vec<_> = expired.iter().map(|l| l.id.clone()).collect();
+        let mut expected = vec![unsold, bid_on];
+        expected.sort();
+        assert_eq!(ids, expected);
+
+        assert_eq!(house.deliveries["seller"].len(), 1);
+        assert_eq!(house.deliveries["seller"][0].name, "Fire Blade");
+        assert_eq!(house.deliveries["bidder"][0].name, "Ice Staff");
+        assert_eq!(house.payouts["seller"], 80);
+        assert!(house.listings.contains_key(&later));
+    }
+
+    #[test]
+    fn test_listing_ids_deterministic() {
+        let mut a = AuctionHouse::default();
+        let mut b = AuctionHouse::default();
+        let id_a = a.list("seller", blade(), 500, 10).unwrap();
+        let id_b = b.list("seller", blade(), 500, 10).unwrap();
+        assert_eq!(id_a, id_b);
+
+        // Same item again in the same tick gets a distinct id
+        let again = a.list("seller", blade(), 500, 10).unwrap();
+        assert_ne!(again, id_a);
+        a.expire_listings(5);
+        assert_ne!(a.list("seller", blade(), 500, 10).unwrap(), id_a);
+
+        let mut soulbound = blade();
+        soulbound.soulbound = true;
+        assert_eq!(
+            a.list("seller", soulbound, 500, 10),
+            Err(AuctionError::Soulbound)
+        );
+    }
+}
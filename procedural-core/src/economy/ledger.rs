@@ -0,0 +1,178 @@
+//! Shard supply tracking for balance work.
+//!
+//! Faucets create shards (drops, quest rewards); sinks destroy them
+//! (crafting, repair, auction fees). Lifetime totals give the overall net
+//! flow; a rolling window of recent flows gives the current inflation ratio.
+//! `inflation_adjust` scales rewards against the total shards in circulation
+//! so they keep their value as players get richer.
+
+use std::collections::VecDeque;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Number of recent flows `inflation_ratio` looks at
+pub const LEDGER_WINDOW: usize = 256;
+
+/// Shards in circulation up to which rewards are paid at face value
+pub const BASELINE_MONEY_SUPPLY: u64 = 1_000_000;
+
+/// One recorded movement of shards into or out of the economy
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ShardFlow {
+    Faucet(u64),
+    Sink(u64),
+}
+
+/// Shards created vs destroyed
+#[derive(Resource, Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EconomyLedger {
+    pub total_created: u64,
+    pub total_destroyed: u64,
+    /// Most recent flows, oldest first, at most `LEDGER_WINDOW` long
+    pub recent: VecDeque<ShardFlow>,
+}
+
+impl EconomyLedger {
+    pub const fn new() -> Self {
+        Self {
+            total_created: 0,
+            total_destroyed: 0,
+            recent: VecDeque::new(),
+        }
+    }
+
+    /// Shards entering the economy
+    pub fn record_faucet(&mut self, amount: u64) {
+        self.total_created = self.total_created.saturating_add(amount);
+        self.push(ShardFlow::Faucet(amount));
+    }
+
+    /// Shards leaving the economy
+    pub fn record_sink(&mut self, amount: u64) {
+        self.total_destroyed = self.total_destroyed.saturating_add(amount);
+        self.push(ShardFlow::Sink(amount));
+    }
+
+    /// Lifetime created minus destroyed (positive = supply growing)
+    pub fn net_flow(&self) -> i64 {
+        self.total_created as i64 - self.total_destroyed as i64
+    }
+
+    /// Shards currently in circulation (never below zero)
+    pub fn money_supply(&self) -> u64 {
+        self.total_created.saturating_sub(self.total_destroyed)
+    }
+
+    /// Created / destroyed over the recent window. 1.0 is a stable supply,
+    /// above 1.0 is inflating. An empty window reads as 1.0.
+    pub fn inflation_ratio(&self) -> f32 {
+        let (created, destroyed) =
+            self.recent
+                .iter()
+                .fold((0u64, 0u64), |(c, d), flow| match flow {
+                    ShardFlow::Faucet(a) => (c + a, d),
+                    ShardFlow::Sink(a) => (c, d + a),
+                });
+        if created == 0 && destroyed == 0 {
+            return 1.0;
+        }
+        created as f32 / destroyed.max(1) as f32
+    }
+
+    fn push(&mut self, flow: ShardFlow) {
+        if self.recent.len() == LEDGER_WINDOW {
+            self.recent.pop_front();
+        }
+        self.recent.push_back(flow);
+    }
+}
+
+/// Scale `base_reward` to keep its value against the money supply. At or
+/// below `BASELINE_MONEY_SUPPLY` the reward is unchanged; above it the
+/// reward grows in proportion to the supply.
+pub fn inflation_adjust(base_reward: u64, total_economy_shards: u64) -> u64 {
+    if total_economy_shards <= BASELINE_MONEY_SUPPLY {
+        return base_reward;
+    }
+    let scaled = base_reward as u128 * total_economy_shards as u128 / BASELINE_MONEY_SUPPLY as u128;
+    scaled.min(u64::MAX as u128) as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_balanced_flow_ratio_near_one() {
+        let mut ledger = EconomyLedger::new();
+        assert_eq!(ledger.inflation_ratio(), 1.0);
+        for i in 0..100 {
+            ledger.record_faucet(100 + i);
+            ledger.record_sink(100 + i);
+        }
+        assert!((ledger.inflation_ratio() - 1.0).abs() < 0.01);
+        assert_eq!(ledger.net_flow(), 0);
+    }
+
+    #[test]
+    fn test_faucet_heavy_trends_above_one() {
+        let mut ledger = EconomyLedger::new();
+        for _ in 0..50 {
+            ledger.record_faucet(100);
+            ledger.record_sink(100);
+        }
+        let balanced = ledger.inflation_ratio();
+        for _ in 0..50 {
+            ledger.record_faucet(300);
+            ledger.record_sink(100);
+        }
+        let heavier = ledger.inflation_ratio();
+        assert!(heavier > balanced);
+        assert!(heavier > 1.5);
+        assert_eq!(ledger.net_flow(), 50 * 200);
+    }
+
+    #[test]
+    fn test_window_forgets_old_flows() {
+        let mut ledger = EconomyLedger::new();
+        ledger.record_sink(1_000_000);
+        for _ in 0..LEDGER_WINDOW {
+            ledger.record_faucet(10);
+        }
+        assert_eq!(ledger.recent.len(), LEDGER_WINDOW);
+        // The big sink has rolled out of the window but not the totals
+        assert!(ledger.inflation_ratio() > 1.0);
+        assert!(ledger.net_flow() < 0);
+    }
+
+    #[test]
+    fn test_fresh_economy_pays_base_reward() {
+        let ledger = EconomyLedger::new();
+        assert_eq!(inflation_adjust(250, ledger.money_supply()), 250);
+        assert_eq!(inflation_adjust(250, BASELINE_MONEY_SUPPLY), 250);
+    }
+
+    #[test]
+    fn test_reward_grows_with_supply() {
+        let mut ledger = EconomyLedger::new();
+        ledger.record_faucet(BASELINE_MONEY_SUPPLY * 4);
+        ledger.record_sink(BASELINE_MONEY_SUPPLY);
+        assert_eq!(ledger.money_supply(), BASELINE_MONEY_SUPPLY * 3);
+        assert_eq!(inflation_adjust(100, ledger.money_supply()), 300);
+        assert_eq!(inflation_adjust(100, BASELINE_MONEY_SUPPLY * 10), 1000);
+        // Huge supplies saturate rather than overflow
+        assert_eq!(inflation_adjust(u64::MAX, u64::MAX), u64::MAX);
+    }
+
+    #[test]
+    fn test_inflation_adjust_monotonic() {
+        let mut last = 0;
+        for step in 0..200u64 {
+            let adjusted = inflation_adjust(75, step * 50_000);
+            assert!(adjusted >= last);
+            assert!(adjusted >= 75);
+            last = adjusted;
+        }
+    }
+}
@@ -1,13 +1,19 @@
 use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
 
+pub mod auction;
 pub mod crafting;
+pub mod ledger;
+
+pub use auction::{AuctionError, AuctionHouse, Listing};
+pub use ledger::{inflation_adjust, EconomyLedger, ShardFlow, BASELINE_MONEY_SUPPLY};
 
 pub struct EconomyPlugin;
 
 impl Plugin for EconomyPlugin {
     fn build(&self, app: &mut App) {
         app.insert_resource(MarketState::default())
+            .insert_resource(EconomyLedger::new())
             .add_systems(Update, update_market_prices);
     }
 }
@@ -15,6 +15,8 @@ pub(crate) fn tile_type_to_u8(tile: &wfc::TileType) -> u8 {
         wfc::TileType::Shrine => 9,
         wfc::TileType::WindColumn => 10,
         wfc::TileType::VoidPit => 11,
+        wfc::TileType::Lever => 12,
+        wfc::TileType::Gate => 13,
     }
 }
 
@@ -1,8 +1,7 @@
-use crate::constants::*;
 use crate::engine::config::EngineConfig;
 use crate::engine::messages::WorldCycleMsg;
 use crate::generation::TowerSeed;
-use crate::world::BreathPhase;
+use crate::world::breath_phase_at;
 
 /// GameStateService — manages world state queries and synchronization
 pub struct GameStateService {
@@ -25,30 +24,7 @@ impl GameStateService {
     }
 
     pub fn get_world_cycle(&self, elapsed_seconds: f32) -> WorldCycleMsg {
-        let cycle_pos = elapsed_seconds % BREATH_CYCLE_TOTAL;
-
-        let hold_start = BREATH_INHALE_SECS;
-        let exhale_start = hold_start + BREATH_HOLD_SECS;
-        let pause_start = exhale_start + BREATH_EXHALE_SECS;
-
-        let (phase, phase_progress) = if cycle_pos < hold_start {
-            (BreathPhase::Inhale, cycle_pos / BREATH_INHALE_SECS)
-        } else if cycle_pos < exhale_start {
-            (
-                BreathPhase::Hold,
-                (cycle_pos - hold_start) / BREATH_HOLD_SECS,
-            )
-        } else if cycle_pos < pause_start {
-            (
-                BreathPhase::Exhale,
-                (cycle_pos - exhale_start) / BREATH_EXHALE_SECS,
-            )
-        } else {
-            (
-                BreathPhase::Pause,
-                (cycle_pos - pause_start) / BREATH_PAUSE_SECS,
-            )
-        };
+        let (phase, phase_progress) = breath_phase_at(elapsed_seconds);
 
         WorldCycleMsg {
             current_phase: format!("{:?}", phase),
@@ -108,7 +108,7 @@ impl GenerationService {
         let tags = SemanticTags {
             tags: source_tags.to_vec(),
         };
-        let items = loot::generate_loot(&tags, floor_level, drop_hash);
+        let items = loot::generate_loot(&tags, floor_level, drop_hash, None);
 
         items
             .iter()
@@ -163,6 +163,17 @@ impl MasteryService {
         let synergies: Vec<SynergyInfoMsg> = spec_profile
             .map(|sp| {
                 let branch_ids: Vec<String> = sp.chosen_branches.values().cloned().collect();
+                let tris = crate::specialization::find_active_tri_synergies(&branch_ids)
+                    .into_iter()
+                    .map(|s| SynergyInfoMsg {
+                        synergy_name: s.name.clone(),
+                        required_branches: vec![
+                            s.branch_a.clone(),
+                            s.branch_b.clone(),
+                            s.branch_c.clone(),
+                        ],
+                        bonus_description: s.description.clone(),
+                    });
                 crate::specialization::find_active_synergies(&branch_ids)
                     .into_iter()
                     .map(|s| SynergyInfoMsg {
@@ -170,6 +181,7 @@ impl MasteryService {
                         required_branches: vec![s.branch_a.clone(), s.branch_b.clone()],
                         bonus_description: s.description.clone(),
                     })
+                    .chain(tris)
                     .collect()
             })
             .unwrap_or_default();
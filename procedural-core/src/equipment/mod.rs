@@ -13,6 +13,16 @@
 
 use serde::{Deserialize, Serialize};
 
+pub mod sets;
+pub mod stats;
+
+pub use sets::{active_set_bonuses, tag_set_bonus};
+pub use stats::{affix_range, roll_stats, Affix, AffixStat, RolledStats};
+
+/// Defensive tags: their items roll armor as the primary stat, and their
+/// 4-piece set bonus is damage reduction rather than cooldown reduction
+const DEFENSIVE_TAGS: [&str; 3] = ["earth", "water", "holy"];
+
 /// Equipment effect trigger conditions
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum EffectTrigger {
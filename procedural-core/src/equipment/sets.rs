@@ -0,0 +1,140 @@
+//! Tag-keyed set bonuses.
+//!
+//! Unlike the named sets in `tower_equipment_sets`, these need no matching
+//! piece ids: any gear whose dominant tag is the same counts toward that
+//! tag's set. Wearing four void pieces grants the void 2- and 4-piece bonuses.
+
+use crate::semantic::SemanticTags;
+
+use super::{EffectAction, EffectTrigger, EquipmentEffect, SetBonus, DEFENSIVE_TAGS};
+
+/// Minimum dominant-tag value for a piece to count toward a set
+pub const SET_TAG_THRESHOLD: f32 = 0.5;
+
+/// Pieces needed for each bonus tier
+pub const SET_TIERS: [u32; 2] = [2, 4];
+
+/// Bonus granted by `pieces_required` pieces sharing `tag`
+pub fn tag_set_bonus(tag: &str, pieces_required: u32) -> SetBonus {
+    let boost = EquipmentEffect {
+        name: format!("{} Resonance", capitalize(tag)),
+        trigger: EffectTrigger::Passive,
+        action: EffectAction::SemanticBoost {
+            tag: tag.to_string(),
+            amount: 0.05 * pieces_required as f32,
+        },
+    };
+
+    let mut effects = vec![boost];
+    if pieces_required >= SET_TIERS[1] {
+        let action = if DEFENSIVE_TAGS.contains(&tag) {
+            EffectAction::DamageReduction { percent: 0.10 }
+        } else {
+            EffectAction::CooldownReduction { percent: 0.10 }
+        };
+        effects.push(EquipmentEffect {
+            name: format!("{} Attunement", capitalize(tag)),
+            trigger: EffectTrigger::Passive,
+            action,
+        });
+    }
+
+    SetBonus {
+        pieces_required,
+        description: format!("{}-piece {} set", pieces_required, tag),
+        effects,
+    }
+}
+
+/// Set bonuses active for the equipped pieces' tags.
+///
+/// Pieces are grouped by dominant tag (ignoring pieces whose dominant value is
+/// below `SET_TAG_THRESHOLD`); every tier a group reaches is granted. Bonuses
+/// are ordered by tag, then tier.
+pub fn active_set_bonuses(equipped: &[SemanticTags]) -> Vec<SetBonus> {
+    let mut counts: Vec<(&str, u32)> = Vec::new();
+    for piece in equipped {
+        let Some((tag, value)) = piece.dominant() else {
+            continue;
+        };
+        if value < SET_TAG_THRESHOLD {
+            continue;
+        }
+        match counts.iter_mut().find(|(t, _)| *t == tag) {
+            Some((_, n)) => *n += 1,
+            None => counts.push((tag, 1)),
+        }
+    }
+    counts.sort_by(|a, b| a.0.cmp(b.0));
+
+    counts
+        .into_iter()
+        .flat_map(|(tag, n)| {
+            SET_TIERS
+                .iter()
+                .filter(move |&&tier| n >= tier)
+                .map(move |&tier| tag_set_bonus(tag, tier))
+        })
+        .collect()
+}
+
+fn capitalize(tag: &str) -> String {
+    let mut chars = tag.chars();
+    match chars.next() {
+        Some(c) => c.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn piece(tag: &str, value: f32) -> SemanticTags {
+        SemanticTags::new(vec![(tag, value), ("combat", 0.2)])
+    }
+
+    #[test]
+    fn test_three_piece_fire_set() {
+        let equipped = vec![piece("fire", 0.8), piece("fire", 0.7), piece("fire", 0.9)];
+        let bonuses = active_set_bonuses(&equipped);
+        assert_eq!(bonuses.len(), 1);
+        assert_eq!(bonuses[0].pieces_required, 2);
+        assert!(bonuses.iter().all(|b| b.pieces_required != 4));
+    }
+
+    #[test]
+    fn test_four_piece_set_grants_both_tiers() {
+        let equipped = vec![piece("void", 0.8); 4];
+        let bonuses = active_set_bonuses(&equipped);
+        let tiers: Vec<u32> = bonuses.iter().map(|b| b.pieces_required).collect();
+        assert_eq!(tiers, vec![2, 4]);
+        assert!(bonuses[1].effects.len() > bonuses[0].effects.len());
+    }
+
+    #[test]
+    fn test_mixed_set_triggers_nothing() {
+        let equipped = vec![
+            piece("fire", 0.8),
+            piece("water", 0.8),
+            piece("earth", 0.8),
+            piece("void", 0.8),
+        ];
+        assert!(active_set_bonuses(&equipped).is_empty());
+    }
+
+    #[test]
+    fn test_weak_tags_do_not_count() {
+        let equipped = vec![piece("fire", 0.8), piece("fire", 0.3)];
+        assert!(active_set_bonuses(&equipped).is_empty());
+    }
+
+    #[test]
+    fn test_defensive_four_piece_reduces_damage() {
+        let bonus = tag_set_bonus("earth", 4);
+        assert!(bonus
+            .effects
+            .iter()
+            .any(|e| matches!(e.action, EffectAction::DamageReduction { .. })));
+    }
+}
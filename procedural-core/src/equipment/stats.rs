@@ -0,0 +1,273 @@
+//! Rolled stat affixes for equipment drops.
+//!
+//! Every piece gets one primary affix (damage or armor, depending on its
+//! dominant tag) and `rarity` secondary slots. Values stay small — a Mythic
+//! roll is about three times a Common one, not an order of magnitude.
+
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Sha3_256};
+
+use super::DEFENSIVE_TAGS;
+use crate::combat::damage::ELEMENTAL_TAGS;
+use crate::economy::ItemRarity;
+use crate::semantic::SemanticTags;
+
+/// Stats an affix can roll
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AffixStat {
+    Damage,
+    Armor,
+    /// Added crit chance (0.01 = +1%)
+    CritChance,
+    /// Added crit damage multiplier
+    CritDamage,
+    /// Added attack speed fraction
+    AttackSpeed,
+    MaxHealth,
+    /// Fraction of damage dealt returned as health
+    Lifesteal,
+    /// Bonus damage of the item's dominant element
+    ElementalDamage,
+}
+
+/// One rolled stat line
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Affix {
+    pub stat: AffixStat,
+    pub value: f32,
+    /// Element for `ElementalDamage`, `None` otherwise
+    #[serde(default)]
+    pub element: Option<String>,
+}
+
+/// Stats rolled onto a piece of equipment
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RolledStats {
+    pub primary: Affix,
+    pub secondary: Vec<Affix>,
+}
+
+impl RolledStats {
+    /// Primary plus secondary affixes
+    pub fn affix_count(&self) -> usize {
+        1 + self.secondary.len()
+    }
+}
+
+/// Secondary stats available to every item, in pick order
+const SECONDARY_POOL: [AffixStat; 7] = [
+    AffixStat::CritChance,
+    AffixStat::CritDamage,
+    AffixStat::AttackSpeed,
+    AffixStat::MaxHealth,
+    AffixStat::Lifesteal,
+    AffixStat::Damage,
+    AffixStat::Armor,
+];
+
+fn rarity_multiplier(rarity: ItemRarity) -> f32 {
+    match rarity {
+        ItemRarity::Common => 1.0,
+        ItemRarity::Uncommon => 1.3,
+        ItemRarity::Rare => 1.6,
+        ItemRarity::Epic => 2.0,
+        ItemRarity::Legendary => 2.5,
+        ItemRarity::Mythic => 3.0,
+    }
+}
+
+/// Secondary affix slots unlocked by a rarity (Common 0 .. Mythic 5)
+pub fn secondary_slots(rarity: ItemRarity) -> usize {
+    match rarity {
+        ItemRarity::Common => 0,
+        ItemRarity::Uncommon => 1,
+        ItemRarity::Rare => 2,
+        ItemRarity::Epic => 3,
+        ItemRarity::Legendary => 4,
+        ItemRarity::Mythic => 5,
+    }
+}
+
+/// Inclusive value range of `stat` at `rarity`
+pub fn affix_range(stat: AffixStat, rarity: ItemRarity) -> (f32, f32) {
+    let (min, max) = match stat {
+        AffixStat::Damage => (2.0, 5.0),
+        AffixStat::Armor => (2.0, 5.0),
+        AffixStat::CritChance => (0.01, 0.03),
+        AffixStat::CritDamage => (0.05, 0.10),
+        AffixStat::AttackSpeed => (0.01, 0.03),
+        AffixStat::MaxHealth => (5.0, 15.0),
+        AffixStat::Lifesteal => (0.005, 0.015),
+        AffixStat::ElementalDamage => (0.02, 0.05),
+    };
+    let mult = rarity_multiplier(rarity);
+    (min * mult, max * mult)
+}
+
+fn affix_roll(roll_hash: u64, slot: u64) -> u64 {
+    let mut hasher = Sha3_256::new();
+    hasher.update(b"affix");
+    hasher.update(roll_hash.to_le_bytes());
+    hasher.update(slot.to_le_bytes());
+    let result = hasher.finalize();
+    u64::from_le_bytes(result[0..8].try_into().unwrap())
+}
+
+fn roll_affix(stat: AffixStat, rarity: ItemRarity, roll: u64, element: Option<&str>) -> Affix {
+    let (min, max) = affix_range(stat, rarity);
+    let t = (roll >> 16) as f32 / (u64::MAX >> 16) as f32;
+    Affix {
+        stat,
+        value: min + (max - min) * t,
+        element: element.map(str::to_string),
+    }
+}
+
+/// Roll deterministic affixes for an item.
+///
+/// The primary stat is armor for defensive dominant tags, damage otherwise.
+/// Secondary slots draw distinct stats from the pool; an elemental dominant
+/// tag adds `ElementalDamage` of that element to the pool.
+pub fn roll_stats(item_tags: &SemanticTags, rarity: ItemRarity, roll_hash: u64) -> RolledStats {
+    let dominant = item_tags.dominant().filter(|(_, v)| *v > 0.0);
+    let dominant_tag = dominant.map(|(t, _)| t);
+
+    let primary_stat = if dominant_tag.is_some_and(|t| DEFENSIVE_TAGS.contains(&t)) {
+        AffixStat::Armor
+    } else {
+        AffixStat::Damage
+    };
+    let primary = roll_affix(primary_stat, rarity, affix_roll(roll_hash, 0), None);
+
+    let element = dominant_tag.filter(|t| ELEMENTAL_TAGS.contains(t));
+    let mut pool: Vec<AffixStat> = SECONDARY_POOL
+        .iter()
+        .copied()
+        .filter(|s| *s != primary_stat)
+        .collect();
+    if element.is_some() {
+        pool.push(AffixStat::ElementalDamage);
+    }
+
+    let mut secondary = Vec::new();
+    for slot in 0..secondary_slots(rarity) {
+        if pool.is_empty() {
+            break;
+        }
+        let roll = affix_roll(roll_hash, slot as u64 + 1);
+        let stat = pool.remove((roll % pool.len() as u64) as usize);
+        let stat_element = if stat == AffixStat::ElementalDamage {
+            element
+        } else {
+            None
+        };
+        secondary.push(roll_affix(stat, rarity, roll, stat_element));
+    }
+
+    RolledStats { primary, secondary }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALL_RARITIES: [ItemRarity; 6] = [
+        ItemRarity::Common,
+        ItemRarity::Uncommon,
+        ItemRarity::Rare,
+        ItemRarity::Epic,
+        ItemRarity::Legendary,
+        ItemRarity::Mythic,
+    ];
+
+    fn fire_tags() -> SemanticTags {
+        SemanticTags::new(vec![("fire", 0.8), ("wind", 0.2)])
+    }
+
+    #[test]
+    fn test_roll_stats_deterministic() {
+        let a = roll_stats(&fire_tags(), ItemRarity::Epic, 42);
+        let b = roll_stats(&fire_tags(), ItemRarity::Epic, 42);
+        assert_eq!(a, b);
+
+        let differs = (1..20u64).any(|h| roll_stats(&fire_tags(), ItemRarity::Epic, h) != a);
+        assert!(
+            differs,
+            "different roll hashes should produce different stats"
+        );
+    }
+
+    #[test]
+    fn test_legendary_rolls_more_affixes_than_common() {
+        for hash in 0..50u64 {
+            let common = roll_stats(&fire_tags(), ItemRarity::Common, hash);
+            let legendary = roll_stats(&fire_tags(), ItemRarity::Legendary, hash);
+            assert_eq!(common.affix_count(), 1);
+            assert!(legendary.affix_count() > common.affix_count());
+        }
+    }
+
+    #[test]
+    fn test_affix_values_within_rarity_range() {
+        for rarity in ALL_RARITIES {
+            for hash in 0..100u64 {
+                let stats = roll_stats(&fire_tags(), rarity, hash);
+                for affix in std::iter::once(&stats.primary).chain(&stats.secondary) {
+                    let (min, max) = affix_range(affix.stat, rarity);
+                    assert!(
+                        affix.value >= min && affix.value <= max,
+                        "{:?} {:?} = {} outside [{}, {}]",
+                        rarity,
+                        affix.stat,
+                        affix.value,
+                        min,
+                        max
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_secondary_affixes_are_distinct() {
+        for hash in 0..50u64 {
+            let stats = roll_stats(&fire_tags(), ItemRarity::Mythic, hash);
+            for (i, a) in stats.secondary.iter().enumerate() {
+                assert_ne!(a.stat, stats.primary.stat);
+                assert!(stats.secondary[i + 1..].iter().all(|b| b.stat != a.stat));
+            }
+        }
+    }
+
+    #[test]
+    fn test_primary_follows_dominant_tag() {
+        let earth = SemanticTags::new(vec![("earth", 0.9)]);
+        assert_eq!(
+            roll_stats(&earth, ItemRarity::Rare, 7).primary.stat,
+            AffixStat::Armor
+        );
+        assert_eq!(
+            roll_stats(&fire_tags(), ItemRarity::Rare, 7).primary.stat,
+            AffixStat::Damage
+        );
+    }
+
+    #[test]
+    fn test_elemental_affix_only_for_elemental_items() {
+        let neutral = SemanticTags::new(vec![("echo", 0.9)]);
+        for hash in 0..50u64 {
+            let stats = roll_stats(&neutral, ItemRarity::Mythic, hash);
+            assert!(stats
+                .secondary
+                .iter()
+                .all(|a| a.stat != AffixStat::ElementalDamage));
+
+            let fire = roll_stats(&fire_tags(), ItemRarity::Mythic, hash);
+            for a in &fire.secondary {
+                if a.stat == AffixStat::ElementalDamage {
+                    assert_eq!(a.element.as_deref(), Some("fire"));
+                }
+            }
+        }
+    }
+}
@@ -20,8 +20,10 @@ pub struct EventsPlugin;
 impl Plugin for EventsPlugin {
     fn build(&self, app: &mut App) {
         app.insert_resource(EventManager::default())
+            .init_resource::<TowerMemoryBank>()
             .add_event::<WorldEvent>()
-            .add_event::<EventTrigger>();
+            .add_event::<EventTrigger>()
+            .add_systems(Update, remember_tower_memory);
     }
 }
 
@@ -53,6 +55,34 @@ pub enum EventSeverity {
     Critical, // Floor-wide transformation
 }
 
+impl EventSeverity {
+    /// Relative selection weight among event variants (Minor is most common)
+    pub fn selection_weight(&self) -> u64 {
+        match self {
+            Self::Minor => 8,
+            Self::Moderate => 4,
+            Self::Major => 2,
+            Self::Critical => 1,
+        }
+    }
+
+    fn escalated(&self) -> Self {
+        match self {
+            Self::Minor => Self::Moderate,
+            Self::Moderate => Self::Major,
+            Self::Major | Self::Critical => Self::Critical,
+        }
+    }
+
+    fn muted(&self) -> Self {
+        match self {
+            Self::Minor | Self::Moderate => Self::Minor,
+            Self::Major => Self::Moderate,
+            Self::Critical => Self::Major,
+        }
+    }
+}
+
 /// Effect that an event applies
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum EventEffect {
@@ -106,6 +136,19 @@ pub struct WorldEventData {
     pub semantic_tags: Vec<(String, f32)>,
 }
 
+/// Events that cascade: each stage fires once the one before it completes
+/// (e.g. a faction clash followed by a reinforcement wave)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EventChain {
+    pub stages: Vec<WorldEventData>,
+}
+
+/// Stage to fire after `completed` stages of `chain` are done, or None once
+/// the chain has run out
+pub fn next_stage(chain: &EventChain, completed: usize) -> Option<&WorldEventData> {
+    chain.stages.get(completed)
+}
+
 /// Bevy event for when a world event fires
 #[derive(Event, Debug, Clone)]
 pub struct WorldEvent {
@@ -135,6 +178,10 @@ pub struct TriggerContext {
     pub corruption_level: f32,
     /// Active factions on floor
     pub active_factions: Vec<String>,
+    /// Faction pairs whose influence zones overlap (see
+    /// `faction::overlapping_pairs`); `None` when zones weren't computed
+    #[serde(default)]
+    pub contested_factions: Option<Vec<(String, String)>>,
     /// Player action history (last N actions as tag)
     pub action_history: Vec<String>,
     /// Floor-specific hash for determinism
@@ -150,12 +197,48 @@ impl Default for TriggerContext {
             echo_count: 0,
             corruption_level: 0.0,
             active_factions: vec![],
+            contested_factions: None,
             action_history: vec![],
             floor_hash: 0,
         }
     }
 }
 
+/// Repetitions of one action before the tower starts to remember it
+pub const TOWER_MEMORY_THRESHOLD: u32 = 3;
+
+/// What the tower remembers of player behavior across floors, keyed by the
+/// same action tags as `TriggerContext::action_history`
+#[derive(Resource, Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TowerMemoryBank {
+    pub actions: std::collections::BTreeMap<String, u32>,
+}
+
+impl TowerMemoryBank {
+    pub fn remember(&mut self, action: &str) {
+        *self.actions.entry(action.to_string()).or_insert(0) += 1;
+    }
+
+    /// Tally a run of actions, e.g. a `TriggerContext::action_history`
+    pub fn from_history(history: &[String]) -> Self {
+        let mut bank = Self::default();
+        for action in history {
+            bank.remember(action);
+        }
+        bank
+    }
+
+    /// Most repeated action once it reaches `TOWER_MEMORY_THRESHOLD`
+    /// (ties go to the alphabetically first action)
+    pub fn dominant(&self) -> Option<(&str, u32)> {
+        self.actions
+            .iter()
+            .filter(|(_, &count)| count >= TOWER_MEMORY_THRESHOLD)
+            .max_by(|(a_name, a), (b_name, b)| a.cmp(b).then(b_name.cmp(a_name)))
+            .map(|(action, &count)| (action.as_str(), count))
+    }
+}
+
 /// Manages event generation and cooldowns
 #[derive(Resource, Debug, Clone, Default)]
 pub struct EventManager {
@@ -164,6 +247,13 @@ pub struct EventManager {
     pub events_triggered: u64,
 }
 
+/// Persisted cooldown state so events don't re-fire when a new floor loads
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct EventCooldownSnapshot {
+    pub cooldowns: Vec<(EventTriggerType, f32)>,
+    pub events_triggered: u64,
+}
+
 #[derive(Debug, Clone)]
 pub struct ActiveEvent {
     pub data: WorldEventData,
@@ -200,6 +290,26 @@ impl EventManager {
         self.active_events.retain(|e| e.remaining_secs > 0.0);
     }
 
+    /// Capture cooldowns and trigger count for persistence across floor loads
+    pub fn capture(&self) -> EventCooldownSnapshot {
+        EventCooldownSnapshot {
+            cooldowns: self.cooldowns.clone(),
+            events_triggered: self.events_triggered,
+        }
+    }
+
+    /// Restore cooldowns from a snapshot (active events are not restored).
+    /// Expired cooldowns are dropped; negative remaining times clamp to zero.
+    pub fn restore(&mut self, snapshot: &EventCooldownSnapshot) {
+        self.cooldowns = snapshot
+            .cooldowns
+            .iter()
+            .map(|(t, remaining)| (*t, remaining.max(0.0)))
+            .filter(|(_, remaining)| *remaining > 0.0)
+            .collect();
+        self.events_triggered = snapshot.events_triggered;
+    }
+
     /// Get default cooldown for a trigger type
     pub fn default_cooldown(trigger_type: EventTriggerType) -> f32 {
         match trigger_type {
@@ -218,6 +328,46 @@ impl EventManager {
 pub fn evaluate_trigger(
     trigger_type: EventTriggerType,
     context: &TriggerContext,
+) -> Option<WorldEventData> {
+    let roll = event_hash(context, "variant_roll");
+    evaluate_trigger_weighted(trigger_type, context, roll)
+}
+
+/// Evaluate a trigger and pick one of its event variants using `roll`.
+///
+/// Each trigger yields its base event plus a stronger (or, for Critical events,
+/// a weaker) variant. Variants are weighted by severity, so lower-severity
+/// outcomes are more common. Deterministic for a fixed roll.
+pub fn evaluate_trigger_weighted(
+    trigger_type: EventTriggerType,
+    context: &TriggerContext,
+    roll: u64,
+) -> Option<WorldEventData> {
+    let base = evaluate_base_event(trigger_type, context)?;
+    let candidates = event_variants(base);
+
+    let total: u64 = candidates
+        .iter()
+        .map(|e| e.severity.selection_weight())
+        .sum();
+    // Scale the roll into [0, total) so the split is independent of total weight
+    let mut pick = (roll % 10000) * total / 10000;
+    let mut candidates = candidates.into_iter();
+    let mut chosen = candidates.next()?;
+    for candidate in candidates {
+        let weight = chosen.severity.selection_weight();
+        if pick < weight {
+            break;
+        }
+        pick -= weight;
+        chosen = candidate;
+    }
+    Some(chosen)
+}
+
+fn evaluate_base_event(
+    trigger_type: EventTriggerType,
+    context: &TriggerContext,
 ) -> Option<WorldEventData> {
     match trigger_type {
         EventTriggerType::BreathShift => evaluate_breath_shift(context),
@@ -230,6 +380,81 @@ pub fn evaluate_trigger(
     }
 }
 
+/// Base event first, then its intensified (or waning, for Critical) variant
+fn event_variants(base: WorldEventData) -> Vec<WorldEventData> {
+    let variant = if base.severity == EventSeverity::Critical {
+        WorldEventData {
+            severity: base.severity.muted(),
+            name: format!("Waning {}", base.name),
+            effects: base.effects.iter().map(|e| scale_effect(e, 0.5)).collect(),
+            duration_secs: base.duration_secs * 0.75,
+            ..base.clone()
+        }
+    } else {
+        WorldEventData {
+            severity: base.severity.escalated(),
+            name: format!("Surging {}", base.name),
+            effects: base.effects.iter().map(|e| scale_effect(e, 1.5)).collect(),
+            duration_secs: base.duration_secs * 1.25,
+            ..base.clone()
+        }
+    };
+    vec![base, variant]
+}
+
+/// Scale an effect's strength; non-numeric effects are unchanged
+fn scale_effect(effect: &EventEffect, factor: f32) -> EventEffect {
+    match effect {
+        EventEffect::SpawnMonsters {
+            count,
+            element_bias,
+        } => EventEffect::SpawnMonsters {
+            count: ((*count as f32 * factor).round() as u32).max(1),
+            element_bias: element_bias.clone(),
+        },
+        EventEffect::PlayerBuff {
+            stat,
+            multiplier,
+            duration_secs,
+        } => EventEffect::PlayerBuff {
+            stat: stat.clone(),
+            multiplier: 1.0 + (multiplier - 1.0) * factor,
+            duration_secs: *duration_secs,
+        },
+        EventEffect::EnvironmentalHazard {
+            damage_per_sec,
+            duration_secs,
+            element,
+        } => EventEffect::EnvironmentalHazard {
+            damage_per_sec: damage_per_sec * factor,
+            duration_secs: *duration_secs,
+            element: element.clone(),
+        },
+        EventEffect::BonusLoot { rarity_boost } => EventEffect::BonusLoot {
+            rarity_boost: ((*rarity_boost as f32 * factor).round() as u32).max(1),
+        },
+        EventEffect::TagShift { tag, delta } => EventEffect::TagShift {
+            tag: tag.clone(),
+            delta: delta * factor,
+        },
+        EventEffect::AtmosphericChange {
+            intensity,
+            color_shift,
+        } => EventEffect::AtmosphericChange {
+            intensity: (intensity * factor).min(1.0),
+            color_shift: color_shift.clone(),
+        },
+        EventEffect::CorruptionWave {
+            damage,
+            corruption_increase,
+        } => EventEffect::CorruptionWave {
+            damage: damage * factor,
+            corruption_increase: corruption_increase * factor,
+        },
+        other => other.clone(),
+    }
+}
+
 fn event_hash(context: &TriggerContext, salt: &str) -> u64 {
     let mut hasher = Sha3_256::new();
     hasher.update(context.floor_hash.to_le_bytes());
@@ -478,14 +703,22 @@ fn evaluate_floor_anomaly(ctx: &TriggerContext) -> Option<WorldEventData> {
     })
 }
 
+/// Clashes between the first contested pair when zone overlaps are known
+/// (none if no zones overlap); without spatial data, the first two active
+/// factions clash.
 fn evaluate_faction_clash(ctx: &TriggerContext) -> Option<WorldEventData> {
-    if ctx.active_factions.len() < 2 {
-        return None;
-    }
+    let (f1, f2) = match &ctx.contested_factions {
+        Some(contested) => {
+            let (a, b) = contested.first()?;
+            (a, b)
+        }
+        None if ctx.active_factions.len() >= 2 => {
+            (&ctx.active_factions[0], &ctx.active_factions[1])
+        }
+        None => return None,
+    };
 
     let hash = event_hash(ctx, "faction_clash");
-    let f1 = &ctx.active_factions[0];
-    let f2 = &ctx.active_factions[1];
 
     Some(WorldEventData {
         id: hash,
@@ -563,30 +796,38 @@ fn evaluate_corruption_surge(ctx: &TriggerContext) -> Option<WorldEventData> {
     })
 }
 
-fn evaluate_tower_memory(ctx: &TriggerContext) -> Option<WorldEventData> {
-    if ctx.action_history.len() < 5 {
+/// Action the history repeats at least `TOWER_MEMORY_THRESHOLD` times, once
+/// there are enough recent actions to judge a pattern
+fn repeated_action(history: &[String]) -> Option<String> {
+    if history.len() < 5 {
         return None;
     }
+    TowerMemoryBank::from_history(history)
+        .dominant()
+        .map(|(action, _)| action.to_string())
+}
 
-    // Detect repeated action patterns
-    let mut action_counts = std::collections::HashMap::new();
-    for action in &ctx.action_history {
-        *action_counts.entry(action.as_str()).or_insert(0u32) += 1;
+/// Each habit the tower reacts to is remembered across floors
+fn remember_tower_memory(
+    mut triggers: EventReader<EventTrigger>,
+    mut bank: ResMut<TowerMemoryBank>,
+) {
+    for trigger in triggers.read() {
+        if trigger.trigger_type != EventTriggerType::TowerMemory {
+            continue;
+        }
+        if let Some(action) = repeated_action(&trigger.context.action_history) {
+            bank.remember(&action);
+        }
     }
+}
 
-    let (dominant_action, count) = action_counts
-        .iter()
-        .max_by_key(|(_, c)| *c)
-        .map(|(a, c)| (*a, *c))?;
-
-    // Need at least 3 repetitions of same action
-    if count < 3 {
-        return None;
-    }
+fn evaluate_tower_memory(ctx: &TriggerContext) -> Option<WorldEventData> {
+    let dominant_action = repeated_action(&ctx.action_history)?;
 
     let hash = event_hash(ctx, "tower_memory");
 
-    let (name, desc, effects) = match dominant_action {
+    let (name, desc, effects) = match dominant_action.as_str() {
         "attack" | "combat" => (
             "Tower Remembers Violence",
             "The tower recognizes your aggressive nature and responds.",
@@ -654,7 +895,7 @@ fn evaluate_tower_memory(ctx: &TriggerContext) -> Option<WorldEventData> {
         semantic_tags: vec![
             ("memory".into(), 0.8),
             ("tower".into(), 0.6),
-            (dominant_action.to_string(), 0.5),
+            (dominant_action, 0.5),
         ],
     })
 }
@@ -679,11 +920,47 @@ mod tests {
             echo_count: 0,
             corruption_level: 0.0,
             active_factions: vec![],
+            contested_factions: None,
             action_history: vec![],
             floor_hash: 42,
         }
     }
 
+    fn clash_chain() -> EventChain {
+        let mut ctx = base_context();
+        ctx.active_factions = vec!["ascending_order".into(), "deep_dwellers".into()];
+        let clash = evaluate_trigger(EventTriggerType::FactionClash, &ctx).unwrap();
+        let reinforcements = WorldEventData {
+            id: clash.id.wrapping_add(1),
+            name: "Reinforcement Wave".into(),
+            effects: vec![EventEffect::SpawnMonsters {
+                count: 6,
+                element_bias: "neutral".into(),
+            }],
+            ..clash.clone()
+        };
+        EventChain {
+            stages: vec![clash, reinforcements],
+        }
+    }
+
+    #[test]
+    fn test_chain_first_stage_leads_to_second() {
+        let chain = clash_chain();
+        assert_eq!(
+            next_stage(&chain, 0).unwrap().trigger_type,
+            EventTriggerType::FactionClash
+        );
+        assert_eq!(next_stage(&chain, 1).unwrap().name, "Reinforcement Wave");
+    }
+
+    #[test]
+    fn test_chain_ends_after_last_stage() {
+        let chain = clash_chain();
+        assert!(next_stage(&chain, 2).is_none());
+        assert!(next_stage(&EventChain::default(), 0).is_none());
+    }
+
     #[test]
     fn test_breath_shift_hold() {
         let ctx = base_context();
@@ -779,6 +1056,50 @@ mod tests {
         assert!(event.name.contains("Breakers"));
     }
 
+    #[test]
+    fn test_faction_clash_uses_contested_zones() {
+        use crate::faction::{overlapping_pairs, InfluenceZone};
+
+        let zone = |faction: &str, x: f32| InfluenceZone {
+            faction: faction.into(),
+            center_x: x,
+            center_y: 8.0,
+            radius: 3.0,
+        };
+        let mut ctx = base_context();
+        ctx.active_factions = vec!["seekers".into(), "breakers".into(), "wardens".into()];
+        ctx.contested_factions = Some(overlapping_pairs(&[
+            zone("seekers", 2.0),
+            zone("breakers", 14.0),
+            zone("wardens", 17.0),
+        ]));
+
+        let event = evaluate_trigger(EventTriggerType::FactionClash, &ctx).unwrap();
+        assert!(event.name.contains("Breakers"));
+        assert!(event.name.contains("Wardens"));
+        assert!(!event.name.contains("Seekers"));
+    }
+
+    #[test]
+    fn test_faction_clash_needs_overlapping_zones() {
+        use crate::faction::{overlapping_pairs, InfluenceZone};
+
+        let zone = |faction: &str, x: f32| InfluenceZone {
+            faction: faction.into(),
+            center_x: x,
+            center_y: 8.0,
+            radius: 3.0,
+        };
+        let mut ctx = base_context();
+        ctx.active_factions = vec!["seekers".into(), "breakers".into()];
+        ctx.contested_factions = Some(overlapping_pairs(&[
+            zone("seekers", 2.0),
+            zone("breakers", 40.0),
+        ]));
+
+        assert!(evaluate_trigger(EventTriggerType::FactionClash, &ctx).is_none());
+    }
+
     #[test]
     fn test_faction_clash_single_faction() {
         let mut ctx = base_context();
@@ -813,6 +1134,47 @@ mod tests {
         assert!(event.is_none(), "Low corruption should not trigger surge");
     }
 
+    #[test]
+    fn test_tower_memory_bank_dominant() {
+        let mut bank = TowerMemoryBank::default();
+        bank.remember("explore");
+        bank.remember("explore");
+        assert_eq!(bank.dominant(), None);
+        for _ in 0..3 {
+            bank.remember("combat");
+        }
+        bank.remember("explore");
+        // 3 vs 3: alphabetical tie-break
+        assert_eq!(bank.dominant(), Some(("combat", 3)));
+        bank.remember("explore");
+        assert_eq!(bank.dominant(), Some(("explore", 4)));
+    }
+
+    #[test]
+    fn test_tower_memory_triggers_feed_the_bank() {
+        let mut app = App::new();
+        app.add_plugins(EventsPlugin);
+        let mut trigger = |history: Vec<&str>| {
+            let mut ctx = base_context();
+            ctx.action_history = history.into_iter().map(String::from).collect();
+            app.world_mut().send_event(EventTrigger {
+                trigger_type: EventTriggerType::TowerMemory,
+                floor_id: 1,
+                context: ctx,
+            });
+            app.update();
+        };
+        trigger(vec!["attack"; 5]);
+        trigger(vec!["explore", "attack", "explore", "craft", "explore"]);
+        // Too short to be a pattern
+        trigger(vec!["craft"; 3]);
+
+        let bank = app.world().resource::<TowerMemoryBank>();
+        assert_eq!(bank.actions.get("attack"), Some(&1));
+        assert_eq!(bank.actions.get("explore"), Some(&1));
+        assert_eq!(bank.actions.get("craft"), None);
+    }
+
     #[test]
     fn test_tower_memory_combat() {
         let mut ctx = base_context();
@@ -839,6 +1201,59 @@ mod tests {
         assert!(event.is_none());
     }
 
+    #[test]
+    fn test_weighted_selection_deterministic() {
+        let ctx = base_context();
+        for roll in [0u64, 1, 2, 99, 12345] {
+            let a = evaluate_trigger_weighted(EventTriggerType::BreathShift, &ctx, roll).unwrap();
+            let b = evaluate_trigger_weighted(EventTriggerType::BreathShift, &ctx, roll).unwrap();
+            assert_eq!(a.name, b.name);
+            assert_eq!(a.severity, b.severity);
+        }
+    }
+
+    #[test]
+    fn test_weighted_selection_offers_variants() {
+        let ctx = base_context();
+        let names: std::collections::HashSet<String> = (0..20u64)
+            .filter_map(|i| {
+                let roll = i * 7919;
+                evaluate_trigger_weighted(EventTriggerType::BreathShift, &ctx, roll).map(|e| e.name)
+            })
+            .collect();
+        assert!(names.len() >= 2, "Rolls should reach more than one variant");
+    }
+
+    #[test]
+    fn test_weighted_selection_skews_to_lower_severity() {
+        // Major base vs Critical surge, and Critical base vs Major waning
+        let mut ctx = base_context();
+        let mut counts = std::collections::HashMap::new();
+        for i in 0..3000u64 {
+            let roll = i * 7919; // coprime with 10000, spreads rolls evenly
+            ctx.echo_count = 4;
+            let e = evaluate_trigger_weighted(EventTriggerType::EchoConvergence, &ctx, roll);
+            *counts.entry(e.unwrap().severity).or_insert(0u32) += 1;
+            ctx.echo_count = 6;
+            let e = evaluate_trigger_weighted(EventTriggerType::EchoConvergence, &ctx, roll);
+            *counts.entry(e.unwrap().severity).or_insert(0u32) += 1;
+        }
+        let major = counts[&EventSeverity::Major];
+        let critical = counts[&EventSeverity::Critical];
+        assert!(
+            major > critical * 3 / 2,
+            "Major {} vs Critical {}",
+            major,
+            critical
+        );
+    }
+
+    #[test]
+    fn test_weighted_selection_respects_trigger_conditions() {
+        let ctx = base_context(); // echo_count 0
+        assert!(evaluate_trigger_weighted(EventTriggerType::EchoConvergence, &ctx, 7).is_none());
+    }
+
     #[test]
     fn test_event_manager_cooldown() {
         let mut mgr = EventManager::default();
@@ -850,6 +1265,56 @@ mod tests {
         assert!(!mgr.is_on_cooldown(EventTriggerType::BreathShift));
     }
 
+    #[test]
+    fn test_cooldown_snapshot_round_trip() {
+        let mut mgr = EventManager::default();
+        mgr.set_cooldown(EventTriggerType::BreathShift, 45.0);
+        mgr.set_cooldown(EventTriggerType::FloorAnomaly, 200.0);
+        mgr.events_triggered = 7;
+
+        let json = serde_json::to_string(&mgr.capture()).unwrap();
+        let snapshot: EventCooldownSnapshot = serde_json::from_str(&json).unwrap();
+
+        let mut restored = EventManager::default();
+        restored.restore(&snapshot);
+        assert_eq!(restored.capture(), mgr.capture());
+        assert!(restored.is_on_cooldown(EventTriggerType::BreathShift));
+    }
+
+    #[test]
+    fn test_cooldown_restore_drops_expired() {
+        let snapshot = EventCooldownSnapshot {
+            cooldowns: vec![
+                (EventTriggerType::BreathShift, 30.0),
+                (EventTriggerType::CorruptionSurge, 0.0),
+                (EventTriggerType::TowerMemory, -5.0),
+            ],
+            events_triggered: 2,
+        };
+        let mut mgr = EventManager::default();
+        mgr.restore(&snapshot);
+
+        assert_eq!(mgr.cooldowns, vec![(EventTriggerType::BreathShift, 30.0)]);
+        assert!(!mgr.is_on_cooldown(EventTriggerType::TowerMemory));
+    }
+
+    #[test]
+    fn test_restored_cooldowns_tick_down() {
+        let mut mgr = EventManager::default();
+        mgr.restore(&EventCooldownSnapshot {
+            cooldowns: vec![(EventTriggerType::BreathShift, 10.0)],
+            events_triggered: 0,
+        });
+
+        mgr.tick(4.0);
+        assert!(mgr.is_on_cooldown(EventTriggerType::BreathShift));
+        assert!((mgr.cooldowns[0].1 - 6.0).abs() < 0.001);
+
+        mgr.tick(6.0);
+        assert!(!mgr.is_on_cooldown(EventTriggerType::BreathShift));
+        assert!(mgr.cooldowns.is_empty());
+    }
+
     #[test]
     fn test_event_manager_active_events() {
         let mut mgr = EventManager::default();
@@ -0,0 +1,163 @@
+//! Faction influence zones on a floor.
+//!
+//! Each faction present on a floor claims a circular region of the floor's
+//! tile grid, placed from the seed so every client agrees. Where two
+//! regions overlap the territory is contested, which is what the
+//! `FactionClash` trigger reacts to.
+
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Sha3_256};
+
+use crate::generation::wfc::grid_size_for_tier;
+use crate::generation::FloorTier;
+
+/// Zone radius range as a fraction of the floor's width
+const MIN_RADIUS_FRACTION: f32 = 0.15;
+const MAX_RADIUS_FRACTION: f32 = 0.30;
+
+/// A faction's claimed region, in tile coordinates
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InfluenceZone {
+    pub faction: String,
+    pub center_x: f32,
+    pub center_y: f32,
+    pub radius: f32,
+}
+
+impl InfluenceZone {
+    pub fn overlaps(&self, other: &InfluenceZone) -> bool {
+        let dx = self.center_x - other.center_x;
+        let dy = self.center_y - other.center_y;
+        let reach = self.radius + other.radius;
+        dx * dx + dy * dy < reach * reach
+    }
+}
+
+fn zone_roll(seed: u64, floor_id: u32, faction: &str, field: u8) -> f32 {
+    let mut hasher = Sha3_256::new();
+    hasher.update(b"influence");
+    hasher.update(seed.to_le_bytes());
+    hasher.update(floor_id.to_le_bytes());
+    hasher.update(faction.as_bytes());
+    hasher.update([field]);
+    let result = hasher.finalize();
+    let v = u64::from_le_bytes(result[0..8].try_into().unwrap());
+    (v % 10000) as f32 / 10000.0
+}
+
+/// One influence zone per faction on the floor, placed deterministically
+/// from `seed` and `floor_id`. A faction's zone depends only on its own
+/// name, so adding a faction never moves the others.
+pub fn influence_zones(seed: u64, floor_id: u32, factions: &[String]) -> Vec<InfluenceZone> {
+    let (width, height) = grid_size_for_tier(&FloorTier::from_floor_id(floor_id));
+    let (width, height) = (width as f32, height as f32);
+
+    factions
+        .iter()
+        .map(|faction| {
+            let radius_t = zone_roll(seed, floor_id, faction, 2);
+            InfluenceZone {
+                faction: faction.clone(),
+                center_x: zone_roll(seed, floor_id, faction, 0) * width,
+                center_y: zone_roll(seed, floor_id, faction, 1) * height,
+                radius: width
+                    * (MIN_RADIUS_FRACTION
+                        + (MAX_RADIUS_FRACTION - MIN_RADIUS_FRACTION) * radius_t),
+            }
+        })
+        .collect()
+}
+
+/// Faction pairs whose zones overlap, in zone order. Zones of the same
+/// faction never contest each other.
+pub fn overlapping_pairs(zones: &[InfluenceZone]) -> Vec<(String, String)> {
+    let mut pairs = Vec::new();
+    for (i, a) in zones.iter().enumerate() {
+        for b in &zones[i + 1..] {
+            if a.faction != b.faction && a.overlaps(b) {
+                let pair = (a.faction.clone(), b.faction.clone());
+                if !pairs.contains(&pair) {
+                    pairs.push(pair);
+                }
+            }
+        }
+    }
+    pairs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn zone(faction: &str, x: f32, y: f32, radius: f32) -> InfluenceZone {
+        InfluenceZone {
+            faction: faction.into(),
+            center_x: x,
+            center_y: y,
+            radius,
+        }
+    }
+
+    fn tower_factions() -> Vec<String> {
+        [
+            "AscendingOrder",
+            "DeepDwellers",
+            "EchoKeepers",
+            "FreeClimbers",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect()
+    }
+
+    #[test]
+    fn test_influence_zones_deterministic() {
+        let a = influence_zones(42, 10, &tower_factions());
+        let b = influence_zones(42, 10, &tower_factions());
+        assert_eq!(a, b);
+        assert_eq!(a.len(), 4);
+        assert_ne!(a, influence_zones(43, 10, &tower_factions()));
+    }
+
+    #[test]
+    fn test_zones_fit_the_floor() {
+        for floor_id in [1, 150, 400] {
+            let (w, h) = grid_size_for_tier(&FloorTier::from_floor_id(floor_id));
+            for z in influence_zones(7, floor_id, &tower_factions()) {
+                assert!(z.center_x >= 0.0 && z.center_x < w as f32);
+                assert!(z.center_y >= 0.0 && z.center_y < h as f32);
+                assert!(z.radius >= w as f32 * MIN_RADIUS_FRACTION);
+                assert!(z.radius <= w as f32 * MAX_RADIUS_FRACTION);
+            }
+        }
+    }
+
+    #[test]
+    fn test_zone_independent_of_other_factions() {
+        let all = influence_zones(42, 10, &tower_factions());
+        let alone = influence_zones(42, 10, &tower_factions()[2..3]);
+        assert_eq!(all[2], alone[0]);
+    }
+
+    #[test]
+    fn test_overlapping_zones_detected() {
+        let zones = vec![
+            zone("AscendingOrder", 4.0, 4.0, 3.0),
+            zone("DeepDwellers", 8.0, 4.0, 2.0),
+            zone("EchoKeepers", 30.0, 30.0, 2.0),
+        ];
+        assert_eq!(
+            overlapping_pairs(&zones),
+            vec![("AscendingOrder".to_string(), "DeepDwellers".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_distant_zones_not_contested() {
+        let zones = vec![
+            zone("AscendingOrder", 2.0, 2.0, 2.0),
+            zone("DeepDwellers", 14.0, 14.0, 2.0),
+        ];
+        assert!(overlapping_pairs(&zones).is_empty());
+    }
+}
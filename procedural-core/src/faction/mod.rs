@@ -1,7 +1,12 @@
 use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
 
+pub mod influence;
 pub mod npcs;
+pub mod reputation;
+
+pub use influence::{influence_zones, overlapping_pairs, InfluenceZone};
+pub use reputation::{FactionPerk, ReputationProfile};
 
 pub struct FactionPlugin;
 
@@ -91,19 +96,11 @@ impl FactionStanding {
 
     /// Reputation tier based on standing value
     pub fn tier(&self, faction: &Faction) -> ReputationTier {
-        let value = self.get(faction);
-        match value as i32 {
-            -100..=-50 => ReputationTier::Hostile,
-            -49..=-10 => ReputationTier::Unfriendly,
-            -9..=9 => ReputationTier::Neutral,
-            10..=49 => ReputationTier::Friendly,
-            50..=89 => ReputationTier::Honored,
-            _ => ReputationTier::Exalted,
-        }
+        ReputationTier::from_value(self.get(faction))
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum ReputationTier {
     Hostile,
     Unfriendly,
@@ -113,6 +110,20 @@ pub enum ReputationTier {
     Exalted,
 }
 
+impl ReputationTier {
+    /// Tier for a standing value (-100 to 100)
+    pub fn from_value(value: f32) -> Self {
+        match value as i32 {
+            i32::MIN..=-50 => Self::Hostile,
+            -49..=-10 => Self::Unfriendly,
+            -9..=9 => Self::Neutral,
+            10..=49 => Self::Friendly,
+            50..=89 => Self::Honored,
+            _ => Self::Exalted,
+        }
+    }
+}
+
 /// Global faction registry with dynamic faction relationships
 #[derive(Resource, Debug, Default)]
 pub struct FactionRegistry {
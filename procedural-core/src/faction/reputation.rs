@@ -0,0 +1,251 @@
+//! Name-keyed faction reputation with decay and tier perks.
+//!
+//! Unlike `FactionStanding` (fixed fields for the four tower factions), a
+//! `ReputationProfile` tracks any faction by name, so floor-generated and
+//! event factions share the same model. Reputation drifts back toward
+//! neutral while idle, and rivalries mirror gains as losses.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::{Faction, ReputationTier};
+
+/// Standing bounds, matching `FactionStanding`
+pub const REPUTATION_MIN: f32 = -100.0;
+pub const REPUTATION_MAX: f32 = 100.0;
+/// Fraction of reputation lost toward neutral per hour
+pub const REPUTATION_DECAY_PER_HOUR: f32 = 0.02;
+/// Share of a gain mirrored as a loss by the base tower rivalries
+const TOWER_RIVALRY_RATIO: f32 = 0.5;
+
+/// Perk unlocked by reaching a reputation tier with a faction
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FactionPerk {
+    pub faction: String,
+    pub tier: ReputationTier,
+    pub name: String,
+}
+
+/// Perk name for reaching `tier`, if it grants one
+fn tier_perk(tier: ReputationTier) -> Option<&'static str> {
+    match tier {
+        ReputationTier::Friendly => Some("Vendor Discount"),
+        ReputationTier::Honored => Some("Quartermaster Access"),
+        ReputationTier::Exalted => Some("Faction Title"),
+        _ => None,
+    }
+}
+
+/// Two factions whose reputations move in opposite directions
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Rivalry {
+    pub a: String,
+    pub b: String,
+    /// Share of a gain with one applied as a loss to the other
+    pub ratio: f32,
+}
+
+/// A player's reputation with every faction they've met
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ReputationProfile {
+    pub reputation: HashMap<String, f32>,
+    #[serde(default)]
+    pub rivalries: Vec<Rivalry>,
+}
+
+impl ReputationProfile {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Profile with the tower's built-in rivalries (factions whose
+    /// `base_relation` is negative) already linked
+    pub fn with_tower_rivalries() -> Self {
+        let mut profile = Self::new();
+        let factions = [
+            Faction::AscendingOrder,
+            Faction::DeepDwellers,
+            Faction::EchoKeepers,
+            Faction::FreeClimbers,
+        ];
+        for (i, a) in factions.iter().enumerate() {
+            for b in &factions[i + 1..] {
+                if a.base_relation(b) < 0.0 {
+                    profile.link_rivals(
+                        &format!("{:?}", a),
+                        &format!("{:?}", b),
+                        TOWER_RIVALRY_RATIO,
+                    );
+                }
+            }
+        }
+        profile
+    }
+
+    /// Make gains with either faction cost `ratio` of that much with the other
+    pub fn link_rivals(&mut self, a: &str, b: &str, ratio: f32) {
+        self.rivalries
+            .retain(|r| !((r.a == a && r.b == b) || (r.a == b && r.b == a)));
+        self.rivalries.push(Rivalry {
+            a: a.to_string(),
+            b: b.to_string(),
+            ratio: ratio.clamp(0.0, 1.0),
+        });
+    }
+
+    pub fn get(&self, faction: &str) -> f32 {
+        self.reputation.get(faction).copied().unwrap_or(0.0)
+    }
+
+    pub fn tier(&self, faction: &str) -> ReputationTier {
+        ReputationTier::from_value(self.get(faction))
+    }
+
+    /// Change reputation with `faction` (and its rivals, mirrored). Returns
+    /// perks for every tier newly reached upward by any affected faction.
+    pub fn gain(&mut self, faction: &str, amount: f32) -> Vec<FactionPerk> {
+        let rival_changes: Vec<(String, f32)> = self
+            .rivalries
+            .iter()
+            .filter_map(|r| {
+                let rival = if r.a == faction {
+                    &r.b
+                } else if r.b == faction {
+                    &r.a
+                } else {
+                    return None;
+                };
+                Some((rival.clone(), -amount * r.ratio))
+            })
+            .collect();
+
+        let mut perks = self.adjust(faction, amount);
+        for (rival, delta) in rival_changes {
+            perks.extend(self.adjust(&rival, delta));
+        }
+        perks
+    }
+
+    /// Pull every reputation toward neutral; extreme values lose the most
+    pub fn apply_decay(&mut self, elapsed_secs: f32) {
+        let hours = elapsed_secs.max(0.0) / 3600.0;
+        let keep = (1.0 - REPUTATION_DECAY_PER_HOUR).powf(hours);
+        for value in self.reputation.values_mut() {
+            *value *= keep;
+        }
+    }
+
+    /// Perks currently held with `faction` (every perk tier at or below its tier)
+    pub fn perks(&self, faction: &str) -> Vec<FactionPerk> {
+        let tier = self.tier(faction);
+        perks_between(faction, ReputationTier::Neutral, tier)
+    }
+
+    fn adjust(&mut self, faction: &str, delta: f32) -> Vec<FactionPerk> {
+        let before = self.tier(faction);
+        let value = self.reputation.entry(faction.to_string()).or_insert(0.0);
+        *value = (*value + delta).clamp(REPUTATION_MIN, REPUTATION_MAX);
+        let after = ReputationTier::from_value(*value);
+        perks_between(faction, before, after)
+    }
+}
+
+/// Perks for tiers above `from` up to and including `to`
+fn perks_between(faction: &str, from: ReputationTier, to: ReputationTier) -> Vec<FactionPerk> {
+    [
+        ReputationTier::Friendly,
+        ReputationTier::Honored,
+        ReputationTier::Exalted,
+    ]
+    .into_iter()
+    .filter(|t| *t > from && *t <= to)
+    .filter_map(|tier| {
+        tier_perk(tier).map(|name| FactionPerk {
+            faction: faction.to_string(),
+            tier,
+            name: name.to_string(),
+        })
+    })
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tier_thresholds() {
+        let mut profile = ReputationProfile::new();
+        assert_eq!(profile.tier("EchoKeepers"), ReputationTier::Neutral);
+
+        let cases = [
+            (9.0, ReputationTier::Neutral),
+            (1.0, ReputationTier::Friendly),
+            (40.0, ReputationTier::Honored),
+            (40.0, ReputationTier::Exalted),
+        ];
+        for (amount, tier) in cases {
+            profile.gain("EchoKeepers", amount);
+            assert_eq!(profile.tier("EchoKeepers"), tier);
+        }
+
+        profile.gain("EchoKeepers", -250.0);
+        assert_eq!(profile.get("EchoKeepers"), REPUTATION_MIN);
+        assert_eq!(profile.tier("EchoKeepers"), ReputationTier::Hostile);
+    }
+
+    #[test]
+    fn test_crossing_thresholds_unlocks_perks() {
+        let mut profile = ReputationProfile::new();
+        assert!(profile.gain("FreeClimbers", 5.0).is_empty());
+
+        let perks = profile.gain("FreeClimbers", 50.0);
+        let names: Vec<&str> = perks.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["Vendor Discount", "Quartermaster Access"]);
+        assert_eq!(profile.perks("FreeClimbers").len(), 2);
+
+        // Already held: no repeat unlock
+        assert!(profile.gain("FreeClimbers", 1.0).is_empty());
+    }
+
+    #[test]
+    fn test_decay_pulls_toward_neutral() {
+        let mut profile = ReputationProfile::new();
+        profile.gain("AscendingOrder", 100.0);
+        profile.gain("DeepDwellers", -100.0);
+        profile.gain("EchoKeepers", 10.0);
+
+        profile.apply_decay(24.0 * 3600.0);
+
+        let ao = profile.get("AscendingOrder");
+        let dd = profile.get("DeepDwellers");
+        assert!(ao < 100.0 && ao > 0.0);
+        assert!(dd > -100.0 && dd < 0.0);
+        // Extreme values lose more than mild ones
+        assert!(100.0 - ao > 10.0 - profile.get("EchoKeepers"));
+    }
+
+    #[test]
+    fn test_rival_factions_move_as_a_pair() {
+        let mut profile = ReputationProfile::with_tower_rivalries();
+        profile.gain("AscendingOrder", 40.0);
+        assert_eq!(profile.get("AscendingOrder"), 40.0);
+        assert_eq!(profile.get("DeepDwellers"), -20.0);
+
+        profile.gain("DeepDwellers", 20.0);
+        assert_eq!(profile.get("DeepDwellers"), 0.0);
+        assert_eq!(profile.get("AscendingOrder"), 30.0);
+
+        // Unlinked factions are unaffected
+        assert_eq!(profile.get("FreeClimbers"), 0.0);
+    }
+
+    #[test]
+    fn test_custom_rivalry() {
+        let mut profile = ReputationProfile::new();
+        profile.link_rivals("Cult", "Wardens", 1.0);
+        profile.gain("Wardens", 15.0);
+        assert_eq!(profile.tier("Cult"), ReputationTier::Unfriendly);
+    }
+}
@@ -0,0 +1,78 @@
+//! Treasure chest tiers.
+//!
+//! Every chest on a floor is Wooden, Silver or Gold, picked from the floor
+//! hash and the chest's index so every client sees the same chest. Deeper
+//! floors shift the odds toward better chests; `loot::chest_loot` turns the
+//! tier into loot quality.
+
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Sha3_256};
+
+/// Visible chest quality
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum ChestTier {
+    Wooden,
+    Silver,
+    Gold,
+}
+
+/// Silver and Gold weights (out of 100) on floor 1; Wooden takes the rest
+const BASE_SILVER_WEIGHT: u32 = 20;
+const BASE_GOLD_WEIGHT: u32 = 5;
+/// Upper bounds reached deep in the tower
+const MAX_SILVER_WEIGHT: u32 = 40;
+const MAX_GOLD_WEIGHT: u32 = 35;
+
+/// Tier of the `chest_index`-th chest on a floor
+pub fn chest_tier(floor_hash: u64, chest_index: u32, floor_level: u32) -> ChestTier {
+    let mut hasher = Sha3_256::new();
+    hasher.update(b"chest");
+    hasher.update(floor_hash.to_le_bytes());
+    hasher.update(chest_index.to_le_bytes());
+    let result = hasher.finalize();
+    let roll = (u64::from_le_bytes(result[0..8].try_into().unwrap()) % 100) as u32;
+
+    let gold = (BASE_GOLD_WEIGHT + floor_level / 20).min(MAX_GOLD_WEIGHT);
+    let silver = (BASE_SILVER_WEIGHT + floor_level / 30).min(MAX_SILVER_WEIGHT);
+    if roll < gold {
+        ChestTier::Gold
+    } else if roll < gold + silver {
+        ChestTier::Silver
+    } else {
+        ChestTier::Wooden
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn count(floor_level: u32, tier: ChestTier) -> usize {
+        (0..2000u64)
+            .filter(|&h| chest_tier(h, 0, floor_level) == tier)
+            .count()
+    }
+
+    #[test]
+    fn test_chest_tier_deterministic() {
+        for index in 0..10 {
+            assert_eq!(
+                chest_tier(0xDEAD_BEEF, index, 120),
+                chest_tier(0xDEAD_BEEF, index, 120)
+            );
+        }
+    }
+
+    #[test]
+    fn test_deeper_floors_favor_better_chests() {
+        assert!(count(600, ChestTier::Gold) > count(1, ChestTier::Gold) * 3);
+        assert!(count(600, ChestTier::Wooden) < count(1, ChestTier::Wooden));
+    }
+
+    #[test]
+    fn test_every_tier_reachable_on_floor_one() {
+        for tier in [ChestTier::Wooden, ChestTier::Silver, ChestTier::Gold] {
+            assert!(count(1, tier) > 0, "{tier:?} never rolled");
+        }
+    }
+}
@@ -5,6 +5,7 @@
 
 use bevy::prelude::*;
 
+use super::puzzles::place_lever_gates;
 use super::wfc::{generate_layout, FloorLayout, TileType};
 use super::{FloorSpec, TowerSeed};
 
@@ -92,7 +93,8 @@ pub fn handle_floor_transitions(
 
         // Generate the new floor
         let spec = FloorSpec::generate(&progress.tower_seed, next_floor);
-        let layout = generate_layout(&spec);
+        let mut layout = generate_layout(&spec);
+        place_lever_gates(&mut layout, spec.hash);
 
         // Update progression
         progress.current_floor = next_floor;
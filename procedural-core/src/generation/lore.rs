@@ -0,0 +1,293 @@
+//! Environmental storytelling markers (murals, corpses, inscriptions).
+//!
+//! Each floor gets a few readable markers picked deterministically from the
+//! floor hash. Most are themed after the floor's biome tags; once the tower
+//! remembers a player habit (`TowerMemoryBank::dominant`) some markers echo
+//! that habit instead. Markers name a room slot rather than a tile so the
+//! client can place them on any wall of that room.
+
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Sha3_256};
+
+use super::FloorSpec;
+use crate::events::TowerMemoryBank;
+
+/// How a lore snippet is presented
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum LoreMarkerKind {
+    Mural,
+    Corpse,
+    Inscription,
+}
+
+/// A readable marker placed on a floor
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LoreMarker {
+    pub kind: LoreMarkerKind,
+    /// Biome tag or remembered action the snippet is themed after
+    pub theme: String,
+    pub text: String,
+    /// Room holding the marker, taken modulo the layout's room count
+    pub room_slot: u32,
+}
+
+type Snippet = (LoreMarkerKind, &'static str);
+
+const FIRE_LORE: &[Snippet] = &[
+    (
+        LoreMarkerKind::Mural,
+        "Smiths kneel before a furnace shaped like the tower's heart.",
+    ),
+    (
+        LoreMarkerKind::Corpse,
+        "A climber, armor fused to bone by some terrible heat.",
+    ),
+    (
+        LoreMarkerKind::Inscription,
+        "THE FLAME REMEMBERS WHAT THE STONE FORGETS",
+    ),
+];
+const WATER_LORE: &[Snippet] = &[
+    (
+        LoreMarkerKind::Mural,
+        "A flood rising through every floor, drowning the stairs.",
+    ),
+    (
+        LoreMarkerKind::Corpse,
+        "A diver still clutching a lantern that never went out.",
+    ),
+    (
+        LoreMarkerKind::Inscription,
+        "Every floor drinks from the one above.",
+    ),
+];
+const CORRUPTION_LORE: &[Snippet] = &[
+    (
+        LoreMarkerKind::Mural,
+        "Figures with hollow eyes reaching down from the void.",
+    ),
+    (
+        LoreMarkerKind::Corpse,
+        "Veins of black crystal grow from a fallen knight.",
+    ),
+    (
+        LoreMarkerKind::Inscription,
+        "IT WAS NEVER EMPTY. WE WERE NEVER ALONE.",
+    ),
+];
+const EXPLORATION_LORE: &[Snippet] = &[
+    (
+        LoreMarkerKind::Mural,
+        "A map of floors that do not exist yet.",
+    ),
+    (
+        LoreMarkerKind::Corpse,
+        "A cartographer, journal open to a half-drawn room.",
+    ),
+    (
+        LoreMarkerKind::Inscription,
+        "Turn left at the weeping stair. Trust no door twice.",
+    ),
+];
+const GENERIC_LORE: &[Snippet] = &[
+    (
+        LoreMarkerKind::Mural,
+        "Climbers in a line, each one smaller than the last.",
+    ),
+    (
+        LoreMarkerKind::Inscription,
+        "Count the floors. The tower counts you.",
+    ),
+];
+
+const COMBAT_MEMORY_LORE: &[Snippet] = &[
+    (
+        LoreMarkerKind::Mural,
+        "A warrior painted in fresh red, wearing your colors.",
+    ),
+    (
+        LoreMarkerKind::Inscription,
+        "THE TOWER HAS LEARNED THE SHAPE OF YOUR BLADE",
+    ),
+];
+const EXPLORE_MEMORY_LORE: &[Snippet] = &[
+    (
+        LoreMarkerKind::Mural,
+        "Your own footprints, carved into the stone ahead of you.",
+    ),
+    (
+        LoreMarkerKind::Inscription,
+        "The curious one returns. Let them see more.",
+    ),
+];
+const CRAFT_MEMORY_LORE: &[Snippet] = &[
+    (
+        LoreMarkerKind::Mural,
+        "Hands assembling the tower from pieces of itself.",
+    ),
+    (
+        LoreMarkerKind::Inscription,
+        "What you make here, the tower keeps.",
+    ),
+];
+
+/// Biome tags with their own snippet pools
+const BIOME_THEMES: [(&str, &[Snippet]); 4] = [
+    ("fire", FIRE_LORE),
+    ("water", WATER_LORE),
+    ("corruption", CORRUPTION_LORE),
+    ("exploration", EXPLORATION_LORE),
+];
+
+/// Markers per floor: `MIN_MARKERS` plus up to `EXTRA_MARKERS`
+const MIN_MARKERS: u64 = 3;
+const EXTRA_MARKERS: u64 = 3;
+/// Rooms a marker slot is drawn from before the client's modulo
+const ROOM_SLOTS: u64 = 64;
+/// Chance (out of 100) per remembered repetition that a marker echoes memory
+const MEMORY_CHANCE_PER_REPEAT: u64 = 10;
+/// Upper bound on the memory chance
+const MAX_MEMORY_CHANCE: u64 = 60;
+
+fn memory_pool(action: &str) -> Option<&'static [Snippet]> {
+    match action {
+        "attack" | "combat" => Some(COMBAT_MEMORY_LORE),
+        "explore" | "discover" => Some(EXPLORE_MEMORY_LORE),
+        "craft" | "gather" => Some(CRAFT_MEMORY_LORE),
+        _ => None,
+    }
+}
+
+fn marker_roll(hash: u64, index: u64) -> u64 {
+    let mut hasher = Sha3_256::new();
+    hasher.update(b"lore");
+    hasher.update(hash.to_le_bytes());
+    hasher.update(index.to_le_bytes());
+    let result = hasher.finalize();
+    u64::from_le_bytes(result[0..8].try_into().unwrap())
+}
+
+/// Pick the biome pool for a roll, weighted by the floor's positive biome tags
+fn biome_pool(spec: &FloorSpec, roll: u64) -> (&'static str, &'static [Snippet]) {
+    let weights: Vec<f32> = BIOME_THEMES
+        .iter()
+        .map(|(tag, _)| spec.biome_tags.get(tag).max(0.0))
+        .collect();
+    let total: f32 = weights.iter().sum();
+    if total <= 0.0 {
+        return ("generic", GENERIC_LORE);
+    }
+
+    let mut target = (roll % 10_000) as f32 / 10_000.0 * total;
+    for ((tag, pool), weight) in BIOME_THEMES.iter().zip(&weights) {
+        if target < *weight {
+            return (tag, pool);
+        }
+        target -= weight;
+    }
+    let (tag, pool) = BIOME_THEMES[weights.iter().rposition(|w| *w > 0.0).unwrap()];
+    (tag, pool)
+}
+
+/// Lore markers for a floor. Same spec, memory and hash give the same markers.
+pub fn lore_markers(spec: &FloorSpec, memory: &TowerMemoryBank, hash: u64) -> Vec<LoreMarker> {
+    let count = MIN_MARKERS + marker_roll(hash, u64::MAX) % (EXTRA_MARKERS + 1);
+    let remembered = memory
+        .dominant()
+        .and_then(|(action, repeats)| Some((action, memory_pool(action)?, repeats)));
+
+    let mut markers: Vec<LoreMarker> = Vec::with_capacity(count as usize);
+    for index in 0..count {
+        let roll = marker_roll(hash, index);
+        let (theme, pool) = match remembered {
+            Some((action, pool, repeats))
+                if (roll >> 48) % 100
+                    < (repeats as u64 * MEMORY_CHANCE_PER_REPEAT).min(MAX_MEMORY_CHANCE) =>
+            {
+                (action, pool)
+            }
+            _ => biome_pool(spec, roll),
+        };
+
+        // Walk the pool from the rolled snippet to avoid repeating a text
+        let start = (roll >> 16) as usize % pool.len();
+        let Some(&(kind, text)) = (0..pool.len())
+            .map(|offset| &pool[(start + offset) % pool.len()])
+            .find(|(_, text)| !markers.iter().any(|m| m.text == *text))
+        else {
+            continue;
+        };
+
+        markers.push(LoreMarker {
+            kind,
+            theme: theme.to_string(),
+            text: text.to_string(),
+            room_slot: ((roll >> 32) % ROOM_SLOTS) as u32,
+        });
+    }
+    markers
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generation::FloorTier;
+    use crate::semantic::SemanticTags;
+
+    fn spec(tags: Vec<(&str, f32)>) -> FloorSpec {
+        FloorSpec {
+            id: 7,
+            tier: FloorTier::Echelon2,
+            hash: 0,
+            biome_tags: SemanticTags::new(tags),
+        }
+    }
+
+    #[test]
+    fn test_corruption_floor_gets_corruption_lore() {
+        let floor = spec(vec![("corruption", 0.9)]);
+        for hash in 0..20 {
+            let markers = lore_markers(&floor, &TowerMemoryBank::default(), hash);
+            assert!(markers.len() >= MIN_MARKERS as usize);
+            assert!(
+                markers.iter().all(|m| m.theme == "corruption"),
+                "{markers:?}"
+            );
+            let texts: Vec<&str> = CORRUPTION_LORE.iter().map(|(_, t)| *t).collect();
+            assert!(markers.iter().all(|m| texts.contains(&m.text.as_str())));
+        }
+    }
+
+    #[test]
+    fn test_lore_placement_reproducible() {
+        let floor = spec(vec![("fire", 0.5), ("water", 0.4), ("exploration", 0.3)]);
+        let mut memory = TowerMemoryBank::default();
+        for _ in 0..4 {
+            memory.remember("explore");
+        }
+        assert_eq!(
+            lore_markers(&floor, &memory, 0xABCD),
+            lore_markers(&floor, &memory, 0xABCD)
+        );
+    }
+
+    #[test]
+    fn test_tower_memory_shapes_lore() {
+        let floor = spec(vec![("water", 0.8)]);
+        let mut memory = TowerMemoryBank::default();
+        for _ in 0..10 {
+            memory.remember("combat");
+        }
+
+        let count_theme = |memory: &TowerMemoryBank, theme: &str| {
+            (0..20)
+                .flat_map(|hash| lore_markers(&floor, memory, hash))
+                .filter(|m| m.theme == theme)
+                .count()
+        };
+        assert_eq!(count_theme(&TowerMemoryBank::default(), "combat"), 0);
+        assert!(count_theme(&memory, "combat") > 0);
+        // Still mostly a water floor
+        assert!(count_theme(&memory, "water") > 0);
+    }
+}
@@ -1,6 +1,21 @@
+pub mod chests;
 pub mod floor_manager;
+pub mod lore;
+pub mod mood;
+pub mod props;
+pub mod puzzles;
+pub mod shrines;
+pub mod verify;
 pub mod wfc;
 
+pub use chests::{chest_tier, ChestTier};
+pub use lore::{lore_markers, LoreMarker, LoreMarkerKind};
+pub use mood::{floor_mood, FloorMood};
+pub use props::{place_props, PropKind, PropPlacement};
+pub use puzzles::{mark_phase_gated, place_lever_gates, toggle_lever, LeverGate, PhaseGatedTile};
+pub use shrines::{shrine_effect, ShrineEffect};
+pub use verify::{layout_checksum, verify_layout, LayoutMismatch, LayoutVerdict, TileMismatch};
+
 use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
 use sha3::{Digest, Sha3_256};
@@ -51,6 +66,15 @@ impl TowerSeed {
     }
 }
 
+/// One xorshift64 step; the shared deterministic RNG for generation, loot
+/// and mutators. `x` must be non-zero (zero maps to itself).
+pub fn xorshift(mut x: u64) -> u64 {
+    x ^= x << 13;
+    x ^= x >> 7;
+    x ^= x << 17;
+    x
+}
+
 /// Floor tier determines difficulty and mechanics
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum FloorTier {
@@ -60,13 +84,54 @@ pub enum FloorTier {
     Echelon4, // 501+: architects, voting rights
 }
 
+/// Last floor of each echelon; everything above `echelon3_max` is Echelon4.
+/// Configurable so server rulesets can shorten or stretch the tower.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TierBoundaries {
+    pub echelon1_max: u32,
+    pub echelon2_max: u32,
+    pub echelon3_max: u32,
+}
+
+impl Default for TierBoundaries {
+    fn default() -> Self {
+        Self {
+            echelon1_max: 100,
+            echelon2_max: 300,
+            echelon3_max: 500,
+        }
+    }
+}
+
+impl TierBoundaries {
+    /// First floor of `tier` under these boundaries
+    pub fn first_floor(&self, tier: FloorTier) -> u32 {
+        match tier {
+            FloorTier::Echelon1 => 1,
+            FloorTier::Echelon2 => self.echelon1_max + 1,
+            FloorTier::Echelon3 => self.echelon2_max + 1,
+            FloorTier::Echelon4 => self.echelon3_max + 1,
+        }
+    }
+}
+
 impl FloorTier {
     pub fn from_floor_id(id: u32) -> Self {
-        match id {
-            1..=100 => Self::Echelon1,
-            101..=300 => Self::Echelon2,
-            301..=500 => Self::Echelon3,
-            _ => Self::Echelon4,
+        Self::from_floor_id_with(id, &TierBoundaries::default())
+    }
+
+    /// Classify a floor using custom tier boundaries
+    pub fn from_floor_id_with(id: u32, boundaries: &TierBoundaries) -> Self {
+        if id == 0 {
+            Self::Echelon4
+        } else if id <= boundaries.echelon1_max {
+            Self::Echelon1
+        } else if id <= boundaries.echelon2_max {
+            Self::Echelon2
+        } else if id <= boundaries.echelon3_max {
+            Self::Echelon3
+        } else {
+            Self::Echelon4
         }
     }
 }
@@ -111,6 +176,74 @@ impl FloorSpec {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_default_boundaries_match_fixed_tiers() {
+        let expected = |id: u32| match id {
+            1..=100 => FloorTier::Echelon1,
+            101..=300 => FloorTier::Echelon2,
+            301..=500 => FloorTier::Echelon3,
+            _ => FloorTier::Echelon4,
+        };
+        let defaults = TierBoundaries::default();
+        for id in (0..=1000).chain([u32::MAX]) {
+            assert_eq!(FloorTier::from_floor_id_with(id, &defaults), expected(id));
+            assert_eq!(FloorTier::from_floor_id(id), expected(id));
+        }
+    }
+
+    #[test]
+    fn test_custom_boundaries_reclassify() {
+        let short = TierBoundaries {
+            echelon1_max: 20,
+            echelon2_max: 50,
+            echelon3_max: 80,
+        };
+        assert_eq!(
+            FloorTier::from_floor_id_with(20, &short),
+            FloorTier::Echelon1
+        );
+        assert_eq!(
+            FloorTier::from_floor_id_with(21, &short),
+            FloorTier::Echelon2
+        );
+        assert_eq!(
+            FloorTier::from_floor_id_with(60, &short),
+            FloorTier::Echelon3
+        );
+        assert_eq!(
+            FloorTier::from_floor_id_with(100, &short),
+            FloorTier::Echelon4
+        );
+        assert_eq!(FloorTier::from_floor_id(100), FloorTier::Echelon1);
+    }
+
+    #[test]
+    fn test_first_floor_starts_each_tier() {
+        let short = TierBoundaries {
+            echelon1_max: 20,
+            echelon2_max: 50,
+            echelon3_max: 80,
+        };
+        for boundaries in [TierBoundaries::default(), short] {
+            for tier in [
+                FloorTier::Echelon1,
+                FloorTier::Echelon2,
+                FloorTier::Echelon3,
+                FloorTier::Echelon4,
+            ] {
+                let first = boundaries.first_floor(tier);
+                assert_eq!(FloorTier::from_floor_id_with(first, &boundaries), tier);
+                if first > 1 {
+                    assert_ne!(FloorTier::from_floor_id_with(first - 1, &boundaries), tier);
+                }
+            }
+        }
+        assert_eq!(
+            TierBoundaries::default().first_floor(FloorTier::Echelon3),
+            301
+        );
+    }
+
     #[test]
     fn test_deterministic_generation() {
         let seed = TowerSeed { seed: 12345 };
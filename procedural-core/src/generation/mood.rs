@@ -0,0 +1,142 @@
+//! Floor mood for audio direction.
+//!
+//! Summarizes a floor as tension / mystery / danger in 0..1 plus a music tag
+//! the client maps to a soundtrack. Inputs are the floor's biome tags, its
+//! tier and its active mutators, so the mood is as deterministic as the floor.
+
+use serde::{Deserialize, Serialize};
+
+use super::{FloorSpec, FloorTier};
+use crate::mutators::{FloorMutator, MutatorCategory, MutatorType};
+
+/// Mood descriptor for one floor
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FloorMood {
+    pub tension: f32,
+    pub mystery: f32,
+    pub danger: f32,
+    /// "dread", "tense", "mysterious" or "calm"
+    pub music_tag: String,
+}
+
+/// Mutator difficulty × intensity that counts as full pressure
+const FULL_MUTATOR_PRESSURE: f32 = 12.0;
+
+/// Mood of a floor with the given active mutators
+pub fn floor_mood(spec: &FloorSpec, mutators: &[FloorMutator]) -> FloorMood {
+    let tags = &spec.biome_tags;
+    let corruption = tags.get("corruption").clamp(0.0, 1.0);
+    let fire = tags.get("fire").clamp(0.0, 1.0);
+    let water = tags.get("water").clamp(0.0, 1.0);
+    let exploration = tags.get("exploration").clamp(0.0, 1.0);
+
+    let depth = match spec.tier {
+        FloorTier::Echelon1 => 0.0,
+        FloorTier::Echelon2 => 0.33,
+        FloorTier::Echelon3 => 0.66,
+        FloorTier::Echelon4 => 1.0,
+    };
+
+    let pressure = (mutators
+        .iter()
+        .map(|m| m.difficulty as f32 * m.intensity)
+        .sum::<f32>()
+        / FULL_MUTATOR_PRESSURE)
+        .min(1.0);
+    let challenge = if mutators
+        .iter()
+        .any(|m| m.category == MutatorCategory::Challenge)
+    {
+        1.0
+    } else {
+        0.0
+    };
+    let obscured = if mutators.iter().any(|m| {
+        matches!(
+            m.mutator_type,
+            MutatorType::Darkness | MutatorType::TagShift | MutatorType::MagneticField
+        )
+    }) {
+        1.0
+    } else {
+        0.0
+    };
+
+    let danger = (0.35 * depth + 0.35 * corruption + 0.1 * fire + 0.2 * pressure).clamp(0.0, 1.0);
+    let tension =
+        (0.3 * depth + 0.25 * corruption + 0.3 * pressure + 0.15 * challenge).clamp(0.0, 1.0);
+    let mystery = (0.5 * exploration + 0.2 * water + 0.3 * obscured).clamp(0.0, 1.0);
+
+    let music_tag = if danger >= 0.7 {
+        "dread"
+    } else if tension >= 0.5 {
+        "tense"
+    } else if mystery >= 0.5 {
+        "mysterious"
+    } else {
+        "calm"
+    };
+
+    FloorMood {
+        tension,
+        mystery,
+        danger,
+        music_tag: music_tag.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generation::TowerSeed;
+    use crate::mutators::{all_mutator_types, generate_floor_mutators};
+    use crate::semantic::SemanticTags;
+
+    fn spec(tier: FloorTier, tags: Vec<(&str, f32)>) -> FloorSpec {
+        FloorSpec {
+            id: 1,
+            tier,
+            hash: 0,
+            biome_tags: SemanticTags::new(tags),
+        }
+    }
+
+    #[test]
+    fn test_corrupted_deep_floor_is_dangerous() {
+        let floor = spec(
+            FloorTier::Echelon4,
+            vec![("corruption", 0.95), ("fire", 0.6)],
+        );
+        let hard: Vec<FloorMutator> = all_mutator_types()
+            .into_iter()
+            .filter(|m| m.difficulty >= 4)
+            .take(4)
+            .collect();
+        let mood = floor_mood(&floor, &hard);
+        assert!(mood.danger >= 0.7, "{mood:?}");
+        assert!(mood.tension >= 0.7, "{mood:?}");
+        assert_eq!(mood.music_tag, "dread");
+    }
+
+    #[test]
+    fn test_calm_low_floor_is_calm() {
+        let floor = spec(
+            FloorTier::Echelon1,
+            vec![("corruption", 0.05), ("exploration", 0.2)],
+        );
+        let mood = floor_mood(&floor, &[]);
+        assert!(mood.danger < 0.2, "{mood:?}");
+        assert!(mood.tension < 0.2, "{mood:?}");
+        assert_eq!(mood.music_tag, "calm");
+    }
+
+    #[test]
+    fn test_mood_deterministic() {
+        let seed = TowerSeed { seed: 777 };
+        for floor_id in [1, 150, 420, 900] {
+            let floor = FloorSpec::generate(&seed, floor_id);
+            let mutators = generate_floor_mutators(seed.seed, floor_id);
+            assert_eq!(floor_mood(&floor, &mutators), floor_mood(&floor, &mutators));
+        }
+    }
+}
@@ -0,0 +1,231 @@
+//! Ambient prop placement (torches, rubble, banners, ...).
+//!
+//! Props are purely visual and placed deterministically from the floor hash,
+//! so every client decorates the same floor identically. Props hug walls and
+//! corners and never sit in corridors, so they can't block a path.
+
+use serde::{Deserialize, Serialize};
+
+use super::wfc::{FloorLayout, TileType};
+use super::xorshift;
+use crate::semantic::SemanticTags;
+
+/// Visual prop kinds, grouped by biome theme
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum PropKind {
+    // Fire
+    Torch,
+    Brazier,
+    EmberPile,
+    // Water
+    Puddle,
+    CoralGrowth,
+    DrippingPipe,
+    // Corruption
+    VoidCrystal,
+    Cobweb,
+    BoneHeap,
+    // Exploration
+    Banner,
+    MapTable,
+    // Generic
+    Rubble,
+    Crate,
+    Barrel,
+}
+
+impl PropKind {
+    /// Biome tag this prop is themed after, None for generic props
+    pub fn theme(&self) -> Option<&'static str> {
+        match self {
+            Self::Torch | Self::Brazier | Self::EmberPile => Some("fire"),
+            Self::Puddle | Self::CoralGrowth | Self::DrippingPipe => Some("water"),
+            Self::VoidCrystal | Self::Cobweb | Self::BoneHeap => Some("corruption"),
+            Self::Banner | Self::MapTable => Some("exploration"),
+            Self::Rubble | Self::Crate | Self::Barrel => None,
+        }
+    }
+}
+
+const FIRE_PROPS: &[PropKind] = &[PropKind::Torch, PropKind::Brazier, PropKind::EmberPile];
+const WATER_PROPS: &[PropKind] = &[
+    PropKind::Puddle,
+    PropKind::CoralGrowth,
+    PropKind::DrippingPipe,
+];
+const CORRUPTION_PROPS: &[PropKind] =
+    &[PropKind::VoidCrystal, PropKind::Cobweb, PropKind::BoneHeap];
+const EXPLORATION_PROPS: &[PropKind] = &[PropKind::Banner, PropKind::MapTable];
+const GENERIC_PROPS: &[PropKind] = &[PropKind::Rubble, PropKind::Crate, PropKind::Barrel];
+
+/// A single prop placed on the floor grid
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PropPlacement {
+    pub x: usize,
+    pub y: usize,
+    pub kind: PropKind,
+    /// Yaw in degrees, facing away from the supporting wall (0 = +Y)
+    pub rotation_deg: f32,
+    /// True when the prop sits in a corner (two adjacent walls)
+    pub in_corner: bool,
+}
+
+/// Chance (out of 100) that a wall-adjacent tile gets a prop
+const WALL_PROP_CHANCE: u64 = 15;
+/// Chance (out of 100) that a corner tile gets a prop
+const CORNER_PROP_CHANCE: u64 = 40;
+/// Chance (out of 100) that a prop uses the biome palette instead of generic
+const THEMED_PROP_CHANCE: u64 = 65;
+
+/// Place ambient props on a generated layout.
+///
+/// Only plain `Floor` tiles next to a wall are considered; tiles walled on two
+/// opposite sides (corridors) are skipped so props never block movement.
+pub fn place_props(layout: &FloorLayout, biome: &SemanticTags, hash: u64) -> Vec<PropPlacement> {
+    let palette = biome_palette(biome);
+    let mut placements = Vec::new();
+    let mut rng = hash.max(1);
+
+    for y in 0..layout.height {
+        for x in 0..layout.width {
+            if layout.tiles[y][x] != TileType::Floor {
+                continue;
+            }
+
+            let north = is_wall(layout, x as i64, y as i64 - 1);
+            let south = is_wall(layout, x as i64, y as i64 + 1);
+            let west = is_wall(layout, x as i64 - 1, y as i64);
+            let east = is_wall(layout, x as i64 + 1, y as i64);
+
+            // Corridor or dead-end: placing here could block a path
+            if (north && south) || (west && east) {
+                continue;
+            }
+            if !(north || south || west || east) {
+                continue;
+            }
+
+            let in_corner = (north || south) && (west || east);
+            let chance = if in_corner {
+                CORNER_PROP_CHANCE
+            } else {
+                WALL_PROP_CHANCE
+            };
+
+            rng = xorshift(rng);
+            if rng % 100 >= chance {
+                continue;
+            }
+
+            rng = xorshift(rng);
+            let kind = if !palette.is_empty() && rng % 100 < THEMED_PROP_CHANCE {
+                rng = xorshift(rng);
+                palette[(rng % palette.len() as u64) as usize]
+            } else {
+                rng = xorshift(rng);
+                GENERIC_PROPS[(rng % GENERIC_PROPS.len() as u64) as usize]
+            };
+
+            // Face away from the wall the prop leans against
+            let rotation_deg = if north {
+                0.0
+            } else if east {
+                90.0
+            } else if south {
+                180.0
+            } else {
+                270.0
+            };
+
+            placements.push(PropPlacement {
+                x,
+                y,
+                kind,
+                rotation_deg,
+                in_corner,
+            });
+        }
+    }
+
+    placements
+}
+
+/// Themed props for the floor's dominant biome tag (empty when no theme is strong)
+fn biome_palette(biome: &SemanticTags) -> &'static [PropKind] {
+    let themes: [(&str, &'static [PropKind]); 4] = [
+        ("fire", FIRE_PROPS),
+        ("water", WATER_PROPS),
+        ("corruption", CORRUPTION_PROPS),
+        ("exploration", EXPLORATION_PROPS),
+    ];
+
+    let mut best: (&'static [PropKind], f32) = (&[], 0.3);
+    for (tag, props) in themes {
+        let val = biome.get(tag);
+        if val > best.1 {
+            best = (props, val);
+        }
+    }
+    best.0
+}
+
+fn is_wall(layout: &FloorLayout, x: i64, y: i64) -> bool {
+    if x < 0 || y < 0 || x as usize >= layout.width || y as usize >= layout.height {
+        return true;
+    }
+    layout.tiles[y as usize][x as usize] == TileType::Wall
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generation::wfc::generate_layout;
+    use crate::generation::{FloorSpec, TowerSeed};
+
+    fn layout_for(floor_id: u32) -> (FloorSpec, FloorLayout) {
+        let spec = FloorSpec::generate(&TowerSeed { seed: 42 }, floor_id);
+        let layout = generate_layout(&spec);
+        (spec, layout)
+    }
+
+    #[test]
+    fn test_fire_biome_places_fire_props() {
+        let (spec, layout) = layout_for(200);
+        let fire = SemanticTags::new(vec![("fire", 0.9), ("water", 0.1)]);
+        let props = place_props(&layout, &fire, spec.hash);
+
+        assert!(!props.is_empty(), "Floor should get some props");
+        let fire_count = props
+            .iter()
+            .filter(|p| p.kind.theme() == Some("fire"))
+            .count();
+        assert!(fire_count > 0, "Fire biome should place fire props");
+        assert!(props
+            .iter()
+            .all(|p| matches!(p.kind.theme(), Some("fire") | None)));
+    }
+
+    #[test]
+    fn test_props_avoid_corridors() {
+        for floor_id in 1..50 {
+            let (spec, layout) = layout_for(floor_id);
+            let props = place_props(&layout, &spec.biome_tags, spec.hash);
+
+            for p in &props {
+                assert_eq!(layout.tiles[p.y][p.x], TileType::Floor);
+                let (x, y) = (p.x as i64, p.y as i64);
+                let ns = is_wall(&layout, x, y - 1) && is_wall(&layout, x, y + 1);
+                let we = is_wall(&layout, x - 1, y) && is_wall(&layout, x + 1, y);
+                assert!(!ns && !we, "Prop at ({}, {}) blocks a corridor", p.x, p.y);
+            }
+        }
+    }
+
+    #[test]
+    fn test_props_reproducible() {
+        let (spec, layout) = layout_for(7);
+        let a = place_props(&layout, &spec.biome_tags, spec.hash);
+        let b = place_props(&layout, &spec.biome_tags, spec.hash);
+        assert_eq!(a, b);
+    }
+}
@@ -0,0 +1,272 @@
+//! Environmental puzzle elements (lever-and-gate).
+//!
+//! Each treasure chest is sealed behind a gate; the lever that opens it is
+//! placed on a wall elsewhere on the floor, always reachable with every gate
+//! still closed, so a floor can never lock the player out of its own puzzle.
+//! Placement is deterministic from the floor hash like `place_props`; the
+//! floor manager seals chests when it loads a floor.
+//!
+//! Breath-synced doors and shrines only respond during one phase of the
+//! Tower's breath; see `world::phase_gated_interactable`. `generate_layout`
+//! flags them on every floor.
+
+use serde::{Deserialize, Serialize};
+
+use super::wfc::{flood_from_spawn, FloorLayout, RoomType, TileType};
+use super::xorshift;
+use crate::world::{phase_gated_interactable, BreathPhase};
+
+/// A lever and the gate it controls
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LeverGate {
+    pub id: u32,
+    pub lever: (usize, usize),
+    pub gate: (usize, usize),
+    /// Tile revealed when the gate opens (the sealed chest)
+    pub behind: TileType,
+    pub open: bool,
+}
+
+/// Seal each treasure chest behind a gate and place its lever.
+///
+/// Gates are recorded on `layout.lever_gates` for `toggle_lever`; the new
+/// puzzles are also returned. Chests with no valid lever spot stay unsealed.
+pub fn place_lever_gates(layout: &mut FloorLayout, hash: u64) -> Vec<LeverGate> {
+    let mut rng = hash.max(1);
+    let mut placed = Vec::new();
+
+    let treasure_rooms: Vec<_> = layout
+        .rooms
+        .iter()
+        .filter(|r| r.room_type == RoomType::Treasure)
+        .map(|r| (r.x, r.y, r.width, r.height))
+        .collect();
+
+    for &(rx, ry, rw, rh) in &treasure_rooms {
+        let chest = (rx + rw / 2, ry + rh / 2);
+        if layout.tiles.get(chest.1).and_then(|row| row.get(chest.0)) != Some(&TileType::Chest) {
+            continue;
+        }
+
+        // Close the gate first so the lever spot is checked against the sealed layout
+        layout.tiles[chest.1][chest.0] = TileType::Gate;
+        let reached = flood_from_spawn(layout);
+
+        let candidates: Vec<(usize, usize)> = (0..layout.height)
+            .flat_map(|y| (0..layout.width).map(move |x| (x, y)))
+            .filter(|&(x, y)| {
+                layout.tiles[y][x] == TileType::Floor
+                    && reached[y][x]
+                    && touches_wall(layout, x, y)
+                    && !treasure_rooms
+                        .iter()
+                        .any(|&(tx, ty, tw, th)| x >= tx && x < tx + tw && y >= ty && y < ty + th)
+            })
+            .collect();
+
+        if candidates.is_empty() {
+            layout.tiles[chest.1][chest.0] = TileType::Chest;
+            continue;
+        }
+
+        rng = xorshift(rng);
+        let lever = candidates[(rng % candidates.len() as u64) as usize];
+        layout.tiles[lever.1][lever.0] = TileType::Lever;
+
+        let gate = LeverGate {
+            id: layout.lever_gates.len() as u32,
+            lever,
+            gate: chest,
+            behind: TileType::Chest,
+            open: false,
+        };
+        layout.lever_gates.push(gate.clone());
+        placed.push(gate);
+    }
+
+    placed
+}
+
+/// Pull a lever, toggling its linked gate. Returns the gate's new open
+/// state, or None if the floor has no such lever.
+pub fn toggle_lever(layout: &mut FloorLayout, lever_id: u32) -> Option<bool> {
+    let lg = layout.lever_gates.iter_mut().find(|lg| lg.id == lever_id)?;
+    lg.open = !lg.open;
+    let (x, y) = lg.gate;
+    layout.tiles[y][x] = if lg.open { lg.behind } else { TileType::Gate };
+    Some(lg.open)
+}
+
+/// A door or shrine that only works during one breath phase
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PhaseGatedTile {
+    pub x: usize,
+    pub y: usize,
+    pub required_phase: BreathPhase,
+}
+
+/// Chance (out of 100) that a door or shrine becomes breath-synced
+const PHASE_GATE_CHANCE: u64 = 25;
+
+const BREATH_PHASES: [BreathPhase; 4] = [
+    BreathPhase::Inhale,
+    BreathPhase::Hold,
+    BreathPhase::Exhale,
+    BreathPhase::Pause,
+];
+
+/// Flag some doors and shrines as breath-synced, recording them on
+/// `layout.phase_gated`. Tiles themselves are unchanged.
+pub fn mark_phase_gated(layout: &mut FloorLayout, hash: u64) -> Vec<PhaseGatedTile> {
+    let mut rng = hash.max(1);
+    let mut marked = Vec::new();
+
+    for y in 0..layout.height {
+        for x in 0..layout.width {
+            if !matches!(layout.tiles[y][x], TileType::Door | TileType::Shrine) {
+                continue;
+            }
+            rng = xorshift(rng);
+            if rng % 100 >= PHASE_GATE_CHANCE {
+                continue;
+            }
+            rng = xorshift(rng);
+            marked.push(PhaseGatedTile {
+                x,
+                y,
+                required_phase: BREATH_PHASES[(rng % BREATH_PHASES.len() as u64) as usize],
+            });
+        }
+    }
+
+    layout.phase_gated.extend(marked.iter().cloned());
+    marked
+}
+
+impl FloorLayout {
+    /// Can the tile at (x, y) be interacted with during `phase`?
+    /// Tiles that aren't breath-synced are always usable.
+    pub fn is_interactable(&self, x: usize, y: usize, phase: BreathPhase) -> bool {
+        self.phase_gated
+            .iter()
+            .find(|g| g.x == x && g.y == y)
+            .is_none_or(|g| phase_gated_interactable(g.required_phase, phase))
+    }
+}
+
+fn touches_wall(layout: &FloorLayout, x: usize, y: usize) -> bool {
+    let neighbors = [
+        (x.wrapping_sub(1), y),
+        (x + 1, y),
+        (x, y.wrapping_sub(1)),
+        (x, y + 1),
+    ];
+    neighbors.iter().any(|&(nx, ny)| {
+        ny < layout.height && nx < layout.width && layout.tiles[ny][nx] == TileType::Wall
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generation::wfc::{connectivity_check, generate_layout};
+    use crate::generation::{FloorSpec, TowerSeed};
+
+    fn layout_for(floor_id: u32) -> (FloorSpec, FloorLayout) {
+        let spec = FloorSpec::generate(&TowerSeed { seed: 42 }, floor_id);
+        let layout = generate_layout(&spec);
+        (spec, layout)
+    }
+
+    #[test]
+    fn test_every_gate_has_reachable_lever() {
+        let mut total = 0;
+        for floor_id in 1..80 {
+            let (spec, mut layout) = layout_for(floor_id);
+            let gates = place_lever_gates(&mut layout, spec.hash);
+            total += gates.len();
+
+            let reached = flood_from_spawn(&layout);
+            for g in &gates {
+                assert_eq!(layout.tiles[g.gate.1][g.gate.0], TileType::Gate);
+                assert_eq!(layout.tiles[g.lever.1][g.lever.0], TileType::Lever);
+                assert!(
+                    reached[g.lever.1][g.lever.0],
+                    "Floor {} lever {} unreachable with gates closed",
+                    floor_id, g.id
+                );
+            }
+            // Gates only seal the chest itself, never a path
+            assert!(connectivity_check(&layout).is_empty());
+        }
+        assert!(total > 0, "Some floors should get lever puzzles");
+    }
+
+    #[test]
+    fn test_toggle_opens_linked_gate() {
+        let (spec, mut layout) = (1..80)
+            .map(layout_for)
+            .find(|(_, l)| {
+                l.rooms
+                    .iter()
+                    .filter(|r| r.room_type == RoomType::Treasure)
+                    .count()
+                    >= 2
+            })
+            .expect("a floor with two treasure rooms");
+        let gates = place_lever_gates(&mut layout, spec.hash);
+        assert!(gates.len() >= 2);
+
+        assert_eq!(toggle_lever(&mut layout, gates[1].id), Some(true));
+        let (gx, gy) = gates[1].gate;
+        assert_eq!(layout.tiles[gy][gx], TileType::Chest);
+        // The other gate stays shut
+        let (ox, oy) = gates[0].gate;
+        assert_eq!(layout.tiles[oy][ox], TileType::Gate);
+
+        assert_eq!(toggle_lever(&mut layout, gates[1].id), Some(false));
+        assert_eq!(layout.tiles[gy][gx], TileType::Gate);
+        assert_eq!(toggle_lever(&mut layout, 999), None);
+    }
+
+    #[test]
+    fn test_phase_gated_tiles_follow_breath() {
+        let (_, layout) = (1..80)
+            .map(layout_for)
+            .find(|(_, l)| !l.phase_gated.is_empty())
+            .expect("a floor with a breath-synced tile");
+        let g = &layout.phase_gated[0];
+        assert!(matches!(
+            layout.tiles[g.y][g.x],
+            TileType::Door | TileType::Shrine
+        ));
+
+        for t in (0..1080).step_by(30) {
+            let (phase, _) = crate::world::breath_phase_at(t as f32);
+            assert_eq!(
+                layout.is_interactable(g.x, g.y, phase),
+                phase == g.required_phase
+            );
+        }
+        // Unflagged tiles are always usable
+        assert!(layout.is_interactable(0, 0, BreathPhase::Pause));
+    }
+
+    #[test]
+    fn test_lever_gates_deterministic() {
+        let (spec, mut a) = layout_for(12);
+        let (_, mut b) = layout_for(12);
+        assert_eq!(
+            place_lever_gates(&mut a, spec.hash),
+            place_lever_gates(&mut b, spec.hash)
+        );
+        assert_eq!(a.tiles, b.tiles);
+    }
+
+    #[test]
+    fn test_phase_gating_deterministic() {
+        let (_, a) = layout_for(12);
+        let (_, b) = layout_for(12);
+        assert_eq!(a.phase_gated, b.phase_gated);
+    }
+}
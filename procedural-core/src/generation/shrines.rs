@@ -0,0 +1,78 @@
+//! Shrine effects.
+//!
+//! Each `Shrine` tile on a floor gets one effect, picked from the floor hash
+//! and the shrine's index so every client agrees on what a shrine does.
+
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Sha3_256};
+
+/// What touching a shrine does
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ShrineEffect {
+    /// Restore health
+    Heal,
+    /// Temporary damage buff
+    Buff,
+    /// Remove negative status effects
+    Cleanse,
+    /// Move to another shrine or the floor's entrance
+    Teleport,
+}
+
+/// Relative odds of each effect (heals are most common, teleports rarest)
+const SHRINE_WEIGHTS: [(ShrineEffect, u64); 4] = [
+    (ShrineEffect::Heal, 35),
+    (ShrineEffect::Buff, 30),
+    (ShrineEffect::Cleanse, 20),
+    (ShrineEffect::Teleport, 15),
+];
+
+/// Effect of the `shrine_index`-th shrine (row-major tile order) on a floor
+pub fn shrine_effect(floor_hash: u64, shrine_index: u32) -> ShrineEffect {
+    let mut hasher = Sha3_256::new();
+    hasher.update(b"shrine");
+    hasher.update(floor_hash.to_le_bytes());
+    hasher.update(shrine_index.to_le_bytes());
+    let result = hasher.finalize();
+    let roll = u64::from_le_bytes(result[0..8].try_into().unwrap());
+
+    let total: u64 = SHRINE_WEIGHTS.iter().map(|(_, w)| w).sum();
+    let mut pick = roll % total;
+    for (effect, weight) in SHRINE_WEIGHTS {
+        if pick < weight {
+            return effect;
+        }
+        pick -= weight;
+    }
+    ShrineEffect::Heal
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_shrine_effect_deterministic() {
+        for index in 0..10 {
+            assert_eq!(
+                shrine_effect(0xDEAD_BEEF, index),
+                shrine_effect(0xDEAD_BEEF, index)
+            );
+        }
+    }
+
+    #[test]
+    fn test_shrines_on_a_floor_can_differ() {
+        let effects: HashSet<_> = (0..8).map(|i| shrine_effect(42, i)).collect();
+        assert!(effects.len() > 1);
+    }
+
+    #[test]
+    fn test_all_shrine_effects_reachable() {
+        let effects: HashSet<_> = (0..200u64).map(|seed| shrine_effect(seed, 0)).collect();
+        for (effect, _) in SHRINE_WEIGHTS {
+            assert!(effects.contains(&effect), "{effect:?} never rolled");
+        }
+    }
+}
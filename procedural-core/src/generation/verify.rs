@@ -0,0 +1,211 @@
+//! Client/server layout parity.
+//!
+//! A floor layout is fully determined by the tower seed and floor id, so the
+//! server can regenerate it and compare against what a client claims to have
+//! built. Identical layouts are accepted on their checksum alone; otherwise
+//! the comparison covers the tile grid, room count and the spawn and exit
+//! points, and reports every difference it finds.
+
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Sha3_256};
+
+use super::wfc::{generate_layout, FloorLayout, TileType};
+use super::{FloorSpec, TowerSeed};
+
+/// Differing tiles listed in a report; the rest only show up in the count
+pub const MAX_REPORTED_TILES: usize = 32;
+
+/// Canonical vs claimed value of one compared field
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FieldMismatch<T> {
+    pub expected: T,
+    pub claimed: T,
+}
+
+/// One tile that differs from the canonical layout
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TileMismatch {
+    pub x: usize,
+    pub y: usize,
+    pub expected: TileType,
+    /// `None` if the claimed grid has no tile at this coordinate
+    pub claimed: Option<TileType>,
+}
+
+/// Everything that differs between the canonical and claimed layouts
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct LayoutMismatch {
+    /// `(width, height)`; a claimed grid whose rows don't match its own
+    /// declared size also lands here
+    pub dimensions: Option<FieldMismatch<(usize, usize)>>,
+    /// First `MAX_REPORTED_TILES` differing or missing tiles in row-major
+    /// order (only compared when the declared size matches)
+    pub tiles: Vec<TileMismatch>,
+    pub tile_mismatch_count: usize,
+    pub room_count: Option<FieldMismatch<usize>>,
+    pub spawn_points: Option<FieldMismatch<Vec<(usize, usize)>>>,
+    pub exit_point: Option<FieldMismatch<(usize, usize)>>,
+}
+
+/// Outcome of `verify_layout`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum LayoutVerdict {
+    Match,
+    Mismatch(LayoutMismatch),
+}
+
+impl LayoutVerdict {
+    pub fn is_match(&self) -> bool {
+        matches!(self, Self::Match)
+    }
+}
+
+/// Checksum of a layout, comparable across machines
+pub fn layout_checksum(layout: &FloorLayout) -> u64 {
+    let mut hasher = Sha3_256::new();
+    hasher.update(serde_json::to_vec(layout).unwrap_or_default());
+    let result = hasher.finalize();
+    u64::from_le_bytes(result[0..8].try_into().unwrap())
+}
+
+fn field<T: PartialEq>(expected: T, claimed: T) -> Option<FieldMismatch<T>> {
+    (expected != claimed).then_some(FieldMismatch { expected, claimed })
+}
+
+/// Regenerate floor `floor_id` of tower `seed` and compare `claimed` to it
+pub fn verify_layout(seed: u64, floor_id: u32, claimed: &FloorLayout) -> LayoutVerdict {
+    let spec = FloorSpec::generate(&TowerSeed { seed }, floor_id);
+    let expected = generate_layout(&spec);
+    if layout_checksum(claimed) == layout_checksum(&expected) {
+        return LayoutVerdict::Match;
+    }
+
+    let mut report = LayoutMismatch::default();
+    let well_formed = claimed.tiles.len() == claimed.height
+        && claimed.tiles.iter().all(|row| row.len() == claimed.width);
+    let size = (expected.width, expected.height);
+    let claimed_size = (claimed.width, claimed.height);
+    if size != claimed_size || !well_formed {
+        report.dimensions = Some(FieldMismatch {
+            expected: size,
+            claimed: claimed_size,
+        });
+    }
+    if size == claimed_size {
+        for (y, row) in expected.tiles.iter().enumerate() {
+            for (x, &tile) in row.iter().enumerate() {
+                let claimed_tile = claimed.tiles.get(y).and_then(|r| r.get(x)).copied();
+                if claimed_tile == Some(tile) {
+                    continue;
+                }
+                report.tile_mismatch_count += 1;
+                if report.tiles.len() < MAX_REPORTED_TILES {
+                    report.tiles.push(TileMismatch {
+                        x,
+                        y,
+                        expected: tile,
+                        claimed: claimed_tile,
+                    });
+                }
+            }
+        }
+    }
+
+    report.room_count = field(expected.rooms.len(), claimed.rooms.len());
+    report.spawn_points = field(expected.spawn_points, claimed.spawn_points.clone());
+    report.exit_point = field(expected.exit_point, claimed.exit_point);
+
+    if report == LayoutMismatch::default() {
+        LayoutVerdict::Match
+    } else {
+        LayoutVerdict::Mismatch(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn canonical(seed: u64, floor_id: u32) -> FloorLayout {
+        generate_layout(&FloorSpec::generate(&TowerSeed { seed }, floor_id))
+    }
+
+    #[test]
+    fn test_genuine_layout_matches() {
+        for floor_id in [1, 150, 420] {
+            let layout = canonical(42, floor_id);
+            assert_eq!(verify_layout(42, floor_id, &layout), LayoutVerdict::Match);
+        }
+        // Same layout claimed for another seed does not
+        assert!(!verify_layout(43, 1, &canonical(42, 1)).is_match());
+    }
+
+    #[test]
+    fn test_swapped_tile_reported_with_coordinate() {
+        let mut layout = canonical(42, 7);
+        let (x, y) = layout.exit_point;
+        let (sx, sy) = (x, (y + 1) % layout.height);
+        let original = layout.tiles[sy][sx];
+        let swapped = if original == TileType::Wall {
+            TileType::Floor
+        } else {
+            TileType::Wall
+        };
+        layout.tiles[sy][sx] = swapped;
+
+        let LayoutVerdict::Mismatch(report) = verify_layout(42, 7, &layout) else {
+            panic!("tampered layout accepted");
+        };
+        assert_eq!(report.tile_mismatch_count, 1);
+        assert_eq!(
+            report.tiles,
+            vec![TileMismatch {
+                x: sx,
+                y: sy,
+                expected: original,
+                claimed: Some(swapped),
+            }]
+        );
+        assert!(report.dimensions.is_none());
+        assert!(report.room_count.is_none());
+        assert!(report.exit_point.is_none());
+    }
+
+    #[test]
+    fn test_structural_mismatches_reported() {
+        let mut layout = canonical(42, 3);
+        layout.rooms.pop();
+        layout.exit_point = (0, 0);
+        let last_row = layout.tiles.pop().unwrap();
+
+        let LayoutVerdict::Mismatch(report) = verify_layout(42, 3, &layout) else {
+            panic!("tampered layout accepted");
+        };
+        // Ragged grid: a size problem, with the missing row reported tile by tile
+        assert!(report.dimensions.is_some());
+        assert_eq!(report.tile_mismatch_count, layout.width);
+        let y = layout.height - 1;
+        assert_eq!(
+            report.tiles[0],
+            TileMismatch {
+                x: 0,
+                y,
+                expected: last_row[0],
+                claimed: None,
+            }
+        );
+        assert!(report.tiles.iter().all(|t| t.y == y && t.claimed.is_none()));
+        let rooms = report.room_count.unwrap();
+        assert_eq!(rooms.claimed + 1, rooms.expected);
+        assert_eq!(report.exit_point.unwrap().claimed, (0, 0));
+    }
+
+    #[test]
+    fn test_checksum_tracks_layout_content() {
+        let layout = canonical(42, 5);
+        assert_eq!(layout_checksum(&layout), layout_checksum(&canonical(42, 5)));
+        let mut changed = layout.clone();
+        changed.exit_point.0 += 1;
+        assert_ne!(layout_checksum(&layout), layout_checksum(&changed));
+    }
+}
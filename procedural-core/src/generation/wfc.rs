@@ -4,6 +4,7 @@
 //! Each tile has adjacency rules derived from semantic tags.
 
 use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
 
 use super::{FloorSpec, FloorTier};
 use crate::semantic::SemanticTags;
@@ -27,6 +28,8 @@ pub enum TileType {
     Shrine,     // faction shrine
     WindColumn, // aerial updraft
     VoidPit,    // void fall area
+    Lever,      // puzzle lever, opens a linked gate
+    Gate,       // closed puzzle gate
 }
 
 impl TileType {
@@ -62,6 +65,8 @@ impl TileType {
             Self::Shrine => 2.0,
             Self::WindColumn => 3.0,
             Self::VoidPit => 2.0,
+            // Puzzle elements are placed after generation, never by WFC
+            Self::Lever | Self::Gate => 0.0,
         }
     }
 
@@ -124,6 +129,12 @@ pub struct FloorLayout {
     pub rooms: Vec<Room>,
     pub spawn_points: Vec<(usize, usize)>,
     pub exit_point: (usize, usize),
+    /// Lever-and-gate puzzles placed by `place_lever_gates`
+    #[serde(default)]
+    pub lever_gates: Vec<super::puzzles::LeverGate>,
+    /// Doors/shrines usable only during one breath phase (`mark_phase_gated`)
+    #[serde(default)]
+    pub phase_gated: Vec<super::puzzles::PhaseGatedTile>,
 }
 
 /// A room within the floor
@@ -159,9 +170,7 @@ impl DeterministicRng {
     }
 
     fn next(&mut self) -> u64 {
-        self.state ^= self.state << 13;
-        self.state ^= self.state >> 7;
-        self.state ^= self.state << 17;
+        self.state = super::xorshift(self.state);
         self.state
     }
 
@@ -201,18 +210,192 @@ pub fn generate_layout(spec: &FloorSpec) -> FloorLayout {
         .first()
         .copied()
         .unwrap_or((width / 2, height / 2));
+    if tiles[exit_point.1][exit_point.0] == TileType::Wall {
+        tiles[exit_point.1][exit_point.0] = TileType::Floor;
+    }
 
-    FloorLayout {
+    // Phase 5: Reconnect any walkable region unreachable from spawn
+    let mut layout = FloorLayout {
         width,
         height,
         tiles,
         rooms,
         spawn_points,
         exit_point,
+        lever_gates: Vec::new(),
+        phase_gated: Vec::new(),
+    };
+    ensure_connectivity(&mut layout);
+
+    // Phase 6: Flag breath-synced doors/shrines (tiles are left as they are)
+    super::puzzles::mark_phase_gated(&mut layout, spec.hash);
+    layout
+}
+
+/// Walkable tiles that cannot be reached from any spawn point.
+/// Returns an empty list for a fully connected layout.
+pub fn connectivity_check(layout: &FloorLayout) -> Vec<(usize, usize)> {
+    let reached = flood_from_spawn(layout);
+    let mut unreachable = Vec::new();
+    for (y, row) in layout.tiles.iter().enumerate() {
+        for (x, tile) in row.iter().enumerate() {
+            if is_walkable(*tile) && !reached[y][x] {
+                unreachable.push((x, y));
+            }
+        }
     }
+    unreachable
 }
 
-fn grid_size_for_tier(tier: &FloorTier) -> (usize, usize) {
+impl FloorLayout {
+    /// Steps on the shortest walkable 4-way path between two tiles, or None
+    /// if `to` can't be reached from `from`
+    pub fn path_length(&self, from: (usize, usize), to: (usize, usize)) -> Option<usize> {
+        let in_bounds = |(x, y): (usize, usize)| x < self.width && y < self.height;
+        if !in_bounds(from) || !in_bounds(to) || !is_walkable(self.tiles[from.1][from.0]) {
+            return None;
+        }
+
+        let mut dist = vec![vec![usize::MAX; self.width]; self.height];
+        let mut queue = VecDeque::from([from]);
+        dist[from.1][from.0] = 0;
+
+        while let Some((x, y)) = queue.pop_front() {
+            let d = dist[y][x];
+            if (x, y) == to {
+                return Some(d);
+            }
+            let neighbors = [
+                (x.wrapping_sub(1), y),
+                (x + 1, y),
+                (x, y.wrapping_sub(1)),
+                (x, y + 1),
+            ];
+            for (nx, ny) in neighbors {
+                if in_bounds((nx, ny))
+                    && dist[ny][nx] == usize::MAX
+                    && is_walkable(self.tiles[ny][nx])
+                {
+                    dist[ny][nx] = d + 1;
+                    queue.push_back((nx, ny));
+                }
+            }
+        }
+        None
+    }
+}
+
+/// Tiles a player can stand on; void pits are a fall, not a floor
+pub(super) fn is_walkable(tile: TileType) -> bool {
+    !matches!(
+        tile,
+        TileType::Wall | TileType::Empty | TileType::Gate | TileType::VoidPit
+    )
+}
+
+/// Flood fill over walkable tiles starting from the spawn points
+/// (or the first walkable tile if the layout has no spawn).
+pub(super) fn flood_from_spawn(layout: &FloorLayout) -> Vec<Vec<bool>> {
+    let mut starts = layout.spawn_points.clone();
+    if starts.is_empty() {
+        starts.extend(
+            (0..layout.height)
+                .flat_map(|y| (0..layout.width).map(move |x| (x, y)))
+                .find(|&(x, y)| is_walkable(layout.tiles[y][x])),
+        );
+    }
+    flood_fill(&layout.tiles, &starts)
+}
+
+fn flood_fill(tiles: &[Vec<TileType>], starts: &[(usize, usize)]) -> Vec<Vec<bool>> {
+    let height = tiles.len();
+    let width = tiles.first().map_or(0, |r| r.len());
+    let mut visited = vec![vec![false; width]; height];
+    let mut stack: Vec<(usize, usize)> = Vec::new();
+
+    for &(x, y) in starts {
+        if y < height && x < width && is_walkable(tiles[y][x]) && !visited[y][x] {
+            visited[y][x] = true;
+            stack.push((x, y));
+        }
+    }
+
+    while let Some((x, y)) = stack.pop() {
+        let neighbors = [
+            (x.wrapping_sub(1), y),
+            (x + 1, y),
+            (x, y.wrapping_sub(1)),
+            (x, y + 1),
+        ];
+        for (nx, ny) in neighbors {
+            if ny < height && nx < width && !visited[ny][nx] && is_walkable(tiles[ny][nx]) {
+                visited[ny][nx] = true;
+                stack.push((nx, ny));
+            }
+        }
+    }
+
+    visited
+}
+
+/// Carve the shortest corridor from each disconnected region back to the
+/// reachable area until every walkable tile is reachable.
+fn ensure_connectivity(layout: &mut FloorLayout) {
+    let (width, height) = (layout.width, layout.height);
+    loop {
+        let reached = flood_from_spawn(layout);
+        let orphan = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .find(|&(x, y)| is_walkable(layout.tiles[y][x]) && !reached[y][x]);
+        let Some(orphan) = orphan else {
+            return;
+        };
+
+        // One BFS outward from the whole region (seeded in scan order) to the
+        // nearest reached tile; `previous` leads back along the corridor
+        let region = flood_fill(&layout.tiles, &[orphan]);
+        let mut seen = region.clone();
+        let mut previous: Vec<Vec<Option<(usize, usize)>>> = vec![vec![None; width]; height];
+        let mut queue: VecDeque<(usize, usize)> = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .filter(|&(x, y)| region[y][x])
+            .collect();
+        let mut target = None;
+        while let Some((x, y)) = queue.pop_front() {
+            if reached[y][x] {
+                target = Some((x, y));
+                break;
+            }
+            let neighbors = [
+                (x.wrapping_sub(1), y),
+                (x + 1, y),
+                (x, y.wrapping_sub(1)),
+                (x, y + 1),
+            ];
+            for (nx, ny) in neighbors {
+                if ny < height && nx < width && !seen[ny][nx] {
+                    seen[ny][nx] = true;
+                    previous[ny][nx] = Some((x, y));
+                    queue.push_back((nx, ny));
+                }
+            }
+        }
+
+        let Some((tx, ty)) = target else {
+            // Nothing reachable at all (no walkable start) — nothing to connect to
+            return;
+        };
+        let mut step = previous[ty][tx];
+        while let Some((x, y)) = step {
+            if !is_walkable(layout.tiles[y][x]) {
+                layout.tiles[y][x] = TileType::Floor;
+            }
+            step = previous[y][x];
+        }
+    }
+}
+
+pub(crate) fn grid_size_for_tier(tier: &FloorTier) -> (usize, usize) {
     match tier {
         FloorTier::Echelon1 => (16, 16),
         FloorTier::Echelon2 => (24, 24),
@@ -549,6 +732,73 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_connectivity_check_detects_island() {
+        let mut tiles = vec![vec![TileType::Wall; 5]; 3];
+        tiles[1][0] = TileType::StairsDown;
+        tiles[1][1] = TileType::Floor;
+        tiles[1][3] = TileType::Floor;
+        tiles[1][4] = TileType::Chest;
+        let layout = FloorLayout {
+            width: 5,
+            height: 3,
+            tiles,
+            rooms: vec![],
+            spawn_points: vec![(0, 1)],
+            exit_point: (1, 1),
+            lever_gates: vec![],
+            phase_gated: vec![],
+        };
+
+        assert_eq!(connectivity_check(&layout), vec![(3, 1), (4, 1)]);
+    }
+
+    #[test]
+    fn test_void_pit_does_not_connect_regions() {
+        // S . V . C — the pit splits the row, so the chest side is an island
+        let mut tiles = vec![vec![TileType::Wall; 5]; 3];
+        tiles[1][0] = TileType::StairsDown;
+        tiles[1][1] = TileType::Floor;
+        tiles[1][2] = TileType::VoidPit;
+        tiles[1][3] = TileType::Floor;
+        tiles[1][4] = TileType::Chest;
+        let mut layout = FloorLayout {
+            width: 5,
+            height: 3,
+            tiles,
+            rooms: vec![],
+            spawn_points: vec![(0, 1)],
+            exit_point: (1, 1),
+            lever_gates: vec![],
+            phase_gated: vec![],
+        };
+        assert_eq!(connectivity_check(&layout), vec![(3, 1), (4, 1)]);
+        assert_eq!(layout.path_length((0, 1), (4, 1)), None);
+
+        ensure_connectivity(&mut layout);
+        assert!(connectivity_check(&layout).is_empty());
+        assert_eq!(layout.path_length((0, 1), (4, 1)), Some(4));
+    }
+
+    #[test]
+    fn test_all_walkable_tiles_reachable() {
+        for seed in 0..500u64 {
+            let tower_seed = TowerSeed { seed };
+            let floor_id = 1 + (seed as u32 * 37) % 700;
+            let spec = FloorSpec::generate(&tower_seed, floor_id);
+            let layout = generate_layout(&spec);
+
+            let unreachable = connectivity_check(&layout);
+            assert!(
+                unreachable.is_empty(),
+                "seed {seed} floor {floor_id}: {} unreachable tiles",
+                unreachable.len()
+            );
+            let (ex, ey) = layout.exit_point;
+            assert!(is_walkable(layout.tiles[ey][ex]), "exit must be walkable");
+        }
+    }
+
     #[test]
     fn test_tile_adjacency_rules() {
         assert!(!TileType::VoidPit.can_be_adjacent(&TileType::StairsUp));
@@ -5,14 +5,15 @@
 //! - Bevy resource updates on config change
 //! - Validation before applying
 //! - Rollback on invalid config
-//! - FFI interface for reload status
+//! - FFI interface for reload status: FFI calls queue commands that
+//!   `drain_ffi_commands` carries out, and read back the status it publishes
 
 use bevy::prelude::*;
 use notify::{Event, RecommendedWatcher, RecursiveMode, Watcher};
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::mpsc::{channel, Receiver, Sender};
-use std::sync::{Arc, Mutex};
+use std::sync::{Arc, Mutex, OnceLock};
 
 pub struct HotReloadPlugin;
 
@@ -21,10 +22,55 @@ impl Plugin for HotReloadPlugin {
         app.insert_resource(HotReloadState::default())
             .add_event::<ConfigReloadEvent>()
             .add_systems(Startup, setup_config_watcher)
-            .add_systems(Update, process_config_changes);
+            .add_systems(Update, (process_config_changes, drain_ffi_commands).chain());
     }
 }
 
+/// Config file watched and reloaded by default
+pub const CONFIG_PATH: &str = "config/engine.json";
+
+/// Commands posted from FFI for the Bevy app to carry out
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HotReloadCommand {
+    Reload,
+}
+
+/// FFI -> Bevy command queue; the receiver is only touched by the drain
+static FFI_COMMANDS: OnceLock<(Sender<HotReloadCommand>, Mutex<Receiver<HotReloadCommand>>)> =
+    OnceLock::new();
+/// Status last published by `drain_ffi_commands`, read by FFI
+static SHARED_STATUS: OnceLock<Mutex<HotReloadStatus>> = OnceLock::new();
+
+fn ffi_commands() -> &'static (Sender<HotReloadCommand>, Mutex<Receiver<HotReloadCommand>>) {
+    FFI_COMMANDS.get_or_init(|| {
+        let (tx, rx) = channel();
+        (tx, Mutex::new(rx))
+    })
+}
+
+fn shared_status_lock() -> std::sync::MutexGuard<'static, HotReloadStatus> {
+    SHARED_STATUS
+        .get_or_init(|| Mutex::new(HotReloadStatus::from_state(&HotReloadState::default())))
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+}
+
+/// Queue a command for the next `drain_ffi_commands` run
+pub fn post_ffi_command(command: HotReloadCommand) -> bool {
+    ffi_commands().0.send(command).is_ok()
+}
+
+/// Take every queued command, oldest first
+pub fn take_ffi_commands() -> Vec<HotReloadCommand> {
+    let receiver = ffi_commands().1.lock().unwrap_or_else(|e| e.into_inner());
+    receiver.try_iter().collect()
+}
+
+/// Latest status published by the Bevy app
+pub fn shared_status() -> HotReloadStatus {
+    shared_status_lock().clone()
+}
+
 /// Hot-reload state tracking
 #[derive(Resource, Default)]
 pub struct HotReloadState {
@@ -53,7 +99,7 @@ struct WatcherResource {
 
 /// Initialize file watcher for config/engine.json
 fn setup_config_watcher(mut commands: Commands, mut state: ResMut<HotReloadState>) {
-    let config_path = PathBuf::from("config/engine.json");
+    let config_path = PathBuf::from(CONFIG_PATH);
 
     if !config_path.exists() {
         warn!("Config file not found: {:?}", config_path);
@@ -112,37 +158,17 @@ fn process_config_changes(
                 if is_config_modify_event(&event, &state.watched_file) {
                     info!("Config file modified, reloading...");
 
-                    match reload_config() {
-                        Ok(_) => {
-                            state.reload_count += 1;
-                            state.last_reload_success = true;
-                            state.last_reload_time = time.elapsed_secs_f64();
-                            state.last_error = None;
-
-                            events.send(ConfigReloadEvent {
-                                path: state.watched_file.clone().unwrap(),
-                                success: true,
-                                error: None,
-                            });
-
-                            info!(
-                                "Config reloaded successfully (count: {})",
-                                state.reload_count
-                            );
-                        }
-                        Err(e) => {
-                            state.last_reload_success = false;
-                            state.last_error = Some(e.clone());
-
-                            events.send(ConfigReloadEvent {
-                                path: state.watched_file.clone().unwrap(),
-                                success: false,
-                                error: Some(e.clone()),
-                            });
-
-                            error!("Config reload failed: {}", e);
-                        }
-                    }
+                    let path = state
+                        .watched_file
+                        .clone()
+                        .unwrap_or_else(|| PathBuf::from(CONFIG_PATH));
+                    let result = reload_config(&path);
+                    events.send(record_reload(
+                        &mut state,
+                        path,
+                        result,
+                        time.elapsed_secs_f64(),
+                    ));
                 }
             }
             Err(e) => {
@@ -152,6 +178,74 @@ fn process_config_changes(
     }
 }
 
+/// Carry out commands queued from FFI, then publish the current status
+/// for `hotreload_get_status`
+pub fn drain_ffi_commands(
+    mut state: ResMut<HotReloadState>,
+    mut events: EventWriter<ConfigReloadEvent>,
+    time: Res<Time>,
+) {
+    for command in take_ffi_commands() {
+        events.send(run_command(&mut state, command, time.elapsed_secs_f64()));
+    }
+    *shared_status_lock() = HotReloadStatus::from_state(&state);
+}
+
+/// Apply one FFI command to the reload state
+fn run_command(
+    state: &mut HotReloadState,
+    command: HotReloadCommand,
+    now: f64,
+) -> ConfigReloadEvent {
+    match command {
+        HotReloadCommand::Reload => {
+            info!("Config reload requested over FFI");
+            let path = state
+                .watched_file
+                .clone()
+                .unwrap_or_else(|| PathBuf::from(CONFIG_PATH));
+            let result = reload_config(&path);
+            record_reload(state, path, result, now)
+        }
+    }
+}
+
+/// Update reload state with the outcome of a reload attempt
+fn record_reload(
+    state: &mut HotReloadState,
+    path: PathBuf,
+    result: Result<ConfigSnapshot, String>,
+    now: f64,
+) -> ConfigReloadEvent {
+    match result {
+        Ok(_) => {
+            state.reload_count += 1;
+            state.last_reload_success = true;
+            state.last_reload_time = now;
+            state.last_error = None;
+            info!(
+                "Config reloaded successfully (count: {})",
+                state.reload_count
+            );
+            ConfigReloadEvent {
+                path,
+                success: true,
+                error: None,
+            }
+        }
+        Err(e) => {
+            state.last_reload_success = false;
+            state.last_error = Some(e.clone());
+            error!("Config reload failed: {}", e);
+            ConfigReloadEvent {
+                path,
+                success: false,
+                error: Some(e),
+            }
+        }
+    }
+}
+
 /// Check if event is a modification to the config file
 fn is_config_modify_event(event: &Event, watched_file: &Option<PathBuf>) -> bool {
     if let Some(_path) = watched_file {
@@ -165,12 +259,9 @@ fn is_config_modify_event(event: &Event, watched_file: &Option<PathBuf>) -> bool
 }
 
 /// Reload configuration from disk
-fn reload_config() -> Result<ConfigSnapshot, String> {
-    let config_path = PathBuf::from("config/engine.json");
-
+fn reload_config(config_path: &Path) -> Result<ConfigSnapshot, String> {
     // Read file
-    let content =
-        std::fs::read_to_string(&config_path).map_err(|e| format!("Read error: {}", e))?;
+    let content = std::fs::read_to_string(config_path).map_err(|e| format!("Read error: {}", e))?;
 
     // Validate JSON
     let _config: serde_json::Value =
@@ -197,7 +288,7 @@ pub struct ConfigSnapshot {
 }
 
 /// Hot-reload status for FFI
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HotReloadStatus {
     pub enabled: bool,
     pub watched_file: Option<String>,
@@ -287,6 +378,48 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_reload_command_records_success() {
+        let mut temp = NamedTempFile::new().unwrap();
+        writeln!(temp, r#"{{"tick_rate": 60}}"#).unwrap();
+        let mut state = HotReloadState {
+            watched_file: Some(temp.path().to_path_buf()),
+            last_error: Some("stale".into()),
+            ..Default::default()
+        };
+
+        let event = run_command(&mut state, HotReloadCommand::Reload, 12.5);
+        assert!(event.success);
+        assert_eq!(state.reload_count, 1);
+        assert!(state.last_reload_success);
+        assert_eq!(state.last_reload_time, 12.5);
+        assert!(state.last_error.is_none());
+    }
+
+    #[test]
+    fn test_reload_command_malformed_config_sets_error() {
+        let mut temp = NamedTempFile::new().unwrap();
+        writeln!(temp, r#"{{invalid json"#).unwrap();
+        let mut state = HotReloadState {
+            watched_file: Some(temp.path().to_path_buf()),
+            reload_count: 4,
+            ..Default::default()
+        };
+
+        let event = run_command(&mut state, HotReloadCommand::Reload, 1.0);
+        assert!(!event.success);
+        assert_eq!(state.reload_count, 4);
+        assert!(!state.last_reload_success);
+        assert!(state.last_error.unwrap().starts_with("JSON parse error"));
+
+        let mut missing = HotReloadState {
+            watched_file: Some(PathBuf::from("does/not/exist.json")),
+            ..Default::default()
+        };
+        run_command(&mut missing, HotReloadCommand::Reload, 1.0);
+        assert!(missing.last_error.unwrap().starts_with("Read error"));
+    }
+
     #[test]
     fn test_hotreload_state_default() {
         let state = HotReloadState::default();
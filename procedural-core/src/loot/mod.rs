@@ -7,6 +7,9 @@ use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
 
 use crate::economy::ItemRarity;
+use crate::equipment::{affix_range, roll_stats, RolledStats};
+use crate::generation::{xorshift, ChestTier};
+use crate::mastery::MasteryTier;
 use crate::semantic::SemanticTags;
 
 pub struct LootPlugin;
@@ -36,6 +39,7 @@ pub enum LootCategory {
     Equipment,      // weapons, armor (later phases)
     Currency,       // tower shards
     EchoFragment,   // rare currency from echoes
+    QuestItem,      // quest objectives, never filtered out
 }
 
 /// Generated loot item
@@ -46,6 +50,9 @@ pub struct LootItem {
     pub rarity: ItemRarity,
     pub quantity: u32,
     pub semantic_tags: Vec<(String, f32)>,
+    /// Rolled affixes, present on equipment
+    #[serde(default)]
+    pub stats: Option<RolledStats>,
 }
 
 /// Loot table entry
@@ -58,15 +65,235 @@ struct LootTableEntry {
     name_prefix: &'static str,
 }
 
-/// Generate loot from a drop event
+/// Share of the floor's tags mixed into dropped items when floor tags are given
+const FLOOR_TAG_BLEND: f32 = 0.4;
+
+/// Generate loot from a drop event.
+///
+/// When `floor_tags` is provided, item tags and names come from a blend of the
+/// monster's tags (60%) and the floor's tags (40%). The loot table itself is
+/// still driven by the monster alone.
 pub fn generate_loot(
     source_tags: &SemanticTags,
     floor_level: u32,
     drop_hash: u64,
+    floor_tags: Option<&SemanticTags>,
+) -> Vec<LootItem> {
+    generate_loot_boosted(
+        source_tags,
+        floor_level,
+        drop_hash,
+        floor_tags,
+        RarityBoost::default(),
+        None,
+    )
+}
+
+/// `generate_loot` with stack sizes scaled by the Breath phase's
+/// `resource_multiplier`. Equipment and quest items are never multiplied,
+/// and every item keeps at least 1.
+pub fn generate_loot_breath(
+    source_tags: &SemanticTags,
+    floor_level: u32,
+    drop_hash: u64,
+    floor_tags: Option<&SemanticTags>,
+    resource_mult: f32,
+) -> Vec<LootItem> {
+    let mut items = generate_loot(source_tags, floor_level, drop_hash, floor_tags);
+    let mult = resource_mult.max(0.0);
+    for item in &mut items {
+        if matches!(
+            item.category,
+            LootCategory::Equipment | LootCategory::QuestItem
+        ) {
+            continue;
+        }
+        item.quantity = ((item.quantity as f32 * mult).round() as u32).max(1);
+    }
+    items
+}
+
+/// Bad-luck protection counters, carried between drops by the caller
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LootPityState {
+    pub kills_since_rare: u32,
+    pub kills_since_legendary: u32,
+    /// Drops without a Rare+ item after which a Rare is guaranteed
+    #[serde(default = "default_rare_pity_threshold")]
+    pub rare_threshold: u32,
+}
+
+/// Default drops without a Rare+ before one is guaranteed
+pub const DEFAULT_RARE_PITY_THRESHOLD: u32 = 30;
+/// Added to the Rare roll window per drop without a Rare+
+const RARE_PITY_STEP: f32 = 0.005;
+/// Added to the Legendary roll window per drop without a Legendary+
+const LEGENDARY_PITY_STEP: f32 = 0.0002;
+/// Cap on the Legendary pity bonus
+const LEGENDARY_PITY_MAX: f32 = 0.04;
+
+fn default_rare_pity_threshold() -> u32 {
+    DEFAULT_RARE_PITY_THRESHOLD
+}
+
+impl Default for LootPityState {
+    fn default() -> Self {
+        Self {
+            kills_since_rare: 0,
+            kills_since_legendary: 0,
+            rare_threshold: DEFAULT_RARE_PITY_THRESHOLD,
+        }
+    }
+}
+
+impl LootPityState {
+    pub fn with_rare_threshold(mut self, threshold: u32) -> Self {
+        self.rare_threshold = threshold.max(1);
+        self
+    }
+
+    fn boost(&self) -> RarityBoost {
+        RarityBoost {
+            rare: self.kills_since_rare as f32 * RARE_PITY_STEP,
+            legendary: (self.kills_since_legendary as f32 * LEGENDARY_PITY_STEP)
+                .min(LEGENDARY_PITY_MAX),
+        }
+    }
+}
+
+/// Extra roll window added to rarity thresholds (0 = base odds)
+#[derive(Debug, Clone, Copy, Default)]
+struct RarityBoost {
+    rare: f32,
+    legendary: f32,
+}
+
+/// `generate_loot` with bad-luck protection: odds improve the longer the
+/// player goes without a Rare or Legendary, and once `rare_threshold` drops
+/// pass without a Rare+ the best item of this drop is upgraded to Rare.
+/// Counters reset when a qualifying item drops.
+pub fn generate_loot_with_pity(
+    source_tags: &SemanticTags,
+    floor_level: u32,
+    drop_hash: u64,
+    floor_tags: Option<&SemanticTags>,
+    pity: &mut LootPityState,
+) -> Vec<LootItem> {
+    let mut items = generate_loot_boosted(
+        source_tags,
+        floor_level,
+        drop_hash,
+        floor_tags,
+        pity.boost(),
+        None,
+    );
+
+    let best = items.iter().map(|i| i.rarity).max();
+    if best < Some(ItemRarity::Rare) && pity.kills_since_rare + 1 >= pity.rare_threshold {
+        if let Some(item) = items.iter_mut().max_by_key(|i| i.rarity) {
+            item.rarity = ItemRarity::Rare;
+        }
+    }
+
+    let best = items.iter().map(|i| i.rarity).max();
+    if best >= Some(ItemRarity::Rare) {
+        pity.kills_since_rare = 0;
+    } else {
+        pity.kills_since_rare += 1;
+    }
+    if best >= Some(ItemRarity::Legendary) {
+        pity.kills_since_legendary = 0;
+    } else {
+        pity.kills_since_legendary += 1;
+    }
+
+    items
+}
+
+/// Extra rarity odds for a chest tier (Wooden chests roll like a normal drop)
+fn chest_boost(tier: ChestTier) -> RarityBoost {
+    match tier {
+        ChestTier::Wooden => RarityBoost::default(),
+        ChestTier::Silver => RarityBoost {
+            rare: 0.15,
+            legendary: 0.01,
+        },
+        ChestTier::Gold => RarityBoost {
+            rare: 0.30,
+            legendary: 0.05,
+        },
+    }
+}
+
+/// Loot from a treasure chest of the given tier (see
+/// `generation::chest_tier`). Items take the floor's tags.
+pub fn chest_loot(
+    tier: ChestTier,
+    floor_tags: &SemanticTags,
+    floor_level: u32,
+    hash: u64,
+) -> Vec<LootItem> {
+    generate_loot_boosted(floor_tags, floor_level, hash, None, chest_boost(tier), None)
+}
+
+/// Upper bound on the player-affinity bias for targeted loot
+pub const MAX_TARGET_BIAS: f32 = 0.5;
+
+/// Tag an item is nudged toward, and how often/strongly (0..=MAX_TARGET_BIAS)
+struct TagAffinity {
+    target: SemanticTags,
+    bias: f32,
+}
+
+/// `generate_loot` nudged toward what the killer plays.
+///
+/// If the monster carries the player's dominant tag (positive overlap), each
+/// item has a `bias` chance of having its tags blended toward that tag by
+/// `bias`. `bias` is clamped to [0, MAX_TARGET_BIAS], so at most half the
+/// drops are steered and the rest roll as usual. No overlap means no nudge.
+pub fn generate_loot_targeted(
+    source_tags: &SemanticTags,
+    floor_level: u32,
+    drop_hash: u64,
+    floor_tags: Option<&SemanticTags>,
+    player_profile_tags: &SemanticTags,
+    bias: f32,
+) -> Vec<LootItem> {
+    let bias = bias.clamp(0.0, MAX_TARGET_BIAS);
+    let affinity = player_profile_tags
+        .dominant()
+        .filter(|(tag, val)| *val > 0.0 && source_tags.get(tag) > 0.0 && bias > 0.0)
+        .map(|(tag, _)| TagAffinity {
+            target: SemanticTags::new(vec![(tag, 1.0)]),
+            bias,
+        });
+
+    generate_loot_boosted(
+        source_tags,
+        floor_level,
+        drop_hash,
+        floor_tags,
+        RarityBoost::default(),
+        affinity.as_ref(),
+    )
+}
+
+fn generate_loot_boosted(
+    source_tags: &SemanticTags,
+    floor_level: u32,
+    drop_hash: u64,
+    floor_tags: Option<&SemanticTags>,
+    boost: RarityBoost,
+    affinity: Option<&TagAffinity>,
 ) -> Vec<LootItem> {
     let mut items = Vec::new();
     let mut hash = drop_hash;
 
+    let item_tags = match floor_tags {
+        Some(floor) => SemanticTags::blended(source_tags, floor, FLOOR_TAG_BLEND),
+        None => source_tags.clone(),
+    };
+
     // Number of drops (1-4, scaling with floor level)
     let drop_count = 1 + ((hash % 3) as usize).min(3);
     hash = xorshift(hash);
@@ -75,7 +302,12 @@ pub fn generate_loot(
 
     for _ in 0..drop_count {
         hash = xorshift(hash);
-        if let Some(item) = roll_loot(&table, source_tags, floor_level, hash) {
+        // High bits so the steer roll is independent of the category roll
+        let nudged = affinity
+            .filter(|a| ((hash >> 32) % 10000) as f32 / 10000.0 < a.bias)
+            .map(|a| SemanticTags::blended(&item_tags, &a.target, a.bias));
+        let tags = nudged.as_ref().unwrap_or(&item_tags);
+        if let Some(item) = roll_loot(&table, tags, floor_level, hash, boost) {
             items.push(item);
         }
         hash = xorshift(hash);
@@ -114,6 +346,13 @@ fn build_loot_table(source_tags: &SemanticTags, floor_level: u32) -> Vec<LootTab
             max_quantity: 2,
             name_prefix: "Potion",
         },
+        LootTableEntry {
+            category: LootCategory::Equipment,
+            weight: 5.0,
+            min_quantity: 1,
+            max_quantity: 1,
+            name_prefix: "Gear",
+        },
     ];
 
     // Elemental monsters drop more resources
@@ -144,6 +383,7 @@ fn roll_loot(
     source_tags: &SemanticTags,
     floor_level: u32,
     hash: u64,
+    boost: RarityBoost,
 ) -> Option<LootItem> {
     let total_weight: f32 = table.iter().map(|e| e.weight).sum();
     if total_weight <= 0.0 {
@@ -159,7 +399,7 @@ fn roll_loot(
             let hash2 = xorshift(hash);
 
             // Determine rarity
-            let rarity = roll_rarity(floor_level, hash2);
+            let rarity = roll_rarity(floor_level, hash2, boost);
 
             // Determine quantity
             let range = entry.max_quantity.saturating_sub(entry.min_quantity);
@@ -174,20 +414,25 @@ fn roll_loot(
             let element_name = dominant_element_name(source_tags);
             let name = format!("{} {}", element_name, entry.name_prefix);
 
-            // Copy relevant tags from source (thematic consistency)
+            // Copy relevant tags from source (thematic consistency).
+            // Strong negative tags carry over too, as resistances.
             let item_tags: Vec<(String, f32)> = source_tags
                 .tags
                 .iter()
-                .filter(|(_, v)| *v > 0.3)
+                .filter(|(_, v)| v.abs() > 0.3)
                 .map(|(k, v)| (k.clone(), v * 0.5))
                 .collect();
 
+            let stats = (entry.category == LootCategory::Equipment)
+                .then(|| roll_stats(source_tags, rarity, xorshift(hash2)));
+
             return Some(LootItem {
                 name,
                 category: entry.category,
                 rarity,
                 quantity,
                 semantic_tags: item_tags,
+                stats,
             });
         }
     }
@@ -195,15 +440,15 @@ fn roll_loot(
     None
 }
 
-fn roll_rarity(floor_level: u32, hash: u64) -> ItemRarity {
+fn roll_rarity(floor_level: u32, hash: u64, boost: RarityBoost) -> ItemRarity {
     let luck_bonus = (floor_level as f32 * 0.001).min(0.1);
     let roll = (hash % 10000) as f32 / 10000.0;
 
-    if roll < 0.01 + luck_bonus * 0.1 {
+    if roll < 0.01 + luck_bonus * 0.1 + boost.legendary {
         ItemRarity::Legendary
-    } else if roll < 0.05 + luck_bonus * 0.5 {
+    } else if roll < 0.05 + luck_bonus * 0.5 + boost.legendary {
         ItemRarity::Epic
-    } else if roll < 0.15 + luck_bonus {
+    } else if roll < 0.15 + luck_bonus + boost.legendary + boost.rare {
         ItemRarity::Rare
     } else if roll < 0.40 + luck_bonus {
         ItemRarity::Uncommon
@@ -237,11 +482,220 @@ fn dominant_element_name(tags: &SemanticTags) -> &'static str {
         .unwrap_or("Tower")
 }
 
-fn xorshift(mut x: u64) -> u64 {
-    x ^= x << 13;
-    x ^= x >> 7;
-    x ^= x << 17;
-    x
+/// Auto-pickup filter so players can skip junk drops.
+/// Quest items are always picked up regardless of the filter.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PickupFilter {
+    pub min_rarity: ItemRarity,
+    pub ignore_categories: Vec<LootCategory>,
+}
+
+impl Default for PickupFilter {
+    fn default() -> Self {
+        Self {
+            min_rarity: ItemRarity::Common,
+            ignore_categories: Vec::new(),
+        }
+    }
+}
+
+impl PickupFilter {
+    pub fn should_pickup(&self, item: &LootItem) -> bool {
+        if item.category == LootCategory::QuestItem {
+            return true;
+        }
+        if self.ignore_categories.contains(&item.category) {
+            return false;
+        }
+        item.rarity >= self.min_rarity
+    }
+}
+
+/// Enchanting errors
+#[derive(Debug, Clone, PartialEq)]
+pub enum EnchantError {
+    /// Only equipment carries rerollable affixes
+    NotEnchantable { category: LootCategory },
+    /// Item has no affixes to reroll
+    NoAffixes,
+}
+
+/// Affix magnitude range (min, max) allowed for a rarity
+fn affix_bounds(rarity: ItemRarity) -> (f32, f32) {
+    match rarity {
+        ItemRarity::Common => (0.05, 0.2),
+        ItemRarity::Uncommon => (0.1, 0.3),
+        ItemRarity::Rare => (0.15, 0.4),
+        ItemRarity::Epic => (0.2, 0.5),
+        ItemRarity::Legendary => (0.3, 0.65),
+        ItemRarity::Mythic => (0.4, 0.8),
+    }
+}
+
+/// Number of rolls per affix; the best one is kept, so higher tiers skew strong
+fn enchant_rolls(tier: MasteryTier) -> u32 {
+    match tier {
+        MasteryTier::Novice => 1,
+        MasteryTier::Apprentice => 1,
+        MasteryTier::Journeyman => 2,
+        MasteryTier::Expert => 2,
+        MasteryTier::Master => 3,
+        MasteryTier::Grandmaster => 4,
+    }
+}
+
+/// Reroll an item's affixes with the Enchanting mastery.
+///
+/// Equipment with rolled stats has those rerolled: each affix keeps its stat
+/// (and element) and its value is rerolled within `affix_range`. Items without
+/// rolled stats fall back to rerolling their semantic tags, keeping names and
+/// signs (a fire resistance stays a fire resistance) within the rarity bounds.
+/// Deterministic from `hash`.
+pub fn enchant_reroll(
+    item: &mut LootItem,
+    enchant_tier: MasteryTier,
+    hash: u64,
+) -> Result<(), EnchantError> {
+    if item.category != LootCategory::Equipment {
+        return Err(EnchantError::NotEnchantable {
+            category: item.category,
+        });
+    }
+    if item.stats.is_none() && item.semantic_tags.is_empty() {
+        return Err(EnchantError::NoAffixes);
+    }
+
+    let rolls = enchant_rolls(enchant_tier);
+    let mut rng = hash.max(1);
+    let mut best_roll = || {
+        let mut best = 0.0_f32;
+        for _ in 0..rolls {
+            rng = xorshift(rng);
+            best = best.max((rng % 10000) as f32 / 10000.0);
+        }
+        best
+    };
+
+    let rarity = item.rarity;
+    match item.stats.as_mut() {
+        Some(stats) => {
+            for affix in std::iter::once(&mut stats.primary).chain(stats.secondary.iter_mut()) {
+                let (min, max) = affix_range(affix.stat, rarity);
+                affix.value = min + (max - min) * best_roll();
+            }
+        }
+        None => {
+            let (min, max) = affix_bounds(rarity);
+            for (_, value) in item.semantic_tags.iter_mut() {
+                let magnitude = min + (max - min) * best_roll();
+                *value = if *value < 0.0 { -magnitude } else { magnitude };
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Highest mystery box tier; higher tiers are clamped to this
+pub const MYSTERY_BOX_MAX_TIER: u32 = 3;
+/// Floor level from which top-tier boxes can contain Mythics (Echelon 4)
+const MYSTERY_BOX_MYTHIC_FLOOR: u32 = 501;
+
+/// Rarity odds for a mystery box tier, summing to 1.0.
+/// Top-tier boxes opened at Echelon 4 trade a sliver of Legendary for Mythic.
+pub fn mystery_box_odds(tier: u32, floor_level: u32) -> Vec<(ItemRarity, f32)> {
+    let mut odds = match tier.min(MYSTERY_BOX_MAX_TIER) {
+        0 => vec![
+            (ItemRarity::Common, 0.60),
+            (ItemRarity::Uncommon, 0.28),
+            (ItemRarity::Rare, 0.10),
+            (ItemRarity::Epic, 0.018),
+            (ItemRarity::Legendary, 0.002),
+        ],
+        1 => vec![
+            (ItemRarity::Common, 0.35),
+            (ItemRarity::Uncommon, 0.35),
+            (ItemRarity::Rare, 0.22),
+            (ItemRarity::Epic, 0.07),
+            (ItemRarity::Legendary, 0.01),
+        ],
+        2 => vec![
+            (ItemRarity::Common, 0.10),
+            (ItemRarity::Uncommon, 0.30),
+            (ItemRarity::Rare, 0.38),
+            (ItemRarity::Epic, 0.18),
+            (ItemRarity::Legendary, 0.04),
+        ],
+        _ => vec![
+            (ItemRarity::Uncommon, 0.15),
+            (ItemRarity::Rare, 0.45),
+            (ItemRarity::Epic, 0.30),
+            (ItemRarity::Legendary, 0.10),
+        ],
+    };
+    if tier >= MYSTERY_BOX_MAX_TIER && floor_level >= MYSTERY_BOX_MYTHIC_FLOOR {
+        if let Some(legendary) = odds.last_mut() {
+            legendary.1 -= 0.01;
+        }
+        odds.push((ItemRarity::Mythic, 0.01));
+    }
+    odds
+}
+
+/// Open a mystery box bought with shards. Rarity follows `mystery_box_odds`;
+/// the item kind and element are rolled from the same hash.
+pub fn open_mystery_box(tier: u32, floor_level: u32, hash: u64) -> LootItem {
+    let mut rng = xorshift(hash.max(1));
+    let roll = (rng % 10000) as f32 / 10000.0;
+
+    let odds = mystery_box_odds(tier, floor_level);
+    let mut rarity = odds[odds.len() - 1].0;
+    let mut accumulated = 0.0;
+    for (r, p) in &odds {
+        accumulated += p;
+        if roll < accumulated {
+            rarity = *r;
+            break;
+        }
+    }
+
+    rng = xorshift(rng);
+    let (category, prefix) = match rng % 10 {
+        0..=4 => (LootCategory::Equipment, "Relic"),
+        5..=7 => (LootCategory::Consumable, "Elixir"),
+        _ => (LootCategory::Material, "Essence"),
+    };
+
+    rng = xorshift(rng);
+    let elements = [
+        ("fire", "Ember"),
+        ("water", "Tide"),
+        ("earth", "Stone"),
+        ("wind", "Gale"),
+        ("void", "Void"),
+    ];
+    let (tag, element_name) = elements[(rng % elements.len() as u64) as usize];
+    let (min, max) = affix_bounds(rarity);
+    rng = xorshift(rng);
+    let magnitude = min + (max - min) * ((rng % 10000) as f32 / 10000.0);
+
+    let semantic_tags = vec![(tag.to_string(), magnitude)];
+    let stats = (category == LootCategory::Equipment).then(|| {
+        roll_stats(
+            &SemanticTags::new(vec![(tag, magnitude)]),
+            rarity,
+            xorshift(rng),
+        )
+    });
+
+    LootItem {
+        name: format!("{} {}", element_name, prefix),
+        category,
+        rarity,
+        quantity: 1,
+        semantic_tags,
+        stats,
+    }
 }
 
 /// Marker for dropped loot on the ground
@@ -253,7 +707,7 @@ pub struct DroppedLoot {
 
 fn process_loot_drops(mut commands: Commands, mut events: EventReader<LootDropEvent>) {
     for event in events.read() {
-        let items = generate_loot(&event.source_tags, event.floor_level, event.drop_hash);
+        let items = generate_loot(&event.source_tags, event.floor_level, event.drop_hash, None);
 
         for (i, item) in items.iter().enumerate() {
             let offset = Vec3::new(i as f32 * 0.5, 0.5, 0.0);
@@ -276,8 +730,8 @@ mod tests {
     #[test]
     fn test_generate_loot_deterministic() {
         let tags = SemanticTags::new(vec![("fire", 0.8), ("corruption", 0.3)]);
-        let loot_a = generate_loot(&tags, 10, 42);
-        let loot_b = generate_loot(&tags, 10, 42);
+        let loot_a = generate_loot(&tags, 10, 42, None);
+        let loot_b = generate_loot(&tags, 10, 42, None);
 
         assert_eq!(loot_a.len(), loot_b.len());
         for (a, b) in loot_a.iter().zip(loot_b.iter()) {
@@ -286,29 +740,395 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_pity_guarantees_rare_at_threshold() {
+        let tags = SemanticTags::new(vec![("neutral", 0.5)]);
+        for start in 0..200u64 {
+            let mut pity = LootPityState::default().with_rare_threshold(5);
+            let mut dry = 0;
+            for kill in 0..20u64 {
+                let items =
+                    generate_loot_with_pity(&tags, 1, start * 1000 + kill + 1, None, &mut pity);
+                if items.iter().any(|i| i.rarity >= ItemRarity::Rare) {
+                    dry = 0;
+                } else {
+                    dry += 1;
+                }
+                assert!(dry < 5, "Went {} drops without a rare", dry);
+                assert_eq!(pity.kills_since_rare, dry);
+            }
+        }
+    }
+
+    #[test]
+    fn test_pity_counters_track_drops() {
+        let tags = SemanticTags::new(vec![("neutral", 0.5)]);
+        let mut pity = LootPityState::default();
+        let items = generate_loot_with_pity(&tags, 1, 12345, None, &mut pity);
+        let got_rare = items.iter().any(|i| i.rarity >= ItemRarity::Rare);
+        assert_eq!(pity.kills_since_rare, if got_rare { 0 } else { 1 });
+        assert!(pity.kills_since_legendary <= 1);
+    }
+
+    #[test]
+    fn test_pity_zero_matches_base_loot() {
+        let tags = SemanticTags::new(vec![("fire", 0.8)]);
+        let base = generate_loot(&tags, 10, 42, None);
+        let mut pity = LootPityState::default();
+        let pitied = generate_loot_with_pity(&tags, 10, 42, None, &mut pity);
+        assert_eq!(base.len(), pitied.len());
+        for (a, b) in base.iter().zip(pitied.iter()) {
+            assert_eq!(a.rarity, b.rarity);
+            assert_eq!(a.name, b.name);
+        }
+    }
+
+    #[test]
+    fn test_mystery_box_odds_sum_to_one() {
+        for tier in 0..=MYSTERY_BOX_MAX_TIER + 1 {
+            for floor in [1, 600] {
+                let total: f32 = mystery_box_odds(tier, floor).iter().map(|(_, p)| p).sum();
+                assert!(
+                    (total - 1.0).abs() < 1e-4,
+                    "tier {} sums to {}",
+                    tier,
+                    total
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_mystery_box_higher_tier_better() {
+        let avg = |tier: u32| {
+            (0..2000u64)
+                .map(|h| open_mystery_box(tier, 50, h * 31 + 1).rarity as u32 as f32)
+                .sum::<f32>()
+                / 2000.0
+        };
+        let averages: Vec<f32> = (0..=MYSTERY_BOX_MAX_TIER).map(avg).collect();
+        for pair in averages.windows(2) {
+            assert!(pair[1] > pair[0], "{:?}", averages);
+        }
+    }
+
+    #[test]
+    fn test_gold_chest_beats_wooden() {
+        let tags = SemanticTags::new(vec![("fire", 0.6)]);
+        let avg = |tier: ChestTier| {
+            let items: Vec<LootItem> = (0..1000u64)
+                .flat_map(|h| chest_loot(tier, &tags, 50, h * 31 + 1))
+                .collect();
+            items.iter().map(|i| i.rarity as u32 as f32).sum::<f32>() / items.len() as f32
+        };
+        let (wooden, silver, gold) = (
+            avg(ChestTier::Wooden),
+            avg(ChestTier::Silver),
+            avg(ChestTier::Gold),
+        );
+        assert!(gold > silver && silver > wooden, "{wooden} {silver} {gold}");
+    }
+
+    #[test]
+    fn test_mystery_box_reproducible() {
+        let a = open_mystery_box(2, 120, 9001);
+        let b = open_mystery_box(2, 120, 9001);
+        assert_eq!(a.name, b.name);
+        assert_eq!(a.rarity, b.rarity);
+        assert_eq!(a.semantic_tags, b.semantic_tags);
+    }
+
     #[test]
     fn test_loot_has_items() {
         let tags = SemanticTags::new(vec![("neutral", 0.5)]);
-        let loot = generate_loot(&tags, 1, 12345);
+        let loot = generate_loot(&tags, 1, 12345, None);
         assert!(!loot.is_empty(), "Loot should not be empty");
     }
 
     #[test]
     fn test_fire_monster_drops_thermal() {
         let tags = SemanticTags::new(vec![("fire", 0.9)]);
-        let loot = generate_loot(&tags, 50, 99999);
+        let loot = generate_loot(&tags, 50, 99999, None);
 
         let has_ember = loot.iter().any(|item| item.name.contains("Ember"));
         assert!(has_ember, "Fire monsters should drop fire-themed loot");
     }
 
+    #[test]
+    fn test_blended_loot_deterministic() {
+        let monster = SemanticTags::new(vec![("fire", 0.9)]);
+        let floor = SemanticTags::new(vec![("water", 0.8)]);
+        let loot_a = generate_loot(&monster, 10, 42, Some(&floor));
+        let loot_b = generate_loot(&monster, 10, 42, Some(&floor));
+
+        assert_eq!(loot_a.len(), loot_b.len());
+        for (a, b) in loot_a.iter().zip(loot_b.iter()) {
+            assert_eq!(a.name, b.name);
+            assert_eq!(a.quantity, b.quantity);
+            assert_eq!(a.semantic_tags, b.semantic_tags);
+        }
+    }
+
+    #[test]
+    fn test_blended_loot_carries_floor_tags() {
+        let monster = SemanticTags::new(vec![("fire", 0.9)]);
+        let floor = SemanticTags::new(vec![("water", 0.9)]);
+        let loot = generate_loot(&monster, 10, 42, Some(&floor));
+
+        assert!(!loot.is_empty());
+        for item in &loot {
+            let tags = SemanticTags {
+                tags: item.semantic_tags.clone(),
+            };
+            assert!(tags.get("fire") > tags.get("water"));
+            assert!(tags.get("water") > 0.0, "floor tag should carry over");
+        }
+    }
+
+    fn ember_count(monster: &SemanticTags, player: &SemanticTags, bias: f32) -> (usize, usize) {
+        let items: Vec<LootItem> = (0..400u64)
+            .flat_map(|h| generate_loot_targeted(monster, 10, h * 7919 + 1, None, player, bias))
+            .collect();
+        let embers = items.iter().filter(|i| i.name.starts_with("Ember")).count();
+        (embers, items.len())
+    }
+
+    #[test]
+    fn test_targeted_loot_favors_player_dominant_tag() {
+        // Water edges out fire on the monster, so untargeted drops are Tide
+        let monster = SemanticTags::new(vec![("fire", 0.5), ("water", 0.6)]);
+        let player = SemanticTags::new(vec![("fire", 0.9), ("earth", 0.2)]);
+
+        let (base, _) = ember_count(&monster, &player, 0.0);
+        let (targeted, total) = ember_count(&monster, &player, 0.5);
+        assert_eq!(base, 0);
+        assert!(
+            targeted > total / 4,
+            "fire drops should be common: {targeted}/{total}"
+        );
+        assert!(targeted < total, "randomness must not be fully overridden");
+    }
+
+    #[test]
+    fn test_targeted_loot_bias_clamped() {
+        let monster = SemanticTags::new(vec![("fire", 0.5), ("water", 0.6)]);
+        let player = SemanticTags::new(vec![("fire", 0.9)]);
+        assert_eq!(
+            ember_count(&monster, &player, 0.5),
+            ember_count(&monster, &player, 3.0)
+        );
+        assert_eq!(
+            ember_count(&monster, &player, 0.0),
+            ember_count(&monster, &player, -1.0)
+        );
+    }
+
+    #[test]
+    fn test_targeted_loot_needs_overlap() {
+        let monster = SemanticTags::new(vec![("water", 0.8)]);
+        let player = SemanticTags::new(vec![("fire", 0.9)]);
+        for h in 0..50u64 {
+            let plain = generate_loot(&monster, 10, h, None);
+            let targeted = generate_loot_targeted(&monster, 10, h, None, &player, 0.5);
+            assert_eq!(plain.len(), targeted.len());
+            for (a, b) in plain.iter().zip(&targeted) {
+                assert_eq!(a.name, b.name);
+                assert_eq!(a.semantic_tags, b.semantic_tags);
+            }
+        }
+    }
+
+    #[test]
+    fn test_equipment_drops_carry_stats() {
+        let tags = SemanticTags::new(vec![("fire", 0.8)]);
+        let mut saw_equipment = false;
+        for h in 0..500u64 {
+            for item in generate_loot(&tags, 10, h, None) {
+                assert_eq!(
+                    item.stats.is_some(),
+                    item.category == LootCategory::Equipment
+                );
+                saw_equipment |= item.category == LootCategory::Equipment;
+            }
+        }
+        assert!(saw_equipment);
+
+        let boxed = open_mystery_box(2, 10, 7);
+        assert_eq!(
+            boxed.stats.is_some(),
+            boxed.category == LootCategory::Equipment
+        );
+    }
+
+    #[test]
+    fn test_breath_resource_mult_increases_quantity() {
+        use crate::world::BreathPhase;
+
+        let tags = SemanticTags::new(vec![("fire", 0.8)]);
+        let total = |mult: Option<f32>| -> u32 {
+            (0..200u64)
+                .flat_map(|h| match mult {
+                    Some(m) => generate_loot_breath(&tags, 10, h, None, m),
+                    None => generate_loot(&tags, 10, h, None),
+                })
+                .map(|i| i.quantity)
+                .sum()
+        };
+        let baseline = total(None);
+        assert_eq!(total(Some(1.0)), baseline);
+        assert!(total(Some(BreathPhase::Inhale.resource_multiplier())) > baseline);
+        assert!(total(Some(BreathPhase::Pause.resource_multiplier())) < baseline);
+    }
+
+    fn test_item(category: LootCategory, rarity: ItemRarity) -> LootItem {
+        LootItem {
+            name: "Test".into(),
+            category,
+            rarity,
+            quantity: 1,
+            semantic_tags: vec![],
+            stats: None,
+        }
+    }
+
+    #[test]
+    fn test_pickup_filter_min_rarity() {
+        let filter = PickupFilter {
+            min_rarity: ItemRarity::Rare,
+            ignore_categories: vec![],
+        };
+        assert!(!filter.should_pickup(&test_item(LootCategory::Material, ItemRarity::Common)));
+        assert!(filter.should_pickup(&test_item(LootCategory::Material, ItemRarity::Rare)));
+        assert!(filter.should_pickup(&test_item(LootCategory::Material, ItemRarity::Legendary)));
+    }
+
+    #[test]
+    fn test_pickup_filter_ignores_categories() {
+        let filter = PickupFilter {
+            min_rarity: ItemRarity::Common,
+            ignore_categories: vec![LootCategory::Consumable],
+        };
+        assert!(!filter.should_pickup(&test_item(LootCategory::Consumable, ItemRarity::Epic)));
+        assert!(filter.should_pickup(&test_item(LootCategory::Currency, ItemRarity::Common)));
+    }
+
+    #[test]
+    fn test_pickup_filter_always_takes_quest_items() {
+        let filter = PickupFilter {
+            min_rarity: ItemRarity::Mythic,
+            ignore_categories: vec![LootCategory::QuestItem],
+        };
+        assert!(filter.should_pickup(&test_item(LootCategory::QuestItem, ItemRarity::Common)));
+    }
+
+    fn enchantable_item() -> LootItem {
+        LootItem {
+            name: "Ember Blade".into(),
+            category: LootCategory::Equipment,
+            rarity: ItemRarity::Rare,
+            quantity: 1,
+            semantic_tags: vec![("fire".into(), 0.2), ("water".into(), -0.2)],
+            stats: None,
+        }
+    }
+
+    #[test]
+    fn test_enchant_reroll_deterministic() {
+        let mut a = enchantable_item();
+        let mut b = enchantable_item();
+        enchant_reroll(&mut a, MasteryTier::Journeyman, 777).unwrap();
+        enchant_reroll(&mut b, MasteryTier::Journeyman, 777).unwrap();
+
+        assert_eq!(a.semantic_tags, b.semantic_tags);
+        assert_ne!(a.semantic_tags, enchantable_item().semantic_tags);
+
+        let (min, max) = affix_bounds(ItemRarity::Rare);
+        assert!(a.semantic_tags[0].1 >= min && a.semantic_tags[0].1 <= max);
+        assert!(a.semantic_tags[1].1 < 0.0, "resistance sign is preserved");
+    }
+
+    #[test]
+    fn test_enchant_higher_tier_rolls_stronger() {
+        let mean_magnitude = |tier: MasteryTier| -> f32 {
+            let mut total = 0.0;
+            for hash in 1..500u64 {
+                let mut item = enchantable_item();
+                enchant_reroll(&mut item, tier, hash * 7919).unwrap();
+                total += item.semantic_tags[0].1;
+            }
+            total / 499.0
+        };
+        assert!(mean_magnitude(MasteryTier::Grandmaster) > mean_magnitude(MasteryTier::Novice));
+    }
+
+    #[test]
+    fn test_enchant_rerolls_rolled_stats() {
+        let mut original = enchantable_item();
+        original.stats = Some(roll_stats(
+            &SemanticTags {
+                tags: original.semantic_tags.clone(),
+            },
+            ItemRarity::Rare,
+            5,
+        ));
+        let mut item = original.clone();
+        enchant_reroll(&mut item, MasteryTier::Journeyman, 777).unwrap();
+
+        // Rolled stats are the affixes; tags are left alone
+        assert_eq!(item.semantic_tags, original.semantic_tags);
+        let (stats, before) = (item.stats.unwrap(), original.stats.unwrap());
+        assert_ne!(stats, before);
+        assert_eq!(stats.secondary.len(), before.secondary.len());
+        for (affix, old) in std::iter::once(&stats.primary)
+            .chain(&stats.secondary)
+            .zip(std::iter::once(&before.primary).chain(&before.secondary))
+        {
+            assert_eq!(affix.stat, old.stat);
+            assert_eq!(affix.element, old.element);
+            let (min, max) = affix_range(affix.stat, ItemRarity::Rare);
+            assert!(affix.value >= min && affix.value <= max);
+        }
+    }
+
+    #[test]
+    fn test_enchant_rejects_non_equipment() {
+        let mut item = test_item(LootCategory::Consumable, ItemRarity::Epic);
+        item.semantic_tags = vec![("fire".into(), 0.3)];
+        assert_eq!(
+            enchant_reroll(&mut item, MasteryTier::Master, 1),
+            Err(EnchantError::NotEnchantable {
+                category: LootCategory::Consumable
+            })
+        );
+
+        let mut bare = test_item(LootCategory::Equipment, ItemRarity::Epic);
+        assert_eq!(
+            enchant_reroll(&mut bare, MasteryTier::Master, 1),
+            Err(EnchantError::NoAffixes)
+        );
+    }
+
+    #[test]
+    fn test_negative_tags_carry_as_resistance() {
+        let tags = SemanticTags::new(vec![("water", 0.8), ("fire", -0.6)]);
+        let loot = generate_loot(&tags, 10, 42, None);
+
+        assert!(!loot.is_empty());
+        for item in &loot {
+            let item_tags = SemanticTags {
+                tags: item.semantic_tags.clone(),
+            };
+            assert!(item_tags.get("fire") < 0.0);
+        }
+    }
+
     #[test]
     fn test_rarity_distribution() {
         let mut common_count = 0;
         let mut rare_plus_count = 0;
 
         for i in 0..1000 {
-            let rarity = roll_rarity(1, i * 7 + 13);
+            let rarity = roll_rarity(1, i * 7 + 13, RarityBoost::default());
             match rarity {
                 ItemRarity::Common | ItemRarity::Uncommon => common_count += 1,
                 _ => rare_plus_count += 1,
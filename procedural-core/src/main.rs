@@ -57,6 +57,7 @@ fn main() {
         .add_plugins(movement::MovementPlugin)
         .add_plugins(aerial::AerialPlugin)
         .add_plugins(death::DeathPlugin)
+        .add_plugins(specialization::SpecializationPlugin)
         // Entity systems
         .add_plugins(monster::MonsterPlugin)
         .add_plugins(player::PlayerPlugin)
@@ -156,6 +156,32 @@ pub struct MasteryProgress {
     pub xp: u64,
     pub tier: MasteryTier,
     pub unlocked_nodes: Vec<String>,
+    /// Profile clock (seconds) at the last XP gain, used for inactivity decay
+    #[serde(default)]
+    pub last_gain_tick: u64,
+    /// Times this domain has been prestiged from Grandmaster
+    #[serde(default)]
+    pub prestige_level: u32,
+    /// Nodes unlocked before each prestige reset
+    #[serde(default)]
+    pub prestige_history: Vec<PrestigeRecord>,
+}
+
+/// Snapshot of a domain's unlocked nodes at the moment it was prestiged
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PrestigeRecord {
+    pub prestige_level: u32,
+    pub unlocked_nodes: Vec<String>,
+}
+
+/// Permanent bonus per prestige level (2% per level)
+pub const PRESTIGE_BONUS_PER_LEVEL: f32 = 0.02;
+
+/// Mastery errors
+#[derive(Debug, Clone, PartialEq)]
+pub enum MasteryError {
+    UnknownDomain(MasteryDomain),
+    NotGrandmaster { current: MasteryTier },
 }
 
 impl MasteryProgress {
@@ -165,9 +191,35 @@ impl MasteryProgress {
             xp: 0,
             tier: MasteryTier::Novice,
             unlocked_nodes: Vec::new(),
+            last_gain_tick: 0,
+            prestige_level: 0,
+            prestige_history: Vec::new(),
         }
     }
 
+    /// Permanent bonus earned through prestige, themed by domain
+    pub fn prestige_bonus(&self) -> Option<SkillEffect> {
+        if self.prestige_level == 0 {
+            return None;
+        }
+        let bonus = self.prestige_level as f32 * PRESTIGE_BONUS_PER_LEVEL;
+        let effect = match self.domain.category() {
+            MasteryCategory::Weapon => SkillEffect::DamageBonus(bonus),
+            MasteryCategory::CombatTechnique => SkillEffect::ResourceCostReduction(bonus),
+            MasteryCategory::Crafting => SkillEffect::CraftingQualityBonus(bonus),
+            MasteryCategory::Gathering => SkillEffect::GatheringYieldBonus(bonus),
+            MasteryCategory::Other => match self.domain {
+                MasteryDomain::Trading => SkillEffect::TradeDiscount(bonus),
+                MasteryDomain::Exploration => SkillEffect::ExplorationRadius(bonus),
+                _ => SkillEffect::SemanticAffinity {
+                    tag: "attunement".into(),
+                    bonus,
+                },
+            },
+        };
+        Some(effect)
+    }
+
     /// Add XP from using this skill. Returns true if tier changed.
     pub fn add_xp(&mut self, amount: u64) -> bool {
         let old_tier = self.tier;
@@ -265,10 +317,34 @@ pub enum SkillEffect {
     ExplorationRadius(f32),
 }
 
+/// Seconds in a real day (decay unit)
+pub const SECS_PER_DAY: u64 = 86_400;
+
+/// Opt-in XP decay for unused domains
+#[derive(Debug, Clone)]
+pub struct DecayConfig {
+    /// Share of XP lost per full day of inactivity
+    pub fraction_per_day: f32,
+    /// Per-domain tier floors, e.g. the tier a chosen specialization branch requires
+    pub tier_floors: HashMap<MasteryDomain, MasteryTier>,
+}
+
+impl Default for DecayConfig {
+    fn default() -> Self {
+        Self {
+            fraction_per_day: 0.01,
+            tier_floors: HashMap::new(),
+        }
+    }
+}
+
 /// Player's complete mastery profile
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MasteryProfile {
     pub masteries: HashMap<MasteryDomain, MasteryProgress>,
+    /// Profile clock in seconds, advanced by `apply_decay`
+    #[serde(default)]
+    pub clock_tick: u64,
 }
 
 impl Default for MasteryProfile {
@@ -307,18 +383,81 @@ impl MasteryProfile {
         for domain in all_domains {
             masteries.insert(domain, MasteryProgress::new(domain));
         }
-        Self { masteries }
+        Self {
+            masteries,
+            clock_tick: 0,
+        }
     }
 
     /// Add XP to a specific domain. Returns true if tier changed.
     pub fn gain_xp(&mut self, domain: MasteryDomain, amount: u64) -> bool {
         if let Some(progress) = self.masteries.get_mut(&domain) {
+            progress.last_gain_tick = self.clock_tick;
             progress.add_xp(amount)
         } else {
             false
         }
     }
 
+    /// Prestige a Grandmaster domain: XP and unlocked nodes reset (nodes are
+    /// kept in `prestige_history`) in exchange for a permanent bonus.
+    /// Returns the new prestige level.
+    pub fn prestige(&mut self, domain: MasteryDomain) -> Result<u32, MasteryError> {
+        let progress = self
+            .masteries
+            .get_mut(&domain)
+            .ok_or(MasteryError::UnknownDomain(domain))?;
+        if progress.tier != MasteryTier::Grandmaster {
+            return Err(MasteryError::NotGrandmaster {
+                current: progress.tier,
+            });
+        }
+
+        progress.prestige_level += 1;
+        progress.prestige_history.push(PrestigeRecord {
+            prestige_level: progress.prestige_level,
+            unlocked_nodes: std::mem::take(&mut progress.unlocked_nodes),
+        });
+        progress.xp = 0;
+        progress.tier = MasteryTier::Novice;
+        Ok(progress.prestige_level)
+    }
+
+    /// Advance the profile clock and decay domains above Journeyman that
+    /// haven't gained XP for full days. XP never drops below the Journeyman
+    /// threshold or below the domain's floor from `config.tier_floors`.
+    pub fn apply_decay(&mut self, elapsed_secs: f32, config: &DecayConfig) {
+        let window_start = self.clock_tick;
+        self.clock_tick += elapsed_secs.max(0.0) as u64;
+        let rate = config.fraction_per_day.clamp(0.0, 1.0);
+
+        for progress in self.masteries.values_mut() {
+            if progress.tier <= MasteryTier::Journeyman {
+                continue;
+            }
+
+            // Only full idle days completed inside this window count
+            let idle_before = window_start.saturating_sub(progress.last_gain_tick) / SECS_PER_DAY;
+            let idle_now = self.clock_tick.saturating_sub(progress.last_gain_tick) / SECS_PER_DAY;
+            let days = idle_now - idle_before;
+            if days == 0 {
+                continue;
+            }
+
+            let floor_tier = config
+                .tier_floors
+                .get(&progress.domain)
+                .copied()
+                .map_or(MasteryTier::Journeyman, |t| t.max(MasteryTier::Journeyman));
+            let floor_xp = floor_tier.xp_threshold();
+
+            let decayed =
+                (progress.xp as f64 * (1.0 - rate as f64).powi(days as i32)).round() as u64;
+            progress.xp = decayed.max(floor_xp).min(progress.xp);
+            progress.tier = MasteryTier::from_xp(progress.xp);
+        }
+    }
+
     /// Get mastery for a domain
     pub fn get(&self, domain: MasteryDomain) -> Option<&MasteryProgress> {
         self.masteries.get(&domain)
@@ -351,6 +490,7 @@ impl MasteryProfile {
                     effects.extend(node.effects.clone());
                 }
             }
+            effects.extend(progress.prestige_bonus());
         }
         effects
     }
@@ -436,6 +576,17 @@ impl SkillTree {
             effects: vec![SkillEffect::UnlockAbility("Riposte".into())],
         });
 
+        // === Gauntlet Mastery Tree ===
+        nodes.push(SkillTreeNode {
+            id: "gauntlet_slam".into(),
+            name: "Ground Slam".into(),
+            description: "Unlock a stunning AoE ground slam.".into(),
+            domain: MasteryDomain::GauntletMastery,
+            required_tier: MasteryTier::Expert,
+            prerequisites: vec![],
+            effects: vec![SkillEffect::UnlockAbility("Ground Slam".into())],
+        });
+
         // === Dodge Mastery Tree ===
         nodes.push(SkillTreeNode {
             id: "dodge_extended".into(),
@@ -772,4 +923,117 @@ mod tests {
         assert_eq!(progress.tier, MasteryTier::Grandmaster);
         assert!((progress.tier_progress() - 1.0).abs() < 0.01); // capped at 1.0
     }
+
+    #[test]
+    fn test_decay_leaves_novice_untouched() {
+        let mut profile = MasteryProfile::new();
+        profile.gain_xp(MasteryDomain::Mining, 50);
+
+        profile.apply_decay((SECS_PER_DAY * 30) as f32, &DecayConfig::default());
+        assert_eq!(profile.get(MasteryDomain::Mining).unwrap().xp, 50);
+    }
+
+    #[test]
+    fn test_decay_per_idle_day_floors_at_journeyman() {
+        let mut profile = MasteryProfile::new();
+        profile.gain_xp(MasteryDomain::SwordMastery, 2000); // Expert
+        let config = DecayConfig {
+            fraction_per_day: 0.1,
+            ..Default::default()
+        };
+
+        // Less than a full day: nothing happens
+        profile.apply_decay((SECS_PER_DAY / 2) as f32, &config);
+        assert_eq!(profile.get(MasteryDomain::SwordMastery).unwrap().xp, 2000);
+
+        // Completes the first idle day
+        profile.apply_decay((SECS_PER_DAY / 2) as f32, &config);
+        assert_eq!(profile.get(MasteryDomain::SwordMastery).unwrap().xp, 1800);
+
+        profile.apply_decay((SECS_PER_DAY * 365) as f32, &config);
+        let sword = profile.get(MasteryDomain::SwordMastery).unwrap();
+        assert_eq!(sword.xp, MasteryTier::Journeyman.xp_threshold());
+        assert_eq!(sword.tier, MasteryTier::Journeyman);
+    }
+
+    #[test]
+    fn test_decay_halts_at_spec_locked_floor() {
+        let mut profile = MasteryProfile::new();
+        profile.gain_xp(MasteryDomain::SwordMastery, 5000); // Master
+        let mut config = DecayConfig {
+            fraction_per_day: 0.2,
+            ..Default::default()
+        };
+        config
+            .tier_floors
+            .insert(MasteryDomain::SwordMastery, MasteryTier::Expert);
+
+        profile.apply_decay((SECS_PER_DAY * 100) as f32, &config);
+        let sword = profile.get(MasteryDomain::SwordMastery).unwrap();
+        assert_eq!(sword.xp, MasteryTier::Expert.xp_threshold());
+        assert_eq!(sword.tier, MasteryTier::Expert);
+    }
+
+    #[test]
+    fn test_gaining_xp_resets_decay_timer() {
+        let mut profile = MasteryProfile::new();
+        profile.gain_xp(MasteryDomain::DaggerMastery, 3000);
+        let config = DecayConfig {
+            fraction_per_day: 0.1,
+            ..Default::default()
+        };
+
+        profile.apply_decay((SECS_PER_DAY - 10) as f32, &config);
+        profile.gain_xp(MasteryDomain::DaggerMastery, 1);
+        profile.apply_decay(20.0, &config);
+        assert_eq!(profile.get(MasteryDomain::DaggerMastery).unwrap().xp, 3001);
+    }
+
+    #[test]
+    fn test_prestige_requires_grandmaster() {
+        let mut profile = MasteryProfile::new();
+        profile.gain_xp(MasteryDomain::Alchemy, 7999); // Master
+
+        assert_eq!(
+            profile.prestige(MasteryDomain::Alchemy),
+            Err(MasteryError::NotGrandmaster {
+                current: MasteryTier::Master
+            })
+        );
+        assert_eq!(profile.get(MasteryDomain::Alchemy).unwrap().xp, 7999);
+    }
+
+    #[test]
+    fn test_prestige_two_cycles() {
+        let tree = SkillTree::new();
+        let mut profile = MasteryProfile::new();
+        let domain = MasteryDomain::SwordMastery;
+
+        profile.gain_xp(domain, 8000);
+        let node = tree.get_node("sword_keen_edge").unwrap().clone();
+        profile
+            .masteries
+            .get_mut(&domain)
+            .unwrap()
+            .unlock_node(&node);
+        assert_eq!(profile.prestige(domain), Ok(1));
+
+        let sword = profile.get(domain).unwrap();
+        assert_eq!(sword.xp, 0);
+        assert_eq!(sword.tier, MasteryTier::Novice);
+        assert!(sword.unlocked_nodes.is_empty());
+        assert_eq!(
+            sword.prestige_history[0].unlocked_nodes,
+            vec!["sword_keen_edge"]
+        );
+
+        profile.gain_xp(domain, 9000);
+        assert_eq!(profile.prestige(domain), Ok(2));
+        assert_eq!(profile.get(domain).unwrap().prestige_history.len(), 2);
+
+        let effects = profile.active_effects(&tree);
+        assert!(effects.iter().any(
+            |e| matches!(e, SkillEffect::DamageBonus(b) if (b - 2.0 * PRESTIGE_BONUS_PER_LEVEL).abs() < 1e-6)
+        ));
+    }
 }
@@ -0,0 +1,313 @@
+//! Floor bosses: the regular monster grammar plus phases and mechanics.
+//!
+//! A boss is always Large or Colossal, scales well past `compute_stats`, and
+//! carries at least one special mechanic. Deeper echelons add phases.
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::{generate_name, MonsterBehavior, MonsterSize, MonsterStats, MonsterTemplate};
+use crate::generation::{xorshift, FloorTier};
+
+/// Boss HP relative to a regular monster with the same template
+pub const BOSS_HP_MULT: f32 = 8.0;
+/// Boss damage relative to a regular monster with the same template
+pub const BOSS_DAMAGE_MULT: f32 = 2.0;
+/// Boss XP reward relative to a regular monster with the same template
+pub const BOSS_XP_MULT: u32 = 5;
+
+/// One stage of a boss fight, entered when HP drops to `hp_threshold`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BossPhase {
+    /// Fraction of max HP (1.0 = fight start) at which this phase begins
+    pub hp_threshold: f32,
+    pub damage_mult: f32,
+    pub speed_mult: f32,
+    /// Behavior the boss switches to for this phase (None = keep template's)
+    #[serde(default)]
+    pub behavior: Option<MonsterBehavior>,
+}
+
+/// Tracks which phase a boss fight has reached.
+///
+/// `thresholds` are the HP fractions (descending) at which phases 1, 2, ...
+/// begin. Phases only move forward: healing back above a threshold keeps
+/// the boss in the phase it already reached.
+#[derive(Component, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BossPhases {
+    pub thresholds: Vec<f32>,
+    #[serde(default)]
+    reached: usize,
+}
+
+impl BossPhases {
+    pub fn new(mut thresholds: Vec<f32>) -> Self {
+        thresholds.sort_by(|a, b| b.total_cmp(a));
+        Self {
+            thresholds,
+            reached: 0,
+        }
+    }
+
+    /// Phase for the current HP fraction, never lower than one already reached
+    pub fn current_phase(&mut self, hp_frac: f32) -> usize {
+        let by_hp = self.thresholds.iter().filter(|&&t| hp_frac <= t).count();
+        self.reached = self.reached.max(by_hp);
+        self.reached
+    }
+
+    /// Highest phase reached so far
+    pub fn reached(&self) -> usize {
+        self.reached
+    }
+}
+
+/// Special behavior layered on top of the base monster
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum BossMechanic {
+    /// Damage spikes once HP falls below `hp_threshold`
+    Enrage { hp_threshold: f32, damage_mult: f32 },
+    /// Calls `count` regular monsters every `interval_secs`
+    SummonAdds { count: u32, interval_secs: f32 },
+    /// Goes invulnerable for `invulnerable_secs` between phases
+    PhaseTransition { invulnerable_secs: f32 },
+}
+
+/// A boss: monster template plus phases and mechanics
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BossTemplate {
+    pub template: MonsterTemplate,
+    pub phases: Vec<BossPhase>,
+    pub mechanics: Vec<BossMechanic>,
+}
+
+impl MonsterTemplate {
+    /// Generate a floor boss. Same hash and floor always give the same boss.
+    pub fn boss_from_hash(hash: u64, floor_level: u32) -> BossTemplate {
+        let mut template = Self::from_hash(hash, floor_level);
+        template.size = if hash & 0x7 >= 5 {
+            MonsterSize::Colossal
+        } else {
+            MonsterSize::Large
+        };
+        if template.behavior == MonsterBehavior::Passive {
+            template.behavior = MonsterBehavior::Guardian;
+        }
+        template.name = generate_name(template.size, template.element, template.corruption);
+
+        let mut rng = xorshift(hash.max(1));
+        let (min_phases, max_phases) = match FloorTier::from_floor_id(floor_level) {
+            FloorTier::Echelon1 => (1, 2),
+            FloorTier::Echelon2 => (2, 2),
+            FloorTier::Echelon3 => (2, 3),
+            FloorTier::Echelon4 => (3, 4),
+        };
+        let phase_count = min_phases + (rng % (max_phases - min_phases + 1));
+        let phases = (0..phase_count)
+            .map(|i| {
+                let step = i as f32;
+                BossPhase {
+                    hp_threshold: 1.0 - step / phase_count as f32,
+                    damage_mult: 1.0 + 0.25 * step,
+                    speed_mult: 1.0 + 0.1 * step,
+                    // Later phases drop any defensive stance and press the attack
+                    behavior: (i > 0).then_some(MonsterBehavior::Aggressive),
+                }
+            })
+            .collect::<Vec<_>>();
+
+        rng = xorshift(rng);
+        let mut pool = vec![
+            BossMechanic::Enrage {
+                hp_threshold: 0.2 + (rng % 20) as f32 / 100.0,
+                damage_mult: 1.5,
+            },
+            BossMechanic::SummonAdds {
+                count: 2 + (rng >> 8) as u32 % 3,
+                interval_secs: 20.0 + ((rng >> 16) % 20) as f32,
+            },
+        ];
+        if phases.len() >= 2 {
+            pool.push(BossMechanic::PhaseTransition {
+                invulnerable_secs: 2.0 + ((rng >> 24) % 3) as f32,
+            });
+        }
+
+        // Pick 1..=pool.len() mechanics, starting at a hash-chosen offset
+        rng = xorshift(rng);
+        let count = 1 + (rng % pool.len() as u64) as usize;
+        rng = xorshift(rng);
+        let start = (rng % pool.len() as u64) as usize;
+        let mechanics = (0..count)
+            .map(|i| pool[(start + i) % pool.len()].clone())
+            .collect();
+
+        BossTemplate {
+            template,
+            phases,
+            mechanics,
+        }
+    }
+}
+
+impl BossTemplate {
+    /// Stats of the underlying monster, scaled up to boss strength
+    pub fn compute_stats(&self) -> MonsterStats {
+        let base = self.template.compute_stats();
+        MonsterStats {
+            max_hp: base.max_hp * BOSS_HP_MULT,
+            damage: base.damage * BOSS_DAMAGE_MULT,
+            xp_reward: base.xp_reward * BOSS_XP_MULT,
+            ..base
+        }
+    }
+
+    /// Phase tracker for a fresh fight against this boss
+    pub fn phase_tracker(&self) -> BossPhases {
+        BossPhases::new(self.phases.iter().skip(1).map(|p| p.hp_threshold).collect())
+    }
+
+    /// Behavior during `phase` (clamped to the last phase)
+    pub fn behavior_for_phase(&self, phase: usize) -> MonsterBehavior {
+        self.phase(phase)
+            .and_then(|p| p.behavior)
+            .unwrap_or(self.template.behavior)
+    }
+
+    /// Boss stats with `phase`'s damage/speed multipliers and behavior applied
+    pub fn compute_stats_for_phase(&self, phase: usize) -> MonsterStats {
+        let template = MonsterTemplate {
+            behavior: self.behavior_for_phase(phase),
+            ..self.template.clone()
+        };
+        let base = BossTemplate {
+            template,
+            phases: vec![],
+            mechanics: vec![],
+        }
+        .compute_stats();
+
+        let (damage_mult, speed_mult) = self
+            .phase(phase)
+            .map_or((1.0, 1.0), |p| (p.damage_mult, p.speed_mult));
+        MonsterStats {
+            damage: base.damage * damage_mult,
+            speed: base.speed * speed_mult,
+            ..base
+        }
+    }
+
+    fn phase(&self, phase: usize) -> Option<&BossPhase> {
+        self.phases.get(phase).or(self.phases.last())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_boss_deterministic() {
+        let a = MonsterTemplate::boss_from_hash(4242, 150);
+        let b = MonsterTemplate::boss_from_hash(4242, 150);
+        assert_eq!(a.template.name, b.template.name);
+        assert_eq!(a.phases, b.phases);
+        assert_eq!(a.mechanics, b.mechanics);
+    }
+
+    #[test]
+    fn test_boss_always_has_mechanic() {
+        for hash in 0..500u64 {
+            for floor in [1, 150, 350, 600] {
+                let boss = MonsterTemplate::boss_from_hash(hash * 31 + 7, floor);
+                assert!(!boss.mechanics.is_empty());
+                assert!(!boss.phases.is_empty());
+                assert!(matches!(
+                    boss.template.size,
+                    MonsterSize::Large | MonsterSize::Colossal
+                ));
+            }
+        }
+    }
+
+    #[test]
+    fn test_deep_bosses_have_multiple_phases() {
+        for hash in 0..500u64 {
+            for floor in [301, 420, 500, 501, 900] {
+                let boss = MonsterTemplate::boss_from_hash(hash * 31 + 7, floor);
+                assert!(
+                    boss.phases.len() >= 2,
+                    "floor {floor} boss has {} phases",
+                    boss.phases.len()
+                );
+                assert_eq!(boss.phases[0].hp_threshold, 1.0);
+            }
+        }
+    }
+
+    #[test]
+    fn test_boss_outscales_regular_monster() {
+        let boss = MonsterTemplate::boss_from_hash(12345, 200);
+        let regular = boss.template.compute_stats();
+        let stats = boss.compute_stats();
+        assert!(stats.max_hp >= regular.max_hp * BOSS_HP_MULT);
+        assert!(stats.damage > regular.damage);
+        assert_eq!(stats.xp_reward, regular.xp_reward * BOSS_XP_MULT);
+    }
+
+    #[test]
+    fn test_boss_phases_advance_on_threshold() {
+        let mut phases = BossPhases::new(vec![0.4, 0.7]);
+        assert_eq!(phases.thresholds, vec![0.7, 0.4]);
+        assert_eq!(phases.current_phase(1.0), 0);
+        assert_eq!(phases.current_phase(0.71), 0);
+        assert_eq!(phases.current_phase(0.7), 1);
+        assert_eq!(phases.current_phase(0.2), 2);
+    }
+
+    #[test]
+    fn test_boss_phases_do_not_regress_on_heal() {
+        let mut phases = BossPhases::new(vec![0.5]);
+        assert_eq!(phases.current_phase(0.45), 1);
+        assert_eq!(phases.current_phase(0.9), 1);
+        assert_eq!(phases.current_phase(1.0), 1);
+        assert_eq!(phases.reached(), 1);
+    }
+
+    #[test]
+    fn test_phase_modifiers_apply_to_stats() {
+        let boss = (0..100u64)
+            .map(|h| MonsterTemplate::boss_from_hash(h, 600))
+            .find(|b| b.template.behavior != MonsterBehavior::Aggressive)
+            .expect("a non-aggressive boss");
+        assert!(boss.phases.len() >= 3);
+
+        let p0 = boss.compute_stats_for_phase(0);
+        let p2 = boss.compute_stats_for_phase(2);
+        assert_eq!(p0.damage, boss.compute_stats().damage);
+        assert!((p2.damage - p0.damage * boss.phases[2].damage_mult).abs() < 0.01);
+        assert!(p2.speed > p0.speed);
+        assert_eq!(p0.max_hp, p2.max_hp);
+
+        assert_eq!(boss.behavior_for_phase(0), boss.template.behavior);
+        assert_eq!(boss.behavior_for_phase(2), MonsterBehavior::Aggressive);
+        assert_ne!(p0.detection_range, p2.detection_range);
+
+        let mut tracker = boss.phase_tracker();
+        assert_eq!(tracker.thresholds.len(), boss.phases.len() - 1);
+        assert_eq!(tracker.current_phase(1.0), 0);
+    }
+
+    #[test]
+    fn test_phase_transition_needs_phases() {
+        for hash in 0..500u64 {
+            let boss = MonsterTemplate::boss_from_hash(hash, 10);
+            if boss.phases.len() < 2 {
+                assert!(!boss
+                    .mechanics
+                    .iter()
+                    .any(|m| matches!(m, BossMechanic::PhaseTransition { .. })));
+            }
+        }
+    }
+}
@@ -8,9 +8,12 @@ use serde::{Deserialize, Serialize};
 
 use crate::combat::{CombatResources, CombatState};
 use crate::death::Mortal;
-use crate::semantic::SemanticTags;
+use crate::semantic::{SemanticTags, VULN_PREFIX};
 
 pub mod ai;
+pub mod boss;
+
+pub use boss::{BossMechanic, BossPhase, BossPhases, BossTemplate};
 
 pub struct MonsterPlugin;
 
@@ -190,7 +193,8 @@ impl MonsterTemplate {
         }
     }
 
-    /// Generate semantic tags for this monster
+    /// Generate semantic tags for this monster, normalized to unit length
+    /// so `similarity`-based systems see comparable vectors
     pub fn semantic_tags(&self) -> SemanticTags {
         let mut tags = vec![];
 
@@ -234,7 +238,85 @@ impl MonsterTemplate {
         };
         tags.push(("presence", presence));
 
-        SemanticTags::new(tags)
+        let mut tags = SemanticTags::new(tags);
+        tags.normalize();
+        tags
+    }
+
+    /// Damage-taken multipliers (> 1.0) against attack tags this monster is weak to
+    pub fn weaknesses(&self) -> Vec<(String, f32)> {
+        let mut out = Vec::new();
+        let counter = match self.element {
+            MonsterElement::Fire => Some("water"),
+            MonsterElement::Water => Some("earth"),
+            MonsterElement::Earth => Some("wind"),
+            MonsterElement::Wind => Some("fire"),
+            MonsterElement::Void => Some("holy"),
+            MonsterElement::Neutral => None,
+        };
+        if let Some(tag) = counter {
+            out.push((tag.to_string(), ELEMENT_WEAKNESS_MULT));
+        }
+
+        let holy = match self.corruption {
+            CorruptionLevel::Corrupted => 1.25,
+            CorruptionLevel::Abyssal => 1.5,
+            _ => 1.0,
+        };
+        if holy > 1.0 {
+            merge_multiplier(&mut out, "holy", holy);
+        }
+        out
+    }
+
+    /// Damage-taken multipliers (< 1.0) against attack tags this monster resists
+    pub fn resistances(&self) -> Vec<(String, f32)> {
+        let mut out = Vec::new();
+        let own = match self.element {
+            MonsterElement::Fire => Some("fire"),
+            MonsterElement::Water => Some("water"),
+            MonsterElement::Earth => Some("earth"),
+            MonsterElement::Wind => Some("wind"),
+            MonsterElement::Void => Some("void"),
+            MonsterElement::Neutral => None,
+        };
+        if let Some(tag) = own {
+            out.push((tag.to_string(), ELEMENT_RESISTANCE_MULT));
+        }
+
+        let corruption = match self.corruption {
+            CorruptionLevel::Corrupted => 0.75,
+            CorruptionLevel::Abyssal => 0.5,
+            _ => 1.0,
+        };
+        if corruption < 1.0 {
+            merge_multiplier(&mut out, "corruption", corruption);
+        }
+        out
+    }
+
+    /// `semantic_tags` plus weaknesses and resistances encoded as
+    /// `VULN_PREFIX` entries; pass these as the defender tags in combat
+    pub fn combat_tags(&self) -> SemanticTags {
+        let mut tags = self.semantic_tags();
+        for (tag, mult) in self.weaknesses().into_iter().chain(self.resistances()) {
+            tags.tags
+                .push((format!("{VULN_PREFIX}{tag}"), (mult - 1.0).clamp(-1.0, 1.0)));
+        }
+        tags
+    }
+}
+
+/// Damage taken from the element that counters a monster's own
+const ELEMENT_WEAKNESS_MULT: f32 = 1.5;
+/// Damage taken from a monster's own element
+const ELEMENT_RESISTANCE_MULT: f32 = 0.5;
+
+/// Fold a multiplier into a list, multiplying with any existing entry for the tag
+fn merge_multiplier(list: &mut Vec<(String, f32)>, tag: &str, mult: f32) {
+    match list.iter_mut().find(|(k, _)| k == tag) {
+        Some(entry) => entry.1 *= mult,
+        None => list.push((tag.to_string(), mult)),
     }
 }
 
@@ -404,7 +486,61 @@ mod tests {
         };
         let tags = template.semantic_tags();
         assert!(tags.get("fire") > 0.5);
-        assert!(tags.get("aggression") > 0.8);
+        assert!(tags.get("aggression") > 0.6);
         assert!(tags.get("corruption") > 0.2);
+        assert_eq!(tags.dominant().unwrap().0, "aggression");
+    }
+
+    fn fire_monster(corruption: CorruptionLevel) -> MonsterTemplate {
+        MonsterTemplate {
+            name: "T".into(),
+            size: MonsterSize::Medium,
+            element: MonsterElement::Fire,
+            corruption,
+            behavior: MonsterBehavior::Aggressive,
+            base_level: 1,
+        }
+    }
+
+    #[test]
+    fn test_fire_monster_weak_to_water() {
+        let m = fire_monster(CorruptionLevel::Pure);
+        assert_eq!(m.weaknesses(), vec![("water".to_string(), 1.5)]);
+        assert_eq!(m.resistances(), vec![("fire".to_string(), 0.5)]);
+
+        let tags = m.combat_tags();
+        assert!((tags.get("vuln:water") - 0.5).abs() < f32::EPSILON);
+        assert!((tags.get("vuln:fire") + 0.5).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn test_corruption_adds_holy_weakness() {
+        let m = fire_monster(CorruptionLevel::Abyssal);
+        assert!(m.weaknesses().contains(&("holy".to_string(), 1.5)));
+        assert!(m.resistances().contains(&("corruption".to_string(), 0.5)));
+        assert!(fire_monster(CorruptionLevel::Tainted)
+            .weaknesses()
+            .iter()
+            .all(|(k, _)| k != "holy"));
+    }
+
+    #[test]
+    fn test_combat_tags_keep_semantic_core() {
+        let m = fire_monster(CorruptionLevel::Tainted);
+        let core = m.semantic_tags();
+        let combat = m.combat_tags();
+        assert_eq!(combat.core_tags().tags, core.tags);
+    }
+
+    #[test]
+    fn test_semantic_tags_normalized() {
+        for hash in [1u64, 42, 12345, 999_999] {
+            let tags = MonsterTemplate::from_hash(hash, 10).semantic_tags();
+            assert!(
+                (tags.magnitude() - 1.0).abs() < 0.001,
+                "monster tags must be unit length, got {}",
+                tags.magnitude()
+            );
+        }
     }
 }
@@ -1,5 +1,7 @@
 use bevy::prelude::*;
 
+use crate::aerial::FlightState;
+
 pub struct MovementPlugin;
 
 impl Plugin for MovementPlugin {
@@ -51,6 +53,8 @@ pub struct MovementInput {
     pub direction: Vec2, // normalized XZ movement
     pub jump: bool,
     pub dash: bool,
+    /// Held to sprint; ignored while out of stamina
+    pub sprint: bool,
 }
 
 /// Dash ability parameters
@@ -73,6 +77,61 @@ impl Default for DashAbility {
     }
 }
 
+/// Sprint state. Sprinting spends the entity's stamina pool (the same
+/// `FlightState::stamina` flight uses), which only regenerates while the
+/// entity is grounded and not sprinting.
+#[derive(Component, Debug)]
+pub struct SprintState {
+    pub drain_rate: f32, // per second while sprinting
+    pub sprinting: bool,
+}
+
+impl Default for SprintState {
+    fn default() -> Self {
+        Self {
+            drain_rate: 20.0,
+            sprinting: false,
+        }
+    }
+}
+
+/// Move speed multiplier while sprinting
+pub const SPRINT_SPEED_MULTIPLIER: f32 = 1.6;
+/// Stamina an attack costs when started mid-sprint
+pub const SPRINT_ATTACK_COST: f32 = 15.0;
+
+impl SprintState {
+    /// Sprint for `dt` seconds, draining `stamina`. Returns false (and drops
+    /// to a walk) once stamina is depleted.
+    pub fn try_sprint(&mut self, stamina: &mut f32, dt: f32) -> bool {
+        if *stamina <= 0.0 {
+            self.sprinting = false;
+            return false;
+        }
+        *stamina = (*stamina - self.drain_rate * dt).max(0.0);
+        self.sprinting = true;
+        true
+    }
+
+    /// Not sprinting this frame, so stamina can regenerate
+    pub fn stop(&mut self) {
+        self.sprinting = false;
+    }
+
+    /// Pay for an attack. Free from a walk; mid-sprint it costs
+    /// `SPRINT_ATTACK_COST` and is refused if stamina can't cover it.
+    pub fn try_sprint_attack(&self, stamina: &mut f32) -> bool {
+        if !self.sprinting {
+            return true;
+        }
+        if *stamina < SPRINT_ATTACK_COST {
+            return false;
+        }
+        *stamina -= SPRINT_ATTACK_COST;
+        true
+    }
+}
+
 const GRAVITY: f32 = -20.0;
 const TERMINAL_VELOCITY: f32 = -50.0;
 
@@ -86,15 +145,42 @@ fn apply_gravity(time: Res<Time>, mut query: Query<&mut MovementState>) {
     }
 }
 
-fn process_movement_input(mut query: Query<(&mut MovementState, &MovementInput)>) {
-    for (mut state, input) in &mut query {
+fn process_movement_input(
+    time: Res<Time>,
+    mut query: Query<(
+        &mut MovementState,
+        &MovementInput,
+        Option<&mut SprintState>,
+        Option<&mut FlightState>,
+    )>,
+) {
+    let dt = time.delta_secs();
+    for (mut state, input, sprint, flight) in &mut query {
         // Horizontal movement
         let move_dir = Vec3::new(input.direction.x, 0.0, input.direction.y);
-        state.velocity.x = move_dir.x * state.move_speed;
-        state.velocity.z = move_dir.z * state.move_speed;
+        let moving = move_dir.length_squared() > 0.01;
+
+        // Sprint drains the flight stamina pool while moving
+        let sprinting = match (sprint, flight) {
+            (Some(mut sprint), Some(mut flight)) if input.sprint && moving => {
+                sprint.try_sprint(&mut flight.stamina, dt)
+            }
+            (Some(mut sprint), _) => {
+                sprint.stop();
+                false
+            }
+            (None, _) => false,
+        };
+        let speed = if sprinting {
+            state.move_speed * SPRINT_SPEED_MULTIPLIER
+        } else {
+            state.move_speed
+        };
+        state.velocity.x = move_dir.x * speed;
+        state.velocity.z = move_dir.z * speed;
 
         // Update facing
-        if move_dir.length_squared() > 0.01 {
+        if moving {
             state.facing = move_dir.normalize();
         }
 
@@ -164,4 +250,47 @@ mod tests {
         assert!((dash.speed - 25.0).abs() < f32::EPSILON);
         assert!(dash.invulnerable);
     }
+
+    #[test]
+    fn test_sprinting_drains_stamina() {
+        let mut sprint = SprintState::default();
+        let mut stamina = 100.0;
+        assert!(sprint.try_sprint(&mut stamina, 1.0));
+        assert!(sprint.sprinting);
+        assert!((stamina - 80.0).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn test_depletion_forces_walk() {
+        let mut sprint = SprintState::default();
+        let mut stamina = 100.0;
+        // 5 seconds at 20/s empties the bar
+        for _ in 0..5 {
+            assert!(sprint.try_sprint(&mut stamina, 1.0));
+        }
+        assert_eq!(stamina, 0.0);
+        assert!(!sprint.try_sprint(&mut stamina, 0.1));
+        assert!(!sprint.sprinting);
+    }
+
+    #[test]
+    fn test_sprint_attack_needs_stamina() {
+        let mut sprint = SprintState::default();
+        let mut stamina = 100.0;
+        sprint.try_sprint(&mut stamina, 4.5); // 10 stamina left, still sprinting
+        assert!(sprint.sprinting);
+        assert!(!sprint.try_sprint_attack(&mut stamina));
+        assert!((stamina - 10.0).abs() < 0.001);
+
+        // From a walk attacks are free
+        sprint.stop();
+        assert!(sprint.try_sprint_attack(&mut stamina));
+        assert!((stamina - 10.0).abs() < 0.001);
+
+        // Mid-sprint with enough stamina the attack is paid for
+        stamina = 50.0;
+        sprint.try_sprint(&mut stamina, 0.0);
+        assert!(sprint.try_sprint_attack(&mut stamina));
+        assert!((stamina - 35.0).abs() < 0.001);
+    }
 }
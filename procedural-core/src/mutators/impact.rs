@@ -0,0 +1,178 @@
+//! Mutator impact preview for balance work.
+//!
+//! Runs a mutator set's aggregate effects against a standard encounter and
+//! reports how damage output, survivability and clear time move relative to
+//! the unmutated encounter. The model is a rough steady-state estimate, not a
+//! combat simulation: it is meant for comparing mutator sets side by side.
+
+use serde::{Deserialize, Serialize};
+
+use super::MutatorEffects;
+
+/// Time-to-death reported when sustain outpaces incoming damage
+pub const MAX_SURVIVAL_SECS: f32 = 3600.0;
+
+/// Extra damage a crit deals on top of a normal hit
+const CRIT_BONUS_DAMAGE: f32 = 0.5;
+
+/// A standard encounter to measure mutators against
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EncounterBaseline {
+    pub player_hp: f32,
+    pub player_dps: f32,
+    pub player_heal_per_sec: f32,
+    pub player_speed: f32,
+    pub monster_count: u32,
+    pub monster_hp: f32,
+    /// Damage per second from each monster
+    pub monster_dps: f32,
+    /// Distance walked between fights while clearing the floor
+    pub travel_distance: f32,
+}
+
+impl Default for EncounterBaseline {
+    fn default() -> Self {
+        Self {
+            player_hp: 100.0,
+            player_dps: 20.0,
+            player_heal_per_sec: 2.0,
+            player_speed: 6.0,
+            monster_count: 5,
+            monster_hp: 60.0,
+            monster_dps: 3.0,
+            travel_distance: 120.0,
+        }
+    }
+}
+
+/// Estimated effect of a mutator set on an encounter. `*_change` fields are
+/// ratios against the unmutated encounter (1.0 = no change).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ImpactReport {
+    /// Effective player DPS after damage, crit and monster armor modifiers
+    pub damage_dealt_change: f32,
+    /// Incoming damage per second after damage-taken and toxic modifiers
+    pub damage_taken_change: f32,
+    /// Seconds until death under sustained fire (capped at `MAX_SURVIVAL_SECS`)
+    pub time_to_death_secs: f32,
+    /// Below 1.0 the player dies faster than without mutators
+    pub survivability_change: f32,
+    pub clear_time_secs: f32,
+    pub clear_time_change: f32,
+    /// Fraction of player HP expected to be lost over the clear (1.0+ = death)
+    pub expected_hp_loss: f32,
+    /// Clear would run past the mutator time limit
+    pub exceeds_time_limit: bool,
+}
+
+struct Estimate {
+    dps: f32,
+    incoming: f32,
+    time_to_death: f32,
+    clear_time: f32,
+    hp_loss: f32,
+}
+
+fn estimate(fx: &MutatorEffects, base: &EncounterBaseline) -> Estimate {
+    let dps = base.player_dps
+        * fx.damage_dealt_mult
+        * (1.0 + fx.crit_chance_bonus.clamp(0.0, 1.0) * CRIT_BONUS_DAMAGE)
+        / fx.monster_armor_mult.max(0.01);
+    let total_monster_hp = base.monster_hp * base.monster_count as f32 * fx.monster_respawn_mult;
+    let combat_time = if dps > 0.0 {
+        total_monster_hp / dps
+    } else {
+        MAX_SURVIVAL_SECS
+    };
+    let speed = (base.player_speed * fx.speed_mult).max(0.01);
+    let clear_time = combat_time + base.travel_distance / speed;
+
+    let incoming = base.monster_dps * base.monster_count as f32 * fx.damage_taken_mult
+        + base.player_hp * fx.toxic_dps_percent;
+    let sustain = base.player_heal_per_sec * fx.healing_mult + dps * fx.lifesteal_percent;
+    let drain = incoming - sustain;
+    let time_to_death = if drain > 0.0 {
+        (base.player_hp / drain).min(MAX_SURVIVAL_SECS)
+    } else {
+        MAX_SURVIVAL_SECS
+    };
+    let hp_loss = (drain.max(0.0) * combat_time / base.player_hp.max(1.0)).max(0.0);
+
+    Estimate {
+        dps,
+        incoming,
+        time_to_death,
+        clear_time,
+        hp_loss,
+    }
+}
+
+fn ratio(value: f32, baseline: f32) -> f32 {
+    if baseline > 0.0 {
+        value / baseline
+    } else {
+        1.0
+    }
+}
+
+/// Estimate how `effects` change survivability and clear difficulty of
+/// `baseline` compared to the same encounter without mutators
+pub fn simulate_impact(effects: &MutatorEffects, baseline: &EncounterBaseline) -> ImpactReport {
+    let plain = estimate(&MutatorEffects::default(), baseline);
+    let mutated = estimate(effects, baseline);
+
+    ImpactReport {
+        damage_dealt_change: ratio(mutated.dps, plain.dps),
+        damage_taken_change: ratio(mutated.incoming, plain.incoming),
+        time_to_death_secs: mutated.time_to_death,
+        survivability_change: ratio(mutated.time_to_death, plain.time_to_death),
+        clear_time_secs: mutated.clear_time,
+        clear_time_change: ratio(mutated.clear_time, plain.clear_time),
+        expected_hp_loss: mutated.hp_loss,
+        exceeds_time_limit: effects
+            .time_limit_secs
+            .is_some_and(|limit| mutated.clear_time > limit),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mutators::{compute_effects, FloorMutator, MutatorType};
+
+    fn impact(mutator: MutatorType) -> ImpactReport {
+        let fx = compute_effects(&[FloorMutator::from_type(mutator, 1.0)]);
+        simulate_impact(&fx, &EncounterBaseline::default())
+    }
+
+    #[test]
+    fn test_no_mutators_no_change() {
+        let report = simulate_impact(&MutatorEffects::default(), &EncounterBaseline::default());
+        assert_eq!(report.damage_dealt_change, 1.0);
+        assert_eq!(report.survivability_change, 1.0);
+        assert_eq!(report.clear_time_change, 1.0);
+    }
+
+    #[test]
+    fn test_glass_cannon_trades_survivability_for_damage() {
+        let report = impact(MutatorType::GlassCannon);
+        assert!(report.damage_dealt_change > 1.5, "{report:?}");
+        assert!(report.damage_taken_change > 1.0, "{report:?}");
+        assert!(report.survivability_change < 1.0, "{report:?}");
+        assert!(report.clear_time_change < 1.0, "{report:?}");
+    }
+
+    #[test]
+    fn test_no_healing_reduces_survivability() {
+        let report = impact(MutatorType::NoHealing);
+        assert!(report.survivability_change < 1.0, "{report:?}");
+        assert_eq!(report.damage_dealt_change, 1.0);
+    }
+
+    #[test]
+    fn test_speed_boost_shortens_clear() {
+        let report = impact(MutatorType::SpeedBoost);
+        assert!(report.clear_time_change < 1.0, "{report:?}");
+        assert_eq!(report.survivability_change, 1.0);
+    }
+}
@@ -15,7 +15,11 @@ use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
 use sha3::{Digest, Sha3_256};
 
-use crate::generation::FloorTier;
+use crate::generation::{xorshift, FloorTier, TierBoundaries};
+
+pub mod impact;
+
+pub use impact::{simulate_impact, EncounterBaseline, ImpactReport};
 
 pub struct MutatorsPlugin;
 
@@ -221,6 +225,28 @@ impl MutatorType {
     }
 }
 
+/// Mutator pairs whose rules contradict each other and must never share a
+/// floor. When both appear, the lower-difficulty one is dropped.
+pub const MUTATOR_CONFLICTS: [(MutatorType, MutatorType); 5] = [
+    // GoldenFloor zeroes the loot Bountiful doubles
+    (MutatorType::Bountiful, MutatorType::GoldenFloor),
+    (MutatorType::Bountiful, MutatorType::Scarcity),
+    // Lifesteal is healing
+    (MutatorType::NoHealing, MutatorType::VampiricCombat),
+    // Escalation only progresses through kills Pacifist rewards avoiding
+    (MutatorType::Pacifist, MutatorType::Escalation),
+    (MutatorType::ElementalPurity, MutatorType::ElementalChaos),
+];
+
+impl MutatorType {
+    /// Whether this mutator contradicts another (see `MUTATOR_CONFLICTS`)
+    pub fn conflicts_with(&self, other: &MutatorType) -> bool {
+        MUTATOR_CONFLICTS
+            .iter()
+            .any(|(a, b)| (a == self && b == other) || (a == other && b == self))
+    }
+}
+
 /// Complete list of all mutator types (for random selection)
 const ALL_MUTATORS: [MutatorType; 28] = [
     MutatorType::DoubleDamage,
@@ -434,6 +460,16 @@ pub fn compute_effects(mutators: &[FloorMutator]) -> MutatorEffects {
     fx
 }
 
+/// Intensity scales with tier
+fn base_intensity_for_tier(tier: FloorTier) -> f32 {
+    match tier {
+        FloorTier::Echelon1 => 0.5,
+        FloorTier::Echelon2 => 0.75,
+        FloorTier::Echelon3 => 1.0,
+        FloorTier::Echelon4 => 1.25,
+    }
+}
+
 /// How many mutators a floor gets based on tier
 fn mutator_count_for_tier(tier: FloorTier) -> usize {
     match tier {
@@ -493,24 +529,78 @@ pub fn generate_floor_mutators(seed: u64, floor_id: u32) -> Vec<FloorMutator> {
         let mt = ALL_MUTATORS[idx].clone();
         used_categories.push(mt.category());
 
-        // Intensity scales with tier
-        let base_intensity = match tier {
-            FloorTier::Echelon1 => 0.5,
-            FloorTier::Echelon2 => 0.75,
-            FloorTier::Echelon3 => 1.0,
-            FloorTier::Echelon4 => 1.25,
-        };
-
         // Minor per-mutator variation from hash
         let variation = ((result[(i * 2 + 16) % 32] as f32) / 255.0) * 0.4 - 0.2; // -0.2..+0.2
-        let intensity = (base_intensity + variation).clamp(0.3, 2.0);
+        let intensity = (base_intensity_for_tier(tier) + variation).clamp(0.3, 2.0);
 
         mutators.push(FloorMutator::from_type(mt, intensity));
     }
 
+    resolve_conflicts(&mut mutators);
     mutators
 }
 
+/// Remove contradictory mutator pairs. The lower-difficulty side of each
+/// conflict is replaced by the next mutator in `ALL_MUTATORS` order that is
+/// no harder than it, conflicts with nothing, and (where possible) adds no
+/// duplicate category. Deterministic, so generation stays reproducible.
+pub fn resolve_conflicts(mutators: &mut Vec<FloorMutator>) {
+    while let Some((i, j)) = find_conflict(mutators) {
+        let drop = if mutators[i].difficulty <= mutators[j].difficulty {
+            i
+        } else {
+            j
+        };
+        let dropped = mutators[drop].clone();
+        let start = ALL_MUTATORS
+            .iter()
+            .position(|mt| *mt == dropped.mutator_type)
+            .unwrap_or(0);
+
+        let others: Vec<&FloorMutator> = mutators
+            .iter()
+            .enumerate()
+            .filter(|(k, _)| *k != drop)
+            .map(|(_, m)| m)
+            .collect();
+        let eligible = |mt: &MutatorType| {
+            *mt != dropped.mutator_type
+                && mt.difficulty_rating() <= dropped.difficulty
+                && others
+                    .iter()
+                    .all(|o| o.mutator_type != *mt && !o.mutator_type.conflicts_with(mt))
+        };
+        let ordered =
+            || (1..ALL_MUTATORS.len()).map(|k| &ALL_MUTATORS[(start + k) % ALL_MUTATORS.len()]);
+
+        let replacement = ordered()
+            .find(|mt| eligible(mt) && others.iter().all(|o| o.category != mt.category()))
+            .or_else(|| ordered().find(|mt| eligible(mt)))
+            .cloned();
+
+        match replacement {
+            Some(mt) => mutators[drop] = FloorMutator::from_type(mt, dropped.intensity),
+            None => {
+                mutators.remove(drop);
+            }
+        }
+    }
+}
+
+fn find_conflict(mutators: &[FloorMutator]) -> Option<(usize, usize)> {
+    for i in 0..mutators.len() {
+        for j in i + 1..mutators.len() {
+            if mutators[i]
+                .mutator_type
+                .conflicts_with(&mutators[j].mutator_type)
+            {
+                return Some((i, j));
+            }
+        }
+    }
+    None
+}
+
 /// Get the full list of all available mutator types (for UI display)
 pub fn all_mutator_types() -> Vec<FloorMutator> {
     ALL_MUTATORS
@@ -542,6 +632,168 @@ pub fn generate_mutator_set(seed: u64, floor_id: u32) -> FloorMutatorSet {
     }
 }
 
+/// Player-requested reroll of one mutator slot.
+///
+/// The replacement is a different mutator of equal or higher difficulty whose
+/// category isn't already used by the other slots, so rerolling can never make
+/// a floor easier. Echelon1 floors still never roll difficulty 5. Returns
+/// false (leaving the set untouched) for an out-of-range slot or when no
+/// replacement qualifies.
+pub fn reroll_mutator(set: &mut FloorMutatorSet, slot: usize, reroll_seed: u64) -> bool {
+    let Some(current) = set.mutators.get(slot) else {
+        return false;
+    };
+    let min_difficulty = current.mutator_type.difficulty_rating();
+    let tier = FloorTier::from_floor_id(set.floor_id);
+
+    let others: Vec<&FloorMutator> = set
+        .mutators
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| *i != slot)
+        .map(|(_, m)| m)
+        .collect();
+
+    let candidates: Vec<&MutatorType> = ALL_MUTATORS
+        .iter()
+        .filter(|mt| **mt != current.mutator_type)
+        .filter(|mt| mt.difficulty_rating() >= min_difficulty)
+        .filter(|mt| !(tier == FloorTier::Echelon1 && mt.difficulty_rating() >= 5))
+        .filter(|mt| others.iter().all(|o| o.category != mt.category()))
+        .filter(|mt| others.iter().all(|o| !o.mutator_type.conflicts_with(mt)))
+        .collect();
+
+    if candidates.is_empty() {
+        return false;
+    }
+
+    let x = xorshift(reroll_seed.max(1));
+    let pick = candidates[(x % candidates.len() as u64) as usize].clone();
+
+    let intensity = current.intensity;
+    set.mutators[slot] = FloorMutator::from_type(pick, intensity);
+    set.effects = compute_effects(&set.mutators);
+    true
+}
+
+/// "Mutation of the Week": one fixed mutator combination per week and tier,
+/// identical for every player. Selection is weighted by difficulty squared so
+/// weekly challenges skew harder than regular floors. `floor_id` on the
+/// returned set is the first floor of the tier.
+pub fn weekly_mutator_set(week_seed: u64, tier: FloorTier) -> FloorMutatorSet {
+    weekly_mutator_set_with(week_seed, tier, &TierBoundaries::default())
+}
+
+/// `weekly_mutator_set` with custom tier boundaries for `floor_id`
+pub fn weekly_mutator_set_with(
+    week_seed: u64,
+    tier: FloorTier,
+    boundaries: &TierBoundaries,
+) -> FloorMutatorSet {
+    let mut hasher = Sha3_256::new();
+    hasher.update(b"weekly_mutators");
+    hasher.update(week_seed.to_le_bytes());
+    hasher.update(format!("{:?}", tier).as_bytes());
+    let result = hasher.finalize();
+
+    let mut rng = u64::from_le_bytes(result[0..8].try_into().unwrap_or([1; 8])).max(1);
+    let count = mutator_count_for_tier(tier);
+    let mut mutators: Vec<FloorMutator> = Vec::with_capacity(count);
+
+    for _ in 0..count {
+        let candidates: Vec<&MutatorType> = ALL_MUTATORS
+            .iter()
+            .filter(|mt| !(tier == FloorTier::Echelon1 && mt.difficulty_rating() >= 5))
+            .filter(|mt| {
+                mutators
+                    .iter()
+                    .all(|m| m.category != mt.category() && !m.mutator_type.conflicts_with(mt))
+            })
+            .collect();
+        let total: u64 = candidates
+            .iter()
+            .map(|mt| (mt.difficulty_rating() as u64).pow(2))
+            .sum();
+        if total == 0 {
+            break;
+        }
+
+        rng = xorshift(rng);
+        let mut roll = rng % total;
+        let mut pick = candidates[0];
+        for mt in &candidates {
+            let w = (mt.difficulty_rating() as u64).pow(2);
+            if roll < w {
+                pick = mt;
+                break;
+            }
+            roll -= w;
+        }
+
+        mutators.push(FloorMutator::from_type(
+            pick.clone(),
+            base_intensity_for_tier(tier),
+        ));
+    }
+
+    resolve_conflicts(&mut mutators);
+    let effects = compute_effects(&mutators);
+    FloorMutatorSet {
+        floor_id: boundaries.first_floor(tier),
+        tier: format!("{:?}", tier),
+        mutators,
+        effects,
+    }
+}
+
+/// One sampled mutator combination in the reward fairness audit
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RewardAuditEntry {
+    pub mutators: Vec<MutatorType>,
+    pub total_difficulty: u32,
+    pub reward_multiplier: f32,
+}
+
+/// Floors sampled from the generator when building the audit
+const AUDIT_SAMPLE_FLOORS: u32 = 200;
+
+/// Reward fairness audit: every single mutator, every pair, and the combos the
+/// generator actually produces on sampled floors, sorted by total difficulty.
+/// A harder combination must never pay out less than an easier one.
+pub fn reward_audit() -> Vec<RewardAuditEntry> {
+    let mut combos: Vec<Vec<MutatorType>> = Vec::new();
+
+    for (i, a) in ALL_MUTATORS.iter().enumerate() {
+        combos.push(vec![a.clone()]);
+        for b in &ALL_MUTATORS[i + 1..] {
+            combos.push(vec![a.clone(), b.clone()]);
+        }
+    }
+    for floor_id in 1..=AUDIT_SAMPLE_FLOORS {
+        let generated = generate_floor_mutators(0, floor_id);
+        combos.push(generated.into_iter().map(|m| m.mutator_type).collect());
+    }
+
+    let mut entries: Vec<RewardAuditEntry> = combos
+        .into_iter()
+        .map(|types| {
+            let mutators: Vec<FloorMutator> = types
+                .iter()
+                .map(|mt| FloorMutator::from_type(mt.clone(), 1.0))
+                .collect();
+            let fx = compute_effects(&mutators);
+            RewardAuditEntry {
+                mutators: types,
+                total_difficulty: fx.total_difficulty,
+                reward_multiplier: fx.reward_multiplier,
+            }
+        })
+        .collect();
+
+    entries.sort_by_key(|e| e.total_difficulty);
+    entries
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -773,4 +1025,206 @@ mod tests {
         assert!((fx.loot_quantity_mult).abs() < f32::EPSILON);
         assert!(fx.shard_mult > 2.0);
     }
+
+    #[test]
+    fn test_reroll_never_decreases_difficulty() {
+        for floor_id in [5, 150, 350, 700] {
+            for reroll_seed in 0..50u64 {
+                let mut set = generate_mutator_set(42, floor_id);
+                let before = set.effects.total_difficulty;
+                let slot = reroll_seed as usize % set.mutators.len();
+                let original = set.mutators[slot].mutator_type.clone();
+
+                if reroll_mutator(&mut set, slot, reroll_seed) {
+                    assert!(set.effects.total_difficulty >= before);
+                    assert_ne!(set.mutators[slot].mutator_type, original);
+                } else {
+                    assert_eq!(set.effects.total_difficulty, before);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_reroll_avoids_category_duplication() {
+        let mut set = generate_mutator_set(42, 600);
+        assert!(reroll_mutator(&mut set, 0, 7));
+        let mut categories: Vec<_> = set.mutators.iter().map(|m| m.category).collect();
+        let len = categories.len();
+        categories.sort_by_key(|c| format!("{:?}", c));
+        categories.dedup();
+        assert_eq!(categories.len(), len);
+    }
+
+    #[test]
+    fn test_reroll_out_of_range_slot() {
+        let mut set = generate_mutator_set(42, 200);
+        let before = set.mutators.len();
+        assert!(!reroll_mutator(&mut set, before, 1));
+        assert_eq!(set.mutators.len(), before);
+    }
+
+    #[test]
+    fn test_conflict_pairs_never_generated_together() {
+        for floor_id in 1..=1000 {
+            let mutators = generate_floor_mutators(42, floor_id);
+            for (a, b) in &MUTATOR_CONFLICTS {
+                let has_a = mutators.iter().any(|m| m.mutator_type == *a);
+                let has_b = mutators.iter().any(|m| m.mutator_type == *b);
+                assert!(
+                    !(has_a && has_b),
+                    "Floor {} has conflicting {:?} + {:?}",
+                    floor_id,
+                    a,
+                    b
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_resolve_conflicts_drops_lower_difficulty() {
+        let mut mutators = vec![
+            FloorMutator::from_type(MutatorType::VampiricCombat, 1.0),
+            FloorMutator::from_type(MutatorType::NoHealing, 1.0),
+            FloorMutator::from_type(MutatorType::GoldenFloor, 1.0),
+            FloorMutator::from_type(MutatorType::Bountiful, 1.0),
+        ];
+        resolve_conflicts(&mut mutators);
+
+        assert_eq!(mutators.len(), 4);
+        let types: Vec<_> = mutators.iter().map(|m| &m.mutator_type).collect();
+        assert!(types.contains(&&MutatorType::NoHealing));
+        assert!(types.contains(&&MutatorType::GoldenFloor));
+        assert!(!types.contains(&&MutatorType::VampiricCombat));
+        assert!(!types.contains(&&MutatorType::Bountiful));
+        assert!(find_conflict(&mutators).is_none());
+    }
+
+    #[test]
+    fn test_conflicts_symmetric() {
+        for (a, b) in &MUTATOR_CONFLICTS {
+            assert!(a.conflicts_with(b));
+            assert!(b.conflicts_with(a));
+        }
+        assert!(!MutatorType::Darkness.conflicts_with(&MutatorType::Ironman));
+    }
+
+    #[test]
+    fn test_weekly_floor_follows_tier_boundaries() {
+        assert_eq!(weekly_mutator_set(2607, FloorTier::Echelon2).floor_id, 101);
+        let short = TierBoundaries {
+            echelon1_max: 20,
+            echelon2_max: 50,
+            echelon3_max: 80,
+        };
+        let set = weekly_mutator_set_with(2607, FloorTier::Echelon3, &short);
+        assert_eq!(set.floor_id, 51);
+        // Boundaries only move the floor, not the selection
+        assert_eq!(
+            set.mutators.len(),
+            weekly_mutator_set(2607, FloorTier::Echelon3).mutators.len()
+        );
+    }
+
+    #[test]
+    fn test_weekly_mutators_consistent() {
+        let a = weekly_mutator_set(2607, FloorTier::Echelon3);
+        let b = weekly_mutator_set(2607, FloorTier::Echelon3);
+        let types = |s: &FloorMutatorSet| {
+            s.mutators
+                .iter()
+                .map(|m| m.mutator_type.clone())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(types(&a), types(&b));
+        assert_eq!(a.mutators.len(), 3);
+
+        let differing = (2608..2620)
+            .filter(|w| types(&weekly_mutator_set(*w, FloorTier::Echelon3)) != types(&a))
+            .count();
+        assert!(differing > 0, "Different weeks should rotate mutators");
+    }
+
+    #[test]
+    fn test_weekly_echelon1_excludes_difficulty5() {
+        for week in 0..200 {
+            let set = weekly_mutator_set(week, FloorTier::Echelon1);
+            for m in &set.mutators {
+                assert!(
+                    m.difficulty < 5,
+                    "Week {} rolled {:?}",
+                    week,
+                    m.mutator_type
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_weekly_skews_harder() {
+        let weeks = 300;
+        let weekly: u32 = (0..weeks)
+            .map(|w| {
+                weekly_mutator_set(w, FloorTier::Echelon4)
+                    .effects
+                    .total_difficulty
+            })
+            .sum();
+        let regular: u32 = (0..weeks as u32)
+            .map(|f| generate_mutator_set(42, 501 + f).effects.total_difficulty)
+            .sum();
+        assert!(weekly > regular, "weekly {} vs regular {}", weekly, regular);
+    }
+
+    #[test]
+    fn test_reward_audit_monotonic() {
+        let audit = reward_audit();
+        assert!(audit.len() > ALL_MUTATORS.len());
+
+        for pair in audit.windows(2) {
+            let (easier, harder) = (&pair[0], &pair[1]);
+            assert!(
+                harder.reward_multiplier >= easier.reward_multiplier,
+                "{:?} (difficulty {}) pays less than {:?} (difficulty {})",
+                harder.mutators,
+                harder.total_difficulty,
+                easier.mutators,
+                easier.total_difficulty
+            );
+        }
+    }
+
+    #[test]
+    fn test_reward_audit_covers_generated_sets() {
+        let audit = reward_audit();
+        for floor_id in 1..=AUDIT_SAMPLE_FLOORS {
+            let live = generate_mutator_set(0, floor_id);
+            let types: Vec<MutatorType> = live
+                .mutators
+                .iter()
+                .map(|m| m.mutator_type.clone())
+                .collect();
+            let entry = audit
+                .iter()
+                .find(|e| e.mutators == types)
+                .unwrap_or_else(|| panic!("floor {floor_id}: {types:?} missing from the audit"));
+
+            // The audit's fixed intensity pays what the live floor pays
+            let reward = live.effects.reward_multiplier;
+            assert!((entry.reward_multiplier - reward).abs() < 1e-6);
+            // No single mutator is worth more than +50%
+            assert!(reward > 1.0 && reward <= 1.0 + 0.5 * types.len() as f32);
+        }
+    }
+
+    #[test]
+    fn test_reward_audit_equal_difficulty_equal_reward() {
+        let audit = reward_audit();
+        for pair in audit.windows(2) {
+            if pair[0].total_difficulty == pair[1].total_difficulty {
+                assert!((pair[0].reward_multiplier - pair[1].reward_multiplier).abs() < 1e-6);
+            }
+        }
+    }
 }
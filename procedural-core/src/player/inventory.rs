@@ -1,19 +1,39 @@
 //! Inventory and equipment system.
 //!
 //! Players have a fixed-size inventory grid and equipment slots.
-//! Items are semantic-tagged for thematic consistency.
+//! Items are semantic-tagged for thematic consistency. Loot of the same
+//! kind stacks into one slot up to its category's stack limit.
 
 use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
 
 use crate::combat::weapons::Weapon;
 use crate::economy::ItemRarity;
-use crate::loot::LootItem;
+use crate::loot::{LootCategory, LootItem};
 
 /// Inventory capacity tiers
 const BASE_INVENTORY_SIZE: usize = 20;
 const MAX_INVENTORY_SIZE: usize = 60;
 
+/// Most of one loot item a single slot can hold
+pub fn stack_limit(category: LootCategory) -> u32 {
+    match category {
+        LootCategory::Equipment => 1,
+        LootCategory::Consumable => 20,
+        LootCategory::Material | LootCategory::QuestItem => 99,
+        LootCategory::CombatResource | LootCategory::Currency | LootCategory::EchoFragment => 9999,
+    }
+}
+
+/// Why an item could not be added
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum InventoryError {
+    /// Not enough free slots or stack room for the whole quantity
+    Full { capacity: usize },
+    /// Zero-quantity item
+    EmptyItem,
+}
+
 /// Equipment slots
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum EquipSlot {
@@ -48,14 +68,14 @@ pub struct ResourceBonus {
 }
 
 /// Player inventory component
-#[derive(Component, Debug)]
+#[derive(Component, Debug, Serialize, Deserialize)]
 pub struct Inventory {
     pub items: Vec<InventorySlot>,
     pub capacity: usize,
 }
 
 /// A single inventory slot
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum InventorySlot {
     Empty,
     Loot(LootItem),
@@ -73,15 +93,66 @@ impl Default for Inventory {
 }
 
 impl Inventory {
-    /// Try to add an item to the first empty slot
-    pub fn add_loot(&mut self, item: LootItem) -> bool {
+    /// Add loot, topping up matching stacks before taking empty slots.
+    /// All or nothing: if the full quantity doesn't fit, nothing changes.
+    pub fn add(&mut self, item: LootItem) -> Result<(), InventoryError> {
+        if item.quantity == 0 {
+            return Err(InventoryError::EmptyItem);
+        }
+        let limit = stack_limit(item.category);
+        let stack_room: u64 = self
+            .items
+            .iter()
+            .filter_map(|s| match s {
+                InventorySlot::Loot(l) if stacks_with(l, &item) => {
+                    Some(limit.saturating_sub(l.quantity) as u64)
+                }
+                _ => None,
+            })
+            .sum();
+        let empty = self
+            .items
+            .iter()
+            .filter(|s| matches!(s, InventorySlot::Empty))
+            .count() as u64;
+        if stack_room + empty * (limit as u64) < item.quantity as u64 {
+            return Err(InventoryError::Full {
+                capacity: self.capacity,
+            });
+        }
+
+        let mut remaining = item.quantity;
         for slot in &mut self.items {
+            if remaining == 0 {
+                break;
+            }
+            if let InventorySlot::Loot(l) = slot {
+                if stacks_with(l, &item) {
+                    let moved = limit.saturating_sub(l.quantity).min(remaining);
+                    l.quantity += moved;
+                    remaining -= moved;
+                }
+            }
+        }
+        for slot in &mut self.items {
+            if remaining == 0 {
+                break;
+            }
             if matches!(slot, InventorySlot::Empty) {
-                *slot = InventorySlot::Loot(item);
-                return true;
+                let moved = limit.min(remaining);
+                *slot = InventorySlot::Loot(LootItem {
+                    quantity: moved,
+                    ..item.clone()
+                });
+                remaining -= moved;
             }
         }
-        false // inventory full
+        Ok(())
+    }
+
+    /// Try to add loot; false if it doesn't fit (see `add`)
+    pub fn add_loot(&mut self, item: LootItem) -> bool {
+        self.add(item).is_ok()
     }
 
     /// Try to add equipment
@@ -211,6 +282,11 @@ impl Equipment {
     }
 }
 
+/// Same item, so it can share a slot
+fn stacks_with(a: &LootItem, b: &LootItem) -> bool {
+    a.name == b.name && a.category == b.category && a.rarity == b.rarity
+}
+
 /// System: auto-pickup loot near player
 pub fn auto_pickup_loot(
     mut commands: Commands,
@@ -230,7 +306,6 @@ pub fn auto_pickup_loot(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::loot::LootCategory;
 
     #[test]
     fn test_inventory_default() {
@@ -248,6 +323,7 @@ mod tests {
             rarity: ItemRarity::Common,
             quantity: 10,
             semantic_tags: vec![],
+            stats: None,
         };
 
         assert!(inv.add_loot(item.clone()));
@@ -268,6 +344,7 @@ mod tests {
                     rarity: ItemRarity::Common,
                     quantity: 1,
                     semantic_tags: vec![],
+                    stats: None,
                 });
                 3
             ],
@@ -280,11 +357,78 @@ mod tests {
             rarity: ItemRarity::Common,
             quantity: 1,
             semantic_tags: vec![],
+            stats: None,
         };
 
         assert!(!inv.add_loot(extra), "Should fail when full");
     }
 
+    fn herb(quantity: u32) -> LootItem {
+        LootItem {
+            name: "Ember Herb".into(),
+            category: LootCategory::Material,
+            rarity: ItemRarity::Common,
+            quantity,
+            semantic_tags: vec![],
+            stats: None,
+        }
+    }
+
+    fn small_inventory(capacity: usize) -> Inventory {
+        Inventory {
+            items: vec![InventorySlot::Empty; capacity],
+            capacity,
+        }
+    }
+
+    #[test]
+    fn test_add_up_to_capacity() {
+        let mut inv = small_inventory(3);
+        for name in ["A", "B", "C"] {
+            let mut item = herb(1);
+            item.name = name.into();
+            assert_eq!(inv.add(item), Ok(()));
+        }
+        assert_eq!(inv.used_slots(), 3);
+    }
+
+    #[test]
+    fn test_stacking_uses_no_extra_slots() {
+        let mut inv = small_inventory(3);
+        inv.add(herb(40)).unwrap();
+        inv.add(herb(50)).unwrap();
+        assert_eq!(inv.used_slots(), 1);
+
+        // Overflowing the stack spills into one new slot
+        inv.add(herb(20)).unwrap();
+        assert_eq!(inv.used_slots(), 2);
+        let total: u32 = inv
+            .items
+            .iter()
+            .filter_map(|s| match s {
+                InventorySlot::Loot(l) => Some(l.quantity),
+                _ => None,
+            })
+            .sum();
+        assert_eq!(total, 110);
+    }
+
+    #[test]
+    fn test_add_past_capacity_errors() {
+        let mut inv = small_inventory(2);
+        inv.add(herb(99)).unwrap();
+        let mut other = herb(1);
+        other.name = "Frost Moss".into();
+        inv.add(other.clone()).unwrap();
+
+        other.name = "Void Salt".into();
+        assert_eq!(inv.add(other), Err(InventoryError::Full { capacity: 2 }));
+        // A full stack can't take more either, and nothing is half-added
+        assert_eq!(inv.add(herb(5)), Err(InventoryError::Full { capacity: 2 }));
+        assert!(matches!(&inv.items[0], InventorySlot::Loot(l) if l.quantity == 99));
+        assert_eq!(inv.add(herb(0)), Err(InventoryError::EmptyItem));
+    }
+
     #[test]
     fn test_inventory_expand() {
         let mut inv = Inventory::default();
@@ -11,11 +11,13 @@ use crate::combat::{CombatResources, CombatState};
 use crate::death::Mortal;
 use crate::economy::Wallet;
 use crate::faction::FactionStanding;
-use crate::movement::{DashAbility, MovementInput, MovementState};
+use crate::movement::{DashAbility, MovementInput, MovementState, SprintState};
 use crate::semantic::SemanticTags;
 
 pub mod inventory;
 
+pub use inventory::{Inventory, InventoryError};
+
 pub struct PlayerPlugin;
 
 impl Plugin for PlayerPlugin {
@@ -137,6 +139,7 @@ fn spawn_player(mut commands: Commands) {
         },
         MovementInput::default(),
         DashAbility::default(),
+        SprintState::default(),
         // Combat
         CombatState::default(),
         CombatResources::default(),
@@ -202,6 +205,9 @@ fn read_keyboard_input(
         actions.send(PlayerActionEvent::Dash);
     }
 
+    // Sprint (hold)
+    move_input.sprint = keyboard.pressed(KeyCode::ControlLeft);
+
     // Combat
     if mouse.just_pressed(MouseButton::Left) {
         actions.send(PlayerActionEvent::Attack);
@@ -241,20 +247,22 @@ fn read_keyboard_input(
     }
 }
 
+/// Player components that actions read or change
+type ActionTargets = (
+    &'static mut CombatState,
+    &'static mut CombatResources,
+    &'static mut FlightState,
+    &'static mut DiveAttack,
+    &'static AbilitySlots,
+    Option<&'static SprintState>,
+);
+
 fn process_player_actions(
     mut actions: EventReader<PlayerActionEvent>,
-    mut query: Query<
-        (
-            &mut CombatState,
-            &mut CombatResources,
-            &mut FlightState,
-            &mut DiveAttack,
-            &AbilitySlots,
-        ),
-        With<Player>,
-    >,
+    mut query: Query<ActionTargets, With<Player>>,
 ) {
-    let Ok((mut combat, mut resources, mut flight, mut dive, abilities)) = query.get_single_mut()
+    let Ok((mut combat, mut resources, mut flight, mut dive, abilities, sprint)) =
+        query.get_single_mut()
     else {
         return;
     };
@@ -262,7 +270,10 @@ fn process_player_actions(
     for action in actions.read() {
         match action {
             PlayerActionEvent::Attack => {
-                if combat.phase == crate::combat::AttackPhase::Idle {
+                // Attacking out of a sprint costs stamina
+                if combat.phase == crate::combat::AttackPhase::Idle
+                    && sprint.is_none_or(|s| s.try_sprint_attack(&mut flight.stamina))
+                {
                     combat.phase = crate::combat::AttackPhase::Windup;
                     combat.phase_timer = 0.0;
                     combat.combo_step = (combat.combo_step + 1).min(combat.max_combo);
@@ -10,11 +10,14 @@
 //! 4. Playback → regenerate floor from seed, apply inputs frame-by-frame
 //! 5. Verify → compare resulting DeltaLog to original for determinism
 
+use std::collections::BTreeMap;
+
 use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
 use sha3::{Digest, Sha3_256};
 
-use crate::generation::TowerSeed;
+use crate::generation::wfc::generate_layout;
+use crate::generation::{FloorSpec, TowerSeed};
 use crate::replication::Delta;
 
 pub struct ReplayPlugin;
@@ -28,7 +31,7 @@ impl Plugin for ReplayPlugin {
 }
 
 /// Type of player input
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub enum InputType {
     Move,         // WASD movement
     Attack,       // Left mouse button
@@ -81,6 +84,12 @@ pub struct ReplayHeader {
     pub total_frames: usize,
     pub outcome: ReplayOutcome,
     pub version: u32, // Replay format version
+    /// Floor hash derived from `seed` + `floor_id` when recording started
+    #[serde(default)]
+    pub floor_hash: u64,
+    /// Checksum of the floor layout generated when recording started
+    #[serde(default)]
+    pub layout_checksum: u64,
 }
 
 impl ReplayHeader {
@@ -102,10 +111,17 @@ impl ReplayHeader {
             total_frames: 0,
             outcome: ReplayOutcome::InProgress,
             version: 1,
+            floor_hash: TowerSeed { seed }.floor_hash(floor_id),
+            layout_checksum: layout_checksum(&FloorSpec::generate(&TowerSeed { seed }, floor_id)),
         }
     }
 }
 
+/// Checksum of the layout generated for `spec`, comparable across machines
+fn layout_checksum(spec: &FloorSpec) -> u64 {
+    crate::generation::layout_checksum(&generate_layout(spec))
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ReplayOutcome {
     InProgress,
@@ -164,6 +180,130 @@ impl ReplayRecording {
     }
 }
 
+/// Result of re-deriving a recording's floor and checking its inputs
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VerifyReport {
+    /// The recorded seed + floor_id still generate the recorded floor hash
+    pub seed_ok: bool,
+    /// `recording_hash` matches the seed, floor and input frames
+    pub hash_ok: bool,
+    /// The regenerated layout matches the recorded `layout_checksum`
+    pub layout_ok: bool,
+    /// Input frames are in tick order
+    pub frames_ok: bool,
+    pub frame_count: usize,
+    /// Order-sensitive checksum of the input frame sequence
+    pub frame_checksum: u64,
+    /// Checksum of the regenerated floor layout, comparable across machines
+    pub layout_checksum: u64,
+}
+
+impl VerifyReport {
+    pub fn passed(&self) -> bool {
+        self.seed_ok && self.hash_ok && self.layout_ok && self.frames_ok
+    }
+}
+
+/// Check that a recording reproduces: regenerate its floor from the recorded
+/// seed and compare against the recorded floor hash and layout checksum, then
+/// check the recording hash and the structure of the input frame sequence.
+/// Recordings made before `floor_hash` / `layout_checksum` were recorded fail
+/// `seed_ok` / `layout_ok`.
+pub fn verify(recording: &ReplayRecording) -> VerifyReport {
+    let header = &recording.header;
+    let spec = FloorSpec::generate(&TowerSeed { seed: header.seed }, header.floor_id);
+    let layout_checksum = layout_checksum(&spec);
+
+    let mut frame_hasher = Sha3_256::new();
+    for (index, frame) in recording.frames.iter().enumerate() {
+        frame_hasher.update((index as u64).to_le_bytes());
+        frame_hasher.update(frame.hash().to_le_bytes());
+    }
+    let frame_result = frame_hasher.finalize();
+
+    VerifyReport {
+        seed_ok: spec.id == header.floor_id && spec.hash == header.floor_hash,
+        hash_ok: recording.verify(),
+        layout_ok: layout_checksum == header.layout_checksum,
+        frames_ok: recording.frames.windows(2).all(|w| w[0].tick <= w[1].tick),
+        frame_count: recording.frames.len(),
+        frame_checksum: u64::from_le_bytes(frame_result[0..8].try_into().unwrap()),
+        layout_checksum,
+    }
+}
+
+/// Where two recordings of the same floor part ways
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReplayDiff {
+    /// Tick of the first frame pair that differs (None = identical inputs)
+    pub first_divergent_tick: Option<u64>,
+    /// Differing frames per input type. A pair whose types differ counts
+    /// once for each type; frames past the end of the shorter recording
+    /// count for their own type.
+    pub differing_frames: BTreeMap<InputType, u32>,
+}
+
+impl ReplayDiff {
+    pub fn is_identical(&self) -> bool {
+        self.first_divergent_tick.is_none()
+    }
+}
+
+/// Why two recordings can't be diffed
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ReplayDiffError {
+    SeedMismatch { a: u64, b: u64 },
+    FloorMismatch { a: u32, b: u32 },
+}
+
+/// Compare two recordings of the same seed + floor frame by frame.
+/// Frames are paired by position in the input sequence; a pair differs if
+/// its tick, input type or payload differ.
+pub fn diff(a: &ReplayRecording, b: &ReplayRecording) -> Result<ReplayDiff, ReplayDiffError> {
+    if a.header.seed != b.header.seed {
+        return Err(ReplayDiffError::SeedMismatch {
+            a: a.header.seed,
+            b: b.header.seed,
+        });
+    }
+    if a.header.floor_id != b.header.floor_id {
+        return Err(ReplayDiffError::FloorMismatch {
+            a: a.header.floor_id,
+            b: b.header.floor_id,
+        });
+    }
+
+    let mut first_divergent_tick = None;
+    let mut differing_frames = BTreeMap::new();
+    for index in 0..a.frames.len().max(b.frames.len()) {
+        let (tick, types) = match (a.frames.get(index), b.frames.get(index)) {
+            (Some(fa), Some(fb)) => {
+                if fa.tick == fb.tick && fa.input_type == fb.input_type && fa.payload == fb.payload
+                {
+                    continue;
+                }
+                let types = if fa.input_type == fb.input_type {
+                    vec![fa.input_type]
+                } else {
+                    vec![fa.input_type, fb.input_type]
+                };
+                (fa.tick.min(fb.tick), types)
+            }
+            (Some(f), None) | (None, Some(f)) => (f.tick, vec![f.input_type]),
+            (None, None) => unreachable!(),
+        };
+        first_divergent_tick.get_or_insert(tick);
+        for input_type in types {
+            *differing_frames.entry(input_type).or_insert(0) += 1;
+        }
+    }
+
+    Ok(ReplayDiff {
+        first_divergent_tick,
+        differing_frames,
+    })
+}
+
 /// Playback state machine
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum PlaybackState {
@@ -418,6 +558,57 @@ mod tests {
         assert!(frame.hash() > 0);
     }
 
+    fn diff_run(seed: u64, late: Option<(InputType, &str)>) -> ReplayRecording {
+        let header = ReplayHeader::new("diff", seed, 5, "P1", "{}");
+        let mut frames: Vec<InputFrame> = (0..100)
+            .map(|tick| InputFrame::new(tick, InputType::Move, r#"{"x":1.0}"#))
+            .collect();
+        if let Some((input_type, payload)) = late {
+            frames[95] = InputFrame::new(95, input_type, payload);
+        }
+        ReplayRecording::new(header, frames, vec![])
+    }
+
+    #[test]
+    fn test_diff_identical_recordings() {
+        let d = diff(&diff_run(42, None), &diff_run(42, None)).unwrap();
+        assert!(d.is_identical());
+        assert!(d.differing_frames.is_empty());
+    }
+
+    #[test]
+    fn test_diff_single_late_divergence() {
+        let a = diff_run(42, None);
+        let b = diff_run(42, Some((InputType::Dodge, "{}")));
+        let d = diff(&a, &b).unwrap();
+        assert_eq!(d.first_divergent_tick, Some(95));
+        assert_eq!(d.differing_frames[&InputType::Move], 1);
+        assert_eq!(d.differing_frames[&InputType::Dodge], 1);
+        assert_eq!(d.differing_frames.len(), 2);
+
+        // Same type, different payload; b also runs longer
+        let mut c = diff_run(42, Some((InputType::Move, r#"{"x":-1.0}"#)));
+        c.frames.push(InputFrame::new(100, InputType::Jump, "{}"));
+        let d = diff(&a, &c).unwrap();
+        assert_eq!(d.first_divergent_tick, Some(95));
+        assert_eq!(d.differing_frames[&InputType::Move], 1);
+        assert_eq!(d.differing_frames[&InputType::Jump], 1);
+    }
+
+    #[test]
+    fn test_diff_rejects_seed_mismatch() {
+        assert_eq!(
+            diff(&diff_run(42, None), &diff_run(43, None)),
+            Err(ReplayDiffError::SeedMismatch { a: 42, b: 43 })
+        );
+        let mut other_floor = diff_run(42, None);
+        other_floor.header.floor_id = 6;
+        assert_eq!(
+            diff(&diff_run(42, None), &other_floor),
+            Err(ReplayDiffError::FloorMismatch { a: 5, b: 6 })
+        );
+    }
+
     #[test]
     fn test_input_frame_hash_deterministic() {
         let f1 = InputFrame::new(100, InputType::Attack, r#"{"combo":1}"#);
@@ -709,4 +900,64 @@ mod tests {
         let size = recording.estimated_size();
         assert!(size > 200); // header + frames
     }
+
+    fn recorded_run() -> ReplayRecording {
+        let header = ReplayHeader::new("qa_1", 42, 7, "P1", "{}");
+        let frames = vec![
+            InputFrame::new(0, InputType::Move, r#"{"x":1.0,"y":0.0}"#),
+            InputFrame::new(3, InputType::Attack, r#"{"combo":1}"#),
+            InputFrame::new(3, InputType::Dodge, "{}"),
+        ];
+        ReplayRecording::new(header, frames, vec![])
+    }
+
+    #[test]
+    fn test_verify_matching_recording_passes() {
+        let recording = recorded_run();
+        let report = verify(&recording);
+        assert!(report.passed(), "{report:?}");
+        assert_eq!(report.frame_count, 3);
+        // Re-verifying reproduces the same checksums
+        assert_eq!(verify(&recording), report);
+    }
+
+    #[test]
+    fn test_verify_tampered_seed_fails() {
+        let mut recording = recorded_run();
+        recording.header.seed = 43;
+        let report = verify(&recording);
+        assert!(!report.seed_ok);
+        assert!(!report.hash_ok);
+        assert!(!report.layout_ok);
+        assert!(!report.passed());
+    }
+
+    #[test]
+    fn test_verify_layout_mismatch_fails() {
+        let mut recording = recorded_run();
+        assert!(verify(&recording).layout_ok);
+        recording.header.layout_checksum ^= 1;
+        let report = verify(&recording);
+        assert!(report.seed_ok && report.hash_ok);
+        assert!(!report.layout_ok);
+        assert!(!report.passed());
+    }
+
+    #[test]
+    fn test_verify_tampered_hash_fails() {
+        let mut recording = recorded_run();
+        recording.recording_hash ^= 1;
+        let report = verify(&recording);
+        assert!(report.seed_ok);
+        assert!(!report.hash_ok);
+
+        let mut reordered = recorded_run();
+        reordered.frames.swap(0, 2);
+        let report = verify(&reordered);
+        assert!(!report.frames_ok);
+        assert_ne!(
+            report.frame_checksum,
+            verify(&recorded_run()).frame_checksum
+        );
+    }
 }
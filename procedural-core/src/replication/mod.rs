@@ -11,6 +11,7 @@
 use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
 use sha3::{Digest, Sha3_256};
+use std::collections::BTreeSet;
 
 use crate::generation::TowerSeed;
 
@@ -41,6 +42,21 @@ pub enum DeltaType {
     QuestProgress,
 }
 
+impl DeltaType {
+    /// Entity-scoped mutations where only the latest state per entity matters
+    /// (a door toggled 100 times needs just its final delta in a snapshot)
+    pub fn is_entity_scoped(&self) -> bool {
+        matches!(
+            self,
+            Self::ChestOpen
+                | Self::DoorUnlock
+                | Self::ShrineActivate
+                | Self::TrapDisarm
+                | Self::StairsUnlock
+        )
+    }
+}
+
 /// A single mutation to the world state
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Delta {
@@ -188,6 +204,14 @@ impl DeltaLog {
         self.deltas.retain(|d| d.floor_id != floor_id);
     }
 
+    /// Drop superseded deltas.
+    /// Entity-scoped types keep only their last entry per (floor, entity, type);
+    /// everything else (kills, pickups, ...) is kept in order. Surviving deltas
+    /// keep their original sequence numbers.
+    pub fn collapse_superseded(&mut self) {
+        collapse_superseded(&mut self.deltas);
+    }
+
     /// Compact: keep only last N deltas per floor
     pub fn compact(&mut self, max_per_floor: usize) {
         use std::collections::HashMap;
@@ -213,6 +237,29 @@ impl DeltaLog {
     }
 }
 
+fn collapse_superseded(deltas: &mut Vec<Delta>) {
+    use std::collections::HashSet;
+    let mut seen: HashSet<(u32, u64, DeltaType)> = HashSet::new();
+
+    // Walk from the end so the latest entry per key is the one kept
+    let mut keep = vec![true; deltas.len()];
+    for i in (0..deltas.len()).rev() {
+        let d = &deltas[i];
+        if d.delta_type.is_entity_scoped()
+            && !seen.insert((d.floor_id, d.entity_hash, d.delta_type))
+        {
+            keep[i] = false;
+        }
+    }
+
+    let mut idx = 0;
+    deltas.retain(|_| {
+        let k = keep[idx];
+        idx += 1;
+        k
+    });
+}
+
 /// Snapshot: seed + deltas = full state reconstruction
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FloorSnapshot {
@@ -223,9 +270,18 @@ pub struct FloorSnapshot {
 }
 
 impl FloorSnapshot {
-    /// Create snapshot from current state
-    pub fn capture(seed: &TowerSeed, floor_id: u32, log: &DeltaLog, current_tick: u64) -> Self {
-        let floor_deltas: Vec<Delta> = log.for_floor(floor_id).into_iter().cloned().collect();
+    /// Create snapshot from current state, optionally dropping superseded deltas
+    pub fn capture(
+        seed: &TowerSeed,
+        floor_id: u32,
+        log: &DeltaLog,
+        current_tick: u64,
+        collapse: bool,
+    ) -> Self {
+        let mut floor_deltas: Vec<Delta> = log.for_floor(floor_id).into_iter().cloned().collect();
+        if collapse {
+            collapse_superseded(&mut floor_deltas);
+        }
 
         Self {
             seed: seed.seed,
@@ -279,6 +335,63 @@ impl FloorSnapshot {
         // seed(8) + floor_id(4) + tick(8) + deltas
         20 + self.deltas.len() * 60 // ~60 bytes per delta average
     }
+
+    /// Order-sensitive checksum over seed, floor and delta contents, for
+    /// desync detection. `snapshot_tick` is left out so snapshots of the
+    /// same state taken on different ticks agree.
+    pub fn checksum(&self) -> u64 {
+        let mut hasher = Sha3_256::new();
+        hasher.update(b"snapshot");
+        hasher.update(self.seed.to_le_bytes());
+        hasher.update(self.floor_id.to_le_bytes());
+        for (index, delta) in self.deltas.iter().enumerate() {
+            hasher.update((index as u64).to_le_bytes());
+            hasher.update(delta.compute_hash().to_le_bytes());
+        }
+        let result = hasher.finalize();
+        u64::from_le_bytes(result[0..8].try_into().unwrap())
+    }
+
+    /// Replay deltas in sequence order to reconstruct the floor's mutated state
+    pub fn apply(&self) -> FloorState {
+        let mut state = FloorState::default();
+        state.apply_snapshot(self);
+        state
+    }
+}
+
+/// Mutated entity state of a floor, derived by replaying its deltas
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FloorState {
+    pub opened_chests: BTreeSet<u64>,
+    pub killed_monsters: BTreeSet<u64>,
+    pub unlocked_doors: BTreeSet<u64>,
+    pub disarmed_traps: BTreeSet<u64>,
+    pub activated_shrines: BTreeSet<u64>,
+}
+
+impl FloorState {
+    /// Apply a single delta. Set semantics make re-applying a no-op.
+    pub fn apply_delta(&mut self, delta: &Delta) {
+        let set = match delta.delta_type {
+            DeltaType::ChestOpen => &mut self.opened_chests,
+            DeltaType::MonsterKill => &mut self.killed_monsters,
+            DeltaType::DoorUnlock => &mut self.unlocked_doors,
+            DeltaType::TrapDisarm => &mut self.disarmed_traps,
+            DeltaType::ShrineActivate => &mut self.activated_shrines,
+            _ => return,
+        };
+        set.insert(delta.entity_hash);
+    }
+
+    /// Apply all of a snapshot's deltas in sequence order (idempotent)
+    pub fn apply_snapshot(&mut self, snapshot: &FloorSnapshot) {
+        let mut ordered: Vec<&Delta> = snapshot.deltas.iter().collect();
+        ordered.sort_by_key(|d| d.seq);
+        for delta in ordered {
+            self.apply_delta(delta);
+        }
+    }
 }
 
 /// Event fired when a new delta is recorded
@@ -328,6 +441,62 @@ mod tests {
         assert!(delta.verify(), "Delta hash should verify correctly");
     }
 
+    fn checksum_snapshot(deltas: Vec<Delta>) -> FloorSnapshot {
+        FloorSnapshot {
+            seed: 42,
+            floor_id: 3,
+            deltas,
+            snapshot_tick: 0,
+        }
+    }
+
+    #[test]
+    fn test_snapshot_checksum_matches_for_identical_state() {
+        let deltas = vec![
+            Delta::new(0, 10, DeltaType::MonsterKill, 3, 11, "p1", ""),
+            Delta::new(1, 12, DeltaType::ChestOpen, 3, 22, "p1", ""),
+        ];
+        let a = checksum_snapshot(deltas.clone());
+        let mut b = checksum_snapshot(deltas);
+        b.snapshot_tick = 500;
+        assert_eq!(a.checksum(), b.checksum());
+
+        let mut other_seed = a.clone();
+        other_seed.seed = 43;
+        assert_ne!(a.checksum(), other_seed.checksum());
+    }
+
+    #[test]
+    fn test_snapshot_checksum_detects_delta_difference() {
+        let base = checksum_snapshot(vec![Delta::new(
+            0,
+            10,
+            DeltaType::MonsterKill,
+            3,
+            11,
+            "p1",
+            "",
+        )]);
+        let mut changed = base.clone();
+        changed.deltas[0].entity_hash = 12;
+        assert_ne!(base.checksum(), changed.checksum());
+
+        let mut extra = base.clone();
+        extra
+            .deltas
+            .push(Delta::new(1, 11, DeltaType::DoorUnlock, 3, 33, "p2", ""));
+        assert_ne!(base.checksum(), extra.checksum());
+    }
+
+    #[test]
+    fn test_snapshot_checksum_order_sensitive() {
+        let kill = Delta::new(0, 10, DeltaType::MonsterKill, 3, 11, "p1", "");
+        let chest = Delta::new(1, 12, DeltaType::ChestOpen, 3, 22, "p1", "");
+        let a = checksum_snapshot(vec![kill.clone(), chest.clone()]);
+        let b = checksum_snapshot(vec![chest, kill]);
+        assert_ne!(a.checksum(), b.checksum());
+    }
+
     #[test]
     fn test_delta_tamper_detection() {
         let mut delta = Delta::new(
@@ -395,7 +564,7 @@ mod tests {
         log.record(102, DeltaType::ChestOpen, 5, 333, "p1", "");
         log.record(103, DeltaType::MonsterKill, 6, 444, "p1", ""); // different floor
 
-        let snapshot = FloorSnapshot::capture(&seed, 5, &log, 103);
+        let snapshot = FloorSnapshot::capture(&seed, 5, &log, 103, false);
         assert_eq!(snapshot.seed, 42);
         assert_eq!(snapshot.floor_id, 5);
         assert_eq!(snapshot.deltas.len(), 3);
@@ -409,7 +578,7 @@ mod tests {
         let mut log = DeltaLog::default();
         log.record(100, DeltaType::MonsterKill, 1, 999, "p1", "");
 
-        let snapshot = FloorSnapshot::capture(&seed, 1, &log, 100);
+        let snapshot = FloorSnapshot::capture(&seed, 1, &log, 100, false);
         assert!(snapshot.is_entity_mutated(999));
         assert!(!snapshot.is_entity_mutated(888));
     }
@@ -420,7 +589,7 @@ mod tests {
         let mut log = DeltaLog::default();
         log.record(100, DeltaType::MonsterKill, 1, 111, "p1", r#"{"xp":50}"#);
 
-        let snapshot = FloorSnapshot::capture(&seed, 1, &log, 100);
+        let snapshot = FloorSnapshot::capture(&seed, 1, &log, 100, false);
         let json = snapshot.to_json();
         assert!(!json.is_empty());
 
@@ -443,6 +612,91 @@ mod tests {
         assert_eq!(log.deltas.len(), 10);
     }
 
+    #[test]
+    fn test_delta_log_collapse_door_toggles() {
+        let mut log = DeltaLog::default();
+        for i in 0..100 {
+            let state = if i % 2 == 0 { "open" } else { "locked" };
+            log.record(i, DeltaType::DoorUnlock, 1, 77, "p1", state);
+        }
+
+        log.collapse_superseded();
+        assert_eq!(log.deltas.len(), 1);
+        assert_eq!(log.deltas[0].seq, 99);
+        assert_eq!(log.deltas[0].payload, "locked");
+    }
+
+    #[test]
+    fn test_delta_log_collapse_preserves_order() {
+        let mut log = DeltaLog::default();
+        log.record(1, DeltaType::MonsterKill, 1, 10, "p1", "");
+        log.record(2, DeltaType::ChestOpen, 1, 20, "p1", "");
+        log.record(3, DeltaType::MonsterKill, 1, 11, "p1", "");
+        log.record(4, DeltaType::ChestOpen, 1, 20, "p1", "");
+        log.record(5, DeltaType::ChestOpen, 2, 20, "p1", ""); // other floor
+        log.record(6, DeltaType::MonsterKill, 1, 12, "p1", "");
+
+        log.collapse_superseded();
+        let seqs: Vec<u64> = log.deltas.iter().map(|d| d.seq).collect();
+        assert_eq!(seqs, vec![0, 2, 3, 4, 5]);
+        assert!(seqs.windows(2).all(|w| w[0] < w[1]));
+        assert!(log.deltas.iter().all(|d| d.verify()));
+    }
+
+    #[test]
+    fn test_snapshot_capture_collapsed() {
+        let mut log = DeltaLog::default();
+        for i in 0..10 {
+            log.record(i, DeltaType::ShrineActivate, 5, 3, "p1", "");
+        }
+        log.record(10, DeltaType::MonsterKill, 5, 4, "p1", "");
+
+        let seed = TowerSeed { seed: 42 };
+        let full = FloorSnapshot::capture(&seed, 5, &log, 11, false);
+        let collapsed = FloorSnapshot::capture(&seed, 5, &log, 11, true);
+        assert_eq!(full.deltas.len(), 11);
+        assert_eq!(collapsed.deltas.len(), 2);
+        assert_eq!(log.deltas.len(), 11, "capture must not mutate the log");
+    }
+
+    fn synthetic_snapshot() -> FloorSnapshot {
+        let mut log = DeltaLog::default();
+        log.record(1, DeltaType::MonsterKill, 3, 100, "p1", "");
+        log.record(2, DeltaType::ChestOpen, 3, 200, "p1", "");
+        log.record(3, DeltaType::DoorUnlock, 3, 300, "p2", "");
+        log.record(4, DeltaType::TrapDisarm, 3, 400, "p2", "");
+        log.record(5, DeltaType::ShrineActivate, 3, 500, "p1", "");
+        log.record(6, DeltaType::MonsterKill, 3, 101, "p2", "");
+        log.record(7, DeltaType::LootPickup, 3, 600, "p1", "");
+        log.record(8, DeltaType::ChestOpen, 4, 201, "p1", ""); // other floor
+        FloorSnapshot::capture(&TowerSeed { seed: 42 }, 3, &log, 9, false)
+    }
+
+    #[test]
+    fn test_snapshot_apply_reconstructs_state() {
+        let state = synthetic_snapshot().apply();
+
+        assert_eq!(state.killed_monsters, BTreeSet::from([100, 101]));
+        assert_eq!(state.opened_chests, BTreeSet::from([200]));
+        assert_eq!(state.unlocked_doors, BTreeSet::from([300]));
+        assert_eq!(state.disarmed_traps, BTreeSet::from([400]));
+        assert_eq!(state.activated_shrines, BTreeSet::from([500]));
+    }
+
+    #[test]
+    fn test_snapshot_apply_idempotent_and_order_independent() {
+        let mut snapshot = synthetic_snapshot();
+        let expected = snapshot.apply();
+
+        let mut state = FloorState::default();
+        state.apply_snapshot(&snapshot);
+        state.apply_snapshot(&snapshot);
+        assert_eq!(state, expected);
+
+        snapshot.deltas.reverse();
+        assert_eq!(snapshot.apply(), expected);
+    }
+
     #[test]
     fn test_delta_log_clear_floor() {
         let mut log = DeltaLog::default();
@@ -486,7 +740,7 @@ mod tests {
         assert!(log.estimated_size_bytes() > 0);
 
         let seed = TowerSeed { seed: 42 };
-        let snapshot = FloorSnapshot::capture(&seed, 1, &log, 0);
+        let snapshot = FloorSnapshot::capture(&seed, 1, &log, 0, false);
         assert!(snapshot.estimated_size() > 20);
     }
 }
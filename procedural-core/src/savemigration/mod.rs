@@ -2,7 +2,7 @@
 //!
 //! Handles versioned save files with forward migration:
 //! - Each save has a `version` field
-//! - Migration functions transform v(N) → v(N+1) → ... → v(current)
+//! - Registered `MIGRATIONS` steps transform v(N) → v(N+1) → ... → v(current)
 //! - Old saves are never lost — always migrated forward
 //! - Unknown future versions produce an error (no downgrade)
 
@@ -10,7 +10,7 @@ use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
 /// Current save format version
-pub const CURRENT_SAVE_VERSION: u32 = 3;
+pub const CURRENT_SAVE_VERSION: u32 = 4;
 
 /// Minimum supported version (anything below cannot be migrated)
 pub const MIN_SUPPORTED_VERSION: u32 = 1;
@@ -131,32 +131,40 @@ pub fn migrate_save(json_str: &str) -> MigrationResult {
         };
     }
 
-    // Apply migrations sequentially
+    // Apply registered steps in order
     let mut current_version = version;
     let mut steps = Vec::new();
 
-    while current_version < CURRENT_SAVE_VERSION {
-        let result = apply_migration_step(&mut data, current_version);
-        match result {
-            Ok(description) => {
-                steps.push(description);
-                current_version += 1;
-                data["version"] = serde_json::json!(current_version);
-            }
-            Err(detail) => {
-                return MigrationResult {
-                    success: false,
-                    original_version: version,
-                    final_version: current_version,
-                    steps_applied: steps,
-                    error: Some(MigrationError::MigrationStepFailed {
-                        from_version: current_version,
-                        detail,
-                    }),
-                    data: None,
-                };
-            }
+    for step in MIGRATIONS.iter().filter(|s| s.from >= version) {
+        if step.from != current_version {
+            return MigrationResult {
+                success: false,
+                original_version: version,
+                final_version: current_version,
+                steps_applied: steps,
+                error: Some(MigrationError::MigrationStepFailed {
+                    from_version: current_version,
+                    detail: format!("No migration path from version {}", current_version),
+                }),
+                data: None,
+            };
         }
+        if let Err(detail) = (step.transform)(&mut data) {
+            return MigrationResult {
+                success: false,
+                original_version: version,
+                final_version: current_version,
+                steps_applied: steps,
+                error: Some(MigrationError::MigrationStepFailed {
+                    from_version: current_version,
+                    detail,
+                }),
+                data: None,
+            };
+        }
+        steps.push(format!("v{}→v{}: {}", step.from, step.to, step.description));
+        current_version = step.to;
+        data["version"] = serde_json::json!(current_version);
     }
 
     MigrationResult {
@@ -169,21 +177,56 @@ pub fn migrate_save(json_str: &str) -> MigrationResult {
     }
 }
 
-/// Apply a single migration step from `from_version` to `from_version + 1`
-fn apply_migration_step(data: &mut Value, from_version: u32) -> Result<String, String> {
-    match from_version {
-        1 => migrate_v1_to_v2(data),
-        2 => migrate_v2_to_v3(data),
-        _ => Err(format!("No migration path from version {}", from_version)),
+/// Like `migrate_save`, but all-or-nothing: steps run on a copy, and if any
+/// step fails `data` holds the original save untouched (so callers can
+/// persist `data` either way). Unparseable input still returns no data.
+pub fn migrate_save_checked(json_str: &str) -> MigrationResult {
+    let mut result = migrate_save(json_str);
+    if !result.success {
+        result.data = serde_json::from_str(json_str).ok();
     }
+    result
+}
+
+/// One registered version bump
+#[derive(Debug, Clone, Copy)]
+pub struct MigrationStep {
+    pub from: u32,
+    pub to: u32,
+    /// Summary reported in `MigrationResult::steps_applied`
+    pub description: &'static str,
+    pub transform: fn(&mut Value) -> Result<(), String>,
 }
 
+/// Every migration step, in version order. Bumping the save format means
+/// raising `CURRENT_SAVE_VERSION` and appending one step here.
+pub const MIGRATIONS: &[MigrationStep] = &[
+    MigrationStep {
+        from: 1,
+        to: 2,
+        description: "Added mastery, specialization, cosmetics; removed player_level",
+        transform: migrate_v1_to_v2,
+    },
+    MigrationStep {
+        from: 2,
+        to: 3,
+        description: "Added mutator_history, game_flow_state, achievements_v2, item semantic_tags/socket_data",
+        transform: migrate_v2_to_v3,
+    },
+    MigrationStep {
+        from: 3,
+        to: 4,
+        description: "Renamed inventory.shards to inventory.currency; added mastery prestige",
+        transform: migrate_v3_to_v4,
+    },
+];
+
 /// Migration v1 → v2:
 /// - Added `mastery` section with empty profile
 /// - Renamed `player_level` → removed (mastery replaces levels)
 /// - Added `specialization` section
 /// - Added `equipped_cosmetics` array
-fn migrate_v1_to_v2(data: &mut Value) -> Result<String, String> {
+fn migrate_v1_to_v2(data: &mut Value) -> Result<(), String> {
     let obj = data.as_object_mut().ok_or("Save data is not an object")?;
 
     // Remove deprecated player_level field
@@ -216,7 +259,7 @@ fn migrate_v1_to_v2(data: &mut Value) -> Result<String, String> {
         obj.insert("equipped_cosmetics".to_string(), serde_json::json!([]));
     }
 
-    Ok("v1→v2: Added mastery, specialization, cosmetics; removed player_level".to_string())
+    Ok(())
 }
 
 /// Migration v2 → v3:
@@ -225,7 +268,7 @@ fn migrate_v1_to_v2(data: &mut Value) -> Result<String, String> {
 /// - Added `achievements_v2` with new achievement format (categories)
 /// - Renamed `inventory.items` entries to include `semantic_tags` field
 /// - Added `socket_data` field to equipment entries
-fn migrate_v2_to_v3(data: &mut Value) -> Result<String, String> {
+fn migrate_v2_to_v3(data: &mut Value) -> Result<(), String> {
     let obj = data.as_object_mut().ok_or("Save data is not an object")?;
 
     // Add mutator history
@@ -285,7 +328,35 @@ fn migrate_v2_to_v3(data: &mut Value) -> Result<String, String> {
         }
     }
 
-    Ok("v2→v3: Added mutator_history, game_flow_state, achievements_v2, item semantic_tags/socket_data".to_string())
+    Ok(())
+}
+
+/// Migration v3 → v4:
+/// - Renamed `inventory.shards` → `inventory.currency`
+/// - Added `mastery.prestige` (and a fresh `mastery` block if the save lost it)
+fn migrate_v3_to_v4(data: &mut Value) -> Result<(), String> {
+    let obj = data.as_object_mut().ok_or("Save data is not an object")?;
+
+    if let Some(inventory) = obj.get_mut("inventory").and_then(|v| v.as_object_mut()) {
+        if let Some(shards) = inventory.remove("shards") {
+            inventory.entry("currency").or_insert(shards);
+        }
+    }
+
+    let mastery = obj.entry("mastery").or_insert_with(|| {
+        serde_json::json!({
+            "domains": {},
+            "total_xp": 0
+        })
+    });
+    let mastery = mastery
+        .as_object_mut()
+        .ok_or("Save mastery is not an object")?;
+    mastery
+        .entry("prestige")
+        .or_insert_with(|| serde_json::json!(0));
+
+    Ok(())
 }
 
 /// Validate that a save file is at the current version
@@ -309,7 +380,8 @@ pub fn create_new_save(player_name: &str) -> Value {
         "player_name": player_name,
         "mastery": {
             "domains": {},
-            "total_xp": 0
+            "total_xp": 0,
+            "prestige": 0
         },
         "specialization": {
             "chosen_branches": [],
@@ -318,7 +390,7 @@ pub fn create_new_save(player_name: &str) -> Value {
         "equipped_cosmetics": [],
         "inventory": {
             "items": [],
-            "shards": 0,
+            "currency": 0,
             "echo_fragments": 0
         },
         "mutator_history": {
@@ -358,6 +430,148 @@ pub fn get_save_version(json_str: &str) -> Option<u32> {
     data.get("version")?.as_u64().map(|v| v as u32)
 }
 
+/// Outcome of salvaging a damaged save
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecoveryResult {
+    /// Repaired save at CURRENT_SAVE_VERSION
+    pub data: Value,
+    /// Version found in the save, if it was readable and supported
+    pub original_version: Option<u32>,
+    /// Dotted paths of fields kept from the save
+    pub recovered: Vec<String>,
+    /// Dotted paths of fields that were missing or invalid and reset to defaults
+    pub lost: Vec<String>,
+    /// Nothing could be salvaged; `data` is a fresh save
+    pub full_loss: bool,
+}
+
+/// Player name used when the save's own name can't be read
+pub const RECOVERED_PLAYER_NAME: &str = "Unknown";
+
+/// Salvage what we can from a save that `migrate_save` rejects.
+///
+/// Every field of a fresh save is checked against the damaged one: values of
+/// the right JSON type are kept, anything missing or mistyped is reset to its
+/// default. Readable saves at a supported version are migrated first; an
+/// unreadable version is reported lost and the rest is treated as current.
+/// Fields not in the current format are carried over untouched.
+pub fn recover_save(json_str: &str) -> RecoveryResult {
+    let parsed = serde_json::from_str::<Value>(json_str)
+        .ok()
+        .filter(|v| v.is_object());
+    let Some(mut candidate) = parsed else {
+        let data = create_new_save(RECOVERED_PLAYER_NAME);
+        let mut lost = Vec::new();
+        collect_paths(&data, "", &mut lost);
+        return RecoveryResult {
+            data,
+            original_version: None,
+            recovered: vec![],
+            lost,
+            full_loss: true,
+        };
+    };
+
+    let original_version = get_save_version(json_str)
+        .filter(|v| (MIN_SUPPORTED_VERSION..=CURRENT_SAVE_VERSION).contains(v));
+    if original_version.is_some() {
+        let migrated = migrate_save(json_str);
+        if let Some(data) = migrated.data {
+            candidate = data;
+        }
+    }
+
+    let player_name = candidate
+        .get("player_name")
+        .and_then(|v| v.as_str())
+        .unwrap_or(RECOVERED_PLAYER_NAME)
+        .to_string();
+    let template = create_new_save(&player_name);
+
+    let mut recovered = Vec::new();
+    let mut lost = Vec::new();
+    let mut data = salvage(&template, &candidate, "", &mut recovered, &mut lost);
+
+    // Version is never taken from the save: the repaired data is always current
+    recovered.retain(|p| p != "version");
+    lost.retain(|p| p != "version");
+    if original_version.is_some() {
+        recovered.insert(0, "version".to_string());
+    } else {
+        lost.insert(0, "version".to_string());
+    }
+    data["version"] = serde_json::json!(CURRENT_SAVE_VERSION);
+
+    let full_loss = recovered.is_empty();
+    RecoveryResult {
+        data,
+        original_version,
+        recovered,
+        lost,
+        full_loss,
+    }
+}
+
+/// Merge `candidate` onto `template`, recursing into template objects that
+/// define fields. Everything else is a leaf, kept only if the types match.
+fn salvage(
+    template: &Value,
+    candidate: &Value,
+    path: &str,
+    recovered: &mut Vec<String>,
+    lost: &mut Vec<String>,
+) -> Value {
+    match (template, candidate) {
+        (Value::Object(fields), Value::Object(found)) if !fields.is_empty() => {
+            let mut out = found.clone();
+            for (key, default) in fields {
+                let child = join_path(path, key);
+                let value = match found.get(key) {
+                    Some(v) => salvage(default, v, &child, recovered, lost),
+                    None => {
+                        collect_paths(default, &child, lost);
+                        default.clone()
+                    }
+                };
+                out.insert(key.clone(), value);
+            }
+            Value::Object(out)
+        }
+        _ if same_json_type(template, candidate) => {
+            recovered.push(path.to_string());
+            candidate.clone()
+        }
+        _ => {
+            collect_paths(template, path, lost);
+            template.clone()
+        }
+    }
+}
+
+fn same_json_type(a: &Value, b: &Value) -> bool {
+    std::mem::discriminant(a) == std::mem::discriminant(b)
+}
+
+fn join_path(prefix: &str, key: &str) -> String {
+    if prefix.is_empty() {
+        key.to_string()
+    } else {
+        format!("{prefix}.{key}")
+    }
+}
+
+/// Leaf paths of `value`, using the same leaf rule as `salvage`
+fn collect_paths(value: &Value, path: &str, out: &mut Vec<String>) {
+    match value {
+        Value::Object(fields) if !fields.is_empty() => {
+            for (key, child) in fields {
+                collect_paths(child, &join_path(path, key), out);
+            }
+        }
+        _ => out.push(path.to_string()),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -413,28 +627,44 @@ mod tests {
     }
 
     fn make_v3_save() -> String {
+        serde_json::json!({
+            "version": 3,
+            "player_name": "TestPlayer",
+            "mastery": {"domains": {}, "total_xp": 0},
+            "inventory": {"items": [], "shards": 750, "echo_fragments": 3},
+            "game_flow_state": "MainMenu"
+        })
+        .to_string()
+    }
+
+    fn make_current_save() -> String {
         create_new_save("TestPlayer").to_string()
     }
 
     #[test]
     fn test_current_version_no_migration() {
-        let save = make_v3_save();
+        let save = make_current_save();
         let result = migrate_save(&save);
         assert!(result.success);
-        assert_eq!(result.original_version, 3);
-        assert_eq!(result.final_version, 3);
+        assert_eq!(result.original_version, CURRENT_SAVE_VERSION);
+        assert_eq!(result.final_version, CURRENT_SAVE_VERSION);
         assert_eq!(result.steps_applied.len(), 1);
         assert!(result.steps_applied[0].contains("No migration"));
     }
 
     #[test]
-    fn test_migrate_v1_to_v3() {
+    fn test_migrate_v1_to_current() {
         let save = make_v1_save();
         let result = migrate_save(&save);
         assert!(result.success);
         assert_eq!(result.original_version, 1);
-        assert_eq!(result.final_version, 3);
-        assert_eq!(result.steps_applied.len(), 2);
+        assert_eq!(result.final_version, CURRENT_SAVE_VERSION);
+        // One reported step per registered migration, in order
+        assert_eq!(result.steps_applied.len(), MIGRATIONS.len());
+        for (applied, step) in result.steps_applied.iter().zip(MIGRATIONS) {
+            assert!(applied.starts_with(&format!("v{}→v{}", step.from, step.to)));
+            assert!(applied.contains(step.description));
+        }
 
         let data = result.data.unwrap();
         // v1→v2: player_level removed
@@ -453,24 +683,93 @@ mod tests {
             assert!(item.get("semantic_tags").is_some());
             assert!(item.get("socket_data").is_some());
         }
+        // v3→v4: shards renamed, prestige added
+        assert_eq!(data["inventory"]["currency"], 1000);
+        assert!(data["inventory"].get("shards").is_none());
+        assert_eq!(data["mastery"]["prestige"], 0);
         // Version updated
-        assert_eq!(data["version"].as_u64().unwrap(), 3);
+        assert_eq!(
+            data["version"].as_u64().unwrap(),
+            CURRENT_SAVE_VERSION as u64
+        );
     }
 
     #[test]
-    fn test_migrate_v2_to_v3() {
+    fn test_migrate_v2_to_current() {
         let save = make_v2_save();
         let result = migrate_save(&save);
         assert!(result.success);
         assert_eq!(result.original_version, 2);
-        assert_eq!(result.final_version, 3);
-        assert_eq!(result.steps_applied.len(), 1);
+        assert_eq!(result.final_version, CURRENT_SAVE_VERSION);
+        assert_eq!(result.steps_applied.len(), 2);
 
         let data = result.data.unwrap();
         assert!(data.get("mutator_history").is_some());
         assert!(data.get("game_flow_state").is_some());
         // Achievements migrated to v2 format
         assert_eq!(data["achievements"]["format"], "v2");
+        assert_eq!(data["inventory"]["currency"], 2000);
+        // Existing mastery kept alongside the new prestige field
+        assert_eq!(data["mastery"]["total_xp"], 1500);
+        assert_eq!(data["mastery"]["prestige"], 0);
+    }
+
+    #[test]
+    fn test_migrate_v3_to_v4() {
+        let result = migrate_save(&make_v3_save());
+        assert!(result.success);
+        assert_eq!(result.steps_applied.len(), 1);
+        let data = result.data.unwrap();
+        assert_eq!(data["version"], 4);
+        assert_eq!(data["inventory"]["currency"], 750);
+        assert!(data["inventory"].get("shards").is_none());
+        assert_eq!(data["inventory"]["echo_fragments"], 3);
+        assert_eq!(data["mastery"]["prestige"], 0);
+        assert!(validate_save(&data.to_string()));
+    }
+
+    #[test]
+    fn test_migration_registry_reaches_current() {
+        let mut version = MIN_SUPPORTED_VERSION;
+        for step in MIGRATIONS {
+            assert_eq!(step.from, version, "gap before {}→{}", step.from, step.to);
+            assert_eq!(step.to, step.from + 1);
+            version = step.to;
+        }
+        assert_eq!(version, CURRENT_SAVE_VERSION);
+    }
+
+    #[test]
+    fn test_checked_migration_failure_returns_original() {
+        // Survives v2→v3 but the v3→v4 step needs an object mastery block
+        let mut save: Value = serde_json::from_str(&make_v2_save()).unwrap();
+        save["mastery"] = serde_json::json!("corrupt");
+        let result = migrate_save_checked(&save.to_string());
+        assert!(!result.success);
+        assert_eq!(result.final_version, 3);
+        assert_eq!(result.steps_applied.len(), 1);
+        assert!(matches!(
+            result.error,
+            Some(MigrationError::MigrationStepFailed {
+                from_version: 3,
+                ..
+            })
+        ));
+        // Original, not the half-migrated v3 payload
+        assert_eq!(result.data, Some(save));
+    }
+
+    #[test]
+    fn test_checked_migration_matches_unchecked_on_success() {
+        for save in [make_v1_save(), make_v2_save(), make_current_save()] {
+            let checked = migrate_save_checked(&save);
+            let unchecked = migrate_save(&save);
+            assert!(checked.success);
+            assert_eq!(checked.data, unchecked.data);
+            assert_eq!(checked.steps_applied, unchecked.steps_applied);
+            assert_eq!(checked.final_version, unchecked.final_version);
+        }
+        assert!(migrate_save_checked("garbage").data.is_none());
     }
 
     #[test]
@@ -507,7 +806,7 @@ mod tests {
 
     #[test]
     fn test_validate_save_current() {
-        let save = make_v3_save();
+        let save = make_current_save();
         assert!(validate_save(&save));
     }
 
@@ -541,6 +840,10 @@ mod tests {
         assert_eq!(get_save_version(&make_v1_save()), Some(1));
         assert_eq!(get_save_version(&make_v2_save()), Some(2));
         assert_eq!(get_save_version(&make_v3_save()), Some(3));
+        assert_eq!(
+            get_save_version(&make_current_save()),
+            Some(CURRENT_SAVE_VERSION)
+        );
         assert_eq!(get_save_version("garbage"), None);
     }
 
@@ -552,7 +855,7 @@ mod tests {
         let data = result.data.unwrap();
         // Original data preserved
         assert_eq!(data["player_name"], "TestPlayer");
-        assert_eq!(data["inventory"]["shards"].as_u64().unwrap(), 1000);
+        assert_eq!(data["inventory"]["currency"].as_u64().unwrap(), 1000);
         assert_eq!(data["stats"]["highest_floor"].as_u64().unwrap(), 25);
     }
 
@@ -580,12 +883,75 @@ mod tests {
     #[test]
     fn test_idempotent_migration() {
         // Migrating an already-current save should not change it
-        let save = make_v3_save();
+        let save = make_current_save();
         let result1 = migrate_save(&save);
         let data1 = result1.data.unwrap();
         let result2 = migrate_save(&serde_json::to_string(&data1).unwrap());
         assert!(result2.success);
-        assert_eq!(result2.original_version, 3);
+        assert_eq!(result2.original_version, CURRENT_SAVE_VERSION);
+    }
+
+    #[test]
+    fn test_recover_save_one_corrupted_field() {
+        let mut save = create_new_save("Survivor");
+        save["inventory"]["currency"] = serde_json::json!(4321);
+        save["stats"]["highest_floor"] = serde_json::json!(77);
+        save["settings"] = serde_json::json!("%%corrupt%%");
+
+        let result = recover_save(&save.to_string());
+        assert!(!result.full_loss);
+        assert_eq!(result.original_version, Some(CURRENT_SAVE_VERSION));
+        assert_eq!(result.data["player_name"], "Survivor");
+        assert_eq!(result.data["inventory"]["currency"], 4321);
+        assert_eq!(result.data["stats"]["highest_floor"], 77);
+        // Corrupted section reset to defaults and reported
+        assert_eq!(result.data["settings"]["music_volume"], 0.7);
+        assert!(result.lost.iter().all(|p| p.starts_with("settings.")));
+        assert!(result.lost.contains(&"settings.invert_y".to_string()));
+        assert!(result.recovered.contains(&"inventory.currency".to_string()));
+        assert!(validate_save(&result.data.to_string()));
+    }
+
+    #[test]
+    fn test_recover_save_mistyped_leaf() {
+        let mut save = create_new_save("Survivor");
+        save["stats"]["total_deaths"] = serde_json::json!("lots");
+        let result = recover_save(&save.to_string());
+        assert_eq!(result.lost, vec!["stats.total_deaths".to_string()]);
+        assert_eq!(result.data["stats"]["total_deaths"], 0);
+    }
+
+    #[test]
+    fn test_recover_old_save_migrates_first() {
+        let result = recover_save(&make_v1_save());
+        assert_eq!(result.original_version, Some(1));
+        assert_eq!(result.data["version"], CURRENT_SAVE_VERSION);
+        assert_eq!(result.data["inventory"]["currency"], 1000);
+        assert!(result.data.get("player_level").is_none());
+    }
+
+    #[test]
+    fn test_recover_save_bad_version() {
+        let mut save = create_new_save("Survivor");
+        save["version"] = serde_json::json!("three");
+        let result = recover_save(&save.to_string());
+        assert_eq!(result.original_version, None);
+        assert_eq!(result.lost, vec!["version".to_string()]);
+        assert_eq!(result.data["player_name"], "Survivor");
+        assert!(validate_save(&result.data.to_string()));
+    }
+
+    #[test]
+    fn test_recover_totally_invalid_save() {
+        for garbage in ["not json at all", "[1, 2, 3]", "42"] {
+            let result = recover_save(garbage);
+            assert!(result.full_loss);
+            assert!(result.recovered.is_empty());
+            assert_eq!(result.data["player_name"], RECOVERED_PLAYER_NAME);
+            assert!(result.lost.contains(&"player_name".to_string()));
+            assert!(result.lost.contains(&"settings.master_volume".to_string()));
+            assert!(validate_save(&result.data.to_string()));
+        }
     }
 
     #[test]
@@ -601,6 +967,6 @@ mod tests {
         .to_string();
         let result = migrate_save(&save);
         assert!(result.success);
-        assert_eq!(result.final_version, 3);
+        assert_eq!(result.final_version, CURRENT_SAVE_VERSION);
     }
 }
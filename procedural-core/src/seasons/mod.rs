@@ -278,6 +278,17 @@ pub fn generate_daily_quests(day_seed: u64) -> Vec<RecurringQuest> {
     quests
 }
 
+/// Server-side definition of a daily or weekly quest, looked up by id.
+/// Quest ids end in the day/week seed they were generated from; None for
+/// ids no seed produces.
+pub fn find_recurring_quest(quest_id: &str) -> Option<RecurringQuest> {
+    let seed: u64 = quest_id.rsplit_once('_')?.1.parse().ok()?;
+    generate_daily_quests(seed)
+        .into_iter()
+        .chain(generate_weekly_quests(seed))
+        .find(|q| q.id == quest_id)
+}
+
 /// Generate weekly quests
 pub fn generate_weekly_quests(week_seed: u64) -> Vec<RecurringQuest> {
     vec![
@@ -497,6 +508,68 @@ pub fn generate_season_rewards(season_id: u32) -> Vec<SeasonReward> {
     rewards
 }
 
+/// What claiming a completed quest paid out
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ClaimedReward {
+    pub quest_id: String,
+    pub shards: u64,
+    pub mastery_xp: u64,
+    pub season_xp: u64,
+    /// Season pass levels gained from `season_xp`
+    pub levels_gained: u32,
+}
+
+/// Which recurring quests a player has completed and claimed this season
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SeasonProgress {
+    pub completed: Vec<String>,
+    pub claimed: Vec<String>,
+}
+
+impl SeasonProgress {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Mark a quest complete. Returns false if it already was.
+    pub fn complete_quest(&mut self, quest_id: &str) -> bool {
+        if self.is_completed(quest_id) {
+            return false;
+        }
+        self.completed.push(quest_id.to_string());
+        true
+    }
+
+    pub fn is_completed(&self, quest_id: &str) -> bool {
+        self.completed.iter().any(|id| id == quest_id)
+    }
+
+    pub fn is_claimed(&self, quest_id: &str) -> bool {
+        self.claimed.iter().any(|id| id == quest_id)
+    }
+
+    /// Claim a completed, unclaimed quest: its season XP goes into `pass`
+    /// and the payout is returned. None if incomplete or already claimed.
+    pub fn claim(
+        &mut self,
+        quest: &RecurringQuest,
+        pass: &mut SeasonPass,
+    ) -> Option<ClaimedReward> {
+        if !self.is_completed(&quest.id) || self.is_claimed(&quest.id) {
+            return None;
+        }
+        self.claimed.push(quest.id.clone());
+        let levels_gained = pass.add_xp(quest.season_xp_reward);
+        Some(ClaimedReward {
+            quest_id: quest.id.clone(),
+            shards: quest.shard_reward,
+            mastery_xp: quest.mastery_xp_reward,
+            season_xp: quest.season_xp_reward,
+            levels_gained,
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -509,6 +582,21 @@ mod tests {
         assert!(quests.iter().all(|q| !q.completed));
     }
 
+    #[test]
+    fn test_find_recurring_quest_by_id() {
+        for quest in generate_daily_quests(77)
+            .into_iter()
+            .chain(generate_weekly_quests(12))
+        {
+            let found = find_recurring_quest(&quest.id).unwrap();
+            assert_eq!(found.shard_reward, quest.shard_reward);
+            assert_eq!(found.season_xp_reward, quest.season_xp_reward);
+        }
+        assert!(find_recurring_quest("daily_combat_x").is_none());
+        assert!(find_recurring_quest("weekly_made_up_12").is_none());
+        assert!(find_recurring_quest("").is_none());
+    }
+
     #[test]
     fn test_daily_quests_deterministic() {
         let a = generate_daily_quests(999);
@@ -662,4 +750,58 @@ mod tests {
         pass.add_xp(500); // halfway through level 0→1
         assert!((pass.level_progress() - 0.5).abs() < 0.01);
     }
+
+    #[test]
+    fn test_complete_then_claim_grants_reward_once() {
+        let quest = &generate_daily_quests(7)[0];
+        let mut pass = SeasonPass::new(1, "Test".into());
+        let mut progress = SeasonProgress::new();
+
+        assert!(progress.complete_quest(&quest.id));
+        assert!(!progress.complete_quest(&quest.id));
+
+        let reward = progress.claim(quest, &mut pass).unwrap();
+        assert_eq!(reward.shards, quest.shard_reward);
+        assert_eq!(reward.season_xp, quest.season_xp_reward);
+        assert_eq!(pass.xp, quest.season_xp_reward);
+        assert!(progress.is_claimed(&quest.id));
+    }
+
+    #[test]
+    fn test_double_claim_rejected() {
+        let quest = &generate_daily_quests(7)[0];
+        let mut pass = SeasonPass::new(1, "Test".into());
+        let mut progress = SeasonProgress::new();
+        progress.complete_quest(&quest.id);
+
+        assert!(progress.claim(quest, &mut pass).is_some());
+        assert!(progress.claim(quest, &mut pass).is_none());
+        assert_eq!(pass.xp, quest.season_xp_reward, "XP granted only once");
+    }
+
+    #[test]
+    fn test_claim_incomplete_quest_fails() {
+        let quest = &generate_weekly_quests(3)[0];
+        let mut pass = SeasonPass::new(1, "Test".into());
+        let mut progress = SeasonProgress::new();
+
+        assert!(progress.claim(quest, &mut pass).is_none());
+        assert_eq!(pass.xp, 0);
+        assert!(progress.claimed.is_empty());
+    }
+
+    #[test]
+    fn test_claim_levels_up_pass() {
+        let quest = RecurringQuest {
+            season_xp_reward: 2500,
+            ..generate_weekly_quests(3)[0].clone()
+        };
+        let mut pass = SeasonPass::new(1, "Test".into());
+        let mut progress = SeasonProgress::new();
+        progress.complete_quest(&quest.id);
+
+        let reward = progress.claim(&quest, &mut pass).unwrap();
+        assert_eq!(reward.levels_gained, 2);
+        assert_eq!(pass.level, 2);
+    }
 }
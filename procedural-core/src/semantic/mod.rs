@@ -14,6 +14,9 @@ impl Plugin for SemanticPlugin {
 
 /// Semantic tags attached to every game entity.
 /// Example: fire monster has tags [("fire", 0.8), ("aggression", 0.9), ("corruption", 0.3)]
+///
+/// Values live in [-1, 1]. Negative weights express anti-affinity, e.g. a
+/// fire-resist chestplate carries ("fire", -0.7).
 #[derive(Component, Clone, Debug, Serialize, Deserialize)]
 pub struct SemanticTags {
     pub tags: Vec<(String, f32)>,
@@ -35,19 +38,24 @@ impl SemanticTags {
             .unwrap_or(0.0)
     }
 
-    /// Cosine similarity between two tag vectors
+    /// Cosine similarity between the affinities (positive tags) of two tag
+    /// vectors. Negative tags are resistances, handled by
+    /// `resistance_against`, so they neither pull opposing-sign tags apart
+    /// nor make two shared resistances count as a shared theme.
     pub fn similarity(&self, other: &SemanticTags) -> f32 {
         let mut dot = 0.0_f32;
         let mut mag_a = 0.0_f32;
         let mut mag_b = 0.0_f32;
 
         for (key, val_a) in &self.tags {
+            let val_a = val_a.max(0.0);
             mag_a += val_a * val_a;
-            let val_b = other.get(key);
+            let val_b = other.get(key).max(0.0);
             dot += val_a * val_b;
         }
 
         for (_, val_b) in &other.tags {
+            let val_b = val_b.max(0.0);
             mag_b += val_b * val_b;
         }
 
@@ -84,6 +92,112 @@ impl SemanticTags {
         }
     }
 
+    /// Linearly interpolate two tag sets into a new one.
+    ///
+    /// Tags present in both sets are lerped; tags only in `a` are scaled by
+    /// `1 - t` and tags only in `b` by `t`. Duplicate keys are summed, then
+    /// clamped to [-1, 1], and near-zero tags are dropped.
+    /// `blended(a, b, 0.0)` reproduces `a`, `blended(a, b, 1.0)` reproduces `b`.
+    pub fn blended(a: &SemanticTags, b: &SemanticTags, t: f32) -> SemanticTags {
+        let t = t.clamp(0.0, 1.0);
+        let mut merged: Vec<(String, f32)> = Vec::new();
+
+        let weighted_a = a.tags.iter().map(|(k, v)| (k, v * (1.0 - t)));
+        let weighted_b = b.tags.iter().map(|(k, v)| (k, v * t));
+        for (key, val) in weighted_a.chain(weighted_b) {
+            if let Some(entry) = merged.iter_mut().find(|(k, _)| k == key) {
+                entry.1 += val;
+            } else {
+                merged.push((key.clone(), val));
+            }
+        }
+
+        SemanticTags {
+            tags: merged
+                .into_iter()
+                .map(|(k, v)| (k, v.clamp(-1.0, 1.0)))
+                .filter(|(_, v)| v.abs() >= BLEND_EPSILON)
+                .collect(),
+        }
+    }
+
+    /// Mitigation factor (0..=0.9) this tag set grants against an attack.
+    ///
+    /// Derived from the overlap between the attack's positive tags and this
+    /// set's negative tags, weighted by the attack's tag strength. A set with
+    /// no negative tags gives 0; the result is capped so nothing is immune.
+    pub fn resistance_against(&self, attack: &SemanticTags) -> f32 {
+        let mut overlap = 0.0_f32;
+        let mut attack_total = 0.0_f32;
+
+        for (key, val) in &attack.tags {
+            if *val <= 0.0 {
+                continue;
+            }
+            attack_total += val;
+            let resist = -self.get(key);
+            if resist > 0.0 {
+                overlap += val * resist.min(1.0);
+            }
+        }
+
+        if attack_total < f32::EPSILON {
+            return 0.0;
+        }
+        (overlap / attack_total).clamp(0.0, MAX_RESISTANCE)
+    }
+
+    /// Damage-taken multiplier this set's `VULN_PREFIX` entries give against
+    /// an attack, weighted by the attack's positive tag strength.
+    /// An entry `("vuln:water", 0.5)` means water hits for 1.5x.
+    /// Returns 1.0 when nothing applies.
+    pub fn vulnerability_against(&self, attack: &SemanticTags) -> f32 {
+        let mut weighted = 0.0_f32;
+        let mut attack_total = 0.0_f32;
+
+        for (key, val) in &attack.tags {
+            if *val <= 0.0 || key.starts_with(VULN_PREFIX) {
+                continue;
+            }
+            attack_total += val;
+            weighted += val * (1.0 + self.get(&format!("{VULN_PREFIX}{key}")));
+        }
+
+        if attack_total < f32::EPSILON {
+            return 1.0;
+        }
+        (weighted / attack_total).max(0.0)
+    }
+
+    /// This set without its `VULN_PREFIX` entries, for similarity checks
+    pub fn core_tags(&self) -> SemanticTags {
+        SemanticTags {
+            tags: self
+                .tags
+                .iter()
+                .filter(|(k, _)| !k.starts_with(VULN_PREFIX))
+                .cloned()
+                .collect(),
+        }
+    }
+
+    /// L2 norm of the tag vector
+    pub fn magnitude(&self) -> f32 {
+        self.tags.iter().map(|(_, v)| v * v).sum::<f32>().sqrt()
+    }
+
+    /// Rescale tag values so the vector has unit length.
+    /// No-op on empty or zero vectors.
+    pub fn normalize(&mut self) {
+        let mag = self.magnitude();
+        if mag < f32::EPSILON {
+            return;
+        }
+        for (_, v) in &mut self.tags {
+            *v /= mag;
+        }
+    }
+
     /// Get the dominant tag (highest value)
     pub fn dominant(&self) -> Option<(&str, f32)> {
         self.tags
@@ -93,8 +207,43 @@ impl SemanticTags {
     }
 }
 
+/// Weighted average of several tag sets, normalized to unit length
+/// (e.g. a floor's theme from its monsters).
+///
+/// Sets and weights are paired up in order; entries past the shorter slice
+/// are ignored. Negative weights count as zero. Returns an empty set when
+/// nothing carries weight.
+pub fn centroid(sets: &[SemanticTags], weights: &[f32]) -> SemanticTags {
+    let mut sum: Vec<(String, f32)> = Vec::new();
+    let mut total_weight = 0.0_f32;
+
+    for (set, &weight) in sets.iter().zip(weights) {
+        let weight = weight.max(0.0);
+        total_weight += weight;
+        for (key, val) in &set.tags {
+            if let Some(entry) = sum.iter_mut().find(|(k, _)| k == key) {
+                entry.1 += val * weight;
+            } else {
+                sum.push((key.clone(), val * weight));
+            }
+        }
+    }
+
+    if total_weight < f32::EPSILON {
+        return SemanticTags { tags: Vec::new() };
+    }
+    let mut out = SemanticTags {
+        tags: sum
+            .into_iter()
+            .map(|(k, v)| (k, v / total_weight))
+            .collect(),
+    };
+    out.normalize();
+    out
+}
+
 /// Semantic interaction result between two entities
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum SemanticInteraction {
     Synergy(f32),  // similarity > 0.7
     Neutral,       // 0.3..0.7
@@ -125,6 +274,16 @@ pub enum SemanticInteractionType {
     HealingInterference { reduction: f32 },
 }
 
+/// Cap on `SemanticTags::resistance_against` so nothing is fully immune
+pub const MAX_RESISTANCE: f32 = 0.9;
+
+/// Prefix for weakness/resistance entries: `vuln:<tag>` holds the
+/// damage-taken multiplier minus one for attacks carrying `<tag>`
+pub const VULN_PREFIX: &str = "vuln:";
+
+/// Tags whose blended magnitude falls below this are dropped by `SemanticTags::blended`
+const BLEND_EPSILON: f32 = 0.001;
+
 /// Maximum distance for semantic interactions
 const INTERACTION_RANGE: f32 = 10.0;
 
@@ -305,6 +464,141 @@ mod tests {
         assert!((a.get("water") - 0.3).abs() < 0.01);
     }
 
+    #[test]
+    fn test_blended_zero_is_identity() {
+        let a = SemanticTags::new(vec![("fire", 0.8), ("corruption", 0.3)]);
+        let b = SemanticTags::new(vec![("water", 0.9), ("fire", 0.1)]);
+        let out = SemanticTags::blended(&a, &b, 0.0);
+
+        assert_eq!(out.tags.len(), a.tags.len());
+        for ((ka, va), (ko, vo)) in a.tags.iter().zip(out.tags.iter()) {
+            assert_eq!(ka, ko);
+            assert!((va - vo).abs() < f32::EPSILON);
+        }
+    }
+
+    #[test]
+    fn test_blended_mixes_and_carries_over() {
+        let fire = SemanticTags::new(vec![("fire", 1.0), ("earth", 0.5)]);
+        let water = SemanticTags::new(vec![("water", 1.0), ("earth", 0.5)]);
+        let out = SemanticTags::blended(&fire, &water, 0.4);
+
+        assert!((out.get("fire") - 0.6).abs() < 0.001);
+        assert!((out.get("water") - 0.4).abs() < 0.001);
+        // Matching tag is lerped: 0.5 * 0.6 + 0.5 * 0.4
+        assert!((out.get("earth") - 0.5).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_blended_merges_duplicates_and_drops_tiny() {
+        let a = SemanticTags::new(vec![("fire", 0.8), ("fire", 0.7), ("dust", 0.0005)]);
+        let b = SemanticTags::new(vec![]);
+        let out = SemanticTags::blended(&a, &b, 0.0);
+
+        assert_eq!(out.tags.len(), 1, "duplicates merged, tiny tag dropped");
+        assert!(
+            (out.get("fire") - 1.0).abs() < f32::EPSILON,
+            "sum clamped to 1.0"
+        );
+    }
+
+    #[test]
+    fn test_normalize_unit_magnitude() {
+        let mut tags = SemanticTags::new(vec![("fire", 3.0), ("water", 4.0)]);
+        assert!((tags.magnitude() - 5.0).abs() < 0.001);
+
+        tags.normalize();
+        assert!((tags.magnitude() - 1.0).abs() < 0.001);
+        assert!((tags.get("fire") - 0.6).abs() < 0.001);
+        assert!((tags.get("water") - 0.8).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_normalize_preserves_dominant() {
+        let mut tags = SemanticTags::new(vec![("fire", 0.3), ("void", 2.5), ("earth", 1.1)]);
+        tags.normalize();
+        assert_eq!(tags.dominant().unwrap().0, "void");
+        assert!(tags.get("void") > tags.get("earth"));
+        assert!(tags.get("earth") > tags.get("fire"));
+    }
+
+    #[test]
+    fn test_normalize_zero_is_noop() {
+        let mut empty = SemanticTags::new(vec![]);
+        empty.normalize();
+        assert!(empty.tags.is_empty());
+
+        let mut zero = SemanticTags::new(vec![("fire", 0.0)]);
+        zero.normalize();
+        assert_eq!(zero.get("fire"), 0.0);
+    }
+
+    #[test]
+    fn test_blended_keeps_negative_tags() {
+        let resist = SemanticTags::new(vec![("fire", -0.8)]);
+        let plain = SemanticTags::new(vec![("earth", 0.5)]);
+        let out = SemanticTags::blended(&resist, &plain, 0.5);
+        assert!((out.get("fire") + 0.4).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_fire_sword_vs_fire_resist_chestplate() {
+        let sword = SemanticTags::new(vec![("fire", 0.9)]);
+        let chestplate = SemanticTags::new(vec![("fire", -0.7), ("earth", 0.4)]);
+
+        let mitigation = chestplate.resistance_against(&sword);
+        assert!((mitigation - 0.7).abs() < 0.001, "got {mitigation}");
+
+        // Mixed attack: only the fire share is resisted
+        let hybrid = SemanticTags::new(vec![("fire", 0.5), ("wind", 0.5)]);
+        let partial = chestplate.resistance_against(&hybrid);
+        assert!((partial - 0.35).abs() < 0.001, "got {partial}");
+    }
+
+    #[test]
+    fn test_similarity_ignores_opposing_sign_tags() {
+        let sword = SemanticTags::new(vec![("fire", 0.9), ("earth", 0.3)]);
+        let plain = SemanticTags::new(vec![("earth", 0.6)]);
+        let resist = SemanticTags::new(vec![("fire", -0.7), ("earth", 0.6)]);
+
+        // A fire resistance doesn't make the chestplate anti-similar to fire
+        let sim = sword.similarity(&resist);
+        assert!(sim > 0.0, "got {sim}");
+        assert!((sim - sword.similarity(&plain)).abs() < 0.001);
+
+        // Sharing only a resistance isn't a shared theme
+        let a = SemanticTags::new(vec![("fire", -0.7)]);
+        let b = SemanticTags::new(vec![("fire", -0.5)]);
+        assert_eq!(a.similarity(&b), 0.0);
+    }
+
+    #[test]
+    fn test_resistance_without_negative_tags() {
+        let sword = SemanticTags::new(vec![("fire", 0.9)]);
+        let plain = SemanticTags::new(vec![("fire", 0.6), ("earth", 0.4)]);
+        assert_eq!(plain.resistance_against(&sword), 0.0);
+    }
+
+    #[test]
+    fn test_resistance_capped() {
+        let sword = SemanticTags::new(vec![("fire", 1.0)]);
+        let immune = SemanticTags::new(vec![("fire", -1.0)]);
+        assert!((immune.resistance_against(&sword) - MAX_RESISTANCE).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn test_vulnerability_weighted_by_attack() {
+        let monster = SemanticTags::new(vec![("fire", 0.8), ("vuln:water", 0.5)]);
+        let water = SemanticTags::new(vec![("water", 0.9)]);
+        let hybrid = SemanticTags::new(vec![("water", 0.5), ("wind", 0.5)]);
+        let neutral = SemanticTags::new(vec![("physical", 0.9)]);
+
+        assert!((monster.vulnerability_against(&water) - 1.5).abs() < 0.001);
+        assert!((monster.vulnerability_against(&hybrid) - 1.25).abs() < 0.001);
+        assert_eq!(monster.vulnerability_against(&neutral), 1.0);
+        assert_eq!(monster.core_tags().tags.len(), 1);
+    }
+
     #[test]
     fn test_dominant() {
         let tags = SemanticTags::new(vec![("fire", 0.3), ("water", 0.9), ("earth", 0.1)]);
@@ -312,4 +606,48 @@ mod tests {
         assert_eq!(name, "water");
         assert!((val - 0.9).abs() < f32::EPSILON);
     }
+
+    #[test]
+    fn test_centroid_of_identical_sets() {
+        let set = SemanticTags::new(vec![("fire", 0.6), ("corruption", 0.8)]);
+        let out = centroid(&[set.clone(), set.clone(), set.clone()], &[1.0, 2.0, 0.5]);
+        assert_eq!(out.tags.len(), 2);
+        assert!((out.get("fire") - 0.6).abs() < 0.001);
+        assert!((out.get("corruption") - 0.8).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_centroid_pulled_toward_heavy_weight() {
+        let fire = SemanticTags::new(vec![("fire", 1.0)]);
+        let water = SemanticTags::new(vec![("water", 1.0)]);
+        let sets = [fire.clone(), water.clone()];
+
+        let even = centroid(&sets, &[1.0, 1.0]);
+        assert!((even.get("fire") - even.get("water")).abs() < 0.001);
+        assert!((even.magnitude() - 1.0).abs() < 0.001);
+
+        let heavy = centroid(&sets, &[9.0, 1.0]);
+        assert!(heavy.get("fire") > heavy.get("water"));
+        assert!(heavy.similarity(&fire) > even.similarity(&fire));
+    }
+
+    #[test]
+    fn test_centroid_mismatched_lengths() {
+        let fire = SemanticTags::new(vec![("fire", 1.0)]);
+        let water = SemanticTags::new(vec![("water", 1.0)]);
+
+        // The unweighted trailing set is ignored
+        let out = centroid(&[fire.clone(), water], &[1.0]);
+        assert!((out.get("fire") - 1.0).abs() < 0.001);
+        assert_eq!(out.get("water"), 0.0);
+
+        // Extra weights are ignored too
+        let out = centroid(&[fire], &[1.0, 5.0, 5.0]);
+        assert!((out.get("fire") - 1.0).abs() < 0.001);
+
+        assert!(centroid(&[], &[1.0]).tags.is_empty());
+        assert!(centroid(&[SemanticTags::new(vec![("fire", 1.0)])], &[0.0])
+            .tags
+            .is_empty());
+    }
 }
@@ -10,6 +10,8 @@
 //! All social state is stored server-side via Nakama.
 //! This module defines the data structures and validation logic.
 
+use std::collections::HashMap;
+
 use serde::{Deserialize, Serialize};
 
 // =====================
@@ -59,6 +61,9 @@ pub struct Guild {
     pub guild_xp: u64,
     pub settings: GuildSettings,
     pub bank_shards: u64,
+    /// Shared item storage, created on first use
+    #[serde(default)]
+    pub bank: Option<GuildBank>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -116,6 +121,7 @@ impl Guild {
             guild_xp: 0,
             settings: GuildSettings::default(),
             bank_shards: 0,
+            bank: None,
         }
     }
 
@@ -171,6 +177,248 @@ impl Guild {
             self.max_members = 50 + (self.guild_level / 5) * 10;
         }
     }
+
+    /// The guild bank, created empty on first access
+    pub fn bank_mut(&mut self) -> &mut GuildBank {
+        self.bank.get_or_insert_with(GuildBank::default)
+    }
+
+    /// Deposit an item into a bank tab. `now` is a unix timestamp.
+    /// Fails for non-members, ranks without deposit rights, or a full tab.
+    pub fn bank_deposit(&mut self, user_id: &str, tab: usize, item: TradeItem, now: u64) -> bool {
+        let Some(rank) = self.find_member(user_id).map(|m| m.rank) else {
+            return false;
+        };
+        if !rank.bank_permission().can_deposit {
+            return false;
+        }
+        self.bank_mut().deposit(user_id, tab, item, now)
+    }
+
+    /// Withdraw `quantity` of an item from a bank tab, within the member's
+    /// daily rank allowance. `now` is a unix timestamp; days are UTC days.
+    pub fn bank_withdraw(
+        &mut self,
+        user_id: &str,
+        tab: usize,
+        item_name: &str,
+        quantity: u32,
+        now: u64,
+    ) -> bool {
+        let Some(rank) = self.find_member(user_id).map(|m| m.rank) else {
+            return false;
+        };
+        let permission = rank.bank_permission();
+        if !permission.can_withdraw {
+            return false;
+        }
+        self.bank_mut().withdraw(
+            user_id,
+            tab,
+            item_name,
+            quantity,
+            permission.daily_limit,
+            now,
+        )
+    }
+}
+
+// =====================
+// Guild Bank
+// =====================
+
+/// What a rank may do with the guild bank
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BankPermission {
+    pub can_deposit: bool,
+    pub can_withdraw: bool,
+    /// Items this rank may withdraw per day
+    pub daily_limit: u32,
+}
+
+impl GuildRank {
+    pub fn bank_permission(&self) -> BankPermission {
+        let (can_withdraw, daily_limit) = match self {
+            GuildRank::Recruit => (false, 0),
+            GuildRank::Member => (true, 5),
+            GuildRank::Officer => (true, 20),
+            GuildRank::ViceLeader => (true, 50),
+            GuildRank::Leader => (true, u32::MAX),
+        };
+        BankPermission {
+            can_deposit: true,
+            can_withdraw,
+            daily_limit,
+        }
+    }
+}
+
+/// Distinct item stacks a bank tab can hold
+pub const GUILD_BANK_TAB_SLOTS: usize = 50;
+/// Transactions kept in the bank log (oldest dropped first)
+pub const GUILD_BANK_LOG_CAP: usize = 200;
+const SECS_PER_DAY: u64 = 86_400;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GuildBankTab {
+    pub name: String,
+    pub items: Vec<TradeItem>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BankAction {
+    Deposit,
+    Withdraw,
+}
+
+/// One entry in the bank's audit log
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BankTransaction {
+    pub user_id: String,
+    pub action: BankAction,
+    pub tab: usize,
+    pub item_name: String,
+    pub quantity: u32,
+    pub timestamp: u64,
+}
+
+/// Items a member has withdrawn on a given day
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DailyWithdrawals {
+    pub day: u64,
+    pub count: u32,
+}
+
+/// Shared guild item storage
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GuildBank {
+    pub tabs: Vec<GuildBankTab>,
+    pub log: Vec<BankTransaction>,
+    #[serde(default)]
+    pub withdrawals: HashMap<String, DailyWithdrawals>,
+}
+
+impl Default for GuildBank {
+    fn default() -> Self {
+        Self {
+            tabs: vec![GuildBankTab {
+                name: "Main".into(),
+                items: Vec::new(),
+            }],
+            log: Vec::new(),
+            withdrawals: HashMap::new(),
+        }
+    }
+}
+
+impl GuildBank {
+    /// Stack an item into `tab` (same name and rarity stack together).
+    /// Rejected if the stack would overflow `u32`.
+    pub fn deposit(&mut self, user_id: &str, tab: usize, item: TradeItem, now: u64) -> bool {
+        if item.quantity == 0 {
+            return false;
+        }
+        let Some(bank_tab) = self.tabs.get_mut(tab) else {
+            return false;
+        };
+        let slots_used = bank_tab.items.len();
+        match bank_tab
+            .items
+            .iter_mut()
+            .find(|i| i.item_name == item.item_name && i.rarity == item.rarity)
+        {
+            Some(stack) => match stack.quantity.checked_add(item.quantity) {
+                Some(total) => stack.quantity = total,
+                None => return false,
+            },
+            None if slots_used >= GUILD_BANK_TAB_SLOTS => return false,
+            None => bank_tab.items.push(item.clone()),
+        }
+        self.record(
+            user_id,
+            BankAction::Deposit,
+            tab,
+            &item.item_name,
+            item.quantity,
+            now,
+        );
+        true
+    }
+
+    /// Take `quantity` of `item_name` from `tab` if the user has enough of
+    /// today's `daily_limit` left and the tab holds enough
+    pub fn withdraw(
+        &mut self,
+        user_id: &str,
+        tab: usize,
+        item_name: &str,
+        quantity: u32,
+        daily_limit: u32,
+        now: u64,
+    ) -> bool {
+        if quantity == 0
+            || self.withdrawn_today(user_id, now).saturating_add(quantity) > daily_limit
+        {
+            return false;
+        }
+        let Some(bank_tab) = self.tabs.get_mut(tab) else {
+            return false;
+        };
+        let Some(idx) = bank_tab
+            .items
+            .iter()
+            .position(|i| i.item_name == item_name && i.quantity >= quantity)
+        else {
+            return false;
+        };
+        bank_tab.items[idx].quantity -= quantity;
+        if bank_tab.items[idx].quantity == 0 {
+            bank_tab.items.remove(idx);
+        }
+
+        let day = now / SECS_PER_DAY;
+        let entry = self
+            .withdrawals
+            .entry(user_id.to_string())
+            .or_insert(DailyWithdrawals { day, count: 0 });
+        if entry.day != day {
+            *entry = DailyWithdrawals { day, count: 0 };
+        }
+        entry.count += quantity;
+        self.record(user_id, BankAction::Withdraw, tab, item_name, quantity, now);
+        true
+    }
+
+    /// Items `user_id` has withdrawn on the day containing `now`
+    pub fn withdrawn_today(&self, user_id: &str, now: u64) -> u32 {
+        self.withdrawals
+            .get(user_id)
+            .filter(|w| w.day == now / SECS_PER_DAY)
+            .map_or(0, |w| w.count)
+    }
+
+    fn record(
+        &mut self,
+        user_id: &str,
+        action: BankAction,
+        tab: usize,
+        item_name: &str,
+        quantity: u32,
+        now: u64,
+    ) {
+        self.log.push(BankTransaction {
+            user_id: user_id.to_string(),
+            action,
+            tab,
+            item_name: item_name.to_string(),
+            quantity,
+            timestamp: now,
+        });
+        if self.log.len() > GUILD_BANK_LOG_CAP {
+            let excess = self.log.len() - GUILD_BANK_LOG_CAP;
+            self.log.drain(..excess);
+        }
+    }
 }
 
 // =====================
@@ -205,6 +453,13 @@ pub struct Party {
     pub max_size: u32,
     pub loot_rule: LootRule,
     pub target_floor: Option<u32>,
+    /// Index into `members` of the next round-robin recipient
+    #[serde(default)]
+    pub round_robin_cursor: usize,
+    /// Opt-in: Common items bypass NeedBeforeGreed / MasterLooter and go
+    /// round-robin (sharing `round_robin_cursor`)
+    #[serde(default)]
+    pub round_robin_commons: bool,
 }
 
 /// How loot is distributed in a party
@@ -216,6 +471,9 @@ pub enum LootRule {
     MasterLooter,    // leader decides
 }
 
+/// Added to a roll to declare need, so any need roll beats any greed roll
+pub const NEED_ROLL_BONUS: u32 = 1000;
+
 impl Party {
     pub fn new(leader_id: String, leader_name: String) -> Self {
         Self {
@@ -231,6 +489,8 @@ impl Party {
             max_size: 4,
             loot_rule: LootRule::NeedBeforeGreed,
             target_floor: None,
+            round_robin_cursor: 0,
+            round_robin_commons: false,
         }
     }
 
@@ -269,6 +529,184 @@ impl Party {
     pub fn leader(&self) -> Option<&PartyMember> {
         self.members.iter().find(|m| m.is_leader)
     }
+
+    /// Pick the member who receives `item` under the party's loot rule.
+    ///
+    /// `rolls` are `(user_id, roll)` pairs; non-members are ignored.
+    /// - FreeForAll: first member in `rolls` (pickup order) gets it
+    /// - RoundRobin: next member in rotation; the cursor advances and wraps
+    /// - NeedBeforeGreed: highest roll wins, need rolls carry `NEED_ROLL_BONUS`,
+    ///   0 means pass; ties go to the earlier party member
+    /// - MasterLooter: the leader
+    ///
+    /// With `round_robin_commons` set, Common items skip rolling and the
+    /// master looter and go round-robin instead. Falls back to the leader
+    /// when nobody qualifies; empty parties get "".
+    pub fn assign_loot(&mut self, item: &TradeItem, rolls: &[(String, u32)]) -> String {
+        let leader_id = self.leader().map(|m| m.user_id.clone()).unwrap_or_default();
+        let is_member = |id: &str| self.members.iter().any(|m| m.user_id == id);
+
+        let mode = match self.loot_rule {
+            LootRule::NeedBeforeGreed | LootRule::MasterLooter
+                if self.round_robin_commons && item.rarity == "Common" =>
+            {
+                LootRule::RoundRobin
+            }
+            mode => mode,
+        };
+
+        match mode {
+            LootRule::FreeForAll => rolls
+                .iter()
+                .find(|(id, _)| is_member(id))
+                .map(|(id, _)| id.clone())
+                .unwrap_or(leader_id),
+            LootRule::RoundRobin => {
+                if self.members.is_empty() {
+                    return leader_id;
+                }
+                let idx = self.round_robin_cursor % self.members.len();
+                self.round_robin_cursor = (idx + 1) % self.members.len();
+                self.members[idx].user_id.clone()
+            }
+            LootRule::NeedBeforeGreed => self
+                .members
+                .iter()
+                .filter_map(|m| {
+                    rolls
+                        .iter()
+                        .find(|(id, _)| *id == m.user_id)
+                        .filter(|(_, roll)| *roll > 0)
+                        .map(|(id, roll)| (id, *roll))
+                })
+                // max_by_key keeps the last max; reverse so ties favor party order
+                .rev()
+                .max_by_key(|(_, roll)| *roll)
+                .map(|(id, _)| id.clone())
+                .unwrap_or(leader_id),
+            LootRule::MasterLooter => leader_id,
+        }
+    }
+}
+
+/// Group bonuses from the roles present in a party or raid. Each role's
+/// bonus grows per member up to a cap; having every role present adds a
+/// flat all-round bonus on top.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CompositionBonus {
+    pub role_counts: HashMap<PartyRole, u32>,
+    /// Extra damage dealt (Strikers)
+    pub damage_bonus: f32,
+    /// Damage taken reduction (Vanguards)
+    pub damage_reduction: f32,
+    /// Extra healing (Supports)
+    pub healing_bonus: f32,
+    /// Extra crowd-control duration (Tacticians)
+    pub control_bonus: f32,
+    /// All four roles present
+    pub balanced: bool,
+}
+
+/// Per-member bonus and cap for each role's contribution
+const STRIKER_DAMAGE_PER_MEMBER: f32 = 0.05;
+const VANGUARD_REDUCTION_PER_MEMBER: f32 = 0.04;
+const SUPPORT_HEALING_PER_MEMBER: f32 = 0.10;
+const TACTICIAN_CONTROL_PER_MEMBER: f32 = 0.08;
+const ROLE_BONUS_CAP: f32 = 0.30;
+/// Added to every bonus when all four roles are present
+const BALANCED_BONUS: f32 = 0.05;
+
+impl CompositionBonus {
+    pub fn from_roles(roles: impl IntoIterator<Item = PartyRole>) -> Self {
+        let mut role_counts: HashMap<PartyRole, u32> = HashMap::new();
+        for role in roles {
+            *role_counts.entry(role).or_insert(0) += 1;
+        }
+        let count = |role| role_counts.get(&role).copied().unwrap_or(0) as f32;
+        let balanced = role_counts.len() == 4;
+        let extra = if balanced { BALANCED_BONUS } else { 0.0 };
+
+        Self {
+            damage_bonus: (count(PartyRole::Striker) * STRIKER_DAMAGE_PER_MEMBER)
+                .min(ROLE_BONUS_CAP)
+                + extra,
+            damage_reduction: (count(PartyRole::Vanguard) * VANGUARD_REDUCTION_PER_MEMBER)
+                .min(ROLE_BONUS_CAP)
+                + extra,
+            healing_bonus: (count(PartyRole::Support) * SUPPORT_HEALING_PER_MEMBER)
+                .min(ROLE_BONUS_CAP)
+                + extra,
+            control_bonus: (count(PartyRole::Tactician) * TACTICIAN_CONTROL_PER_MEMBER)
+                .min(ROLE_BONUS_CAP)
+                + extra,
+            balanced,
+            role_counts,
+        }
+    }
+}
+
+impl Party {
+    pub fn composition(&self) -> CompositionBonus {
+        CompositionBonus::from_roles(self.members.iter().map(|m| m.role))
+    }
+}
+
+// =====================
+// Raid System
+// =====================
+
+/// Parties a raid holds unless created with another cap
+pub const DEFAULT_RAID_PARTIES: usize = 5;
+
+/// Several parties grouped for world bosses
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Raid {
+    pub parties: Vec<Party>,
+    pub max_parties: usize,
+}
+
+impl Raid {
+    pub fn new(max_parties: usize) -> Self {
+        Self {
+            parties: Vec::new(),
+            max_parties,
+        }
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.parties.len() >= self.max_parties
+    }
+
+    /// Add a party; fails if the raid is full or any of its members is
+    /// already in the raid
+    pub fn add_party(&mut self, party: Party) -> bool {
+        if self.is_full() {
+            return false;
+        }
+        let already_in = |id: &str| {
+            self.parties
+                .iter()
+                .any(|p| p.members.iter().any(|m| m.user_id == id))
+        };
+        if party.members.iter().any(|m| already_in(&m.user_id)) {
+            return false;
+        }
+        self.parties.push(party);
+        true
+    }
+
+    pub fn total_members(&self) -> usize {
+        self.parties.iter().map(|p| p.members.len()).sum()
+    }
+
+    /// Composition bonus over every member of every party
+    pub fn raid_composition(&self) -> CompositionBonus {
+        CompositionBonus::from_roles(
+            self.parties
+                .iter()
+                .flat_map(|p| p.members.iter().map(|m| m.role)),
+        )
+    }
 }
 
 // =====================
@@ -490,12 +928,163 @@ impl Trade {
         self.state = TradeState::Cancelled;
     }
 
-    pub fn execute(&mut self) -> bool {
-        if self.state != TradeState::Confirmed {
-            return false;
+    /// Check that both sides confirmed and that each inventory actually holds
+    /// what its owner offered. Nothing is moved.
+    pub fn validate(
+        &self,
+        inv_a: &PlayerInventory,
+        inv_b: &PlayerInventory,
+    ) -> Result<(), TradeError> {
+        match self.state {
+            TradeState::Confirmed => {}
+            TradeState::Completed | TradeState::Cancelled => {
+                return Err(TradeError::Tampered {
+                    detail: format!("trade already {:?}", self.state),
+                });
+            }
+            _ => return Err(TradeError::NotConfirmed),
+        }
+        if !(self.a_confirmed && self.b_confirmed && self.a_locked && self.b_locked) {
+            return Err(TradeError::Tampered {
+                detail: "state is Confirmed but a side never locked or confirmed".into(),
+            });
+        }
+        if inv_a.player_id != self.player_a || inv_b.player_id != self.player_b {
+            return Err(TradeError::Tampered {
+                detail: "inventory does not belong to the trading player".into(),
+            });
+        }
+
+        for (player, offered, shards, inv) in [
+            (&self.player_a, &self.items_a, self.shards_a, inv_a),
+            (&self.player_b, &self.items_b, self.shards_b, inv_b),
+        ] {
+            if shards > inv.shards {
+                return Err(TradeError::InsufficientItems {
+                    player: player.clone(),
+                    item_name: "shards".into(),
+                    offered: shards,
+                    owned: inv.shards,
+                });
+            }
+            // The same item may be listed more than once; check the total
+            let mut totals: Vec<(&str, &str, u64)> = Vec::new();
+            for item in offered {
+                if item.quantity == 0 {
+                    return Err(TradeError::Tampered {
+                        detail: format!("zero-quantity offer of {}", item.item_name),
+                    });
+                }
+                match totals
+                    .iter_mut()
+                    .find(|(n, r, _)| *n == item.item_name && *r == item.rarity)
+                {
+                    Some(t) => t.2 += item.quantity as u64,
+                    None => totals.push((&item.item_name, &item.rarity, item.quantity as u64)),
+                }
+            }
+            for (name, rarity, qty) in totals {
+                let owned = inv.quantity_of(name, rarity);
+                if qty > owned {
+                    return Err(TradeError::InsufficientItems {
+                        player: player.clone(),
+                        item_name: name.to_string(),
+                        offered: qty,
+                        owned,
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Validate, then swap the offered items and shards between the two
+    /// inventories. On error neither inventory nor the trade is touched.
+    pub fn execute(
+        &mut self,
+        inv_a: &mut PlayerInventory,
+        inv_b: &mut PlayerInventory,
+    ) -> Result<(), TradeError> {
+        self.validate(inv_a, inv_b)?;
+
+        for item in &self.items_a {
+            inv_a.remove(&item.item_name, &item.rarity, item.quantity);
+            inv_b.add(item.clone());
+        }
+        for item in &self.items_b {
+            inv_b.remove(&item.item_name, &item.rarity, item.quantity);
+            inv_a.add(item.clone());
         }
+        inv_a.shards = (inv_a.shards - self.shards_a).saturating_add(self.shards_b);
+        inv_b.shards = (inv_b.shards - self.shards_b).saturating_add(self.shards_a);
+
         self.state = TradeState::Completed;
-        true
+        Ok(())
+    }
+}
+
+/// Why a trade could not be executed
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum TradeError {
+    /// One or both sides have not confirmed yet
+    NotConfirmed,
+    /// A side offered more than it holds (`item_name` is "shards" for currency)
+    InsufficientItems {
+        player: String,
+        item_name: String,
+        offered: u64,
+        owned: u64,
+    },
+    /// The trade record is inconsistent or doesn't match the inventories
+    Tampered { detail: String },
+}
+
+/// What a player holds, as far as trading is concerned
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PlayerInventory {
+    pub player_id: String,
+    pub shards: u64,
+    pub items: Vec<TradeItem>,
+}
+
+impl PlayerInventory {
+    /// Total held of an item, across stacks
+    pub fn quantity_of(&self, item_name: &str, rarity: &str) -> u64 {
+        self.items
+            .iter()
+            .filter(|i| i.item_name == item_name && i.rarity == rarity)
+            .map(|i| i.quantity as u64)
+            .sum()
+    }
+
+    /// Stack `item` onto a matching stack; whatever doesn't fit under
+    /// `u32::MAX` starts a new stack so nothing is lost
+    fn add(&mut self, mut item: TradeItem) {
+        if let Some(stack) = self
+            .items
+            .iter_mut()
+            .find(|i| i.item_name == item.item_name && i.rarity == item.rarity)
+        {
+            let moved = (u32::MAX - stack.quantity).min(item.quantity);
+            stack.quantity += moved;
+            item.quantity -= moved;
+        }
+        if item.quantity > 0 {
+            self.items.push(item);
+        }
+    }
+
+    fn remove(&mut self, item_name: &str, rarity: &str, mut quantity: u32) {
+        for stack in self
+            .items
+            .iter_mut()
+            .filter(|i| i.item_name == item_name && i.rarity == rarity)
+        {
+            let taken = stack.quantity.min(quantity);
+            stack.quantity -= taken;
+            quantity -= taken;
+        }
+        self.items.retain(|i| i.quantity > 0);
     }
 }
 
@@ -601,6 +1190,89 @@ mod tests {
         assert!(guild.max_members > 50); // expanded
     }
 
+    fn bank_guild() -> Guild {
+        let mut guild = Guild::new(
+            "g1".into(),
+            "Test".into(),
+            "TG".into(),
+            "lead".into(),
+            "Leader".into(),
+        );
+        guild.add_member("rec".into(), "Recruit".into());
+        guild.add_member("mem".into(), "Member".into());
+        guild.members[2].rank = GuildRank::Member;
+        guild
+    }
+
+    fn potions(quantity: u32) -> TradeItem {
+        TradeItem {
+            item_name: "Potion".into(),
+            quantity,
+            rarity: "Common".into(),
+        }
+    }
+
+    #[test]
+    fn test_guild_bank_created_on_demand() {
+        let mut guild = bank_guild();
+        assert!(guild.bank.is_none());
+        assert!(guild.bank_deposit("rec", 0, potions(10), 0));
+        assert!(guild.bank_deposit("mem", 0, potions(5), 0));
+        let bank = guild.bank.as_ref().unwrap();
+        assert_eq!(bank.tabs[0].items[0].quantity, 15);
+        assert_eq!(bank.log.len(), 2);
+        assert!(!guild.bank_deposit("stranger", 0, potions(1), 0));
+        assert!(!guild.bank_deposit("mem", 7, potions(1), 0));
+    }
+
+    #[test]
+    fn test_guild_bank_recruit_cannot_withdraw() {
+        let mut guild = bank_guild();
+        guild.bank_deposit("lead", 0, potions(10), 0);
+        assert!(!guild.bank_withdraw("rec", 0, "Potion", 1, 0));
+        assert!(guild.bank_withdraw("mem", 0, "Potion", 1, 0));
+        assert_eq!(guild.bank.unwrap().tabs[0].items[0].quantity, 9);
+    }
+
+    #[test]
+    fn test_guild_bank_daily_limit_resets() {
+        let mut guild = bank_guild();
+        guild.bank_deposit("lead", 0, potions(30), 0);
+        let day1 = 10 * SECS_PER_DAY + 100;
+
+        assert!(guild.bank_withdraw("mem", 0, "Potion", 4, day1));
+        // Member allowance is 5/day
+        assert!(!guild.bank_withdraw("mem", 0, "Potion", 2, day1 + 60));
+        assert!(guild.bank_withdraw("mem", 0, "Potion", 1, day1 + 60));
+        assert!(!guild.bank_withdraw("mem", 0, "Potion", 1, day1 + 3600));
+
+        let day2 = 11 * SECS_PER_DAY;
+        assert_eq!(guild.bank.as_ref().unwrap().withdrawn_today("mem", day2), 0);
+        assert!(guild.bank_withdraw("mem", 0, "Potion", 5, day2));
+        // Leader has no practical limit
+        assert!(guild.bank_withdraw("lead", 0, "Potion", 20, day2));
+        assert!(guild.bank.unwrap().tabs[0].items.is_empty());
+    }
+
+    #[test]
+    fn test_guild_bank_withdraw_needs_stock() {
+        let mut guild = bank_guild();
+        guild.bank_deposit("lead", 0, potions(2), 0);
+        assert!(!guild.bank_withdraw("lead", 0, "Potion", 3, 0));
+        assert!(!guild.bank_withdraw("lead", 0, "Elixir", 1, 0));
+    }
+
+    #[test]
+    fn test_guild_bank_deposit_rejects_stack_overflow() {
+        let mut guild = bank_guild();
+        assert!(guild.bank_deposit("lead", 0, potions(u32::MAX - 1), 0));
+        assert!(!guild.bank_deposit("lead", 0, potions(2), 0));
+        assert!(guild.bank_deposit("lead", 0, potions(1), 0));
+        let bank = guild.bank.unwrap();
+        assert_eq!(bank.tabs[0].items[0].quantity, u32::MAX);
+        assert_eq!(bank.log.len(), 2);
+    }
+
     // Party tests
     #[test]
     fn test_party_create() {
@@ -627,6 +1299,163 @@ mod tests {
         assert!(party.leader().unwrap().user_id == "u2");
     }
 
+    fn raid_party(prefix: &str, roles: [PartyRole; 3]) -> Party {
+        let mut party = Party::new(format!("{prefix}1"), "Leader".into());
+        for (i, role) in roles.into_iter().enumerate() {
+            party.add_member(format!("{prefix}{}", i + 2), "Member".into(), role);
+        }
+        party
+    }
+
+    #[test]
+    fn test_raid_add_party_up_to_cap() {
+        let mut raid = Raid::new(2);
+        let roles = [PartyRole::Striker; 3];
+        assert!(raid.add_party(raid_party("a", roles)));
+        // Same players can't join twice
+        assert!(!raid.add_party(raid_party("a", roles)));
+        assert!(raid.add_party(raid_party("b", roles)));
+        assert!(raid.is_full());
+        assert!(!raid.add_party(raid_party("c", roles)));
+        assert_eq!(raid.parties.len(), 2);
+        assert_eq!(raid.total_members(), 8);
+    }
+
+    #[test]
+    fn test_raid_composition_spans_parties() {
+        let mut raid = Raid::new(DEFAULT_RAID_PARTIES);
+        // Leaders are Strikers
+        raid.add_party(raid_party(
+            "a",
+            [PartyRole::Vanguard, PartyRole::Support, PartyRole::Striker],
+        ));
+        assert!(!raid.raid_composition().balanced);
+        raid.add_party(raid_party(
+            "b",
+            [
+                PartyRole::Tactician,
+                PartyRole::Support,
+                PartyRole::Tactician,
+            ],
+        ));
+
+        let comp = raid.raid_composition();
+        assert_eq!(comp.role_counts[&PartyRole::Striker], 3);
+        assert_eq!(comp.role_counts[&PartyRole::Vanguard], 1);
+        assert_eq!(comp.role_counts[&PartyRole::Support], 2);
+        assert_eq!(comp.role_counts[&PartyRole::Tactician], 2);
+        assert!(comp.balanced);
+        assert!((comp.healing_bonus - (0.2 + BALANCED_BONUS)).abs() < 0.001);
+        // Neither party alone covers every role
+        assert!(!raid.parties[0].composition().balanced);
+        assert!(!raid.parties[1].composition().balanced);
+    }
+
+    #[test]
+    fn test_composition_role_bonus_capped() {
+        let comp = CompositionBonus::from_roles([PartyRole::Striker; 20]);
+        assert_eq!(comp.damage_bonus, ROLE_BONUS_CAP);
+        assert_eq!(comp.damage_reduction, 0.0);
+    }
+
+    fn loot_party(mode: LootRule) -> Party {
+        let mut party = Party::new("u1".into(), "Leader".into());
+        party.add_member("u2".into(), "P2".into(), PartyRole::Striker);
+        party.add_member("u3".into(), "P3".into(), PartyRole::Support);
+        party.loot_rule = mode;
+        party
+    }
+
+    fn loot_item(rarity: &str) -> TradeItem {
+        TradeItem {
+            item_name: "Relic".into(),
+            quantity: 1,
+            rarity: rarity.into(),
+        }
+    }
+
+    fn rolls(entries: &[(&str, u32)]) -> Vec<(String, u32)> {
+        entries.iter().map(|(id, r)| (id.to_string(), *r)).collect()
+    }
+
+    #[test]
+    fn test_loot_free_for_all_first_member() {
+        let mut party = loot_party(LootRule::FreeForAll);
+        let r = rolls(&[("stranger", 99), ("u3", 1), ("u2", 50)]);
+        assert_eq!(party.assign_loot(&loot_item("Rare"), &r), "u3");
+        assert_eq!(party.assign_loot(&loot_item("Rare"), &r), "u3");
+    }
+
+    #[test]
+    fn test_loot_round_robin_wraps() {
+        let mut party = loot_party(LootRule::RoundRobin);
+        let item = loot_item("Rare");
+        let winners: Vec<String> = (0..5).map(|_| party.assign_loot(&item, &[])).collect();
+        assert_eq!(winners, vec!["u1", "u2", "u3", "u1", "u2"]);
+        assert_eq!(party.round_robin_cursor, 2);
+
+        // Cursor survives a member leaving
+        party.remove_member("u3");
+        assert_eq!(party.assign_loot(&item, &[]), "u1");
+    }
+
+    #[test]
+    fn test_loot_need_before_greed() {
+        let mut party = loot_party(LootRule::NeedBeforeGreed);
+        let item = loot_item("Epic");
+        let r = rolls(&[("u1", 95), ("u2", NEED_ROLL_BONUS + 3), ("u3", 0)]);
+        assert_eq!(party.assign_loot(&item, &r), "u2");
+        // Ties go to the earlier member; passes never win
+        let tie = rolls(&[("u3", 40), ("u2", 40), ("u1", 0)]);
+        assert_eq!(party.assign_loot(&item, &tie), "u2");
+        // Everyone passed: leader keeps it
+        assert_eq!(party.assign_loot(&item, &rolls(&[("u2", 0)])), "u1");
+    }
+
+    #[test]
+    fn test_loot_master_looter_routes_to_leader() {
+        let mut party = loot_party(LootRule::MasterLooter);
+        let r = rolls(&[("u2", NEED_ROLL_BONUS + 100)]);
+        assert_eq!(party.assign_loot(&loot_item("Legendary"), &r), "u1");
+        party.remove_member("u1");
+        assert_eq!(party.assign_loot(&loot_item("Legendary"), &r), "u2");
+    }
+
+    #[test]
+    fn test_loot_common_items_follow_mode_by_default() {
+        let mut party = loot_party(LootRule::MasterLooter);
+        let common = loot_item("Common");
+        assert_eq!(party.assign_loot(&common, &[]), "u1");
+        assert_eq!(party.assign_loot(&common, &[]), "u1");
+        assert_eq!(party.round_robin_cursor, 0);
+    }
+
+    #[test]
+    fn test_loot_common_items_round_robin_opt_in() {
+        let mut party = loot_party(LootRule::MasterLooter);
+        party.round_robin_commons = true;
+        let common = loot_item("Common");
+        assert_eq!(party.assign_loot(&common, &[]), "u1");
+        assert_eq!(party.assign_loot(&common, &[]), "u2");
+        // Non-common items still go to the master looter
+        assert_eq!(party.assign_loot(&loot_item("Rare"), &[]), "u1");
+    }
+
+    #[test]
+    fn test_party_json_keeps_loot_rule_key() {
+        let mut json = serde_json::to_value(Party::new("u1".into(), "L".into())).unwrap();
+        assert_eq!(json["loot_rule"], "NeedBeforeGreed");
+
+        // Parties saved before the round-robin fields existed still load
+        let obj = json.as_object_mut().unwrap();
+        obj.remove("round_robin_cursor");
+        obj.remove("round_robin_commons");
+        obj.insert("loot_rule".into(), serde_json::json!("RoundRobin"));
+        let party: Party = serde_json::from_value(json).unwrap();
+        assert_eq!(party.loot_rule, LootRule::RoundRobin);
+        assert_eq!(party.round_robin_cursor, 0);
+    }
+
     // Friends tests
     #[test]
     fn test_friend_list() {
@@ -672,10 +1501,117 @@ mod tests {
         trade.confirm("u2");
         assert_eq!(trade.state, TradeState::Confirmed);
 
-        assert!(trade.execute());
+        let (mut inv_a, mut inv_b) = trade_inventories();
+        assert_eq!(trade.execute(&mut inv_a, &mut inv_b), Ok(()));
         assert_eq!(trade.state, TradeState::Completed);
     }
 
+    fn trade_inventories() -> (PlayerInventory, PlayerInventory) {
+        let inv_a = PlayerInventory {
+            player_id: "u1".into(),
+            shards: 0,
+            items: vec![TradeItem {
+                item_name: "Sword".into(),
+                quantity: 1,
+                rarity: "Rare".into(),
+            }],
+        };
+        let inv_b = PlayerInventory {
+            player_id: "u2".into(),
+            shards: 800,
+            items: vec![],
+        };
+        (inv_a, inv_b)
+    }
+
+    fn confirmed_trade(sword_qty: u32) -> Trade {
+        let mut trade = Trade::new("u1".into(), "u2".into());
+        trade.add_item(
+            "u1",
+            TradeItem {
+                item_name: "Sword".into(),
+                quantity: sword_qty,
+                rarity: "Rare".into(),
+            },
+        );
+        trade.set_shards("u2", 500);
+        trade.lock("u1");
+        trade.lock("u2");
+        trade.confirm("u1");
+        trade.confirm("u2");
+        trade
+    }
+
+    #[test]
+    fn test_trade_execute_moves_items_and_shards() {
+        let mut trade = confirmed_trade(1);
+        let (mut inv_a, mut inv_b) = trade_inventories();
+        assert_eq!(trade.execute(&mut inv_a, &mut inv_b), Ok(()));
+
+        assert!(inv_a.items.is_empty());
+        assert_eq!(inv_a.shards, 500);
+        assert_eq!(inv_b.quantity_of("Sword", "Rare"), 1);
+        assert_eq!(inv_b.shards, 300);
+        // Executing twice is rejected
+        assert!(matches!(
+            trade.execute(&mut inv_a, &mut inv_b),
+            Err(TradeError::Tampered { .. })
+        ));
+    }
+
+    #[test]
+    fn test_trade_receiving_full_stack_does_not_overflow() {
+        let mut trade = confirmed_trade(1);
+        let (mut inv_a, mut inv_b) = trade_inventories();
+        inv_b.items.push(TradeItem {
+            item_name: "Sword".into(),
+            quantity: u32::MAX,
+            rarity: "Rare".into(),
+        });
+        assert_eq!(trade.execute(&mut inv_a, &mut inv_b), Ok(()));
+        assert_eq!(inv_b.quantity_of("Sword", "Rare"), u32::MAX as u64 + 1);
+        assert_eq!(inv_b.items.len(), 2);
+    }
+
+    #[test]
+    fn test_trade_over_offer_rejected() {
+        let mut trade = confirmed_trade(3);
+        let (mut inv_a, mut inv_b) = trade_inventories();
+        assert_eq!(
+            trade.execute(&mut inv_a, &mut inv_b),
+            Err(TradeError::InsufficientItems {
+                player: "u1".into(),
+                item_name: "Sword".into(),
+                offered: 3,
+                owned: 1,
+            })
+        );
+        assert_eq!(trade.state, TradeState::Confirmed);
+        assert_eq!(inv_a.quantity_of("Sword", "Rare"), 1);
+        assert_eq!(inv_b.shards, 800);
+    }
+
+    #[test]
+    fn test_trade_unconfirmed_side_rejected() {
+        let mut trade = Trade::new("u1".into(), "u2".into());
+        trade.set_shards("u2", 500);
+        trade.lock("u1");
+        trade.lock("u2");
+        trade.confirm("u1");
+        let (mut inv_a, mut inv_b) = trade_inventories();
+        assert_eq!(
+            trade.execute(&mut inv_a, &mut inv_b),
+            Err(TradeError::NotConfirmed)
+        );
+
+        // Flipping the state by hand without the flags is caught
+        trade.state = TradeState::Confirmed;
+        assert!(matches!(
+            trade.validate(&inv_a, &inv_b),
+            Err(TradeError::Tampered { .. })
+        ));
+    }
+
     #[test]
     fn test_trade_cancel() {
         let mut trade = Trade::new("u1".into(), "u2".into());
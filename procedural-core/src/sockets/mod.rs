@@ -8,6 +8,8 @@
 //! Socket types: Offensive (red), Defensive (blue), Utility (yellow), Prismatic (any)
 //! Gems provide stat bonuses. Runes provide equipment-like effects.
 
+use std::collections::BTreeMap;
+
 use serde::{Deserialize, Serialize};
 
 /// Socket color — determines what can be inserted
@@ -175,7 +177,11 @@ pub enum GemBonus {
 
 impl GemBonus {
     pub fn scaled(&self, tier: GemTier) -> GemBonus {
-        let mult = tier.multiplier();
+        self.multiplied(tier.multiplier())
+    }
+
+    /// Same bonus with its magnitude multiplied by `mult`
+    pub fn multiplied(&self, mult: f32) -> GemBonus {
         match self {
             Self::AttackPower(v) => Self::AttackPower(v * mult),
             Self::CriticalChance(v) => Self::CriticalChance(v * mult),
@@ -235,6 +241,50 @@ pub enum RuneEffect {
     ExecuteDamage { threshold: f32, bonus_percent: f32 },
 }
 
+/// Extra gem strength from sitting in a Prismatic socket
+pub const PRISMATIC_SOCKET_BONUS: f32 = 0.10;
+/// Extra gem strength when every socket is filled with one color
+pub const MATCHED_COLOR_BONUS: f32 = 0.25;
+
+/// Combined effect of everything socketed into one piece of equipment
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SocketEffects {
+    pub attack_power: f32,
+    pub critical_chance: f32,
+    pub max_hp: f32,
+    pub defense: f32,
+    pub cooldown_reduction: f32,
+    pub resource_regen: f32,
+    pub movement_speed: f32,
+    /// Per element
+    pub elemental_damage: BTreeMap<String, f32>,
+    /// Per element
+    pub elemental_resist: BTreeMap<String, f32>,
+    pub rune_effects: Vec<RuneEffect>,
+    /// Whether the matched-color bonus applied
+    pub color_matched: bool,
+}
+
+impl SocketEffects {
+    fn add_gem(&mut self, bonus: &GemBonus, mult: f32) {
+        match bonus {
+            GemBonus::AttackPower(v) => self.attack_power += v * mult,
+            GemBonus::CriticalChance(v) => self.critical_chance += v * mult,
+            GemBonus::ElementalDamage { element, amount } => {
+                *self.elemental_damage.entry(element.clone()).or_default() += amount * mult
+            }
+            GemBonus::MaxHp(v) => self.max_hp += v * mult,
+            GemBonus::Defense(v) => self.defense += v * mult,
+            GemBonus::ElementalResist { element, amount } => {
+                *self.elemental_resist.entry(element.clone()).or_default() += amount * mult
+            }
+            GemBonus::CooldownReduction(v) => self.cooldown_reduction += v * mult,
+            GemBonus::ResourceRegen(v) => self.resource_regen += v * mult,
+            GemBonus::MovementSpeed(v) => self.movement_speed += v * mult,
+        }
+    }
+}
+
 /// Equipment socket configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SocketedEquipment {
@@ -301,6 +351,52 @@ impl SocketedEquipment {
             .collect()
     }
 
+    /// True when there are 2+ sockets, all filled, all with the same color content
+    pub fn colors_matched(&self) -> bool {
+        let mut colors = self
+            .sockets
+            .iter()
+            .map(|s| s.content.as_ref().map(|c| c.color()));
+        match colors.next() {
+            Some(Some(first)) => self.sockets.len() >= 2 && colors.all(|c| c == Some(first)),
+            _ => false,
+        }
+    }
+
+    /// Sum everything socketed into this piece.
+    ///
+    /// Gem bonuses are tier-scaled, then raised by `PRISMATIC_SOCKET_BONUS` in a
+    /// Prismatic socket and by `MATCHED_COLOR_BONUS` when `colors_matched`.
+    /// Rune effects are collected as-is. Empty sockets contribute nothing.
+    pub fn aggregate_effects(&self) -> SocketEffects {
+        let color_matched = self.colors_matched();
+        let match_mult = if color_matched {
+            1.0 + MATCHED_COLOR_BONUS
+        } else {
+            1.0
+        };
+
+        let mut effects = SocketEffects {
+            color_matched,
+            ..Default::default()
+        };
+        for socket in &self.sockets {
+            match &socket.content {
+                Some(SocketContent::Gem(g)) => {
+                    let prismatic_mult = if socket.color == SocketColor::Prismatic {
+                        1.0 + PRISMATIC_SOCKET_BONUS
+                    } else {
+                        1.0
+                    };
+                    effects.add_gem(&g.bonus.scaled(g.tier), prismatic_mult * match_mult);
+                }
+                Some(SocketContent::Rune(r)) => effects.rune_effects.push(r.effect.clone()),
+                None => {}
+            }
+        }
+        effects
+    }
+
     /// Add a socket (from armorsmith specialization)
     pub fn add_socket(&mut self, color: SocketColor) -> bool {
         if self.sockets.len() >= 4 {
@@ -341,6 +437,54 @@ pub fn combine_gems(gems: &[Gem; 3]) -> Option<Gem> {
     })
 }
 
+/// Base-value multiplier of a gem produced by a partial combine
+pub const PARTIAL_COMBINE_QUALITY: f32 = 0.75;
+
+/// Result of `combine_gems_risky`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum CombineOutcome {
+    /// Next-tier gem, full quality
+    Success(Gem),
+    /// Next-tier gem with a weakened bonus
+    Partial(Gem),
+    /// Nothing created; one input gem is lost and the other two come back
+    Failure { returned: Vec<Gem> },
+    /// The gems can't be combined at all; nothing is consumed
+    Rejected,
+}
+
+/// (failure, partial) chances for combining gems of `tier`; both grow with tier
+pub fn combine_risk(tier: GemTier) -> (f32, f32) {
+    let step = tier as u32 as f32;
+    (step * 0.08, 0.10 + step * 0.05)
+}
+
+/// `combine_gems` with a chance to fail or produce a weaker gem.
+///
+/// `roll` picks the outcome against `combine_risk` of the input tier, so the
+/// same roll always gives the same result. Inputs that `combine_gems` would
+/// reject are `Rejected` without consuming anything.
+pub fn combine_gems_risky(gems: &[Gem; 3], roll: u64) -> CombineOutcome {
+    let Some(combined) = combine_gems(gems) else {
+        return CombineOutcome::Rejected;
+    };
+
+    let (failure, partial) = combine_risk(gems[0].tier);
+    let r = (roll % 10000) as f32 / 10000.0;
+    if r < failure {
+        CombineOutcome::Failure {
+            returned: gems[1..].to_vec(),
+        }
+    } else if r < failure + partial {
+        CombineOutcome::Partial(Gem {
+            bonus: combined.bonus.multiplied(PARTIAL_COMBINE_QUALITY),
+            ..combined
+        })
+    } else {
+        CombineOutcome::Success(combined)
+    }
+}
+
 /// Predefined gems
 pub fn starter_gems() -> Vec<Gem> {
     vec![
@@ -571,6 +715,78 @@ mod tests {
         assert_eq!(bonuses.len(), 2);
     }
 
+    fn ruby() -> Gem {
+        Gem {
+            id: "ruby".into(),
+            name: "Ruby".into(),
+            color: SocketColor::Red,
+            tier: GemTier::Chipped,
+            bonus: GemBonus::AttackPower(2.0),
+        }
+    }
+
+    #[test]
+    fn test_aggregate_matched_colors() {
+        let mut equip =
+            SocketedEquipment::new("test".into(), vec![SocketColor::Red, SocketColor::Red]);
+        equip.insert_at(0, SocketContent::Gem(ruby())).unwrap();
+        equip.insert_at(1, SocketContent::Gem(ruby())).unwrap();
+
+        let effects = equip.aggregate_effects();
+        assert!(effects.color_matched);
+        let expected = 4.0 * (1.0 + MATCHED_COLOR_BONUS);
+        assert!((effects.attack_power - expected).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_aggregate_mixed_colors_no_match_bonus() {
+        let mut equip =
+            SocketedEquipment::new("test".into(), vec![SocketColor::Red, SocketColor::Blue]);
+        let sapphire = Gem {
+            id: "sapphire".into(),
+            name: "Sapphire".into(),
+            color: SocketColor::Blue,
+            tier: GemTier::Flawed,
+            bonus: GemBonus::MaxHp(10.0),
+        };
+        equip.insert_at(0, SocketContent::Gem(ruby())).unwrap();
+        equip.insert_at(1, SocketContent::Gem(sapphire)).unwrap();
+
+        let effects = equip.aggregate_effects();
+        assert!(!effects.color_matched);
+        assert!((effects.attack_power - 2.0).abs() < 1e-5);
+        assert!((effects.max_hp - 20.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_aggregate_prismatic_bonus() {
+        let mut equip = SocketedEquipment::new(
+            "test".into(),
+            vec![SocketColor::Prismatic, SocketColor::Blue],
+        );
+        equip.insert_at(0, SocketContent::Gem(ruby())).unwrap();
+
+        let effects = equip.aggregate_effects();
+        assert!(!effects.color_matched, "an empty socket breaks the match");
+        let expected = 2.0 * (1.0 + PRISMATIC_SOCKET_BONUS);
+        assert!((effects.attack_power - expected).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_aggregate_empty_sockets_sum_to_zero() {
+        let equip = SocketedEquipment::new(
+            "test".into(),
+            vec![SocketColor::Red, SocketColor::Blue, SocketColor::Prismatic],
+        );
+        let effects = equip.aggregate_effects();
+        assert!(!effects.color_matched);
+        assert_eq!(effects.attack_power, 0.0);
+        assert_eq!(effects.max_hp, 0.0);
+        assert_eq!(effects.defense, 0.0);
+        assert!(effects.elemental_damage.is_empty());
+        assert!(effects.rune_effects.is_empty());
+    }
+
     #[test]
     fn test_rune_effects_collection() {
         let mut equip = SocketedEquipment::new("test".into(), vec![SocketColor::Red]);
@@ -653,6 +869,72 @@ mod tests {
         assert!(combine_gems(&gems).is_none());
     }
 
+    fn gems_of(tier: GemTier) -> [Gem; 3] {
+        let gem = Gem { tier, ..ruby() };
+        [gem.clone(), gem.clone(), gem]
+    }
+
+    fn attack_power(gem: &Gem) -> f32 {
+        match gem.bonus {
+            GemBonus::AttackPower(v) => v,
+            _ => panic!("expected attack power"),
+        }
+    }
+
+    #[test]
+    fn test_risky_combine_deterministic() {
+        let gems = gems_of(GemTier::Regular);
+        for roll in 0..200u64 {
+            let a = serde_json::to_string(&combine_gems_risky(&gems, roll)).unwrap();
+            let b = serde_json::to_string(&combine_gems_risky(&gems, roll)).unwrap();
+            assert_eq!(a, b);
+        }
+    }
+
+    #[test]
+    fn test_risky_combine_failure_only_consumes() {
+        for tier in [GemTier::Flawed, GemTier::Regular, GemTier::Perfect] {
+            let gems = gems_of(tier);
+            let mut failures = 0;
+            for roll in 0..10000u64 {
+                match combine_gems_risky(&gems, roll) {
+                    CombineOutcome::Failure { returned } => {
+                        failures += 1;
+                        assert_eq!(returned.len(), 2);
+                        assert!(returned.iter().all(|g| g.tier == tier));
+                    }
+                    CombineOutcome::Partial(g) => {
+                        assert_eq!(Some(g.tier), tier.next_tier());
+                        assert!(attack_power(&g) < attack_power(&gems[0]) + 1e-5);
+                    }
+                    CombineOutcome::Success(g) => assert_eq!(Some(g.tier), tier.next_tier()),
+                    CombineOutcome::Rejected => panic!("matching gems should combine"),
+                }
+            }
+            assert!(failures > 0);
+        }
+    }
+
+    #[test]
+    fn test_higher_tiers_are_riskier() {
+        let count_failures = |tier| {
+            let gems = gems_of(tier);
+            (0..10000u64)
+                .filter(|&r| matches!(combine_gems_risky(&gems, r), CombineOutcome::Failure { .. }))
+                .count()
+        };
+        assert_eq!(count_failures(GemTier::Chipped), 0);
+        assert!(count_failures(GemTier::Flawless) > count_failures(GemTier::Flawed));
+    }
+
+    #[test]
+    fn test_risky_combine_rejects_radiant() {
+        assert!(matches!(
+            combine_gems_risky(&gems_of(GemTier::Radiant), 0),
+            CombineOutcome::Rejected
+        ));
+    }
+
     #[test]
     fn test_max_4_sockets() {
         let mut equip = SocketedEquipment::new(
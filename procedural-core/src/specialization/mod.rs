@@ -6,10 +6,13 @@
 //!
 //! Specializations unlock at Expert tier in a mastery domain.
 //! Each domain has 2-3 branches — you pick ONE per domain.
+//! At most `DEFAULT_ROLE_CAP` branches may share a role affinity.
 //! Branches define playstyle: offensive, defensive, utility.
 //! Roles emerge from specialization choices, not rigid class selection.
 
+use crate::combat::Thorns;
 use crate::mastery::{MasteryDomain, MasteryProfile, MasteryTier};
+use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -100,6 +103,8 @@ pub enum SpecPassive {
     AggroModifier(f32),
     /// Party-wide buff radius
     AuraRadius(f32),
+    /// Share of melee damage taken reflected to the attacker
+    Thorns(f32),
 }
 
 /// Ultimate ability unlocked via specialization
@@ -149,8 +154,28 @@ pub enum UltimateEffect {
     },
 }
 
+/// Whether a player's ultimates share a cooldown
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum CooldownPolicy {
+    /// Each ultimate tracks only its own cooldown
+    Independent,
+    /// Using any ultimate locks all ultimates for `lockout` seconds
+    SharedGlobal { lockout: f32 },
+}
+
+/// Global lockout between ultimates under `CooldownPolicy::SharedGlobal`
+pub const SHARED_ULTIMATE_LOCKOUT: f32 = 30.0;
+
+/// Default max branches a player may hold with the same role affinity
+pub const DEFAULT_ROLE_CAP: u32 = 3;
+
+/// Shard cost of a player's first paid respec; each later one doubles it
+pub const RESPEC_BASE_COST: u64 = 100;
+/// Respecs after which the cost stops doubling
+const RESPEC_COST_MAX_DOUBLINGS: u32 = 10;
+
 /// Player's specialization choices
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Component, Debug, Clone, Default, Serialize, Deserialize)]
 pub struct SpecializationProfile {
     /// Chosen branch per domain (domain → branch_id)
     pub chosen_branches: HashMap<MasteryDomain, String>,
@@ -158,6 +183,12 @@ pub struct SpecializationProfile {
     pub primary_role: Option<CombatRole>,
     /// Secondary role
     pub secondary_role: Option<CombatRole>,
+    /// Max branches per role affinity (None = `DEFAULT_ROLE_CAP`)
+    #[serde(default)]
+    pub role_cap: Option<u32>,
+    /// Paid respecs done so far; drives `respec_cost`
+    #[serde(default)]
+    pub respec_count: u32,
 }
 
 impl SpecializationProfile {
@@ -165,6 +196,16 @@ impl SpecializationProfile {
         Self::default()
     }
 
+    /// Override how many branches of one role affinity may be held
+    pub fn with_role_cap(mut self, cap: u32) -> Self {
+        self.role_cap = Some(cap);
+        self
+    }
+
+    pub fn role_cap(&self) -> u32 {
+        self.role_cap.unwrap_or(DEFAULT_ROLE_CAP)
+    }
+
     /// Choose a specialization branch for a domain
     pub fn choose_branch(
         &mut self,
@@ -185,6 +226,19 @@ impl SpecializationProfile {
             return Err(SpecError::AlreadySpecialized(branch.domain));
         }
 
+        // Cap same-role stacking so hybrids keep a distinct identity
+        let held = self
+            .role_counts()
+            .get(&branch.role_affinity)
+            .copied()
+            .unwrap_or(0);
+        if held >= self.role_cap() {
+            return Err(SpecError::RoleCapExceeded {
+                role: branch.role_affinity,
+                cap: self.role_cap(),
+            });
+        }
+
         self.chosen_branches
             .insert(branch.domain, branch.id.clone());
         self.recalculate_roles();
@@ -201,6 +255,35 @@ impl SpecializationProfile {
         }
     }
 
+    /// Shard cost of the next paid respec
+    pub fn respec_cost(&self) -> u64 {
+        RESPEC_BASE_COST << self.respec_count.min(RESPEC_COST_MAX_DOUBLINGS)
+    }
+
+    /// `reset_branch` for shards: deducts `respec_cost` and bumps the counter.
+    /// Nothing is charged if the domain has no branch or shards fall short.
+    pub fn reset_branch_paid(
+        &mut self,
+        domain: MasteryDomain,
+        shards: &mut u64,
+    ) -> Result<(), RespecError> {
+        if !self.has_specialization(domain) {
+            return Err(RespecError::NotSpecialized(domain));
+        }
+        let cost = self.respec_cost();
+        if *shards < cost {
+            return Err(RespecError::InsufficientShards {
+                cost,
+                available: *shards,
+            });
+        }
+
+        *shards -= cost;
+        self.respec_count += 1;
+        self.reset_branch(domain);
+        Ok(())
+    }
+
     /// Check if player has specialized in a domain
     pub fn has_specialization(&self, domain: MasteryDomain) -> bool {
         self.chosen_branches.contains_key(&domain)
@@ -211,8 +294,8 @@ impl SpecializationProfile {
         self.chosen_branches.get(&domain).map(|s| s.as_str())
     }
 
-    /// Recalculate primary/secondary roles from branch affinities
-    fn recalculate_roles(&mut self) {
+    /// Number of chosen branches per role affinity
+    pub fn role_counts(&self) -> HashMap<CombatRole, u32> {
         let all_branches = all_specialization_branches();
         let mut role_counts: HashMap<CombatRole, u32> = HashMap::new();
 
@@ -221,9 +304,17 @@ impl SpecializationProfile {
                 *role_counts.entry(branch.role_affinity).or_insert(0) += 1;
             }
         }
+        role_counts
+    }
 
-        let mut sorted: Vec<_> = role_counts.into_iter().collect();
-        sorted.sort_by(|a, b| b.1.cmp(&a.1));
+    /// Recalculate primary/secondary roles from branch affinities
+    fn recalculate_roles(&mut self) {
+        let mut sorted: Vec<_> = self.role_counts().into_iter().collect();
+        // Ties break by role name so the result doesn't depend on map order
+        sorted.sort_by(|a, b| {
+            b.1.cmp(&a.1)
+                .then_with(|| a.0.display_name().cmp(b.0.display_name()))
+        });
 
         self.primary_role = sorted.first().map(|(r, _)| *r);
         self.secondary_role = sorted.get(1).map(|(r, _)| *r);
@@ -242,6 +333,32 @@ impl SpecializationProfile {
         passives
     }
 
+    /// Mastery tier floors for XP decay: a specialized domain can't decay
+    /// below the tier its chosen branch requires
+    pub fn decay_floors(&self) -> HashMap<MasteryDomain, MasteryTier> {
+        let all_branches = all_specialization_branches();
+        self.chosen_branches
+            .iter()
+            .filter_map(|(domain, branch_id)| {
+                all_branches
+                    .iter()
+                    .find(|b| b.id == *branch_id)
+                    .map(|b| (*domain, b.required_tier))
+            })
+            .collect()
+    }
+
+    /// Total thorns share from chosen branches (fed into `combat::Thorns`)
+    pub fn thorns_pct(&self) -> f32 {
+        self.active_passives()
+            .iter()
+            .map(|p| match p {
+                SpecPassive::Thorns(pct) => *pct,
+                _ => 0.0,
+            })
+            .sum()
+    }
+
     /// Collect all ultimate abilities from chosen branches
     pub fn ultimate_abilities(&self) -> Vec<UltimateAbility> {
         let all_branches = all_specialization_branches();
@@ -257,6 +374,28 @@ impl SpecializationProfile {
         ults
     }
 
+    /// Cooldown sharing rule for this profile's ultimates.
+    /// Two or more ultimates from branches with the same role affinity would
+    /// stack the same kind of burst, so they share a global lockout.
+    pub fn ultimate_cooldown_policy(&self) -> CooldownPolicy {
+        let all_branches = all_specialization_branches();
+        let mut roles: Vec<CombatRole> = Vec::new();
+
+        for branch_id in self.chosen_branches.values() {
+            if let Some(branch) = all_branches.iter().find(|b| b.id == *branch_id) {
+                if branch.ultimate.is_some() {
+                    if roles.contains(&branch.role_affinity) {
+                        return CooldownPolicy::SharedGlobal {
+                            lockout: SHARED_ULTIMATE_LOCKOUT,
+                        };
+                    }
+                    roles.push(branch.role_affinity);
+                }
+            }
+        }
+        CooldownPolicy::Independent
+    }
+
     pub fn to_json(&self) -> String {
         serde_json::to_string(self).unwrap_or_default()
     }
@@ -269,6 +408,17 @@ pub enum SpecError {
         current: MasteryTier,
     },
     AlreadySpecialized(MasteryDomain),
+    RoleCapExceeded {
+        role: CombatRole,
+        cap: u32,
+    },
+}
+
+/// Why a paid respec was refused
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum RespecError {
+    NotSpecialized(MasteryDomain),
+    InsufficientShards { cost: u64, available: u64 },
 }
 
 /// Synergy between two specialization branches
@@ -281,6 +431,29 @@ pub struct Synergy {
     pub bonus: SpecPassive,
 }
 
+/// Endgame synergy requiring three specialization branches
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TriSynergy {
+    pub branch_a: String,
+    pub branch_b: String,
+    pub branch_c: String,
+    pub name: String,
+    pub description: String,
+    pub bonus: SpecPassive,
+}
+
+impl TriSynergy {
+    pub fn branches(&self) -> [&str; 3] {
+        [&self.branch_a, &self.branch_b, &self.branch_c]
+    }
+
+    /// Does this tri-synergy include both branches of a pair synergy?
+    fn subsumes(&self, pair: &Synergy) -> bool {
+        let branches = self.branches();
+        branches.contains(&pair.branch_a.as_str()) && branches.contains(&pair.branch_b.as_str())
+    }
+}
+
 /// All predefined specialization branches
 pub fn all_specialization_branches() -> Vec<SpecializationBranch> {
     vec![
@@ -316,6 +489,7 @@ pub fn all_specialization_branches() -> Vec<SpecializationBranch> {
             passives: vec![
                 SpecPassive::DefensePercent(0.20),
                 SpecPassive::AggroModifier(1.5),
+                SpecPassive::Thorns(0.15),
             ],
             ultimate: Some(UltimateAbility {
                 id: "ult_guardian_stance".into(),
@@ -325,6 +499,27 @@ pub fn all_specialization_branches() -> Vec<SpecializationBranch> {
                 effect: UltimateEffect::Invulnerable { duration: 5.0 },
             }),
         },
+        SpecializationBranch {
+            id: "sword_spellblade".into(),
+            name: "Spellblade".into(),
+            domain: MasteryDomain::SwordMastery,
+            description: "Utility sword style. Channels semantic energy through the blade for faster ability rotation.".into(),
+            required_tier: MasteryTier::Expert,
+            role_affinity: CombatRole::Specialist,
+            passives: vec![
+                SpecPassive::CooldownReduction(0.15),
+                SpecPassive::ResourceReduction(0.10),
+            ],
+            ultimate: Some(UltimateAbility {
+                id: "ult_runic_edge".into(),
+                name: "Runic Edge".into(),
+                description: "Imbue the blade for 8 seconds: every strike resets one ability cooldown by 1 second.".into(),
+                cooldown_seconds: 100.0,
+                effect: UltimateEffect::PartyBuff {
+                    stat: "cooldown_recovery".into(), amount: 0.25, duration: 8.0,
+                },
+            }),
+        },
 
         // === Staff Mastery Branches ===
         SpecializationBranch {
@@ -367,6 +562,27 @@ pub fn all_specialization_branches() -> Vec<SpecializationBranch> {
                 effect: UltimateEffect::TeamHeal { amount: 0.5, duration: 8.0 },
             }),
         },
+        SpecializationBranch {
+            id: "staff_chronomancer".into(),
+            name: "Chronomancer".into(),
+            domain: MasteryDomain::StaffMastery,
+            description: "Utility magic. Bends time to slow enemies and extend allied buffs.".into(),
+            required_tier: MasteryTier::Expert,
+            role_affinity: CombatRole::Specialist,
+            passives: vec![
+                SpecPassive::BuffDurationIncrease(0.25),
+                SpecPassive::CooldownReduction(0.10),
+            ],
+            ultimate: Some(UltimateAbility {
+                id: "ult_stasis_field".into(),
+                name: "Stasis Field".into(),
+                description: "Warp time in a wide radius, slowing all enemies to a crawl for 6 seconds.".into(),
+                cooldown_seconds: 140.0,
+                effect: UltimateEffect::TimeDistortion {
+                    radius: 18.0, slow_factor: 0.2, duration: 6.0,
+                },
+            }),
+        },
 
         // === Gauntlet Mastery Branches ===
         SpecializationBranch {
@@ -410,6 +626,28 @@ pub fn all_specialization_branches() -> Vec<SpecializationBranch> {
                 effect: UltimateEffect::MassTaunt { duration: 8.0, radius: 20.0 },
             }),
         },
+        SpecializationBranch {
+            id: "gauntlet_grappler".into(),
+            name: "Grappler".into(),
+            domain: MasteryDomain::GauntletMastery,
+            description: "Utility brawling. Throws, pulls, and repositioning to control the fight.".into(),
+            required_tier: MasteryTier::Expert,
+            role_affinity: CombatRole::Specialist,
+            passives: vec![
+                SpecPassive::MoveSpeed(0.10),
+                SpecPassive::CritChance(0.05),
+                SpecPassive::AggroModifier(0.8),
+            ],
+            ultimate: Some(UltimateAbility {
+                id: "ult_earthshaker".into(),
+                name: "Earthshaker Slam".into(),
+                description: "Seize the nearest enemy and slam it down, staggering everything nearby.".into(),
+                cooldown_seconds: 90.0,
+                effect: UltimateEffect::AoeBurst {
+                    radius: 8.0, damage: 250.0, element: "earth".into(),
+                },
+            }),
+        },
 
         // === Parry Mastery Branches ===
         SpecializationBranch {
@@ -581,6 +819,37 @@ pub fn branch_synergies() -> Vec<Synergy> {
     ]
 }
 
+/// Predefined three-branch synergies
+pub fn tri_synergies() -> Vec<TriSynergy> {
+    vec![
+        TriSynergy {
+            branch_a: "sword_bladestorm".into(),
+            branch_b: "parry_riposte".into(),
+            branch_c: "dodge_shadow".into(),
+            name: "Perfect Execution".into(),
+            description: "Perfect parries and dodges both guarantee a critical combo finisher."
+                .into(),
+            bonus: SpecPassive::DamagePercent(0.30),
+        },
+        TriSynergy {
+            branch_a: "sword_guardian".into(),
+            branch_b: "gauntlet_ironwall".into(),
+            branch_c: "parry_bulwark".into(),
+            name: "Living Bulwark".into(),
+            description: "While taunting, allies behind you take 30% less damage.".into(),
+            bonus: SpecPassive::DefensePercent(0.25),
+        },
+        TriSynergy {
+            branch_a: "staff_mender".into(),
+            branch_b: "alchemy_herbalist".into(),
+            branch_c: "dodge_windwalker".into(),
+            name: "Lifebinder".into(),
+            description: "Dodging through allies leaves a healing trail.".into(),
+            bonus: SpecPassive::HealEffectiveness(0.35),
+        },
+    ]
+}
+
 /// Get active synergies for a specialization profile
 pub fn active_synergies(profile: &SpecializationProfile) -> Vec<&Synergy> {
     let synergies = branch_synergies();
@@ -598,21 +867,53 @@ pub fn active_synergies(profile: &SpecializationProfile) -> Vec<&Synergy> {
     vec![]
 }
 
-/// Check which synergies are active for given chosen branches
+/// Check which pair synergies are active for given chosen branches.
+/// Pairs already covered by an active tri-synergy are left out so the
+/// same branches aren't rewarded twice.
 pub fn find_active_synergies(chosen_branch_ids: &[String]) -> Vec<Synergy> {
+    let tris = find_active_tri_synergies(chosen_branch_ids);
     branch_synergies()
         .into_iter()
         .filter(|syn| {
             chosen_branch_ids.iter().any(|b| b == &syn.branch_a)
                 && chosen_branch_ids.iter().any(|b| b == &syn.branch_b)
         })
+        .filter(|syn| !tris.iter().any(|tri| tri.subsumes(syn)))
+        .collect()
+}
+
+/// Check which tri-synergies have all three branches chosen
+pub fn find_active_tri_synergies(chosen_branch_ids: &[String]) -> Vec<TriSynergy> {
+    tri_synergies()
+        .into_iter()
+        .filter(|tri| {
+            tri.branches()
+                .iter()
+                .all(|id| chosen_branch_ids.iter().any(|b| b == id))
+        })
         .collect()
 }
 
-/// Bevy plugin stub
+/// Keep each entity's `Thorns` in step with its specialization passives
+pub fn sync_thorns(
+    mut commands: Commands,
+    query: Query<(Entity, &SpecializationProfile), Changed<SpecializationProfile>>,
+) {
+    for (entity, spec) in &query {
+        let pct = spec.thorns_pct();
+        if pct > 0.0 {
+            commands.entity(entity).insert(Thorns { pct });
+        } else {
+            commands.entity(entity).remove::<Thorns>();
+        }
+    }
+}
+
 pub struct SpecializationPlugin;
-impl bevy::prelude::Plugin for SpecializationPlugin {
-    fn build(&self, _app: &mut bevy::prelude::App) {}
+impl Plugin for SpecializationPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, sync_thorns);
+    }
 }
 
 #[cfg(test)]
@@ -636,13 +937,86 @@ mod tests {
             .iter()
             .filter(|b| b.domain == MasteryDomain::SwordMastery)
             .collect();
-        assert_eq!(sword.len(), 2, "Sword should have 2 branches");
+        assert_eq!(sword.len(), 3, "Sword should have 3 branches");
 
         let staff: Vec<_> = branches
             .iter()
             .filter(|b| b.domain == MasteryDomain::StaffMastery)
             .collect();
-        assert_eq!(staff.len(), 2, "Staff should have 2 branches");
+        assert_eq!(staff.len(), 3, "Staff should have 3 branches");
+    }
+
+    #[test]
+    fn test_third_branches_are_specialist() {
+        let branches = all_specialization_branches();
+        for id in [
+            "sword_spellblade",
+            "staff_chronomancer",
+            "gauntlet_grappler",
+        ] {
+            let branch = branches.iter().find(|b| b.id == id).unwrap();
+            assert_eq!(branch.role_affinity, CombatRole::Specialist);
+            assert!(branch.ultimate.is_some());
+        }
+        let gauntlet = branches
+            .iter()
+            .filter(|b| b.domain == MasteryDomain::GauntletMastery)
+            .count();
+        assert_eq!(gauntlet, 3);
+    }
+
+    #[test]
+    fn test_role_cap_blocks_stacking() {
+        let mut spec = SpecializationProfile::new().with_role_cap(2);
+        let mut profile = MasteryProfile::new();
+        profile.gain_xp(MasteryDomain::SwordMastery, 2000);
+        profile.gain_xp(MasteryDomain::GauntletMastery, 2000);
+        profile.gain_xp(MasteryDomain::ParryMastery, 2000);
+        profile.gain_xp(MasteryDomain::StaffMastery, 2000);
+
+        let branches = all_specialization_branches();
+        let find = |id: &str| branches.iter().find(|b| b.id == id).unwrap();
+        spec.choose_branch(find("sword_bladestorm"), &profile)
+            .unwrap();
+        spec.choose_branch(find("gauntlet_berserker"), &profile)
+            .unwrap();
+
+        let result = spec.choose_branch(find("parry_riposte"), &profile);
+        assert!(matches!(
+            result,
+            Err(SpecError::RoleCapExceeded {
+                role: CombatRole::Striker,
+                cap: 2
+            })
+        ));
+        assert!(!spec.has_specialization(MasteryDomain::ParryMastery));
+
+        // Other roles are unaffected
+        spec.choose_branch(find("staff_mender"), &profile).unwrap();
+        assert_eq!(spec.primary_role, Some(CombatRole::Striker));
+        assert_eq!(spec.secondary_role, Some(CombatRole::Sentinel));
+    }
+
+    #[test]
+    fn test_default_role_cap() {
+        let mut spec = SpecializationProfile::new();
+        let mut profile = MasteryProfile::new();
+        for domain in [
+            MasteryDomain::SwordMastery,
+            MasteryDomain::GauntletMastery,
+            MasteryDomain::ParryMastery,
+            MasteryDomain::DodgeMastery,
+        ] {
+            profile.gain_xp(domain, 2000);
+        }
+
+        let branches = all_specialization_branches();
+        let find = |id: &str| branches.iter().find(|b| b.id == id).unwrap();
+        for id in ["sword_bladestorm", "gauntlet_berserker", "parry_riposte"] {
+            spec.choose_branch(find(id), &profile).unwrap();
+        }
+        assert_eq!(spec.role_cap(), DEFAULT_ROLE_CAP);
+        assert!(spec.choose_branch(find("dodge_shadow"), &profile).is_err());
     }
 
     #[test]
@@ -675,6 +1049,22 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_decay_floors_from_chosen_branches() {
+        let mut spec = SpecializationProfile::new();
+        let mut profile = MasteryProfile::new();
+        profile.gain_xp(MasteryDomain::SwordMastery, 2000);
+        spec.choose_branch(&all_specialization_branches()[0], &profile)
+            .unwrap();
+
+        let floors = spec.decay_floors();
+        assert_eq!(
+            floors.get(&MasteryDomain::SwordMastery),
+            Some(&MasteryTier::Expert)
+        );
+        assert!(!floors.contains_key(&MasteryDomain::DaggerMastery));
+    }
+
     #[test]
     fn test_cannot_double_specialize() {
         let mut spec = SpecializationProfile::new();
@@ -702,6 +1092,67 @@ mod tests {
         assert!(!spec.has_specialization(MasteryDomain::SwordMastery));
     }
 
+    fn sword_spec() -> SpecializationProfile {
+        let mut spec = SpecializationProfile::new();
+        let mut profile = MasteryProfile::new();
+        profile.gain_xp(MasteryDomain::SwordMastery, 2000);
+        spec.choose_branch(&all_specialization_branches()[0], &profile)
+            .unwrap();
+        spec
+    }
+
+    #[test]
+    fn test_respec_cost_rises_per_respec() {
+        let mut spec = sword_spec();
+        let mut profile = MasteryProfile::new();
+        profile.gain_xp(MasteryDomain::SwordMastery, 2000);
+        let mut shards = 10_000;
+
+        let mut last_cost = 0;
+        for _ in 0..3 {
+            let cost = spec.respec_cost();
+            assert!(cost > last_cost);
+            spec.reset_branch_paid(MasteryDomain::SwordMastery, &mut shards)
+                .unwrap();
+            spec.choose_branch(&all_specialization_branches()[0], &profile)
+                .unwrap();
+            last_cost = cost;
+        }
+        assert_eq!(spec.respec_count, 3);
+        assert_eq!(shards, 10_000 - 100 - 200 - 400);
+    }
+
+    #[test]
+    fn test_respec_insufficient_shards_rejected() {
+        let mut spec = sword_spec();
+        let mut shards = 50;
+        assert_eq!(
+            spec.reset_branch_paid(MasteryDomain::SwordMastery, &mut shards),
+            Err(RespecError::InsufficientShards {
+                cost: RESPEC_BASE_COST,
+                available: 50,
+            })
+        );
+        assert_eq!(shards, 50);
+        assert_eq!(spec.respec_count, 0);
+        assert!(spec.has_specialization(MasteryDomain::SwordMastery));
+    }
+
+    #[test]
+    fn test_paid_respec_clears_branch() {
+        let mut spec = sword_spec();
+        let mut shards = RESPEC_BASE_COST;
+        spec.reset_branch_paid(MasteryDomain::SwordMastery, &mut shards)
+            .unwrap();
+        assert!(!spec.has_specialization(MasteryDomain::SwordMastery));
+        assert_eq!(shards, 0);
+
+        assert_eq!(
+            spec.reset_branch_paid(MasteryDomain::SwordMastery, &mut shards),
+            Err(RespecError::NotSpecialized(MasteryDomain::SwordMastery))
+        );
+    }
+
     #[test]
     fn test_role_calculation() {
         let mut spec = SpecializationProfile::new();
@@ -737,6 +1188,52 @@ mod tests {
         assert_eq!(passives.len(), 2);
     }
 
+    #[test]
+    fn test_thorns_passive() {
+        let mut spec = SpecializationProfile::new();
+        let mut profile = MasteryProfile::new();
+        profile.gain_xp(MasteryDomain::SwordMastery, 2000);
+        assert_eq!(spec.thorns_pct(), 0.0);
+
+        let branches = all_specialization_branches();
+        let guardian = branches.iter().find(|b| b.id == "sword_guardian").unwrap();
+        spec.choose_branch(guardian, &profile).unwrap();
+
+        assert!((spec.thorns_pct() - 0.15).abs() < f32::EPSILON);
+        let reflected = crate::combat::thorns_reflect(100.0, spec.thorns_pct());
+        assert!((reflected - 15.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_thorns_passive_syncs_component() {
+        let mut app = App::new();
+        app.add_systems(Update, sync_thorns);
+        let player = app.world_mut().spawn(SpecializationProfile::new()).id();
+        app.update();
+        assert!(app.world().get::<Thorns>(player).is_none());
+
+        let mut profile = MasteryProfile::new();
+        profile.gain_xp(MasteryDomain::SwordMastery, 2000);
+        let branches = all_specialization_branches();
+        let guardian = branches.iter().find(|b| b.id == "sword_guardian").unwrap();
+        app.world_mut()
+            .get_mut::<SpecializationProfile>(player)
+            .unwrap()
+            .choose_branch(guardian, &profile)
+            .unwrap();
+        app.update();
+        let thorns = app.world().get::<Thorns>(player).unwrap();
+        assert!((thorns.pct - 0.15).abs() < f32::EPSILON);
+
+        app.world_mut()
+            .get_mut::<SpecializationProfile>(player)
+            .unwrap()
+            .chosen_branches
+            .clear();
+        app.update();
+        assert!(app.world().get::<Thorns>(player).is_none());
+    }
+
     #[test]
     fn test_ultimate_abilities() {
         let mut spec = SpecializationProfile::new();
@@ -751,6 +1248,45 @@ mod tests {
         assert_eq!(ults[0].name, "Thousand Cuts");
     }
 
+    #[test]
+    fn test_ultimate_policy_independent_roles() {
+        let mut spec = SpecializationProfile::new();
+        let mut profile = MasteryProfile::new();
+        profile.gain_xp(MasteryDomain::SwordMastery, 2000);
+        profile.gain_xp(MasteryDomain::GauntletMastery, 2000);
+
+        let branches = all_specialization_branches();
+        let find = |id: &str| branches.iter().find(|b| b.id == id).unwrap();
+        spec.choose_branch(find("sword_bladestorm"), &profile)
+            .unwrap(); // Striker
+        spec.choose_branch(find("gauntlet_ironwall"), &profile)
+            .unwrap(); // Vanguard
+
+        assert_eq!(spec.ultimate_cooldown_policy(), CooldownPolicy::Independent);
+    }
+
+    #[test]
+    fn test_ultimate_policy_shared_for_same_role() {
+        let mut spec = SpecializationProfile::new();
+        let mut profile = MasteryProfile::new();
+        profile.gain_xp(MasteryDomain::SwordMastery, 2000);
+        profile.gain_xp(MasteryDomain::GauntletMastery, 2000);
+
+        let branches = all_specialization_branches();
+        let find = |id: &str| branches.iter().find(|b| b.id == id).unwrap();
+        spec.choose_branch(find("sword_bladestorm"), &profile)
+            .unwrap(); // Striker
+        spec.choose_branch(find("gauntlet_berserker"), &profile)
+            .unwrap(); // Striker
+
+        assert_eq!(
+            spec.ultimate_cooldown_policy(),
+            CooldownPolicy::SharedGlobal {
+                lockout: SHARED_ULTIMATE_LOCKOUT
+            }
+        );
+    }
+
     #[test]
     fn test_synergies() {
         let synergies = branch_synergies();
@@ -763,6 +1299,42 @@ mod tests {
         assert_eq!(active[0].name, "Counter-Storm");
     }
 
+    #[test]
+    fn test_tri_synergy_three_branch_build() {
+        let chosen = vec![
+            "sword_bladestorm".to_string(),
+            "parry_riposte".to_string(),
+            "dodge_shadow".to_string(),
+        ];
+        let tris = find_active_tri_synergies(&chosen);
+        assert_eq!(tris.len(), 1);
+        assert_eq!(tris[0].name, "Perfect Execution");
+
+        // Counter-Storm and Flash Blade are subsumed by the tri-synergy
+        assert!(find_active_synergies(&chosen).is_empty());
+    }
+
+    #[test]
+    fn test_tri_synergy_needs_all_three() {
+        let chosen = vec!["sword_bladestorm".to_string(), "parry_riposte".to_string()];
+        assert!(find_active_tri_synergies(&chosen).is_empty());
+        assert_eq!(find_active_synergies(&chosen).len(), 1);
+    }
+
+    #[test]
+    fn test_tri_synergy_keeps_unrelated_pairs() {
+        let chosen = vec![
+            "sword_bladestorm".to_string(),
+            "parry_riposte".to_string(),
+            "dodge_shadow".to_string(),
+            "smith_weaponsmith".to_string(),
+            "alchemy_poisoner".to_string(),
+        ];
+        let pairs = find_active_synergies(&chosen);
+        assert_eq!(pairs.len(), 1);
+        assert_eq!(pairs[0].name, "Toxic Arsenal");
+    }
+
     #[test]
     fn test_combat_role_display() {
         assert_eq!(CombatRole::Vanguard.display_name(), "Vanguard");
@@ -5,7 +5,7 @@
 
 use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 
 use crate::generation::FloorTier;
 
@@ -42,6 +42,9 @@ pub struct FloorMapEntry {
     pub first_discovered_utc: u64,
     pub last_visited_utc: u64,
     pub notes: String, // Player notes for this floor
+    /// Times this floor has been cleared (`cleared` only records the first)
+    #[serde(default)]
+    pub clear_count: u32,
 }
 
 impl FloorMapEntry {
@@ -67,6 +70,7 @@ impl FloorMapEntry {
             first_discovered_utc: 0,
             last_visited_utc: 0,
             notes: String::new(),
+            clear_count: 0,
         }
     }
 
@@ -89,6 +93,7 @@ impl FloorMapEntry {
         if !self.cleared {
             self.cleared = true;
         }
+        self.clear_count += 1;
 
         if let Some(best) = self.best_clear_time_secs {
             if clear_time_secs < best {
@@ -191,6 +196,67 @@ pub struct TowerMap {
     pub total_playtime_secs: f32,
     pub first_session_utc: u64,
     pub last_session_utc: u64,
+    /// Depth milestones whose reward has already been claimed
+    #[serde(default)]
+    pub claimed_milestones: Vec<u32>,
+    /// Unlocked shortcuts / stairs as (from, to) floor pairs, usable upward only
+    #[serde(default)]
+    pub shortcuts: Vec<(u32, u32)>,
+}
+
+/// Floors between depth milestones (10, 20, 30, ...)
+pub const DEPTH_MILESTONE_INTERVAL: u32 = 10;
+/// Tower shards granted per floor of milestone depth
+const MILESTONE_SHARDS_PER_FLOOR: u64 = 50;
+
+/// Flat tower shard cost of any fast-travel trip
+const FAST_TRAVEL_BASE_COST: u64 = 20;
+/// Additional shards per floor travelled
+const FAST_TRAVEL_COST_PER_FLOOR: u64 = 5;
+
+/// Tower shards to fast-travel between two floors (0 for staying put)
+pub fn fast_travel_cost(from: u32, to: u32) -> u64 {
+    if from == to {
+        return 0;
+    }
+    FAST_TRAVEL_BASE_COST + from.abs_diff(to) as u64 * FAST_TRAVEL_COST_PER_FLOOR
+}
+
+/// Deaths vs clears on one floor, for spotting difficulty spikes
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FloorDifficulty {
+    pub floor_id: u32,
+    pub deaths: u32,
+    pub clears: u32,
+    /// deaths / (deaths + clears)
+    pub death_rate: f32,
+}
+
+/// Clear time assumed for a floor on a route with no recorded clear time
+pub const UNTIMED_FLOOR_ESTIMATE_SECS: f32 = 300.0;
+
+/// Reward granted for reaching a depth milestone
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MilestoneReward {
+    pub depth: u32,
+    pub tower_shards: u64,
+    /// Cosmetic title unlocked at every fifth milestone (50, 100, ...)
+    pub title: Option<String>,
+}
+
+impl MilestoneReward {
+    pub fn for_depth(depth: u32) -> Self {
+        let title = if depth % (DEPTH_MILESTONE_INTERVAL * 5) == 0 {
+            Some(format!("Conqueror of Floor {}", depth))
+        } else {
+            None
+        };
+        Self {
+            depth,
+            tower_shards: depth as u64 * MILESTONE_SHARDS_PER_FLOOR,
+            title,
+        }
+    }
 }
 
 impl Default for TowerMap {
@@ -204,6 +270,8 @@ impl Default for TowerMap {
             total_playtime_secs: 0.0,
             first_session_utc: 0,
             last_session_utc: 0,
+            claimed_milestones: Vec::new(),
+            shortcuts: Vec::new(),
         }
     }
 }
@@ -282,6 +350,136 @@ impl TowerMap {
         sum / self.floors.len() as f32
     }
 
+    /// Fast travel is only allowed to floors that are discovered and cleared
+    pub fn can_fast_travel(&self, floor: u32) -> bool {
+        self.floors
+            .get(&floor)
+            .is_some_and(|e| e.discovered && e.cleared)
+    }
+
+    /// Death rate of every attempted floor (at least one death or clear),
+    /// highest first; ties are broken by floor id
+    pub fn difficulty_report(&self) -> Vec<FloorDifficulty> {
+        let mut report: Vec<FloorDifficulty> = self
+            .floors
+            .values()
+            .filter(|e| e.death_count + e.clear_count > 0)
+            .map(|e| FloorDifficulty {
+                floor_id: e.floor_id,
+                deaths: e.death_count,
+                clears: e.clear_count,
+                death_rate: e.death_count as f32 / (e.death_count + e.clear_count) as f32,
+            })
+            .collect();
+        report.sort_by(|a, b| {
+            b.death_rate
+                .total_cmp(&a.death_rate)
+                .then(a.floor_id.cmp(&b.floor_id))
+        });
+        report
+    }
+
+    /// Record a shortcut or stairs unlock from floor `from` up to floor `to`
+    pub fn unlock_shortcut(&mut self, from: u32, to: u32) {
+        if to > from && !self.shortcuts.contains(&(from, to)) {
+            self.shortcuts.push((from, to));
+        }
+    }
+
+    /// Highest cleared floor, or 0 if nothing has been cleared yet
+    pub fn highest_cleared_floor(&self) -> u32 {
+        self.floors
+            .values()
+            .filter(|e| e.cleared)
+            .map(|e| e.floor_id)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Shortest floor sequence from the highest cleared floor (or the tower
+    /// entrance, floor 1, if nothing is cleared) up to `target_floor`.
+    /// Floors are climbed one at a time or through unlocked shortcuts; every
+    /// floor stepped onto must be discovered. A discovered target at or below
+    /// the starting floor is reached directly. None if the target is
+    /// undiscovered or no discovered path leads to it.
+    pub fn route_to(&self, target_floor: u32) -> Option<Vec<u32>> {
+        let discovered = |floor: u32| self.floors.get(&floor).is_some_and(|e| e.discovered);
+        if !discovered(target_floor) {
+            return None;
+        }
+        let start = self.highest_cleared_floor().max(1);
+        if target_floor <= start {
+            return Some(vec![target_floor]);
+        }
+
+        // BFS over discovered floors start..=target; `previous` maps each
+        // reached floor to the floor we came from
+        let mut previous: HashMap<u32, u32> = HashMap::new();
+        let mut queue = VecDeque::from([start]);
+        while let Some(floor) = queue.pop_front() {
+            if floor == target_floor {
+                break;
+            }
+            let next = self
+                .shortcuts
+                .iter()
+                .filter(|&&(from, to)| from == floor && to > floor && to <= target_floor)
+                .map(|&(_, to)| to)
+                .chain(std::iter::once(floor + 1));
+            for to in next {
+                if to != start && !previous.contains_key(&to) && discovered(to) {
+                    previous.insert(to, floor);
+                    queue.push_back(to);
+                }
+            }
+        }
+
+        previous.get(&target_floor)?;
+        let mut route = vec![target_floor];
+        let mut floor = target_floor;
+        while let Some(&prev) = previous.get(&floor) {
+            route.push(prev);
+            floor = prev;
+        }
+        route.reverse();
+        Some(route)
+    }
+
+    /// Estimated seconds to clear every floor of `route` after the first,
+    /// using each floor's best recorded clear time
+    pub fn route_clear_time(&self, route: &[u32]) -> f32 {
+        route
+            .iter()
+            .skip(1)
+            .map(|floor| {
+                self.floors
+                    .get(floor)
+                    .and_then(|e| e.best_clear_time_secs)
+                    .unwrap_or(UNTIMED_FLOOR_ESTIMATE_SECS)
+            })
+            .sum()
+    }
+
+    /// All depth milestones at or below the highest floor reached
+    pub fn depth_milestones_reached(&self) -> Vec<u32> {
+        (1..=self.highest_floor_reached / DEPTH_MILESTONE_INTERVAL)
+            .map(|i| i * DEPTH_MILESTONE_INTERVAL)
+            .collect()
+    }
+
+    /// Claim the reward for a depth milestone.
+    /// Returns None if `floor` is not a milestone, hasn't been reached, or was already claimed.
+    pub fn claim_depth_milestone(&mut self, floor: u32) -> Option<MilestoneReward> {
+        if floor == 0 || floor % DEPTH_MILESTONE_INTERVAL != 0 {
+            return None;
+        }
+        if floor > self.highest_floor_reached || self.claimed_milestones.contains(&floor) {
+            return None;
+        }
+        self.claimed_milestones.push(floor);
+        Some(MilestoneReward::for_depth(floor))
+    }
+
     fn update_session_time(&mut self) {
         let now = current_time_utc();
         if self.first_session_utc == 0 {
@@ -380,6 +578,10 @@ pub enum MapEvent {
         floor_id: u32,
         faction: String,
     },
+    ShortcutUnlocked {
+        from: u32,
+        to: u32,
+    },
 }
 
 fn process_map_events(mut events: EventReader<MapEvent>, mut map: ResMut<TowerMap>) {
@@ -437,6 +639,9 @@ fn process_map_events(mut events: EventReader<MapEvent>, mut map: ResMut<TowerMa
                     entry.activate_shrine(faction);
                 }
             }
+            MapEvent::ShortcutUnlocked { from, to } => {
+                map.unlock_shortcut(*from, *to);
+            }
         }
     }
 }
@@ -709,4 +914,161 @@ mod tests {
 
         assert_eq!(entry.visited_count, 3);
     }
+
+    #[test]
+    fn test_depth_milestone_claimable_after_reaching_floor_10() {
+        let mut map = TowerMap::default();
+        map.discover_floor(10, FloorTier::Echelon1, 5, 10, 3);
+
+        assert_eq!(map.depth_milestones_reached(), vec![10]);
+        let reward = map.claim_depth_milestone(10).unwrap();
+        assert_eq!(reward.depth, 10);
+        assert!(reward.tower_shards > 0);
+    }
+
+    #[test]
+    fn test_depth_milestone_no_double_claim() {
+        let mut map = TowerMap::default();
+        map.discover_floor(12, FloorTier::Echelon1, 5, 10, 3);
+
+        assert!(map.claim_depth_milestone(10).is_some());
+        assert!(map.claim_depth_milestone(10).is_none());
+
+        // Claimed state survives serialization
+        let restored = TowerMap::from_json(&map.to_json()).unwrap();
+        assert_eq!(restored.claimed_milestones, vec![10]);
+    }
+
+    #[test]
+    fn test_depth_milestone_unreached_not_claimable() {
+        let mut map = TowerMap::default();
+        map.discover_floor(15, FloorTier::Echelon1, 5, 10, 3);
+
+        assert!(map.claim_depth_milestone(20).is_none());
+        assert!(map.claim_depth_milestone(15).is_none()); // not a milestone
+        assert!(map.claim_depth_milestone(0).is_none());
+        assert!(map.claimed_milestones.is_empty());
+    }
+
+    #[test]
+    fn test_fast_travel_only_to_cleared_floors() {
+        let mut map = TowerMap::default();
+        map.discover_floor(3, FloorTier::Echelon1, 5, 10, 3);
+        map.discover_floor(4, FloorTier::Echelon1, 5, 10, 3);
+        map.clear_floor(3, 120.0);
+
+        assert!(map.can_fast_travel(3));
+        assert!(!map.can_fast_travel(4)); // discovered, not cleared
+        assert!(!map.can_fast_travel(50)); // never seen
+    }
+
+    #[test]
+    fn test_fast_travel_cost_scales_with_distance() {
+        assert_eq!(fast_travel_cost(7, 7), 0);
+        let near = fast_travel_cost(10, 12);
+        let far = fast_travel_cost(10, 40);
+        assert!(far > near);
+        assert_eq!(fast_travel_cost(40, 10), far);
+        assert_eq!(far - near, 28 * FAST_TRAVEL_COST_PER_FLOOR);
+    }
+
+    fn climbed_map(discovered: u32, cleared: u32) -> TowerMap {
+        let mut map = TowerMap::default();
+        for floor in 1..=discovered {
+            map.discover_floor(floor, FloorTier::Echelon1, 5, 10, 3);
+        }
+        for floor in 1..=cleared {
+            map.clear_floor(floor, 100.0 + floor as f32);
+        }
+        map
+    }
+
+    #[test]
+    fn test_route_on_partially_cleared_map() {
+        let mut map = climbed_map(8, 4);
+        map.clear_floor(6, 50.0);
+        // Highest cleared is 6, so the route starts there
+        assert_eq!(map.route_to(8), Some(vec![6, 7, 8]));
+        assert_eq!(
+            map.route_clear_time(&[6, 7, 8]),
+            2.0 * UNTIMED_FLOOR_ESTIMATE_SECS
+        );
+        // Already behind us: reached directly
+        assert_eq!(map.route_to(2), Some(vec![2]));
+
+        let fresh = climbed_map(3, 0);
+        assert_eq!(fresh.route_to(3), Some(vec![1, 2, 3]));
+    }
+
+    #[test]
+    fn test_route_to_undiscovered_target() {
+        let mut map = climbed_map(5, 3);
+        assert_eq!(map.route_to(9), None);
+
+        // Target known but a floor in between never discovered
+        map.discover_floor(9, FloorTier::Echelon1, 5, 10, 3);
+        assert_eq!(map.route_to(9), None);
+
+        // A huge floor id only costs as much as the floors actually known
+        map.discover_floor(u32::MAX, FloorTier::Echelon4, 5, 10, 3);
+        assert_eq!(map.route_to(u32::MAX), None);
+    }
+
+    #[test]
+    fn test_shortcut_shortens_route() {
+        let mut map = climbed_map(10, 2);
+        assert_eq!(map.route_to(10).unwrap().len(), 9);
+
+        map.unlock_shortcut(3, 8);
+        map.unlock_shortcut(3, 8);
+        map.unlock_shortcut(9, 4); // downward, ignored
+        assert_eq!(map.shortcuts, vec![(3, 8)]);
+        assert_eq!(map.route_to(10), Some(vec![2, 3, 8, 9, 10]));
+
+        // Shortcut bypasses an undiscovered stretch
+        map.floors.get_mut(&5).unwrap().discovered = false;
+        assert_eq!(map.route_to(10), Some(vec![2, 3, 8, 9, 10]));
+        map.clear_floor(8, 40.0);
+        assert_eq!(map.route_to(10), Some(vec![8, 9, 10]));
+    }
+
+    #[test]
+    fn test_difficulty_report_rate_math() {
+        let mut map = climbed_map(2, 0);
+        for _ in 0..3 {
+            map.record_death(1);
+        }
+        map.clear_floor(1, 200.0);
+
+        let report = map.difficulty_report();
+        assert_eq!(report.len(), 1);
+        assert_eq!(report[0].floor_id, 1);
+        assert_eq!((report[0].deaths, report[0].clears), (3, 1));
+        assert!((report[0].death_rate - 0.75).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn test_difficulty_report_excludes_unattempted() {
+        let map = climbed_map(5, 0);
+        assert!(map.difficulty_report().is_empty());
+    }
+
+    #[test]
+    fn test_difficulty_report_sorted_by_death_rate() {
+        let mut map = climbed_map(5, 0);
+        // Floor 1: 0/2, floor 2: 4/1, floor 3: 1/1, floor 4: 2/0, floor 5 untouched
+        map.clear_floor(1, 60.0);
+        map.clear_floor(1, 55.0);
+        for (floor, deaths) in [(2, 4), (3, 1), (4, 2)] {
+            for _ in 0..deaths {
+                map.record_death(floor);
+            }
+        }
+        map.clear_floor(2, 90.0);
+        map.clear_floor(3, 90.0);
+
+        let order: Vec<u32> = map.difficulty_report().iter().map(|d| d.floor_id).collect();
+        assert_eq!(order, vec![4, 2, 3, 1]);
+        assert_eq!(map.get_floor(1).unwrap().clear_count, 2);
+    }
 }
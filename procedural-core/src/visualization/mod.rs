@@ -46,6 +46,8 @@ fn tile_color(tile_type: &TileType) -> Color {
         TileType::Shrine => Color::srgb(0.4, 0.8, 0.9),
         TileType::WindColumn => Color::srgb(0.7, 0.9, 1.0),
         TileType::VoidPit => Color::srgb(0.1, 0.0, 0.15),
+        TileType::Lever => Color::srgb(0.7, 0.7, 0.3),
+        TileType::Gate => Color::srgb(0.4, 0.4, 0.45),
     }
 }
 
@@ -57,6 +59,8 @@ fn tile_height(tile_type: &TileType) -> f32 {
         TileType::Shrine => 1.5,
         TileType::WindColumn => 4.0,
         TileType::VoidPit => -1.0,
+        TileType::Gate => WALL_HEIGHT,
+        TileType::Lever => 1.0,
         TileType::StairsUp => 0.8,
         TileType::StairsDown => -0.3,
         _ => 0.1, // floor level
@@ -1,6 +1,11 @@
 use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
 
+use crate::constants::{
+    BREATH_CYCLE_TOTAL, BREATH_EXHALE_SECS, BREATH_HOLD_SECS, BREATH_INHALE_SECS, BREATH_PAUSE_SECS,
+};
+use crate::semantic::SemanticTags;
+
 pub struct WorldPlugin;
 
 impl Plugin for WorldPlugin {
@@ -112,6 +117,96 @@ impl BreathPhase {
     }
 }
 
+/// Semantic intensity treated as neutral: Exhale's, the phase whose spawn
+/// and resource multipliers are both 1.0
+pub const NEUTRAL_SEMANTIC_INTENSITY: f32 = 0.6;
+
+/// Multiplier on synergy/conflict bonus magnitude for a phase's
+/// `semantic_intensity` (Hold 1.4, Inhale 1.2, Exhale 1.0, Pause 0.8)
+pub fn semantic_bonus_scale(semantic_intensity: f32) -> f32 {
+    (1.0 + semantic_intensity - NEUTRAL_SEMANTIC_INTENSITY).max(0.0)
+}
+
+/// Breath phase and progress through it (0.0-1.0) for seconds since the
+/// cycle started. Shared by FFI and engine services so every client agrees.
+pub fn breath_phase_at(elapsed_seconds: f32) -> (BreathPhase, f32) {
+    let cycle_pos = elapsed_seconds.rem_euclid(BREATH_CYCLE_TOTAL);
+
+    let hold_start = BREATH_INHALE_SECS;
+    let exhale_start = hold_start + BREATH_HOLD_SECS;
+    let pause_start = exhale_start + BREATH_EXHALE_SECS;
+
+    if cycle_pos < hold_start {
+        (BreathPhase::Inhale, cycle_pos / BREATH_INHALE_SECS)
+    } else if cycle_pos < exhale_start {
+        (
+            BreathPhase::Hold,
+            (cycle_pos - hold_start) / BREATH_HOLD_SECS,
+        )
+    } else if cycle_pos < pause_start {
+        (
+            BreathPhase::Exhale,
+            (cycle_pos - exhale_start) / BREATH_EXHALE_SECS,
+        )
+    } else {
+        (
+            BreathPhase::Pause,
+            (cycle_pos - pause_start) / BREATH_PAUSE_SECS,
+        )
+    }
+}
+
+/// Seconds into the cycle at which `phase` begins
+fn phase_start(phase: BreathPhase) -> f32 {
+    match phase {
+        BreathPhase::Inhale => 0.0,
+        BreathPhase::Hold => BREATH_INHALE_SECS,
+        BreathPhase::Exhale => BREATH_INHALE_SECS + BREATH_HOLD_SECS,
+        BreathPhase::Pause => BREATH_INHALE_SECS + BREATH_HOLD_SECS + BREATH_EXHALE_SECS,
+    }
+}
+
+/// Phases entered since the cycle origin, counting across cycles
+fn phase_index(elapsed_seconds: f32) -> i64 {
+    let cycle = elapsed_seconds.div_euclid(BREATH_CYCLE_TOTAL) as i64;
+    let (phase, _) = breath_phase_at(elapsed_seconds);
+    let within = match phase {
+        BreathPhase::Inhale => 0,
+        BreathPhase::Hold => 1,
+        BreathPhase::Exhale => 2,
+        BreathPhase::Pause => 3,
+    };
+    cycle * 4 + within
+}
+
+/// The phase that starts next and the seconds until it does. Exactly on a
+/// boundary, the phase just entered is current, so the wait is its full length.
+pub fn next_phase_transition(elapsed_seconds: f32) -> (BreathPhase, f32) {
+    let cycle_pos = elapsed_seconds.rem_euclid(BREATH_CYCLE_TOTAL);
+    let (phase, _) = breath_phase_at(elapsed_seconds);
+    let next = phase.next();
+    let boundary = match next {
+        BreathPhase::Inhale => BREATH_CYCLE_TOTAL,
+        other => phase_start(other),
+    };
+    (next, boundary - cycle_pos)
+}
+
+/// The phase entered if a boundary lies in (prev, now], for BreathShift.
+/// If several were crossed (long ticks), this is the latest one.
+pub fn crossed_boundary(prev_elapsed: f32, now_elapsed: f32) -> Option<BreathPhase> {
+    if phase_index(now_elapsed) > phase_index(prev_elapsed) {
+        Some(breath_phase_at(now_elapsed).0)
+    } else {
+        None
+    }
+}
+
+/// Whether a breath-synced door/shrine can be used right now
+pub fn phase_gated_interactable(required_phase: BreathPhase, current_phase: BreathPhase) -> bool {
+    required_phase == current_phase
+}
+
 /// Tower environmental effect applied to a region
 #[derive(Component, Debug)]
 pub struct TowerEnvironment {
@@ -121,6 +216,20 @@ pub struct TowerEnvironment {
     pub semantic_field: Vec3,  // dominant semantic direction
 }
 
+/// Blend a seasonal theme (e.g. frost season) into a floor's biome tags.
+/// `strength` 0.0 leaves the biome untouched, 1.0 fully adopts the theme's values.
+pub fn apply_seasonal_overlay(
+    biome: &mut SemanticTags,
+    season_theme: &SemanticTags,
+    strength: f32,
+) {
+    let strength = strength.clamp(0.0, 1.0);
+    if strength <= 0.0 {
+        return;
+    }
+    biome.blend(season_theme, strength);
+}
+
 fn update_breath_cycle(time: Res<Time>, mut breath: ResMut<BreathOfTower>) {
     let dt = time.delta_secs();
     breath.phase_timer += dt;
@@ -169,6 +278,72 @@ mod tests {
         assert!((total - 1080.0).abs() < f32::EPSILON);
     }
 
+    #[test]
+    fn test_next_transition_within_phase() {
+        let (next, secs) = next_phase_transition(100.0);
+        assert_eq!(next, BreathPhase::Hold);
+        assert!((secs - (BREATH_INHALE_SECS - 100.0)).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_next_transition_exactly_at_boundary() {
+        let (next, secs) = next_phase_transition(BREATH_INHALE_SECS);
+        assert_eq!(next, BreathPhase::Exhale);
+        assert!((secs - BREATH_HOLD_SECS).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_next_transition_wraps_to_inhale() {
+        let pause_start = BREATH_CYCLE_TOTAL - BREATH_PAUSE_SECS;
+        let (next, secs) = next_phase_transition(pause_start + 20.0);
+        assert_eq!(next, BreathPhase::Inhale);
+        assert!((secs - (BREATH_PAUSE_SECS - 20.0)).abs() < 1e-3);
+
+        let (next, _) = next_phase_transition(3.0 * BREATH_CYCLE_TOTAL + 100.0);
+        assert_eq!(next, BreathPhase::Hold);
+    }
+
+    #[test]
+    fn test_crossed_boundary_exactly_at_boundary() {
+        assert_eq!(
+            crossed_boundary(BREATH_INHALE_SECS - 1.0, BREATH_INHALE_SECS),
+            Some(BreathPhase::Hold)
+        );
+        // Starting on the boundary doesn't cross it again
+        assert_eq!(
+            crossed_boundary(BREATH_INHALE_SECS, BREATH_INHALE_SECS + 1.0),
+            None
+        );
+    }
+
+    #[test]
+    fn test_no_crossing_within_phase() {
+        assert_eq!(crossed_boundary(10.0, 200.0), None);
+        assert_eq!(crossed_boundary(200.0, 200.0), None);
+    }
+
+    #[test]
+    fn test_crossed_boundary_wraparound() {
+        let pause_mid = BREATH_CYCLE_TOTAL - BREATH_PAUSE_SECS / 2.0;
+        assert_eq!(
+            crossed_boundary(pause_mid, BREATH_CYCLE_TOTAL + 5.0),
+            Some(BreathPhase::Inhale)
+        );
+        // A full cycle later lands in the same phase but still crossed
+        assert_eq!(
+            crossed_boundary(100.0, 100.0 + BREATH_CYCLE_TOTAL),
+            Some(BreathPhase::Inhale)
+        );
+    }
+
+    #[test]
+    fn test_semantic_bonus_scale_by_phase() {
+        let scale = |p: BreathPhase| semantic_bonus_scale(p.semantic_intensity());
+        assert!((scale(BreathPhase::Exhale) - 1.0).abs() < 1e-6);
+        assert!(scale(BreathPhase::Hold) > scale(BreathPhase::Inhale));
+        assert!(scale(BreathPhase::Pause) < 1.0);
+    }
+
     #[test]
     fn test_monster_spawn_multipliers() {
         // Hold phase has highest spawn rate
@@ -180,6 +355,54 @@ mod tests {
         assert!(BreathPhase::Pause.monster_spawn_multiplier() < 1.0);
     }
 
+    fn frost_season() -> SemanticTags {
+        SemanticTags::new(vec![("frost", 0.9), ("water", 0.3)])
+    }
+
+    #[test]
+    fn test_seasonal_overlay_adds_frost() {
+        use crate::generation::{FloorSpec, TowerSeed};
+
+        for floor_id in [1, 25, 60] {
+            let mut spec = FloorSpec::generate(&TowerSeed { seed: 42 }, floor_id);
+            assert_eq!(spec.biome_tags.get("frost"), 0.0);
+
+            apply_seasonal_overlay(&mut spec.biome_tags, &frost_season(), 0.3);
+            assert!(spec.biome_tags.get("frost") > 0.2);
+        }
+    }
+
+    #[test]
+    fn test_seasonal_overlay_inactive_keeps_base() {
+        use crate::generation::{FloorSpec, TowerSeed};
+
+        let base = FloorSpec::generate(&TowerSeed { seed: 42 }, 10).biome_tags;
+        let mut biome = base.clone();
+        apply_seasonal_overlay(&mut biome, &frost_season(), 0.0);
+        assert_eq!(biome.tags, base.tags);
+    }
+
+    #[test]
+    fn test_breath_phase_at_boundaries() {
+        assert_eq!(breath_phase_at(0.0).0, BreathPhase::Inhale);
+        assert_eq!(breath_phase_at(BREATH_INHALE_SECS).0, BreathPhase::Hold);
+        assert_eq!(
+            breath_phase_at(BREATH_CYCLE_TOTAL - 1.0).0,
+            BreathPhase::Pause
+        );
+        assert_eq!(breath_phase_at(BREATH_CYCLE_TOTAL).0, BreathPhase::Inhale);
+    }
+
+    #[test]
+    fn test_phase_gated_interactable() {
+        let during_hold = breath_phase_at(BREATH_INHALE_SECS + 10.0).0;
+        let during_pause = breath_phase_at(BREATH_CYCLE_TOTAL - 10.0).0;
+
+        assert!(phase_gated_interactable(BreathPhase::Hold, during_hold));
+        assert!(!phase_gated_interactable(BreathPhase::Hold, during_pause));
+        assert!(phase_gated_interactable(BreathPhase::Pause, during_pause));
+    }
+
     #[test]
     fn test_default_breath() {
         let breath = BreathOfTower::default();
@@ -1100,15 +1100,20 @@ fn trade_execute_without_both_confirmed() {
     let pb = cstr("player_b");
     let trade = ptr_to_string(social_create_trade(pa.as_ptr(), pb.as_ptr()));
 
-    // Try executing without locking/confirming — execute() runs regardless of state
+    // The legacy entry point never executes; it reports an error instead
     let tc = cstr(&trade);
     let result = social_trade_execute(tc.as_ptr());
-    // trade.execute() doesn't validate state, returns the trade object as-is
-    assert!(
-        !result.is_null(),
-        "trade_execute returns trade regardless of state"
-    );
+    assert!(!result.is_null(), "trade_execute returns an error object");
     assert!(is_valid_json(result));
+
+    // The validated entry point rejects an unconfirmed trade
+    let inv_a = cstr(r#"{"player_id":"player_a","shards":0,"items":[]}"#);
+    let inv_b = cstr(r#"{"player_id":"player_b","shards":0,"items":[]}"#);
+    let result = social_trade_execute_validated(tc.as_ptr(), inv_a.as_ptr(), inv_b.as_ptr());
+    assert!(!result.is_null());
+    let s = unsafe { CStr::from_ptr(result).to_str().unwrap().to_owned() };
+    free_string(result);
+    assert!(s.contains("NotConfirmed"), "got {s}");
 }
 
 #[test]
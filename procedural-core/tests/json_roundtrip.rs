@@ -461,11 +461,24 @@ fn roundtrip_social_trade_full_workflow() {
     let pb4 = cstr("trader_b");
     let confirmed_both_json = ptr_to_string(social_trade_confirm(tc6.as_ptr(), pb4.as_ptr()));
 
-    // Execute trade
+    // Execute trade against both inventories
     let tc7 = cstr(&confirmed_both_json);
-    let executed_json = ptr_to_string(social_trade_execute(tc7.as_ptr()));
-    let executed: serde_json::Value = serde_json::from_str(&executed_json).unwrap();
-    assert!(executed.is_object());
+    let inv_a = cstr(
+        r#"{"player_id":"trader_a","shards":0,"items":[{"item_name":"Mythic Staff","quantity":1,"rarity":"Epic"}]}"#,
+    );
+    let inv_b = cstr(
+        r#"{"player_id":"trader_b","shards":0,"items":[{"item_name":"Gold Coins","quantity":100,"rarity":"Common"}]}"#,
+    );
+    let executed = ptr_to_json(social_trade_execute_validated(
+        tc7.as_ptr(),
+        inv_a.as_ptr(),
+        inv_b.as_ptr(),
+    ));
+    assert_eq!(executed["trade"]["state"], "Completed");
+    assert_eq!(
+        executed["inventory_b"]["items"][0]["item_name"],
+        "Gold Coins"
+    );
 }
 
 // ============================================================
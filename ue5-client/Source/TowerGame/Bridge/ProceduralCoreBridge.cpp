@@ -131,7 +131,7 @@ bool FProceduralCoreBridge::Initialize(const FString& DllPath)
     LOAD_DLL_FUNC(SocialTradeAddItem, FnSocialTradeAddItem, "social_trade_add_item");
     LOAD_DLL_FUNC(SocialTradeLock, FnSocialTradeLock, "social_trade_lock");
     LOAD_DLL_FUNC(SocialTradeConfirm, FnSocialTradeConfirm, "social_trade_confirm");
-    LOAD_DLL_FUNC(SocialTradeExecute, FnSocialTradeExecute, "social_trade_execute");
+    LOAD_DLL_FUNC(SocialTradeExecute, FnSocialTradeExecute, "social_trade_execute_validated");
 
     // ---- Hot-Reload (v0.6.0) ----
     LOAD_DLL_FUNC(HotReloadGetStatus, FnHotReloadGetStatus, "hotreload_get_status");
@@ -788,11 +788,16 @@ FString FProceduralCoreBridge::SocialTradeConfirm(const FString& TradeJson, cons
     return RustStringToFString(Fn_SocialTradeConfirm(Utf8Trade.Get(), Utf8Player.Get()), Fn_FreeString);
 }
 
-FString FProceduralCoreBridge::SocialTradeExecute(const FString& TradeJson)
+FString FProceduralCoreBridge::SocialTradeExecute(const FString& TradeJson, const FString& InventoryAJson,
+                                                   const FString& InventoryBJson)
 {
     if (!Fn_SocialTradeExecute) return FString();
-    FTCHARToUTF8 Utf8(*TradeJson);
-    return RustStringToFString(Fn_SocialTradeExecute(Utf8.Get()), Fn_FreeString);
+    FTCHARToUTF8 Utf8Trade(*TradeJson);
+    FTCHARToUTF8 Utf8InvA(*InventoryAJson);
+    FTCHARToUTF8 Utf8InvB(*InventoryBJson);
+    return RustStringToFString(
+        Fn_SocialTradeExecute(Utf8Trade.Get(), Utf8InvA.Get(), Utf8InvB.Get()),
+        Fn_FreeString);
 }
 
 // ============ Hot-Reload (v0.6.0) ============
@@ -133,7 +133,7 @@ typedef char* (*FnSocialCreateTrade)(const char*, const char*);
 typedef char* (*FnSocialTradeAddItem)(const char*, const char*, const char*, uint32, const char*);
 typedef char* (*FnSocialTradeLock)(const char*, const char*);
 typedef char* (*FnSocialTradeConfirm)(const char*, const char*);
-typedef char* (*FnSocialTradeExecute)(const char*);
+typedef char* (*FnSocialTradeExecute)(const char*, const char*, const char*);
 
 // Hot-Reload (v0.6.0 - Session 22)
 typedef char* (*FnHotReloadGetStatus)();
@@ -267,7 +267,8 @@ public:
                                const FString& ItemName, uint32 Quantity, const FString& Rarity);
     FString SocialTradeLock(const FString& TradeJson, const FString& PlayerId);
     FString SocialTradeConfirm(const FString& TradeJson, const FString& PlayerId);
-    FString SocialTradeExecute(const FString& TradeJson);
+    FString SocialTradeExecute(const FString& TradeJson, const FString& InventoryAJson,
+                               const FString& InventoryBJson);
 
     // ============ Hot-Reload (v0.6.0) ============
     FString HotReloadGetStatus();
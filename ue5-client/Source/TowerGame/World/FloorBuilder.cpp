@@ -28,6 +28,8 @@ FLinearColor AFloorBuilder::GetTileColor(int32 TileType)
     case 9:  return FLinearColor(0.4f, 0.6f, 1.0f);     // Shrine — blue
     case 10: return FLinearColor(0.7f, 0.9f, 0.7f);     // WindColumn — light green
     case 11: return FLinearColor(0.05f, 0.0f, 0.15f);   // VoidPit — deep purple
+    case 12: return FLinearColor(0.75f, 0.7f, 0.3f);    // Lever — brass
+    case 13: return FLinearColor(0.4f, 0.4f, 0.45f);    // Gate — iron gray
     default: return FLinearColor(1.0f, 0.0f, 1.0f);     // Unknown — magenta
     }
 }
@@ -49,6 +51,8 @@ FString AFloorBuilder::GetTileName(int32 TileType)
     case 9:  return TEXT("Shrine");
     case 10: return TEXT("WindColumn");
     case 11: return TEXT("VoidPit");
+    case 12: return TEXT("Lever");
+    case 13: return TEXT("Gate");
     default: return TEXT("Unknown");
     }
 }
@@ -10,7 +10,8 @@
  * Tile types from Rust tile_to_u8 (bridge/mod.rs):
  *   0 = Empty, 1 = Floor, 2 = Wall, 3 = Door,
  *   4 = StairsUp, 5 = StairsDown, 6 = Chest, 7 = Trap,
- *   8 = Spawner, 9 = Shrine, 10 = WindColumn, 11 = VoidPit
+ *   8 = Spawner, 9 = Shrine, 10 = WindColumn, 11 = VoidPit,
+ *   12 = Lever, 13 = Gate
  */
 UCLASS()
 class TOWERGAME_API AFloorBuilder : public AActor